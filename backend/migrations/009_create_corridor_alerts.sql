@@ -0,0 +1,13 @@
+-- Create corridor_alerts table for per-corridor success rate thresholds
+CREATE TABLE IF NOT EXISTS corridor_alerts (
+    id TEXT PRIMARY KEY,
+    corridor_key TEXT NOT NULL,
+    min_success_rate REAL NOT NULL,
+    notify_url TEXT NOT NULL,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    last_triggered_at TEXT, -- Set while the alert is breached, cleared on recovery
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_corridor_alerts_corridor ON corridor_alerts(corridor_key, is_active);
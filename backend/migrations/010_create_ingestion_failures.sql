@@ -0,0 +1,13 @@
+-- Ledger ranges skipped by ingestion after exhausting their retries
+CREATE TABLE IF NOT EXISTS ingestion_failures (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    start_ledger INTEGER NOT NULL,
+    end_ledger INTEGER NOT NULL,
+    attempts INTEGER NOT NULL,
+    last_error TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'dead_lettered', -- dead_lettered, requeued or resolved
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_ingestion_failures_status ON ingestion_failures(status, updated_at);
@@ -0,0 +1,23 @@
+-- Expose when each corridor's latest metrics were last written, for sort_by=last_updated
+DROP VIEW IF EXISTS corridor_metrics_latest;
+
+CREATE VIEW corridor_metrics_latest AS
+SELECT 
+    corridor_key,
+    asset_a_code,
+    asset_a_issuer,
+    asset_b_code,
+    asset_b_issuer,
+    SUM(total_transactions) as total_transactions,
+    SUM(successful_transactions) as successful_transactions,
+    SUM(failed_transactions) as failed_transactions,
+    AVG(success_rate) as avg_success_rate,
+    SUM(volume_usd) as total_volume_usd,
+    AVG(avg_slippage_bps) as avg_slippage_bps,
+    AVG(avg_settlement_latency_ms) as avg_settlement_latency_ms,
+    AVG(liquidity_depth_usd) as avg_liquidity_depth_usd,
+    MAX(hour_bucket) as last_updated,
+    MAX(updated_at) as updated_at
+FROM corridor_metrics_hourly
+WHERE hour_bucket >= datetime('now', '-24 hours')
+GROUP BY corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer;
@@ -0,0 +1,2 @@
+-- Share of recent ingestion windows in which each anchor had transactions, 0-100
+ALTER TABLE anchors ADD COLUMN availability REAL;
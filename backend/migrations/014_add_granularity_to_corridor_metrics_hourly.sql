@@ -0,0 +1,3 @@
+-- Bucket width each row was aggregated at (INGESTION_BUCKET); existing rows are hourly
+ALTER TABLE corridor_metrics_hourly
+    ADD COLUMN granularity TEXT NOT NULL DEFAULT 'hour' CHECK (granularity IN ('hour', 'day'));
@@ -0,0 +1,5 @@
+-- Bucket upserts add to the stored totals, so each payment is counted into
+-- corridor metrics once and then flagged
+ALTER TABLE payments ADD COLUMN aggregated INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_payments_unaggregated ON payments(aggregated, created_at);
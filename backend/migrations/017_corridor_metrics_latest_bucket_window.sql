@@ -0,0 +1,44 @@
+-- Daily buckets are dated at midnight, so the 24-hour window takes the buckets
+-- overlapping it: yesterday's and today's
+DROP VIEW IF EXISTS corridor_metrics_latest;
+
+CREATE VIEW corridor_metrics_latest AS
+SELECT
+    m.*,
+    COALESCE(c.source_asset_code, m.asset_a_code) AS source_asset_code,
+    COALESCE(c.source_asset_issuer, m.asset_a_issuer) AS source_asset_issuer,
+    COALESCE(c.destination_asset_code, m.asset_b_code) AS destination_asset_code,
+    COALESCE(c.destination_asset_issuer, m.asset_b_issuer) AS destination_asset_issuer
+FROM (
+    SELECT
+        corridor_key,
+        asset_a_code,
+        asset_a_issuer,
+        asset_b_code,
+        asset_b_issuer,
+        SUM(total_transactions) as total_transactions,
+        SUM(successful_transactions) as successful_transactions,
+        SUM(failed_transactions) as failed_transactions,
+        AVG(success_rate) as avg_success_rate,
+        SUM(volume_usd) as total_volume_usd,
+        AVG(avg_slippage_bps) as avg_slippage_bps,
+        AVG(avg_settlement_latency_ms) as avg_settlement_latency_ms,
+        AVG(liquidity_depth_usd) as avg_liquidity_depth_usd,
+        MAX(hour_bucket) as last_updated,
+        MAX(updated_at) as updated_at
+    FROM corridor_metrics_hourly
+    WHERE hour_bucket >= CASE granularity
+        WHEN 'day' THEN date('now', '-1 day')
+        ELSE datetime('now', '-24 hours')
+    END
+    GROUP BY corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer
+) m
+LEFT JOIN corridors c ON c.id = (
+    SELECT id FROM corridors
+    WHERE (source_asset_code = m.asset_a_code AND source_asset_issuer = m.asset_a_issuer
+           AND destination_asset_code = m.asset_b_code AND destination_asset_issuer = m.asset_b_issuer)
+       OR (source_asset_code = m.asset_b_code AND source_asset_issuer = m.asset_b_issuer
+           AND destination_asset_code = m.asset_a_code AND destination_asset_issuer = m.asset_a_issuer)
+    ORDER BY created_at, id
+    LIMIT 1
+);
@@ -1,6 +1,8 @@
+use crate::analytics::health::StatusThresholds;
 use crate::models::{AnchorMetrics, AnchorStatus};
 
 pub mod corridor;
+pub mod health;
 
 /// Performance metrics for an anchor's individual asset
 #[derive(Debug, Clone)]
@@ -33,6 +35,7 @@ pub fn compute_anchor_metrics(
     successful_transactions: i64,
     failed_transactions: i64,
     avg_settlement_time_ms: Option<i32>,
+    thresholds: &StatusThresholds,
 ) -> AnchorMetrics {
     if total_transactions == 0 {
         return AnchorMetrics {
@@ -60,7 +63,7 @@ pub fn compute_anchor_metrics(
     let settlement_time_score = calculate_settlement_time_score(avg_settlement_time_ms);
     let reliability_score = (success_rate * 0.7) + (settlement_time_score * 0.3);
 
-    let status = AnchorStatus::from_metrics(success_rate, failure_rate);
+    let status = thresholds.anchor_status(success_rate);
 
     AnchorMetrics {
         success_rate,
@@ -199,7 +202,8 @@ mod tests {
 
     #[test]
     fn test_compute_anchor_metrics_perfect_anchor() {
-        let metrics = compute_anchor_metrics(1000, 995, 5, Some(2000));
+        let metrics =
+            compute_anchor_metrics(1000, 995, 5, Some(2000), &StatusThresholds::default());
 
         assert_eq!(metrics.total_transactions, 1000);
         assert_eq!(metrics.successful_transactions, 995);
@@ -212,7 +216,8 @@ mod tests {
 
     #[test]
     fn test_compute_anchor_metrics_yellow_anchor() {
-        let metrics = compute_anchor_metrics(1000, 960, 40, Some(5000));
+        let metrics =
+            compute_anchor_metrics(1000, 960, 40, Some(5000), &StatusThresholds::default());
 
         assert_eq!(metrics.success_rate, 96.0);
         assert_eq!(metrics.failure_rate, 4.0);
@@ -221,16 +226,27 @@ mod tests {
 
     #[test]
     fn test_compute_anchor_metrics_red_anchor() {
-        let metrics = compute_anchor_metrics(1000, 900, 100, Some(9000));
+        let metrics =
+            compute_anchor_metrics(1000, 900, 100, Some(9000), &StatusThresholds::default());
 
         assert_eq!(metrics.success_rate, 90.0);
         assert_eq!(metrics.failure_rate, 10.0);
         assert_eq!(metrics.status, AnchorStatus::Red);
     }
 
+    #[test]
+    fn test_compute_anchor_metrics_uses_configured_thresholds() {
+        let lenient = StatusThresholds::new(95.0, 85.0).unwrap();
+        let metrics = compute_anchor_metrics(1000, 960, 40, Some(5000), &lenient);
+        assert_eq!(metrics.status, AnchorStatus::Green);
+
+        let metrics = compute_anchor_metrics(1000, 900, 100, Some(9000), &lenient);
+        assert_eq!(metrics.status, AnchorStatus::Yellow);
+    }
+
     #[test]
     fn test_compute_anchor_metrics_no_transactions() {
-        let metrics = compute_anchor_metrics(0, 0, 0, None);
+        let metrics = compute_anchor_metrics(0, 0, 0, None, &StatusThresholds::default());
 
         assert_eq!(metrics.success_rate, 0.0);
         assert_eq!(metrics.failure_rate, 0.0);
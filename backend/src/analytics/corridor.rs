@@ -1,6 +1,9 @@
-use crate::models::corridor::{Corridor, CorridorAnalytics, PaymentRecord};
-use chrono::{DateTime, Utc};
+use crate::db::aggregates::CorridorDailyTotals;
+use crate::models::corridor::{parse_corridor_key, CorridorAnalytics, PaymentRecord};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use utoipa::ToSchema;
 
 pub fn compute_corridor_analytics(payments: &[PaymentRecord]) -> Vec<CorridorAnalytics> {
     let mut corridor_payments: HashMap<String, Vec<&PaymentRecord>> = HashMap::new();
@@ -32,7 +35,9 @@ pub fn compute_corridor_analytics(payments: &[PaymentRecord]) -> Vec<CorridorAna
 
         let volume_usd: f64 = corridor_payment_records.iter().map(|p| p.amount).sum();
 
-        let corridor = parse_corridor_key(&corridor_key);
+        let Ok(corridor) = parse_corridor_key(&corridor_key) else {
+            continue;
+        };
 
         analytics.push(CorridorAnalytics {
             corridor,
@@ -94,17 +99,130 @@ pub fn get_corridors_by_success_rate(
         .collect()
 }
 
-fn parse_corridor_key(corridor_key: &str) -> Corridor {
-    let parts: Vec<&str> = corridor_key.split("->").collect();
-    let asset_a_parts: Vec<&str> = parts[0].split(':').collect();
-    let asset_b_parts: Vec<&str> = parts[1].split(':').collect();
-
-    Corridor::new(
-        asset_a_parts[0].to_string(),
-        asset_a_parts[1].to_string(),
-        asset_b_parts[0].to_string(),
-        asset_b_parts[1].to_string(),
-    )
+/// Longest rollup window, in days
+pub const ROLLUP_MAX_WINDOW_DAYS: i64 = 30;
+
+/// Corridor totals over one rollup window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CorridorWindowMetrics {
+    pub total_transactions: i64,
+    pub successful_transactions: i64,
+    pub failed_transactions: i64,
+    /// Successful share of all transactions in the window, in percent
+    pub success_rate: f64,
+    pub volume_usd: f64,
+    /// `volume_usd` in the requested quote currency, when one was given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume: Option<f64>,
+    /// Daily buckets that contributed to the window
+    pub days_with_data: usize,
+}
+
+/// Standard rollup windows; a window is `null` when no history falls in it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CorridorRollupWindows {
+    #[serde(rename = "24h")]
+    pub last_24h: Option<CorridorWindowMetrics>,
+    #[serde(rename = "7d")]
+    pub last_7d: Option<CorridorWindowMetrics>,
+    #[serde(rename = "30d")]
+    pub last_30d: Option<CorridorWindowMetrics>,
+}
+
+/// Earliest daily bucket that can overlap the longest rollup window
+pub fn rollup_history_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    now - Duration::days(ROLLUP_MAX_WINDOW_DAYS + 1)
+}
+
+/// Roll daily corridor history up into the 24h, 7d and 30d windows ending at `now`
+///
+/// History is stored in daily buckets, so a day counts towards a window when
+/// any part of it overlaps the window.
+pub fn rollup_corridor_windows(
+    history: &[CorridorDailyTotals],
+    now: DateTime<Utc>,
+) -> CorridorRollupWindows {
+    CorridorRollupWindows {
+        last_24h: rollup_window(history, now, Duration::hours(24)),
+        last_7d: rollup_window(history, now, Duration::days(7)),
+        last_30d: rollup_window(history, now, Duration::days(ROLLUP_MAX_WINDOW_DAYS)),
+    }
+}
+
+fn rollup_window(
+    history: &[CorridorDailyTotals],
+    now: DateTime<Utc>,
+    window: Duration,
+) -> Option<CorridorWindowMetrics> {
+    let earliest_bucket = now - window - Duration::days(1);
+    let days: Vec<&CorridorDailyTotals> = history
+        .iter()
+        .filter(|day| day.date > earliest_bucket && day.date <= now)
+        .collect();
+
+    if days.is_empty() {
+        return None;
+    }
+
+    let total_transactions: i64 = days.iter().map(|d| d.total_transactions).sum();
+    let successful_transactions: i64 = days.iter().map(|d| d.successful_transactions).sum();
+    let success_rate = if total_transactions > 0 {
+        (successful_transactions as f64 / total_transactions as f64) * 100.0
+    } else {
+        0.0
+    };
+
+    Some(CorridorWindowMetrics {
+        total_transactions,
+        successful_transactions,
+        failed_transactions: days.iter().map(|d| d.failed_transactions).sum(),
+        success_rate,
+        volume_usd: days.iter().map(|d| d.volume_usd).sum(),
+        volume: None,
+        days_with_data: days.len(),
+    })
+}
+
+/// Days of daily history behind the volatility term of [`compute_corridor_risk`]
+pub const RISK_HISTORY_DAYS: i64 = 7;
+
+/// Daily success-rate spread, in percentage points, that counts as fully volatile
+const RISK_MAX_VOLATILITY_PP: f64 = 25.0;
+
+/// Volume, in USD, at which a corridor's risk is weighted in full
+const RISK_FULL_IMPACT_VOLUME_USD: f64 = 10_000_000.0;
+
+/// Corridor risk from 0 (safe) to 100 (risky)
+///
+/// Combines the failure rate (60%) with the standard deviation of daily
+/// success rates in `history` (40%), then scales the result by volume: a
+/// corridor with no volume keeps half its raw risk, one moving
+/// `RISK_FULL_IMPACT_VOLUME_USD` or more keeps all of it. Days without
+/// transactions are ignored, and fewer than two days count as no volatility.
+pub fn compute_corridor_risk(metrics: &CorridorAnalytics, history: &[CorridorDailyTotals]) -> f64 {
+    let failure = (100.0 - metrics.success_rate).clamp(0.0, 100.0);
+
+    let daily_rates: Vec<f64> = history
+        .iter()
+        .filter(|day| day.total_transactions > 0)
+        .map(|day| day.successful_transactions as f64 / day.total_transactions as f64 * 100.0)
+        .collect();
+    let volatility = if daily_rates.len() < 2 {
+        0.0
+    } else {
+        let n = daily_rates.len() as f64;
+        let mean = daily_rates.iter().sum::<f64>() / n;
+        let variance = daily_rates.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n;
+        (variance.sqrt() / RISK_MAX_VOLATILITY_PP * 100.0).min(100.0)
+    };
+
+    let impact = if metrics.volume_usd > 0.0 {
+        (metrics.volume_usd.ln_1p() / RISK_FULL_IMPACT_VOLUME_USD.ln_1p()).min(1.0)
+    } else {
+        0.0
+    };
+
+    ((failure * 0.6 + volatility * 0.4) * (0.5 + 0.5 * impact)).clamp(0.0, 100.0)
 }
 
 #[cfg(test)]
@@ -134,6 +252,118 @@ mod tests {
         }
     }
 
+    fn risk_metrics(success_rate: f64, volume_usd: f64) -> CorridorAnalytics {
+        CorridorAnalytics {
+            corridor: parse_corridor_key("USDC:issuer1->XLM:native").unwrap(),
+            success_rate,
+            total_transactions: 1_000,
+            successful_transactions: (success_rate * 10.0) as i64,
+            failed_transactions: 1_000 - (success_rate * 10.0) as i64,
+            volume_usd,
+        }
+    }
+
+    fn daily_rates(now: DateTime<Utc>, rates: &[i64]) -> Vec<CorridorDailyTotals> {
+        rates
+            .iter()
+            .enumerate()
+            .map(|(days_ago, rate)| daily_totals(now, days_ago as i64, 100, *rate))
+            .collect()
+    }
+
+    #[test]
+    fn test_corridor_risk_stable_vs_volatile() {
+        let now = Utc::now();
+
+        let stable = compute_corridor_risk(
+            &risk_metrics(99.5, 5_000_000.0),
+            &daily_rates(now, &[99, 100, 99, 100, 99, 100, 100]),
+        );
+        let volatile = compute_corridor_risk(
+            &risk_metrics(60.0, 5_000_000.0),
+            &daily_rates(now, &[20, 90, 40, 95, 55, 85, 35]),
+        );
+        assert!(stable < 5.0, "stable risk {}", stable);
+        assert!(volatile > 50.0, "volatile risk {}", volatile);
+
+        // The same failures weigh more on a busier corridor
+        let quiet = compute_corridor_risk(&risk_metrics(60.0, 0.0), &[]);
+        let busy = compute_corridor_risk(&risk_metrics(60.0, 50_000_000.0), &[]);
+        assert_eq!(quiet, 12.0);
+        assert_eq!(busy, 24.0);
+    }
+
+    fn daily_totals(
+        now: DateTime<Utc>,
+        days_ago: i64,
+        total: i64,
+        successful: i64,
+    ) -> CorridorDailyTotals {
+        let day = (now - Duration::days(days_ago))
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc();
+        CorridorDailyTotals {
+            corridor_key: "USDC:issuer1->XLM:native".to_string(),
+            date: day,
+            total_transactions: total,
+            successful_transactions: successful,
+            failed_transactions: total - successful,
+            volume_usd: total as f64 * 10.0,
+        }
+    }
+
+    #[test]
+    fn test_rollup_windows_aggregate_overlapping_days() {
+        let now = "2024-03-31T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let history = vec![
+            daily_totals(now, 0, 10, 9),
+            daily_totals(now, 1, 10, 7),
+            daily_totals(now, 5, 20, 20),
+            daily_totals(now, 20, 60, 30),
+            daily_totals(now, 45, 1000, 0),
+        ];
+
+        let windows = rollup_corridor_windows(&history, now);
+
+        let day = windows.last_24h.unwrap();
+        assert_eq!(day.days_with_data, 2);
+        assert_eq!(day.total_transactions, 20);
+        assert_eq!(day.successful_transactions, 16);
+        assert_eq!(day.failed_transactions, 4);
+        assert!((day.success_rate - 80.0).abs() < 1e-9);
+
+        let week = windows.last_7d.unwrap();
+        assert_eq!(week.days_with_data, 3);
+        assert_eq!(week.total_transactions, 40);
+        assert_eq!(week.volume_usd, 400.0);
+
+        let month = windows.last_30d.unwrap();
+        assert_eq!(month.days_with_data, 4);
+        assert_eq!(month.total_transactions, 100);
+        assert!((month.success_rate - 66.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rollup_windows_without_data_are_null() {
+        let now = "2024-03-31T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        let windows = rollup_corridor_windows(&[daily_totals(now, 10, 5, 5)], now);
+        assert!(windows.last_24h.is_none());
+        assert!(windows.last_7d.is_none());
+        assert_eq!(windows.last_30d.unwrap().success_rate, 100.0);
+
+        let empty = serde_json::to_value(rollup_corridor_windows(&[], now)).unwrap();
+        assert!(empty["24h"].is_null());
+        assert!(empty["7d"].is_null());
+        assert!(empty["30d"].is_null());
+
+        // Days with zero transactions still count as data
+        let quiet = rollup_corridor_windows(&[daily_totals(now, 0, 0, 0)], now);
+        assert_eq!(quiet.last_24h.unwrap().success_rate, 0.0);
+    }
+
     #[test]
     fn test_compute_corridor_analytics_basic() {
         let payments = vec![
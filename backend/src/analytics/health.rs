@@ -0,0 +1,565 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::ingestion::config::parse_var;
+use crate::models::{Anchor, AnchorFilter, AnchorMetricsHistory, AnchorStatus, Asset};
+
+/// Number of assets at which asset coverage scores 100
+const FULL_COVERAGE_ASSETS: f64 = 10.0;
+
+/// Component sub-scores behind an anchor's health, each on a 0-100 scale
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct HealthComponents {
+    /// Share of successful transactions
+    pub success_rate: f64,
+    /// How steady volume has been across recorded history (100 = constant)
+    pub volume_stability: f64,
+    /// Number of issued assets, relative to a 10-asset ceiling
+    pub asset_coverage: f64,
+    /// Share of history snapshots with at least one successful transaction
+    pub uptime: f64,
+}
+
+/// Success-rate cutoffs used to classify an anchor
+///
+/// Loaded once at startup so deployments can pick their own risk tolerance.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct StatusThresholds {
+    /// Minimum success rate for `green`
+    pub green_min_success_rate: f64,
+    /// Minimum success rate for `yellow`; anything lower is `red`
+    pub yellow_min_success_rate: f64,
+}
+
+impl Default for StatusThresholds {
+    fn default() -> Self {
+        Self {
+            green_min_success_rate: 98.0,
+            yellow_min_success_rate: 95.0,
+        }
+    }
+}
+
+impl StatusThresholds {
+    pub fn new(green_min_success_rate: f64, yellow_min_success_rate: f64) -> Result<Self> {
+        for (name, value) in [
+            ("STATUS_GREEN_MIN_SUCCESS_RATE", green_min_success_rate),
+            ("STATUS_YELLOW_MIN_SUCCESS_RATE", yellow_min_success_rate),
+        ] {
+            if !(0.0..=100.0).contains(&value) {
+                bail!("{} must be between 0 and 100, got {}", name, value);
+            }
+        }
+        if green_min_success_rate < yellow_min_success_rate {
+            bail!(
+                "STATUS_GREEN_MIN_SUCCESS_RATE ({}) must be >= STATUS_YELLOW_MIN_SUCCESS_RATE ({})",
+                green_min_success_rate,
+                yellow_min_success_rate
+            );
+        }
+
+        Ok(Self {
+            green_min_success_rate,
+            yellow_min_success_rate,
+        })
+    }
+
+    /// Create from `STATUS_GREEN_MIN_SUCCESS_RATE` and `STATUS_YELLOW_MIN_SUCCESS_RATE`
+    ///
+    /// The former `STATUS_*_MIN_RELIABILITY` names are still read when the
+    /// new ones are unset.
+    pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let defaults = Self::default();
+        let parse = |name: &str, legacy_name: &str, default: f64| -> Result<f64> {
+            let set = |name: &str| lookup(name).filter(|value| !value.trim().is_empty());
+            let found = set(name)
+                .map(|value| (name, value))
+                .or_else(|| set(legacy_name).map(|value| (legacy_name, value)));
+            match found {
+                Some((name, value)) => value
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("Invalid value for {}: {}", name, value)),
+                None => Ok(default),
+            }
+        };
+
+        Self::new(
+            parse(
+                "STATUS_GREEN_MIN_SUCCESS_RATE",
+                "STATUS_GREEN_MIN_RELIABILITY",
+                defaults.green_min_success_rate,
+            )?,
+            parse(
+                "STATUS_YELLOW_MIN_SUCCESS_RATE",
+                "STATUS_YELLOW_MIN_RELIABILITY",
+                defaults.yellow_min_success_rate,
+            )?,
+        )
+    }
+
+    /// Status for a success rate percentage (0-100)
+    pub fn anchor_status(&self, success_rate: f64) -> AnchorStatus {
+        if success_rate >= self.green_min_success_rate {
+            AnchorStatus::Green
+        } else if success_rate >= self.yellow_min_success_rate {
+            AnchorStatus::Yellow
+        } else {
+            AnchorStatus::Red
+        }
+    }
+
+    /// [`Self::anchor_status`] as its stored string
+    pub fn status_for(&self, success_rate: f64) -> &'static str {
+        self.anchor_status(success_rate).as_str()
+    }
+
+    /// Anchors whose reliability score [`Self::anchor_status`] maps to `status`
+    pub fn anchor_filter(&self, status: AnchorStatus) -> AnchorFilter {
+        let (min_reliability, max_reliability) = match status {
+            AnchorStatus::Green => (Some(self.green_min_success_rate), None),
+            AnchorStatus::Yellow => (
+                Some(self.yellow_min_success_rate),
+                Some(self.green_min_success_rate),
+            ),
+            AnchorStatus::Red => (None, Some(self.yellow_min_success_rate)),
+        };
+        AnchorFilter {
+            min_reliability,
+            max_reliability,
+        }
+    }
+}
+
+/// Damping applied to status changes between syncs
+///
+/// A status only changes once the success rate is past the threshold by
+/// `margin` points, and has stayed there for `required_syncs` syncs in a row.
+/// The defaults change status on the first sync past the threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusHysteresis {
+    pub margin: f64,
+    pub required_syncs: u32,
+}
+
+impl Default for StatusHysteresis {
+    fn default() -> Self {
+        Self {
+            margin: 0.0,
+            required_syncs: 1,
+        }
+    }
+}
+
+/// A status an anchor is heading towards, and the syncs it has been seen for
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingStatus {
+    pub status: &'static str,
+    pub syncs: u32,
+}
+
+/// Outcome of one sync under [`StatusHysteresis`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusDecision {
+    /// Status to store for the anchor
+    pub status: &'static str,
+    /// Change still waiting for more syncs, to pass to the next decision
+    pub pending: Option<PendingStatus>,
+}
+
+impl StatusHysteresis {
+    /// Create from `STATUS_HYSTERESIS_MARGIN` and `STATUS_HYSTERESIS_SYNCS`
+    pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let defaults = Self::default();
+        let margin: f64 = parse_var(&lookup, "STATUS_HYSTERESIS_MARGIN", defaults.margin)?;
+        if !(0.0..=100.0).contains(&margin) {
+            bail!(
+                "STATUS_HYSTERESIS_MARGIN must be between 0 and 100, got {}",
+                margin
+            );
+        }
+        let required_syncs =
+            parse_var(&lookup, "STATUS_HYSTERESIS_SYNCS", defaults.required_syncs)?;
+        if required_syncs == 0 {
+            bail!("STATUS_HYSTERESIS_SYNCS must be at least 1");
+        }
+
+        Ok(Self {
+            margin,
+            required_syncs,
+        })
+    }
+
+    /// Status after a sync measuring `success_rate`, given the stored
+    /// `current` status and the change left `pending` by the previous sync
+    ///
+    /// An unrecognised `current` status is replaced straight away.
+    pub fn next_status(
+        &self,
+        thresholds: &StatusThresholds,
+        current: &str,
+        success_rate: f64,
+        pending: Option<PendingStatus>,
+    ) -> StatusDecision {
+        let Some(current_rank) = status_rank(current) else {
+            return StatusDecision {
+                status: thresholds.status_for(success_rate),
+                pending: None,
+            };
+        };
+        let current = STATUSES[current_rank];
+
+        // Judge an upgrade as if the rate were `margin` lower, a downgrade as
+        // if it were `margin` higher, so only a clear crossing counts
+        let raw_rank = rank_of(thresholds.status_for(success_rate));
+        let target = match raw_rank.cmp(&current_rank) {
+            std::cmp::Ordering::Equal => current,
+            std::cmp::Ordering::Greater => thresholds.status_for(success_rate - self.margin),
+            std::cmp::Ordering::Less => thresholds.status_for(success_rate + self.margin),
+        };
+        if target == current {
+            return StatusDecision {
+                status: current,
+                pending: None,
+            };
+        }
+
+        let syncs = match pending {
+            Some(pending) if pending.status == target => pending.syncs + 1,
+            _ => 1,
+        };
+        if syncs >= self.required_syncs {
+            StatusDecision {
+                status: target,
+                pending: None,
+            }
+        } else {
+            StatusDecision {
+                status: current,
+                pending: Some(PendingStatus {
+                    status: target,
+                    syncs,
+                }),
+            }
+        }
+    }
+}
+
+/// Statuses from worst to best
+const STATUSES: [&str; 3] = ["red", "yellow", "green"];
+
+fn status_rank(status: &str) -> Option<usize> {
+    STATUSES.iter().position(|s| *s == status)
+}
+
+fn rank_of(status: &'static str) -> usize {
+    status_rank(status).expect("status_for returns a known status")
+}
+
+/// Health explanation attached to anchor detail responses
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AnchorHealthBreakdown {
+    pub components: HealthComponents,
+    pub thresholds: StatusThresholds,
+    pub status: String,
+}
+
+impl AnchorHealthBreakdown {
+    pub fn new(
+        anchor: &Anchor,
+        assets: &[Asset],
+        history: &[AnchorMetricsHistory],
+        thresholds: &StatusThresholds,
+    ) -> Self {
+        let components = HealthComponents::new(anchor, assets, history);
+        let status = compute_status(&components, thresholds);
+
+        Self {
+            components,
+            thresholds: thresholds.clone(),
+            status,
+        }
+    }
+}
+
+impl HealthComponents {
+    pub fn new(anchor: &Anchor, assets: &[Asset], history: &[AnchorMetricsHistory]) -> Self {
+        let success_rate = if anchor.total_transactions > 0 {
+            anchor.successful_transactions as f64 / anchor.total_transactions as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        let volumes: Vec<f64> = history.iter().filter_map(|h| h.volume_usd).collect();
+
+        let uptime = if history.is_empty() {
+            if anchor.successful_transactions > 0 {
+                100.0
+            } else {
+                0.0
+            }
+        } else {
+            let up = history
+                .iter()
+                .filter(|h| h.successful_transactions > 0)
+                .count();
+            up as f64 / history.len() as f64 * 100.0
+        };
+
+        Self {
+            success_rate,
+            volume_stability: volume_stability(&volumes),
+            asset_coverage: (assets.len() as f64 / FULL_COVERAGE_ASSETS).min(1.0) * 100.0,
+            uptime,
+        }
+    }
+}
+
+/// Stability score from the coefficient of variation of volume samples
+///
+/// Fewer than two samples give no evidence of instability and score 100.
+fn volume_stability(volumes: &[f64]) -> f64 {
+    if volumes.len() < 2 {
+        return 100.0;
+    }
+
+    let mean = volumes.iter().sum::<f64>() / volumes.len() as f64;
+    if mean <= 0.0 {
+        return 100.0;
+    }
+
+    let variance = volumes.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / volumes.len() as f64;
+    let coefficient_of_variation = variance.sqrt() / mean;
+    (1.0 - coefficient_of_variation).clamp(0.0, 1.0) * 100.0
+}
+
+/// Derive the green/yellow/red status from health components
+pub fn compute_status(components: &HealthComponents, thresholds: &StatusThresholds) -> String {
+    thresholds.status_for(components.success_rate).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn components(success_rate: f64) -> HealthComponents {
+        HealthComponents {
+            success_rate,
+            volume_stability: 100.0,
+            asset_coverage: 100.0,
+            uptime: 100.0,
+        }
+    }
+
+    #[test]
+    fn test_compute_status_boundaries() {
+        let thresholds = StatusThresholds::default();
+        let status = |rate| compute_status(&components(rate), &thresholds);
+
+        assert_eq!(status(100.0), "green");
+        assert_eq!(status(98.0), "green");
+        assert_eq!(status(97.99), "yellow");
+        assert_eq!(status(95.0), "yellow");
+        assert_eq!(status(94.99), "red");
+        assert_eq!(status(0.0), "red");
+    }
+
+    #[test]
+    fn test_thresholds_reclassify_borderline_anchor() {
+        let borderline = components(97.0);
+        assert_eq!(
+            compute_status(&borderline, &StatusThresholds::default()),
+            "yellow"
+        );
+
+        let lenient = StatusThresholds::new(96.5, 90.0).unwrap();
+        assert_eq!(compute_status(&borderline, &lenient), "green");
+
+        let strict = StatusThresholds::new(99.9, 97.5).unwrap();
+        assert_eq!(compute_status(&borderline, &strict), "red");
+    }
+
+    #[test]
+    fn test_anchor_filter_matches_only_its_status() {
+        let thresholds = StatusThresholds::default();
+        for rate in [100.0, 98.0, 97.99, 95.0, 94.99, 0.0] {
+            for status in [AnchorStatus::Green, AnchorStatus::Yellow, AnchorStatus::Red] {
+                assert_eq!(
+                    thresholds.anchor_filter(status).matches(rate),
+                    thresholds.anchor_status(rate) == status,
+                    "{} {:?}",
+                    rate,
+                    status
+                );
+            }
+        }
+    }
+
+    fn thresholds_from(vars: &[(&str, &str)]) -> Result<StatusThresholds> {
+        let vars: std::collections::HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        StatusThresholds::from_lookup(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_thresholds_from_env_validation() {
+        assert_eq!(thresholds_from(&[]).unwrap(), StatusThresholds::default());
+        assert_eq!(
+            thresholds_from(&[
+                ("STATUS_GREEN_MIN_SUCCESS_RATE", "99"),
+                ("STATUS_YELLOW_MIN_SUCCESS_RATE", "90"),
+            ])
+            .unwrap(),
+            StatusThresholds::new(99.0, 90.0).unwrap()
+        );
+
+        // green below yellow
+        assert!(thresholds_from(&[("STATUS_GREEN_MIN_SUCCESS_RATE", "90")]).is_err());
+        assert!(thresholds_from(&[("STATUS_YELLOW_MIN_SUCCESS_RATE", "-1")]).is_err());
+        assert!(thresholds_from(&[("STATUS_GREEN_MIN_SUCCESS_RATE", "high")]).is_err());
+    }
+
+    #[test]
+    fn test_legacy_reliability_names_are_a_fallback() {
+        assert_eq!(
+            thresholds_from(&[
+                ("STATUS_GREEN_MIN_RELIABILITY", "99"),
+                ("STATUS_YELLOW_MIN_RELIABILITY", "90"),
+            ])
+            .unwrap(),
+            StatusThresholds::new(99.0, 90.0).unwrap()
+        );
+        assert_eq!(
+            thresholds_from(&[
+                ("STATUS_GREEN_MIN_SUCCESS_RATE", "97"),
+                ("STATUS_GREEN_MIN_RELIABILITY", "99"),
+            ])
+            .unwrap(),
+            StatusThresholds::new(97.0, 95.0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_dip_within_margin_keeps_status() {
+        let thresholds = StatusThresholds::default();
+        let hysteresis = StatusHysteresis {
+            margin: 0.5,
+            required_syncs: 2,
+        };
+
+        // 97.8 is below green's 98.0 but within the margin
+        let dip = hysteresis.next_status(&thresholds, "green", 97.8, None);
+        assert_eq!(
+            dip,
+            StatusDecision {
+                status: "green",
+                pending: None,
+            }
+        );
+
+        // A clear drop for one sync only waits
+        let drop = hysteresis.next_status(&thresholds, "green", 97.0, None);
+        assert_eq!(drop.status, "green");
+        assert_eq!(
+            drop.pending,
+            Some(PendingStatus {
+                status: "yellow",
+                syncs: 1,
+            })
+        );
+        // ...and is forgotten once the rate recovers
+        let recovered = hysteresis.next_status(&thresholds, "green", 99.0, drop.pending);
+        assert_eq!(recovered.status, "green");
+        assert_eq!(recovered.pending, None);
+
+        // Two syncs in a row past the margin change the status
+        let first = hysteresis.next_status(&thresholds, "green", 97.0, None);
+        let second = hysteresis.next_status(&thresholds, "green", 96.0, first.pending);
+        assert_eq!(second.status, "yellow");
+        assert_eq!(second.pending, None);
+
+        // Coming back needs the same margin above the threshold
+        let near = hysteresis.next_status(&thresholds, "yellow", 98.2, None);
+        assert_eq!(near.status, "yellow");
+        assert_eq!(near.pending, None);
+    }
+
+    #[test]
+    fn test_default_hysteresis_matches_thresholds() {
+        let thresholds = StatusThresholds::default();
+        let hysteresis = StatusHysteresis::default();
+        for (current, rate) in [
+            ("green", 97.9),
+            ("yellow", 98.0),
+            ("green", 50.0),
+            ("red", 95.0),
+        ] {
+            let decision = hysteresis.next_status(&thresholds, current, rate, None);
+            assert_eq!(decision.status, thresholds.status_for(rate));
+            assert_eq!(decision.pending, None);
+        }
+        // Statuses outside green/yellow/red are replaced at once
+        let decision = StatusHysteresis {
+            margin: 1.0,
+            required_syncs: 3,
+        }
+        .next_status(&thresholds, "unknown", 99.0, None);
+        assert_eq!(decision.status, "green");
+    }
+
+    #[test]
+    fn test_hysteresis_from_env_validation() {
+        let lookup = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+
+        assert_eq!(
+            StatusHysteresis::from_lookup(lookup(&[])).unwrap(),
+            StatusHysteresis::default()
+        );
+        assert_eq!(
+            StatusHysteresis::from_lookup(lookup(&[
+                ("STATUS_HYSTERESIS_MARGIN", "0.5"),
+                ("STATUS_HYSTERESIS_SYNCS", "3"),
+            ]))
+            .unwrap(),
+            StatusHysteresis {
+                margin: 0.5,
+                required_syncs: 3,
+            }
+        );
+        assert!(
+            StatusHysteresis::from_lookup(lookup(&[("STATUS_HYSTERESIS_SYNCS", "0")])).is_err()
+        );
+        assert!(
+            StatusHysteresis::from_lookup(lookup(&[("STATUS_HYSTERESIS_MARGIN", "-1")])).is_err()
+        );
+    }
+
+    #[test]
+    fn test_volume_stability() {
+        assert_eq!(volume_stability(&[]), 100.0);
+        assert_eq!(volume_stability(&[500.0]), 100.0);
+        assert_eq!(volume_stability(&[100.0, 100.0, 100.0]), 100.0);
+
+        let steady = volume_stability(&[95.0, 100.0, 105.0]);
+        let erratic = volume_stability(&[10.0, 190.0, 100.0]);
+        assert!(steady > erratic);
+        assert_eq!(volume_stability(&[0.0, 0.0, 1000.0]), 0.0);
+    }
+}
@@ -0,0 +1,174 @@
+use axum::{
+    extract::{Query, State},
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::database::Database;
+use crate::db::explain::{ExplainQuery, QueryPlan};
+use crate::db::migrations::MigrationStatus;
+use crate::error::{ApiError, ApiResult};
+use crate::maintenance::MaintenanceMode;
+use crate::models::AuditLogRecord;
+
+/// Most audit entries returned by one query
+const MAX_AUDIT_ENTRIES: i64 = 1000;
+
+#[derive(Debug, Deserialize)]
+pub struct SetMaintenanceRequest {
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MaintenanceStatusResponse {
+    pub enabled: bool,
+    pub retry_after: u64,
+}
+
+/// Handler for POST /api/admin/maintenance - Toggle maintenance mode
+pub async fn set_maintenance_mode(
+    State(maintenance): State<Arc<MaintenanceMode>>,
+    Json(req): Json<SetMaintenanceRequest>,
+) -> Json<MaintenanceStatusResponse> {
+    maintenance.set_enabled(req.enabled);
+    tracing::warn!(
+        "Maintenance mode {}",
+        if req.enabled { "enabled" } else { "disabled" }
+    );
+
+    Json(MaintenanceStatusResponse {
+        enabled: maintenance.is_enabled(),
+        retry_after: maintenance.retry_after_secs(),
+    })
+}
+
+/// Handler for GET /api/admin/migrations - Applied and pending migrations
+///
+/// Migrations whose applied checksum differs from the one embedded in this
+/// build have `checksum_matches: false`.
+pub async fn get_migrations(
+    State(db): State<Arc<Database>>,
+) -> ApiResult<Json<Vec<MigrationStatus>>> {
+    let migrations = db.migration_status().await?;
+    for migration in migrations
+        .iter()
+        .filter(|m| m.checksum_matches == Some(false))
+    {
+        tracing::warn!(
+            "Migration {} ({}) checksum differs from the embedded migration",
+            migration.version,
+            migration.description
+        );
+    }
+    Ok(Json(migrations))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExplainParams {
+    pub query: String,
+}
+
+/// Handler for GET /api/admin/db/explain - Query plan of a named query
+///
+/// Only the names in [`ExplainQuery::ALL`] are accepted; there is no way to
+/// plan arbitrary SQL.
+pub async fn get_query_plan(
+    State(db): State<Arc<Database>>,
+    Query(params): Query<ExplainParams>,
+) -> ApiResult<Json<QueryPlan>> {
+    let query: ExplainQuery = params.query.parse().map_err(ApiError::BadRequest)?;
+    Ok(Json(db.explain_query(query).await?))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuditQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Handler for GET /api/admin/audit - Audit entries recorded in `[from, to]`
+///
+/// Oldest first; at most `MAX_AUDIT_ENTRIES`, so page by moving `from` past
+/// the last `created_at` returned.
+pub async fn get_audit_log(
+    State(db): State<Arc<Database>>,
+    Query(params): Query<AuditQuery>,
+) -> ApiResult<Json<Vec<AuditLogRecord>>> {
+    if let (Some(from), Some(to)) = (params.from, params.to) {
+        if from > to {
+            return Err(ApiError::BadRequest(
+                "from must not be after to".to_string(),
+            ));
+        }
+    }
+    let entries = db
+        .audit_entries(params.from, params.to, MAX_AUDIT_ENTRIES)
+        .await?;
+    Ok(Json(entries))
+}
+
+pub fn routes(maintenance: Arc<MaintenanceMode>, db: Arc<Database>) -> Router {
+    Router::new()
+        .route("/api/admin/maintenance", post(set_maintenance_mode))
+        .with_state(maintenance)
+        .merge(
+            Router::new()
+                .route("/api/admin/migrations", get(get_migrations))
+                .route("/api/admin/db/explain", get(get_query_plan))
+                .route("/api/admin/audit", get(get_audit_log))
+                .with_state(db),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request, http::StatusCode};
+    use sqlx::sqlite::SqlitePoolOptions;
+    use tower::ServiceExt;
+
+    async fn explain(db: &Arc<Database>, query: &str) -> (StatusCode, serde_json::Value) {
+        let app = routes(Arc::new(MaintenanceMode::new(false, 60)), Arc::clone(db));
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/api/admin/db/explain?query={}", query))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_explain_only_named_queries() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = Arc::new(Database::new(pool));
+
+        let (status, _) = explain(&db, "SELECT%20*%20FROM%20anchors").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, plan) = explain(&db, "top_corridors").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(plan["query"], "top_corridors");
+        let steps = plan["steps"].as_array().unwrap();
+        assert!(!steps.is_empty());
+        assert!(steps[0]["detail"]
+            .as_str()
+            .unwrap()
+            .contains("corridor_metrics"));
+    }
+}
@@ -1,46 +1,13 @@
 use axum::{
     extract::{Query, State},
-    http::StatusCode,
-    response::IntoResponse,
     Json,
 };
 use serde::{Deserialize, Serialize};
 
+use crate::api::pagination::Paginated;
+use crate::error::ApiResult;
 use crate::state::AppState;
 
-pub type ApiResult<T> = Result<T, ApiError>;
-
-#[derive(Debug)]
-pub enum ApiError {
-    NotFound(String),
-    BadRequest(String),
-    InternalError(String),
-}
-
-impl IntoResponse for ApiError {
-    fn into_response(self) -> axum::response::Response {
-        let (status, message) = match self {
-            ApiError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
-            ApiError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
-            ApiError::InternalError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-        };
-
-        (status, Json(serde_json::json!({ "error": message }))).into_response()
-    }
-}
-
-impl From<anyhow::Error> for ApiError {
-    fn from(err: anyhow::Error) -> Self {
-        ApiError::InternalError(err.to_string())
-    }
-}
-
-impl From<sqlx::Error> for ApiError {
-    fn from(err: sqlx::Error) -> Self {
-        ApiError::InternalError(err.to_string())
-    }
-}
-
 #[derive(Debug, Deserialize)]
 pub struct ListAnchorsQuery {
     #[serde(default = "default_limit")]
@@ -67,11 +34,7 @@ pub struct AnchorMetricsResponse {
     pub status: String,
 }
 
-#[derive(Debug, Serialize)]
-pub struct AnchorsResponse {
-    pub anchors: Vec<AnchorMetricsResponse>,
-    pub total: usize,
-}
+pub type AnchorsResponse = Paginated<AnchorMetricsResponse>;
 
 /// GET /api/anchors - List all anchors with key metrics
 pub async fn get_anchors(
@@ -109,12 +72,11 @@ pub async fn get_anchors(
         anchor_responses.push(anchor_response);
     }
 
-    let total = anchor_responses.len();
-
-    Ok(Json(AnchorsResponse {
-        anchors: anchor_responses,
-        total,
-    }))
+    Ok(Json(Paginated::from_page(
+        anchor_responses,
+        params.limit,
+        params.offset,
+    )))
 }
 
 #[cfg(test)]
@@ -139,6 +101,7 @@ mod tests {
             avg_settlement_time_ms: 2000,
             reliability_score: 95.5,
             status: "green".to_string(),
+            availability: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -180,6 +143,7 @@ mod tests {
             avg_settlement_time_ms: 0,
             reliability_score: 0.0,
             status: "red".to_string(),
+            availability: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -207,6 +171,7 @@ mod tests {
             avg_settlement_time_ms: 5000,
             reliability_score: 80.0,
             status: "yellow".to_string(),
+            availability: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
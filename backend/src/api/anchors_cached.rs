@@ -1,63 +1,60 @@
 use axum::{
     extract::{Query, State},
-    http::StatusCode,
-    response::IntoResponse,
-    Json,
+    http::{header, HeaderMap, HeaderValue, Uri},
+    response::{IntoResponse, Response},
+    Extension, Json,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
 
-use crate::cache::{keys, CacheManager};
+use crate::analytics::health::StatusThresholds;
+use crate::api::pagination::{
+    accepts_paginated, PageLimits, PageRequest, Paginated, PublicBasePath, PAGINATED_MEDIA_TYPE,
+};
+use crate::api::precision::ResponsePrecision;
+use crate::cache::{keys, CacheSchema};
 use crate::cache_middleware::CacheAware;
-use crate::database::Database;
+use crate::db::backend::DatabaseBackend;
+use crate::error::ApiResult;
+use crate::models::{Anchor, AnchorStatus, SortBy, SortOrder};
 use crate::rpc::StellarRpcClient;
+use crate::server_timing::timed;
+use crate::state::CachedState;
 
-pub type ApiResult<T> = Result<T, ApiError>;
-
-#[derive(Debug)]
-pub enum ApiError {
-    NotFound(String),
-    BadRequest(String),
-    InternalError(String),
-}
-
-impl IntoResponse for ApiError {
-    fn into_response(self) -> axum::response::Response {
-        let (status, message) = match self {
-            ApiError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
-            ApiError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
-            ApiError::InternalError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-        };
-
-        (status, Json(serde_json::json!({ "error": message }))).into_response()
-    }
-}
-
-impl From<anyhow::Error> for ApiError {
-    fn from(err: anyhow::Error) -> Self {
-        ApiError::InternalError(err.to_string())
-    }
-}
-
-impl From<sqlx::Error> for ApiError {
-    fn from(err: sqlx::Error) -> Self {
-        ApiError::InternalError(err.to_string())
-    }
-}
-
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListAnchorsQuery {
-    #[serde(default = "default_limit")]
-    pub limit: i64,
+    /// Page size; defaults to and is clamped by the server's page limits
+    pub limit: Option<i64>,
     #[serde(default)]
     pub offset: i64,
+    /// `success_rate` orders by reliability score
+    #[serde(default)]
+    pub sort_by: SortBy,
+    /// `asc` or `desc`; defaults to descending, or ascending for `name`
+    pub order: Option<SortOrder>,
+    /// Only anchors whose reliability score falls in this status band: `green`, `yellow` or `red`
+    pub status: Option<AnchorStatus>,
 }
 
-fn default_limit() -> i64 {
-    50
+impl ListAnchorsQuery {
+    /// Requested direction, or the natural one for `sort_by`
+    pub fn sort_order(&self) -> SortOrder {
+        self.order.unwrap_or(self.sort_by.default_order())
+    }
+
+    fn cache_key(&self, page: PageRequest) -> String {
+        let mut query = format!("{}:{}", self.sort_by.as_str(), self.sort_order().as_str());
+        if let Some(status) = self.status {
+            query.push_str(":status:");
+            query.push_str(status.as_str());
+        }
+        keys::anchor_list(page.limit, page.offset, &query)
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct AnchorMetricsResponse {
     pub id: String,
     pub name: String,
@@ -71,132 +68,637 @@ pub struct AnchorMetricsResponse {
     pub status: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct AnchorsResponse {
+impl AnchorMetricsResponse {
+    fn apply_precision(&mut self, precision: &ResponsePrecision) {
+        self.reliability_score = precision.rate(self.reliability_score);
+        self.failure_rate = precision.rate(self.failure_rate);
+    }
+}
+
+pub type AnchorsResponse = Paginated<AnchorMetricsResponse>;
+
+impl CacheSchema for AnchorsResponse {
+    const TAG: &'static str = "anchor_list.v1";
+}
+
+/// Body served to clients that did not ask for the paginated envelope
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct LegacyAnchorsResponse {
     pub anchors: Vec<AnchorMetricsResponse>,
     pub total: usize,
 }
 
+impl From<AnchorsResponse> for LegacyAnchorsResponse {
+    fn from(page: AnchorsResponse) -> Self {
+        Self {
+            anchors: page.items,
+            total: page.total,
+        }
+    }
+}
+
 /// GET /api/anchors - List all anchors with key metrics (cached)
 /// 
 /// **DATA SOURCE: RPC + Database**
 /// - Anchor metadata (name, account) from database
 /// - Transaction metrics calculated from RPC payment data
+#[utoipa::path(
+    get,
+    path = "/api/anchors",
+    tag = "anchors",
+    params(
+        ListAnchorsQuery,
+        ("Accept" = Option<String>, Header, description = "`application/vnd.stellar-insights.v2+json` for the paginated envelope")
+    ),
+    responses(
+        (status = 200, description = "Anchors with key metrics; the paginated envelope when requested via `Accept`", content(
+            (LegacyAnchorsResponse = "application/json"),
+            (Paginated<AnchorMetricsResponse> = "application/vnd.stellar-insights.v2+json")
+        )),
+        (status = 400, description = "Negative limit or offset, or unknown sort or status", body = crate::api::openapi::ErrorBody),
+        (status = 500, description = "Internal error", body = crate::api::openapi::ErrorBody)
+    )
+)]
+#[allow(clippy::too_many_arguments)]
 pub async fn get_anchors(
-    State((db, cache, rpc_client)): State<(Arc<Database>, Arc<CacheManager>, Arc<StellarRpcClient>)>,
+    State((db, cache, rpc_client)): State<CachedState>,
+    Extension(thresholds): Extension<Arc<StatusThresholds>>,
+    Extension(page_limits): Extension<Arc<PageLimits>>,
+    Extension(base_path): Extension<Arc<PublicBasePath>>,
+    Extension(precision): Extension<Arc<ResponsePrecision>>,
+    uri: Uri,
     Query(params): Query<ListAnchorsQuery>,
-) -> ApiResult<Json<AnchorsResponse>> {
-    let cache_key = keys::anchor_list(params.limit, params.offset);
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    let page = page_limits.resolve(params.limit, params.offset)?;
+    let cache_key = params.cache_key(page);
 
-    let response = <()>::get_or_fetch(
+    let mut response = <()>::get_or_fetch_tagged(
         &cache,
         &cache_key,
         cache.config.get_ttl("anchor"),
-        async {
-            // Get anchor metadata from database (names, accounts, etc.)
-            let anchors = db.list_anchors(params.limit, params.offset).await?;
-
-            let mut anchor_responses = Vec::new();
-
-            for anchor in anchors {
-                let anchor_id = uuid::Uuid::parse_str(&anchor.id)
-                    .unwrap_or_else(|_| uuid::Uuid::nil());
-                
-                // Get asset count from database (metadata)
-                let assets = db.get_assets_by_anchor(anchor_id).await?;
-
-                // **RPC DATA**: Fetch real-time payment data for this anchor
-                let payments = match rpc_client
-                    .fetch_account_payments(&anchor.stellar_account, 200)
-                    .await
-                {
-                    Ok(payments) => payments,
-                    Err(e) => {
-                        tracing::warn!(
-                            "Failed to fetch payments for anchor {}: {}. Using cached data.",
-                            anchor.stellar_account,
-                            e
-                        );
-                        // Fallback to database values if RPC fails
-                        vec![]
-                    }
-                };
-
-                // Calculate metrics from RPC payment data
-                let (total_transactions, successful_transactions, failed_transactions) = 
-                    if !payments.is_empty() {
-                        let total = payments.len() as i64;
-                        // In Stellar, if a payment appears in the ledger, it was successful
-                        // Failed payments don't appear in the payment stream
-                        let successful = total;
-                        let failed = 0;
-                        (total, successful, failed)
-                    } else {
-                        // Fallback to database values
-                        (
-                            anchor.total_transactions,
-                            anchor.successful_transactions,
-                            anchor.failed_transactions,
-                        )
-                    };
-
-                let failure_rate = if total_transactions > 0 {
-                    (failed_transactions as f64 / total_transactions as f64) * 100.0
-                } else {
-                    0.0
-                };
-
-                let reliability_score = if total_transactions > 0 {
-                    (successful_transactions as f64 / total_transactions as f64) * 100.0
-                } else {
-                    anchor.reliability_score
-                };
-
-                let status = if reliability_score >= 99.0 {
-                    "green".to_string()
-                } else if reliability_score >= 95.0 {
-                    "yellow".to_string()
-                } else {
-                    "red".to_string()
-                };
-
-                let anchor_response = AnchorMetricsResponse {
-                    id: anchor.id.to_string(),
-                    name: anchor.name,
-                    stellar_account: anchor.stellar_account,
-                    reliability_score,
-                    asset_coverage: assets.len(),
-                    failure_rate,
-                    total_transactions,
-                    successful_transactions,
-                    failed_transactions,
-                    status,
-                };
-
-                anchor_responses.push(anchor_response);
-            }
-
-            let total = anchor_responses.len();
-
-            Ok(AnchorsResponse {
-                anchors: anchor_responses,
-                total,
-            })
-        },
+        &[keys::anchors_tag(), keys::anchor_lists_tag()],
+        anchor_page(db.as_ref(), &rpc_client, &thresholds, &params, page),
     )
     .await?;
 
-    Ok(Json(response))
+    response
+        .items
+        .iter_mut()
+        .for_each(|anchor| anchor.apply_precision(&precision));
+
+    let mut response = if accepts_paginated(&headers) {
+        (
+            [(header::CONTENT_TYPE, PAGINATED_MEDIA_TYPE)],
+            Json(response.with_links(&base_path, &uri)),
+        )
+            .into_response()
+    } else {
+        Json(LegacyAnchorsResponse::from(response)).into_response()
+    };
+    response
+        .headers_mut()
+        .append(header::VARY, HeaderValue::from_static("accept"));
+    Ok(response)
+}
+
+/// Anchors on the requested page
+///
+/// `?status=` is matched in the database on the stored reliability score
+/// under the configured thresholds, which is the score the reported status
+/// falls back to without payment data. Live payment data can still move an
+/// anchor's reported status until its metrics are next stored.
+async fn anchor_page(
+    db: &dyn DatabaseBackend,
+    rpc_client: &StellarRpcClient,
+    thresholds: &StatusThresholds,
+    params: &ListAnchorsQuery,
+    page: PageRequest,
+) -> anyhow::Result<AnchorsResponse> {
+    let filter = params
+        .status
+        .map(|status| thresholds.anchor_filter(status))
+        .unwrap_or_default();
+    let anchors = timed(
+        "db",
+        db.list_anchors(
+            filter,
+            params.sort_by,
+            params.sort_order(),
+            page.limit,
+            page.offset,
+        ),
+    )
+    .await?;
+    let total = timed("db", db.count_anchors(filter)).await?;
+
+    let mut responses = Vec::new();
+    for anchor in anchors {
+        responses.push(anchor_metrics(db, rpc_client, thresholds, anchor).await?);
+    }
+    Ok(Paginated::new(
+        responses,
+        total as usize,
+        page.limit,
+        page.offset,
+    ))
+}
+
+/// Key metrics for one anchor, from RPC payment data when available
+async fn anchor_metrics(
+    db: &dyn DatabaseBackend,
+    rpc_client: &StellarRpcClient,
+    thresholds: &StatusThresholds,
+    anchor: Anchor,
+) -> anyhow::Result<AnchorMetricsResponse> {
+    let anchor_id = uuid::Uuid::parse_str(&anchor.id).unwrap_or_else(|_| uuid::Uuid::nil());
+
+    // Get asset count from database (metadata)
+    let assets = timed("db", db.get_assets_by_anchor(anchor_id)).await?;
+
+    // **RPC DATA**: Fetch real-time payment data for this anchor
+    let payments = match timed(
+        "rpc",
+        rpc_client.fetch_account_payments(&anchor.stellar_account, 200),
+    )
+    .await
+    {
+        Ok(payments) => payments,
+        Err(e) => {
+            tracing::warn!(
+                "Failed to fetch payments for anchor {}: {}. Using cached data.",
+                anchor.stellar_account,
+                e
+            );
+            // Fallback to database values if RPC fails
+            vec![]
+        }
+    };
+
+    // Calculate metrics from RPC payment data
+    let (total_transactions, successful_transactions, failed_transactions) = 
+        if !payments.is_empty() {
+            let total = payments.len() as i64;
+            // In Stellar, if a payment appears in the ledger, it was successful
+            // Failed payments don't appear in the payment stream
+            let successful = total;
+            let failed = 0;
+            (total, successful, failed)
+        } else {
+            // Fallback to database values
+            (
+                anchor.total_transactions,
+                anchor.successful_transactions,
+                anchor.failed_transactions,
+            )
+        };
+
+    let failure_rate = if total_transactions > 0 {
+        (failed_transactions as f64 / total_transactions as f64) * 100.0
+    } else {
+        0.0
+    };
+
+    let reliability_score = if total_transactions > 0 {
+        (successful_transactions as f64 / total_transactions as f64) * 100.0
+    } else {
+        anchor.reliability_score
+    };
+
+    let status = thresholds.status_for(reliability_score).to_string();
+
+    Ok(AnchorMetricsResponse {
+        id: anchor.id.to_string(),
+        name: anchor.name,
+        stellar_account: anchor.stellar_account,
+        reliability_score,
+        asset_coverage: assets.len(),
+        failure_rate,
+        total_transactions,
+        successful_transactions,
+        failed_transactions,
+        status,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cache::CacheManager;
+    use crate::db::backend::InMemoryDatabase;
+    use crate::models::{Anchor, Asset};
+    use crate::rpc::StellarRpcClient;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::get,
+        Router,
+    };
+    use chrono::Utc;
+    use tower::ServiceExt;
+
+    fn anchor(name: &str, reliability_score: f64) -> Anchor {
+        Anchor {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            stellar_account: format!("G{}", name.to_uppercase()),
+            home_domain: None,
+            total_transactions: 100,
+            successful_transactions: 90,
+            failed_transactions: 10,
+            total_volume_usd: 1000.0,
+            avg_settlement_time_ms: 2000,
+            reliability_score,
+            status: "yellow".to_string(),
+            availability: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn asset(anchor: &Anchor, code: &str) -> Asset {
+        Asset {
+            id: uuid::Uuid::new_v4().to_string(),
+            anchor_id: anchor.id.clone(),
+            asset_code: code.to_string(),
+            asset_issuer: anchor.stellar_account.clone(),
+            total_supply: None,
+            num_holders: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_anchors_with_in_memory_backend() {
+        let db = InMemoryDatabase::new();
+        let steady = anchor("steady", 99.0);
+        let shaky = anchor("shaky", 80.0);
+        db.insert_asset(asset(&steady, "USDC"));
+        db.insert_asset(asset(&steady, "EURC"));
+        db.insert_anchor(shaky);
+        db.insert_anchor(steady);
+        db.insert_anchor(anchor("flaky", 40.0));
+
+        let cache = Arc::new(CacheManager::in_memory(Default::default()));
+        let rpc = Arc::new(StellarRpcClient::new_with_defaults(true));
+        let state: CachedState = (Arc::new(db), cache, rpc);
+
+        let app = Router::new()
+            .route("/api/anchors", get(get_anchors))
+            .with_state(state)
+            .layer(Extension(Arc::new(StatusThresholds::default())))
+            .layer(Extension(Arc::new(PageLimits::default())))
+            .layer(Extension(Arc::new(PublicBasePath::default())))
+            .layer(Extension(Arc::new(ResponsePrecision::default())));
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/anchors?limit=2&offset=0")
+                    .header(header::ACCEPT, PAGINATED_MEDIA_TYPE)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let page: AnchorsResponse = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(page.total, 3);
+        assert_eq!(page.next_offset, Some(2));
+        let links = page.links.as_ref().unwrap();
+        assert_eq!(links.self_link, "/api/anchors?limit=2&offset=0");
+        assert_eq!(links.next.as_deref(), Some("/api/anchors?limit=2&offset=2"));
+        assert_eq!(links.prev, None);
+        assert_eq!(page.items[0].name, "steady");
+        assert_eq!(page.items[0].asset_coverage, 2);
+        assert_eq!(page.items[1].name, "shaky");
+        assert_eq!(page.items[1].asset_coverage, 0);
+        // Mock RPC reports every payment as successful
+        assert_eq!(page.items[1].status, "green");
+    }
+
+    #[tokio::test]
+    async fn test_head_anchors_returns_get_headers_without_body() {
+        use crate::cache_middleware::{cache_control_middleware, CacheControl};
+        use axum::{http::Method, middleware};
+
+        let db = InMemoryDatabase::new();
+        db.insert_anchor(anchor("steady", 99.0));
+
+        let cache = Arc::new(CacheManager::in_memory(Default::default()));
+        let rpc = Arc::new(StellarRpcClient::new_with_defaults(true));
+        let state: CachedState = (Arc::new(db), cache, rpc);
+
+        let cache_control = middleware::from_fn_with_state(
+            CacheControl::Public { max_age: 60 },
+            cache_control_middleware,
+        );
+        let app = Router::new()
+            .route("/api/anchors", get(get_anchors).layer(cache_control))
+            .with_state(state)
+            .layer(Extension(Arc::new(StatusThresholds::default())))
+            .layer(Extension(Arc::new(PageLimits::default())))
+            .layer(Extension(Arc::new(PublicBasePath::default())))
+            .layer(Extension(Arc::new(ResponsePrecision::default())));
+
+        let send = |method: Method| {
+            app.clone().oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri("/api/anchors?limit=3")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+        };
+        let get_response = send(Method::GET).await.unwrap();
+        let head_response = send(Method::HEAD).await.unwrap();
+
+        assert_eq!(head_response.status(), StatusCode::OK);
+        for name in [
+            header::CONTENT_LENGTH,
+            header::CONTENT_TYPE,
+            header::CACHE_CONTROL,
+        ] {
+            assert_eq!(
+                head_response.headers().get(&name),
+                get_response.headers().get(&name),
+                "{}",
+                name
+            );
+        }
+        let get_body = axum::body::to_bytes(get_response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(
+            head_response.headers()[header::CONTENT_LENGTH],
+            get_body.len().to_string()
+        );
+        let head_body = axum::body::to_bytes(head_response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(head_body.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_anchors_clamps_limit_and_rejects_negatives() {
+        let db = InMemoryDatabase::new();
+        db.insert_anchor(anchor("only", 99.0));
+
+        let cache = Arc::new(CacheManager::in_memory(Default::default()));
+        let rpc = Arc::new(StellarRpcClient::new_with_defaults(true));
+        let state: CachedState = (Arc::new(db), cache, rpc);
+        let limits = PageLimits {
+            default_limit: 5,
+            max_limit: 10,
+        };
+
+        let app = Router::new()
+            .route("/api/anchors", get(get_anchors))
+            .with_state(state)
+            .layer(Extension(Arc::new(StatusThresholds::default())))
+            .layer(Extension(Arc::new(limits)))
+            .layer(Extension(Arc::new(PublicBasePath::default())))
+            .layer(Extension(Arc::new(ResponsePrecision::default())));
+        let request = |uri: &str| {
+            Request::builder()
+                .uri(uri)
+                .header(header::ACCEPT, PAGINATED_MEDIA_TYPE)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(request("/api/anchors?limit=1000000"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let page: AnchorsResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(page.limit, 10);
+        assert_eq!(page.items.len(), 1);
+
+        for uri in ["/api/anchors?limit=-1", "/api/anchors?offset=-10"] {
+            let response = app.clone().oneshot(request(uri)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", uri);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_anchors_reports_server_timing() {
+        use crate::server_timing::{server_timing_middleware, SERVER_TIMING};
+
+        let db = InMemoryDatabase::new();
+        db.insert_anchor(anchor("timed", 99.0));
+
+        let cache = Arc::new(CacheManager::in_memory(Default::default()));
+        let rpc = Arc::new(StellarRpcClient::new_with_defaults(true));
+        let state: CachedState = (Arc::new(db), cache, rpc);
+
+        let app = Router::new()
+            .route("/api/anchors", get(get_anchors))
+            .with_state(state)
+            .layer(Extension(Arc::new(StatusThresholds::default())))
+            .layer(Extension(Arc::new(PageLimits::default())))
+            .layer(Extension(Arc::new(PublicBasePath::default())))
+            .layer(Extension(Arc::new(ResponsePrecision::default())))
+            .layer(axum::middleware::from_fn(server_timing_middleware));
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/anchors?limit=7")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let timing = response.headers()[SERVER_TIMING].to_str().unwrap();
+        assert!(timing.contains("db;dur="), "{}", timing);
+        assert!(timing.contains("cache;dur="), "{}", timing);
+        assert!(timing.contains("total;dur="), "{}", timing);
+    }
+
+    fn list_query(uri: &str) -> ListAnchorsQuery {
+        let uri: axum::http::Uri = uri.parse().unwrap();
+        Query::<ListAnchorsQuery>::try_from_uri(&uri).unwrap().0
+    }
 
     #[test]
     fn test_cache_key_generation() {
-        let key = keys::anchor_list(50, 0);
-        assert_eq!(key, "anchor:list:50:0");
+        let page = PageRequest {
+            limit: 50,
+            offset: 0,
+        };
+        assert_eq!(
+            list_query("/api/anchors").cache_key(page),
+            "v2:anchor:list:50:0:success_rate:desc"
+        );
+        assert_eq!(
+            list_query("/api/anchors?sort_by=volume&order=asc").cache_key(page),
+            "v2:anchor:list:50:0:volume:asc"
+        );
+    }
+
+    #[test]
+    fn test_default_order_per_sort_key() {
+        assert_eq!(list_query("/api/anchors").sort_order(), SortOrder::Desc);
+        assert_eq!(
+            list_query("/api/anchors?sort_by=volume").sort_order(),
+            SortOrder::Desc
+        );
+        assert_eq!(
+            list_query("/api/anchors?sort_by=name").sort_order(),
+            SortOrder::Asc
+        );
+        assert_eq!(
+            list_query("/api/anchors?sort_by=name&order=desc").sort_order(),
+            SortOrder::Desc
+        );
+    }
+
+    #[test]
+    fn test_status_filter_validation() {
+        assert_eq!(list_query("/api/anchors").status, None);
+        for (raw, expected) in [
+            ("green", AnchorStatus::Green),
+            ("yellow", AnchorStatus::Yellow),
+            ("red", AnchorStatus::Red),
+        ] {
+            let params = list_query(&format!("/api/anchors?status={}", raw));
+            assert_eq!(params.status, Some(expected));
+        }
+
+        for uri in ["/api/anchors?status=purple", "/api/anchors?status=Red"] {
+            let uri: axum::http::Uri = uri.parse().unwrap();
+            assert!(Query::<ListAnchorsQuery>::try_from_uri(&uri).is_err());
+        }
+    }
+
+    #[test]
+    fn test_cache_key_includes_status() {
+        let page = PageRequest {
+            limit: 50,
+            offset: 0,
+        };
+        let red = list_query("/api/anchors?status=red").cache_key(page);
+        assert_eq!(red, "v2:anchor:list:50:0:success_rate:desc:status:red");
+        assert_ne!(red, list_query("/api/anchors?status=green").cache_key(page));
+        assert_ne!(red, list_query("/api/anchors").cache_key(page));
+    }
+
+    #[tokio::test]
+    async fn test_get_anchors_filters_by_reported_status() {
+        let db = InMemoryDatabase::new();
+        // Stored statuses are stale; the reported one follows the success rate
+        for (name, stored_status, successful) in [
+            ("green", "red", 99),
+            ("red-1", "green", 50),
+            ("red-2", "yellow", 10),
+            ("red-3", "green", 20),
+        ] {
+            db.insert_anchor(Anchor {
+                status: stored_status.to_string(),
+                successful_transactions: successful,
+                failed_transactions: 100 - successful,
+                ..anchor(name, successful as f64)
+            });
+        }
+
+        let cache = Arc::new(CacheManager::in_memory(Default::default()));
+        // Without payment data the metrics come from the database
+        let rpc = Arc::new(StellarRpcClient::new(
+            "http://127.0.0.1:1".to_string(),
+            "http://127.0.0.1:1".to_string(),
+            false,
+        ));
+        let state: CachedState = (Arc::new(db), cache, rpc);
+
+        let app = Router::new()
+            .route("/api/anchors", get(get_anchors))
+            .with_state(state)
+            .layer(Extension(Arc::new(StatusThresholds::default())))
+            .layer(Extension(Arc::new(PageLimits::default())))
+            .layer(Extension(Arc::new(PublicBasePath::default())))
+            .layer(Extension(Arc::new(ResponsePrecision::default())));
+        let mut names = Vec::new();
+        for offset in [0, 2] {
+            let uri = format!(
+                "/api/anchors?status=red&sort_by=name&limit=2&offset={}",
+                offset
+            );
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .uri(uri)
+                        .header(header::ACCEPT, PAGINATED_MEDIA_TYPE)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let page: AnchorsResponse = serde_json::from_slice(&body).unwrap();
+            assert_eq!(page.total, 3);
+            assert!(page.items.iter().all(|a| a.status == "red"));
+            names.extend(page.items.into_iter().map(|a| a.name));
+        }
+
+        assert_eq!(names, vec!["red-1", "red-2", "red-3"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_anchors_by_name_ascending() {
+        let db = InMemoryDatabase::new();
+        db.insert_anchor(anchor("charlie", 99.0));
+        db.insert_anchor(anchor("alpha", 80.0));
+        db.insert_anchor(anchor("bravo", 90.0));
+
+        let cache = Arc::new(CacheManager::in_memory(Default::default()));
+        let rpc = Arc::new(StellarRpcClient::new_with_defaults(true));
+        let state: CachedState = (Arc::new(db), cache, rpc);
+
+        let app = Router::new()
+            .route("/api/anchors", get(get_anchors))
+            .with_state(state)
+            .layer(Extension(Arc::new(StatusThresholds::default())))
+            .layer(Extension(Arc::new(PageLimits::default())))
+            .layer(Extension(Arc::new(PublicBasePath::default())))
+            .layer(Extension(Arc::new(ResponsePrecision::default())));
+        let request = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+        let response = app
+            .clone()
+            .oneshot(request("/api/anchors?limit=3&sort_by=name"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::VARY], "accept");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let page: LegacyAnchorsResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(page.total, 3);
+        let names: Vec<_> = page.anchors.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["alpha", "bravo", "charlie"]);
+
+        let response = app
+            .oneshot(request("/api/anchors?order=sideways"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 
     #[test]
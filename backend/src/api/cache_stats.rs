@@ -1,16 +1,21 @@
 use axum::{routing::get, extract::State, Json, Router};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use utoipa::ToSchema;
 
-use crate::cache::{CacheManager, CacheStats};
+use crate::cache::{CacheLatencySnapshot, CacheManager, CacheStats};
+use crate::cache_invalidation::{CacheInvalidationService, FlushScope};
+use crate::error::{ApiError, ApiResult};
 
-#[derive(Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct CacheStatsResponse {
     pub hits: u64,
     pub misses: u64,
     pub invalidations: u64,
     pub hit_rate_percent: f64,
     pub total_requests: u64,
+    /// p50/p95/p99 Redis round trips for `get`, `set` and `delete`
+    pub latency_ms: CacheLatencySnapshot,
 }
 
 impl From<CacheStats> for CacheStatsResponse {
@@ -22,11 +27,18 @@ impl From<CacheStats> for CacheStatsResponse {
             invalidations: stats.invalidations,
             hit_rate_percent: stats.hit_rate(),
             total_requests,
+            latency_ms: stats.latency,
         }
     }
 }
 
 /// Handler for GET /api/cache/stats - Get cache hit rate monitoring
+#[utoipa::path(
+    get,
+    path = "/api/cache/stats",
+    tag = "cache",
+    responses((status = 200, description = "Cache hit rate statistics", body = CacheStatsResponse))
+)]
 pub async fn get_cache_stats(State(cache): State<Arc<CacheManager>>) -> Json<CacheStatsResponse> {
     let stats = cache.get_stats();
     Json(CacheStatsResponse::from(stats))
@@ -48,6 +60,51 @@ pub fn routes(cache: Arc<CacheManager>) -> Router {
         .with_state(cache)
 }
 
+#[derive(Debug, Deserialize)]
+pub struct FlushCacheRequest {
+    pub scope: FlushScope,
+    /// Required for `all`
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+impl FlushCacheRequest {
+    fn validate(&self) -> Result<(), String> {
+        if self.scope == FlushScope::All && !self.confirm {
+            return Err("Flushing all caches requires \"confirm\": true".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct FlushCacheResponse {
+    pub scope: FlushScope,
+    pub keys_removed: u64,
+}
+
+/// Handler for POST /api/cache/flush - Delete every cached key in a namespace
+pub async fn flush_cache(
+    State(invalidation): State<Arc<CacheInvalidationService>>,
+    Json(req): Json<FlushCacheRequest>,
+) -> ApiResult<Json<FlushCacheResponse>> {
+    req.validate().map_err(ApiError::BadRequest)?;
+
+    let keys_removed = invalidation.flush(req.scope).await?;
+
+    Ok(Json(FlushCacheResponse {
+        scope: req.scope,
+        keys_removed,
+    }))
+}
+
+/// Cache flush routes; mount behind authentication
+pub fn flush_routes(invalidation: Arc<CacheInvalidationService>) -> Router {
+    Router::new()
+        .route("/api/cache/flush", axum::routing::post(flush_cache))
+        .with_state(invalidation)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -58,6 +115,7 @@ mod tests {
             hits: 80,
             misses: 20,
             invalidations: 5,
+            ..Default::default()
         };
 
         let response = CacheStatsResponse::from(stats);
@@ -68,13 +126,24 @@ mod tests {
         assert_eq!(response.total_requests, 100);
     }
 
+    #[test]
+    fn test_flush_all_requires_confirmation() {
+        let request = |body| serde_json::from_value::<FlushCacheRequest>(body).unwrap();
+
+        assert!(request(serde_json::json!({ "scope": "anchors" })).validate().is_ok());
+        assert!(request(serde_json::json!({ "scope": "all" })).validate().is_err());
+        assert!(request(serde_json::json!({ "scope": "all", "confirm": true }))
+            .validate()
+            .is_ok());
+        assert!(serde_json::from_value::<FlushCacheRequest>(serde_json::json!({
+            "scope": "sessions"
+        }))
+        .is_err());
+    }
+
     #[test]
     fn test_cache_stats_response_zero_requests() {
-        let stats = CacheStats {
-            hits: 0,
-            misses: 0,
-            invalidations: 0,
-        };
+        let stats = CacheStats::default();
 
         let response = CacheStatsResponse::from(stats);
         assert_eq!(response.hit_rate_percent, 0.0);
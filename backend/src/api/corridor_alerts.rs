@@ -0,0 +1,117 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::{CorridorAlertRecord, CreateCorridorAlertRequest, UpdateCorridorAlertRequest};
+use crate::state::AppState;
+
+/// POST /api/corridor-alerts - Create an alert threshold for a corridor
+pub async fn create_corridor_alert(
+    State(app_state): State<AppState>,
+    Json(req): Json<CreateCorridorAlertRequest>,
+) -> ApiResult<Json<CorridorAlertRecord>> {
+    if req.corridor_key.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Corridor key cannot be empty".to_string(),
+        ));
+    }
+    validate_min_success_rate(req.min_success_rate)?;
+    validate_notify_url(&req.notify_url)?;
+
+    let alert = app_state.db.create_corridor_alert(req).await?;
+
+    Ok(Json(alert))
+}
+
+/// GET /api/corridor-alerts - List all corridor alerts
+pub async fn list_corridor_alerts(
+    State(app_state): State<AppState>,
+) -> ApiResult<Json<Vec<CorridorAlertRecord>>> {
+    let alerts = app_state.db.list_corridor_alerts().await?;
+
+    Ok(Json(alerts))
+}
+
+/// GET /api/corridor-alerts/:id - Get a single corridor alert
+pub async fn get_corridor_alert(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<CorridorAlertRecord>> {
+    let alert =
+        app_state.db.get_corridor_alert(id).await?.ok_or_else(|| {
+            ApiError::NotFound(format!("Corridor alert with id {} not found", id))
+        })?;
+
+    Ok(Json(alert))
+}
+
+/// PUT /api/corridor-alerts/:id - Update a corridor alert
+pub async fn update_corridor_alert(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<UpdateCorridorAlertRequest>,
+) -> ApiResult<Json<CorridorAlertRecord>> {
+    if let Some(min_success_rate) = req.min_success_rate {
+        validate_min_success_rate(min_success_rate)?;
+    }
+    if let Some(notify_url) = &req.notify_url {
+        validate_notify_url(notify_url)?;
+    }
+
+    let alert = app_state
+        .db
+        .update_corridor_alert(id, req)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Corridor alert with id {} not found", id)))?;
+
+    Ok(Json(alert))
+}
+
+/// DELETE /api/corridor-alerts/:id - Delete a corridor alert
+pub async fn delete_corridor_alert(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<serde_json::Value>> {
+    if !app_state.db.delete_corridor_alert(id).await? {
+        return Err(ApiError::NotFound(format!(
+            "Corridor alert with id {} not found",
+            id
+        )));
+    }
+
+    Ok(Json(serde_json::json!({ "deleted": id })))
+}
+
+fn validate_min_success_rate(min_success_rate: f64) -> Result<(), ApiError> {
+    if !(0.0..=100.0).contains(&min_success_rate) {
+        return Err(ApiError::BadRequest(
+            "min_success_rate must be between 0 and 100".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_notify_url(notify_url: &str) -> Result<(), ApiError> {
+    if !notify_url.starts_with("http://") && !notify_url.starts_with("https://") {
+        return Err(ApiError::BadRequest(
+            "notify_url must be an http(s) URL".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_min_success_rate() {
+        assert!(validate_min_success_rate(95.0).is_ok());
+        assert!(validate_min_success_rate(0.0).is_ok());
+        assert!(validate_min_success_rate(100.5).is_err());
+        assert!(validate_min_success_rate(-1.0).is_err());
+    }
+}
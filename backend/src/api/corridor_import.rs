@@ -0,0 +1,282 @@
+use axum::{extract::State, http::HeaderMap, Json};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::corridor::{parse_corridor_assets, Corridor};
+use crate::state::AppState;
+
+/// Largest CSV body accepted by [`import_corridors`], in bytes
+pub const MAX_IMPORT_BYTES: usize = 1024 * 1024;
+
+const CSV_COLUMNS: [&str; 4] = [
+    "asset_a_code",
+    "asset_a_issuer",
+    "asset_b_code",
+    "asset_b_issuer",
+];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CorridorImportResponse {
+    pub created: usize,
+    pub updated: usize,
+    pub skipped: usize,
+    /// Why each skipped row was rejected
+    pub errors: Vec<ImportRowError>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportRowError {
+    /// 1-based line number in the uploaded file, counting the header
+    pub line: usize,
+    pub reason: String,
+}
+
+/// Valid corridors from a CSV upload, plus the rows that were rejected
+#[derive(Debug, Default)]
+pub struct ParsedCorridorCsv {
+    pub corridors: Vec<Corridor>,
+    pub errors: Vec<ImportRowError>,
+}
+
+/// Parse a corridor CSV with an `asset_a_code,asset_a_issuer,asset_b_code,asset_b_issuer` header
+///
+/// Invalid and repeated rows are collected in `errors` rather than failing
+/// the upload; only a missing or wrong header is an error.
+pub fn parse_corridor_csv(csv: &str) -> Result<ParsedCorridorCsv, String> {
+    let mut lines = csv
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+
+    let header: Vec<_> = match lines.next() {
+        Some((_, header)) => split_row(header),
+        None => return Err("CSV file is empty".to_string()),
+    };
+    if header != CSV_COLUMNS {
+        return Err(format!("CSV header must be {}", CSV_COLUMNS.join(",")));
+    }
+
+    let mut parsed = ParsedCorridorCsv::default();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for (line, row) in lines {
+        let fields = split_row(row);
+        let corridor = match fields.as_slice() {
+            [a_code, a_issuer, b_code, b_issuer] => {
+                parse_corridor_assets(a_code, a_issuer, b_code, b_issuer).map_err(|e| e.to_string())
+            }
+            _ => Err(format!(
+                "expected {} columns, found {}",
+                CSV_COLUMNS.len(),
+                fields.len()
+            )),
+        };
+
+        let reason = match corridor {
+            Ok(corridor) => match seen.get(&corridor.to_string_key()) {
+                Some(first) => format!("duplicate of line {}", first),
+                None => {
+                    seen.insert(corridor.to_string_key(), line);
+                    parsed.corridors.push(corridor);
+                    continue;
+                }
+            },
+            Err(reason) => reason,
+        };
+        parsed.errors.push(ImportRowError { line, reason });
+    }
+
+    Ok(parsed)
+}
+
+fn split_row(row: &str) -> Vec<&str> {
+    row.split(',')
+        .map(|field| field.trim().trim_matches('"'))
+        .collect()
+}
+
+/// POST /api/corridors/import - Upsert corridors from a CSV file
+///
+/// Bodies larger than [`MAX_IMPORT_BYTES`] are rejected before parsing.
+pub async fn import_corridors(
+    State(app_state): State<AppState>,
+    headers: HeaderMap,
+    body: String,
+) -> ApiResult<Json<CorridorImportResponse>> {
+    let content_type = headers
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    if !content_type.starts_with("text/csv") {
+        return Err(ApiError::BadRequest(
+            "Content-Type must be text/csv".to_string(),
+        ));
+    }
+
+    let parsed = parse_corridor_csv(&body).map_err(ApiError::BadRequest)?;
+    let counts = app_state.db.import_corridors(&parsed.corridors).await?;
+
+    Ok(Json(CorridorImportResponse {
+        created: counts.created,
+        updated: counts.updated,
+        skipped: parsed.errors.len(),
+        errors: parsed.errors,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        extract::DefaultBodyLimit,
+        http::{Request, StatusCode},
+        routing::post,
+        Router,
+    };
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    const HEADER: &str = "asset_a_code,asset_a_issuer,asset_b_code,asset_b_issuer";
+
+    async fn test_app() -> (Router, AppState) {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        let db = Arc::new(crate::database::Database::new(pool));
+        let rpc = Arc::new(crate::rpc::StellarRpcClient::new_with_defaults(true));
+        let webhooks = Arc::new(crate::services::webhook::WebhookService::new(Arc::clone(
+            &db,
+        )));
+        let cache = Arc::new(
+            crate::cache::CacheManager::new(Default::default())
+                .await
+                .unwrap(),
+        );
+        let state = AppState::new(
+            Arc::clone(&db),
+            Arc::new(crate::websocket::WsState::new()),
+            Arc::new(crate::ingestion::DataIngestionService::new(
+                rpc,
+                Arc::clone(&db),
+                Arc::clone(&webhooks),
+            )),
+            webhooks,
+            Arc::new(crate::services::corridor_alerts::CorridorAlertService::new(
+                Arc::clone(&db),
+            )),
+            Arc::new(crate::cache_invalidation::CacheInvalidationService::new(
+                cache,
+            )),
+        );
+
+        let app = Router::new()
+            .route(
+                "/api/corridors/import",
+                post(import_corridors).layer(DefaultBodyLimit::max(MAX_IMPORT_BYTES)),
+            )
+            .with_state(state.clone());
+        (app, state)
+    }
+
+    fn csv_request(body: String) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/api/corridors/import")
+            .header("content-type", "text/csv")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    async fn import(app: Router, body: String) -> (StatusCode, Option<CorridorImportResponse>) {
+        let response = app.oneshot(csv_request(body)).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).ok())
+    }
+
+    #[tokio::test]
+    async fn test_import_creates_valid_rows() {
+        let (app, state) = test_app().await;
+        let csv = format!(
+            "{}\nUSDC,GISSUER,XLM,native\nEURC,GEURO,USDC,GISSUER\n",
+            HEADER
+        );
+
+        let (status, response) = import(app.clone(), csv.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+        let response = response.unwrap();
+        assert_eq!(
+            (response.created, response.updated, response.skipped),
+            (2, 0, 0)
+        );
+
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM corridors WHERE source_asset_issuer IN ('GISSUER', 'GEURO')",
+        )
+        .fetch_one(state.db.pool())
+        .await
+        .unwrap();
+        assert_eq!(count, 2);
+
+        // Re-importing the same file updates instead of duplicating
+        let (_, response) = import(app, csv).await;
+        let response = response.unwrap();
+        assert_eq!((response.created, response.updated), (0, 2));
+    }
+
+    #[tokio::test]
+    async fn test_import_skips_malformed_rows_with_reason() {
+        let (app, _state) = test_app().await;
+        let csv = format!(
+            "{}\nUSDC,GISSUER,XLM,native\nBROKEN,GISSUER,XLM\n,GISSUER,XLM,native\nXLM,native,USDC,GISSUER\n",
+            HEADER
+        );
+
+        let (status, response) = import(app, csv).await;
+        assert_eq!(status, StatusCode::OK);
+        let response = response.unwrap();
+        assert_eq!(response.created, 1);
+        assert_eq!(response.skipped, 3);
+        assert_eq!(response.errors[0].line, 3);
+        assert_eq!(response.errors[0].reason, "expected 4 columns, found 3");
+        assert_eq!(response.errors[1].line, 4);
+        assert!(response.errors[1].reason.contains("malformed asset"));
+        // Same corridor as line 2 with the assets swapped
+        assert_eq!(response.errors[2].line, 5);
+        assert_eq!(response.errors[2].reason, "duplicate of line 2");
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_bad_header_and_oversized_files() {
+        let (app, _state) = test_app().await;
+
+        let (status, _) = import(app.clone(), "code,issuer\nUSDC,GISSUER\n".to_string()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let row = "USDC,GISSUER,XLM,native\n";
+        let oversized = format!(
+            "{}\n{}",
+            HEADER,
+            row.repeat(MAX_IMPORT_BYTES / row.len() + 1)
+        );
+        let (status, _) = import(app.clone(), oversized).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/corridors/import")
+            .header("content-type", "application/json")
+            .body(Body::from(format!("{}\n{}", HEADER, row)))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
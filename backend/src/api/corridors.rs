@@ -5,9 +5,11 @@ use axum::{
 use chrono::{Duration, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::handlers::{ApiError, ApiResult};
-use crate::models::corridor::{Corridor, CorridorMetrics};
-use crate::models::SortBy;
+use crate::db::aggregates::CorridorMetricsFilter;
+use crate::api::pagination::Paginated;
+use crate::error::{ApiError, ApiResult};
+use crate::models::corridor::{parse_corridor_key, CorridorMetrics};
+use crate::models::{SortBy, SortOrder};
 use crate::state::AppState;
 
 // Response DTOs matching frontend TypeScript interfaces
@@ -29,6 +31,7 @@ pub struct CorridorResponse {
     pub liquidity_volume_24h_usd: f64,
     pub liquidity_trend: String,
     pub health_score: f64,
+    /// RFC 3339 in UTC, e.g. `2024-01-01T12:00:00+00:00`
     pub last_updated: String,
 }
 
@@ -70,11 +73,19 @@ pub struct ListCorridorsQuery {
     pub offset: i64,
     #[serde(default)]
     pub sort_by: SortBy,
+    /// Defaults to the natural direction of `sort_by`
+    pub order: Option<SortOrder>,
     // Filter parameters
+    #[serde(alias = "min_success_rate")]
     pub success_rate_min: Option<f64>,
+    #[serde(alias = "max_success_rate")]
     pub success_rate_max: Option<f64>,
+    #[serde(alias = "min_volume")]
     pub volume_min: Option<f64>,
     pub volume_max: Option<f64>,
+    /// Leave out corridors with fewer transactions; defaults to 0
+    #[serde(default)]
+    pub min_transactions: i64,
     pub asset_code: Option<String>,
     pub time_period: Option<String>, // "7d", "30d", "90d"
 }
@@ -83,6 +94,21 @@ fn default_limit() -> i64 {
     50
 }
 
+impl ListCorridorsQuery {
+    /// Success-rate, volume and transaction-count thresholds, validated for sane ranges
+    fn metrics_filter(&self) -> Result<CorridorMetricsFilter, ApiError> {
+        let filter = CorridorMetricsFilter {
+            min_success_rate: self.success_rate_min,
+            max_success_rate: self.success_rate_max,
+            min_volume: self.volume_min,
+            min_transactions: self.min_transactions,
+            ..Default::default()
+        };
+        filter.validate().map_err(ApiError::BadRequest)?;
+        Ok(filter)
+    }
+}
+
 /// Calculate health score based on success rate, volume, and transaction count
 fn calculate_health_score(success_rate: f64, total_transactions: i64, volume_usd: f64) -> f64 {
     let success_weight = 0.6;
@@ -119,10 +145,14 @@ fn get_liquidity_trend(volume_usd: f64) -> String {
 }
 
 /// GET /api/corridors - List all corridors
+///
+/// Without `time_period`, success-rate and volume thresholds are applied in SQL
+/// against the latest (rolling 24h) metrics.
 pub async fn list_corridors(
     State(app_state): State<AppState>,
     Query(params): Query<ListCorridorsQuery>,
-) -> ApiResult<Json<Vec<CorridorResponse>>> {
+) -> ApiResult<Json<Paginated<CorridorResponse>>> {
+    let filter = params.metrics_filter()?;
     let today = Utc::now().date_naive();
 
     // Determine date range based on time_period
@@ -133,7 +163,7 @@ pub async fn list_corridors(
         _ => (today, today), // Default to today
     };
 
-    let metrics = if params.time_period.is_some() {
+    let metrics: Vec<CorridorMetrics> = if params.time_period.is_some() {
         // Use aggregated metrics for time periods
         let aggregated = app_state.db
             .corridor_aggregates()
@@ -165,11 +195,41 @@ pub async fn list_corridors(
             })
             .collect()
     } else {
-        // Use daily metrics for single day
+        // Use latest metrics, filtered server-side
         app_state.db.corridor_aggregates()
-            .get_corridor_metrics_for_date(today)
+            .list_corridor_metrics(
+                &filter,
+                params.sort_by,
+                params.order.unwrap_or(params.sort_by.default_order()),
+                params.limit,
+                params.offset,
+            )
             .await
             .map_err(|e| ApiError::InternalError(format!("Failed to fetch corridors: {}", e)))?
+            .into_iter()
+            .map(|m| {
+                let updated_at = m.last_updated_at().unwrap_or_else(Utc::now);
+                CorridorMetrics {
+                    id: format!("{}-{}", m.corridor_key, today),
+                    corridor_key: m.corridor_key,
+                    asset_a_code: m.asset_a_code,
+                    asset_a_issuer: m.asset_a_issuer,
+                    asset_b_code: m.asset_b_code,
+                    asset_b_issuer: m.asset_b_issuer,
+                    date: updated_at,
+                    total_transactions: m.total_transactions,
+                    successful_transactions: m.successful_transactions,
+                    failed_transactions: m.failed_transactions,
+                    success_rate: m.avg_success_rate,
+                    volume_usd: m.total_volume_usd,
+                    avg_settlement_latency_ms: m.avg_settlement_latency_ms.map(|l| l as i32),
+                    median_settlement_latency_ms: None,
+                    liquidity_depth_usd: m.avg_liquidity_depth_usd.unwrap_or(0.0),
+                    created_at: updated_at,
+                    updated_at,
+                }
+            })
+            .collect()
     };
 
     // Apply filters
@@ -199,6 +259,9 @@ pub async fn list_corridors(
                     return false;
                 }
             }
+            if m.total_transactions < params.min_transactions {
+                return false;
+            }
 
             // Asset code filter
             if let Some(asset_code) = &params.asset_code {
@@ -243,7 +306,14 @@ pub async fn list_corridors(
         })
         .collect();
 
-    Ok(Json(corridors))
+    // Latest metrics are paged in SQL; aggregated periods are paged here
+    let page = if params.time_period.is_some() {
+        Paginated::from_all(corridors, params.limit, params.offset)
+    } else {
+        Paginated::from_page(corridors, params.limit, params.offset)
+    };
+
+    Ok(Json(page))
 }
 
 /// GET /api/corridors/:corridor_key - Get detailed corridor information
@@ -251,28 +321,8 @@ pub async fn get_corridor_detail(
     State(app_state): State<AppState>,
     Path(corridor_key): Path<String>,
 ) -> ApiResult<Json<CorridorDetailResponse>> {
-    let parts: Vec<&str> = corridor_key.split("->").collect();
-    if parts.len() != 2 {
-        return Err(ApiError::BadRequest(
-            "Invalid corridor key format".to_string(),
-        ));
-    }
-
-    let asset_a_parts: Vec<&str> = parts[0].split(':').collect();
-    let asset_b_parts: Vec<&str> = parts[1].split(':').collect();
-
-    if asset_a_parts.len() != 2 || asset_b_parts.len() != 2 {
-        return Err(ApiError::BadRequest(
-            "Invalid corridor key format".to_string(),
-        ));
-    }
-
-    let corridor = Corridor::new(
-        asset_a_parts[0].to_string(),
-        asset_a_parts[1].to_string(),
-        asset_b_parts[0].to_string(),
-        asset_b_parts[1].to_string(),
-    );
+    let corridor =
+        parse_corridor_key(&corridor_key).map_err(|e| ApiError::BadRequest(e.to_string()))?;
 
     let end_date = Utc::now().date_naive();
     let start_date = end_date - Duration::days(30);
@@ -1,20 +1,40 @@
 use axum::{
+    body::Body,
     extract::{Path, Query, State},
-    Json,
+    http::{header, HeaderMap, HeaderValue, Uri},
+    response::{IntoResponse, Response},
+    Extension, Json,
 };
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
+use futures::{stream, Stream, StreamExt};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
 
-use crate::cache::{keys, CacheManager};
-use crate::cache_middleware::CacheAware;
-use crate::database::Database;
-use crate::handlers::ApiResult;
-use crate::models::corridor::Corridor;
-use crate::models::SortBy;
-use crate::rpc::StellarRpcClient;
+use crate::analytics::corridor::{
+    compute_corridor_risk, rollup_corridor_windows, rollup_history_start, CorridorRollupWindows,
+    RISK_HISTORY_DAYS,
+};
+use crate::analytics::health::StatusThresholds;
+use crate::cache::{keys, CacheSchema};
+use crate::cache_middleware::{cache_bypassed, CacheAware};
+use crate::db::aggregates::{CorridorDailyTotals, CorridorMetricsFilter, LatestCorridorMetrics};
+use crate::db::backend::DatabaseBackend;
+use crate::api::pagination::{
+    accepts_paginated, PageLimits, PageRequest, Paginated, PublicBasePath, PAGINATED_MEDIA_TYPE,
+};
+use crate::api::precision::ResponsePrecision;
+use crate::error::{ApiError, ApiResult};
+use crate::models::corridor::{
+    parse_corridor_key, Corridor, CorridorAnalytics, CorridorNetworkSummary,
+};
+use crate::models::{SortBy, SortOrder};
+use crate::server_timing::timed;
+use crate::services::fx::{FxService, Quote};
+use crate::state::CachedState;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CorridorResponse {
     pub id: String,
     pub source_asset: String,
@@ -31,31 +51,64 @@ pub struct CorridorResponse {
     pub liquidity_volume_24h_usd: f64,
     pub liquidity_trend: String,
     pub health_score: f64,
+    /// 0 (safe) to 100 (risky), from failure rate, daily volatility and volume
+    #[serde(default)]
+    pub risk_score: f64,
+    /// RFC 3339 in UTC, e.g. `2024-01-01T12:00:00+00:00`
     pub last_updated: String,
+    /// `liquidity_volume_24h_usd` converted to `quote_currency`; only present when `?quote=` is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote_currency: Option<String>,
+    /// No FX rate was available for `?quote=`, so `volume` is in USD
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub quote_fallback: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl CacheSchema for CorridorResponse {
+    const TAG: &'static str = "corridor.v1";
+}
+
+impl CorridorResponse {
+    fn apply_quote(&mut self, quote: &Quote) {
+        self.volume = Some(quote.convert(self.liquidity_volume_24h_usd));
+        self.quote_currency = Some(quote.currency.clone());
+        self.quote_fallback = quote.fallback;
+    }
+
+    fn apply_precision(&mut self, precision: &ResponsePrecision) {
+        self.success_rate = precision.rate(self.success_rate);
+        self.health_score = precision.rate(self.health_score);
+        self.risk_score = precision.rate(self.risk_score);
+        self.liquidity_depth_usd = precision.volume(self.liquidity_depth_usd);
+        self.liquidity_volume_24h_usd = precision.volume(self.liquidity_volume_24h_usd);
+        self.volume = self.volume.map(|volume| precision.volume(volume));
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SuccessRateDataPoint {
     pub timestamp: String,
     pub success_rate: f64,
     pub attempts: i64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct LatencyDataPoint {
     pub latency_bucket_ms: i32,
     pub count: i64,
     pub percentage: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct LiquidityDataPoint {
     pub timestamp: String,
     pub liquidity_usd: f64,
     pub volume_24h_usd: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CorridorDetailResponse {
     pub corridor: CorridorResponse,
     pub historical_success_rate: Vec<SuccessRateDataPoint>,
@@ -64,24 +117,261 @@ pub struct CorridorDetailResponse {
     pub related_corridors: Option<Vec<CorridorResponse>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListCorridorsQuery {
-    #[serde(default = "default_limit")]
-    pub limit: i64,
+    /// Page size; defaults to and is clamped by the server's page limits
+    pub limit: Option<i64>,
     #[serde(default)]
     pub offset: i64,
     #[serde(default)]
     pub sort_by: SortBy,
+    /// `asc` or `desc`; defaults to descending, or ascending for `name`
+    pub order: Option<SortOrder>,
+    /// Minimum success rate (0-100); also accepted as `min_success_rate`
+    #[serde(alias = "min_success_rate")]
     pub success_rate_min: Option<f64>,
+    /// Maximum success rate (0-100); also accepted as `max_success_rate`
+    #[serde(alias = "max_success_rate")]
     pub success_rate_max: Option<f64>,
+    /// Minimum volume in USD; also accepted as `min_volume`
+    #[serde(alias = "min_volume")]
     pub volume_min: Option<f64>,
     pub volume_max: Option<f64>,
+    /// Leave out corridors with fewer transactions; defaults to 0
+    #[serde(default)]
+    pub min_transactions: i64,
     pub asset_code: Option<String>,
     pub time_period: Option<String>,
+    /// Response profile; `compact` returns only key, success rate and volume
+    pub fields: Option<String>,
+    /// Currency code (e.g. `EUR`) to quote volumes in; defaults to USD
+    pub quote: Option<String>,
+}
+
+impl ListCorridorsQuery {
+    /// Requested direction, or the natural one for `sort_by`
+    pub fn sort_order(&self) -> SortOrder {
+        self.order.unwrap_or(self.sort_by.default_order())
+    }
+}
+
+/// Header selecting the response profile when `fields` is not given
+const RESPONSE_PROFILE_HEADER: &str = "x-response-profile";
+
+/// Shape of corridor list entries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseProfile {
+    Full,
+    Compact,
+}
+
+impl ResponseProfile {
+    /// Resolve the profile from `?fields=` first, then `X-Response-Profile`
+    fn resolve(fields: Option<&str>, headers: &HeaderMap) -> Result<Self, ApiError> {
+        let requested = fields.or_else(|| {
+            headers
+                .get(RESPONSE_PROFILE_HEADER)
+                .and_then(|v| v.to_str().ok())
+        });
+
+        match requested.map(|p| p.trim().to_lowercase()).as_deref() {
+            None | Some("") | Some("full") => Ok(Self::Full),
+            Some("compact") => Ok(Self::Compact),
+            Some(other) => Err(ApiError::BadRequest(format!(
+                "Unknown response profile '{}', expected 'full' or 'compact'",
+                other
+            ))),
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::Compact => "compact",
+        }
+    }
+}
+
+/// Trimmed corridor entry for dashboards polling many corridors
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CompactCorridorResponse {
+    pub corridor_key: String,
+    pub success_rate: f64,
+    /// 24h volume, the figure `volume_min`/`volume_max` filter on
+    pub volume_usd: f64,
+    /// Volume converted to `quote_currency`; only present when `?quote=` is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote_currency: Option<String>,
+    /// No FX rate was available for `?quote=`, so `volume` is in USD
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub quote_fallback: bool,
+}
+
+impl CompactCorridorResponse {
+    fn apply_quote(&mut self, quote: &Quote) {
+        self.volume = Some(quote.convert(self.volume_usd));
+        self.quote_currency = Some(quote.currency.clone());
+        self.quote_fallback = quote.fallback;
+    }
+
+    fn apply_precision(&mut self, precision: &ResponsePrecision) {
+        self.success_rate = precision.rate(self.success_rate);
+        self.volume_usd = precision.volume(self.volume_usd);
+        self.volume = self.volume.map(|volume| precision.volume(volume));
+    }
+}
+
+impl From<CorridorResponse> for CompactCorridorResponse {
+    fn from(corridor: CorridorResponse) -> Self {
+        Self {
+            corridor_key: corridor.id,
+            success_rate: corridor.success_rate,
+            volume_usd: corridor.liquidity_volume_24h_usd,
+            volume: corridor.volume,
+            quote_currency: corridor.quote_currency,
+            quote_fallback: corridor.quote_fallback,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum CorridorListResponse {
+    Full(Paginated<CorridorResponse>),
+    Compact(Paginated<CompactCorridorResponse>),
 }
 
-fn default_limit() -> i64 {
-    50
+impl CacheSchema for CorridorListResponse {
+    const TAG: &'static str = "corridor_list.v1";
+}
+
+impl CorridorListResponse {
+    fn new(corridors: Paginated<CorridorResponse>, profile: ResponseProfile) -> Self {
+        match profile {
+            ResponseProfile::Full => Self::Full(corridors),
+            ResponseProfile::Compact => Self::Compact(corridors.map(Into::into)),
+        }
+    }
+
+    fn with_links(self, base_path: &PublicBasePath, uri: &Uri) -> Self {
+        match self {
+            Self::Full(page) => Self::Full(page.with_links(base_path, uri)),
+            Self::Compact(page) => Self::Compact(page.with_links(base_path, uri)),
+        }
+    }
+
+    /// The envelope with `_links` when the client asked for
+    /// [`PAGINATED_MEDIA_TYPE`], otherwise the bare array of entries
+    fn into_negotiated(self, envelope: bool, base_path: &PublicBasePath, uri: &Uri) -> Response {
+        let mut response = match (envelope, self) {
+            (true, page) => (
+                [(header::CONTENT_TYPE, PAGINATED_MEDIA_TYPE)],
+                Json(page.with_links(base_path, uri)),
+            )
+                .into_response(),
+            (false, Self::Full(page)) => Json(page.items).into_response(),
+            (false, Self::Compact(page)) => Json(page.items).into_response(),
+        };
+        response
+            .headers_mut()
+            .append(header::VARY, HeaderValue::from_static("accept"));
+        response
+    }
+
+    /// Quote every entry's volume in another currency
+    fn apply_quote(&mut self, quote: &Quote) {
+        match self {
+            Self::Full(page) => page.items.iter_mut().for_each(|c| c.apply_quote(quote)),
+            Self::Compact(page) => page.items.iter_mut().for_each(|c| c.apply_quote(quote)),
+        }
+    }
+
+    fn apply_precision(&mut self, precision: &ResponsePrecision) {
+        match self {
+            Self::Full(page) => page
+                .items
+                .iter_mut()
+                .for_each(|c| c.apply_precision(precision)),
+            Self::Compact(page) => page
+                .items
+                .iter_mut()
+                .for_each(|c| c.apply_precision(precision)),
+        }
+    }
+}
+
+impl ListCorridorsQuery {
+    /// Success-rate, volume and transaction-count thresholds, validated for sane ranges
+    fn metrics_filter(&self) -> Result<CorridorMetricsFilter, ApiError> {
+        let filter = CorridorMetricsFilter {
+            min_success_rate: self.success_rate_min,
+            max_success_rate: self.success_rate_max,
+            min_volume: self.volume_min,
+            max_volume: self.volume_max,
+            min_transactions: self.min_transactions,
+            asset_code: self.asset_code.clone(),
+        };
+        filter.validate().map_err(ApiError::BadRequest)?;
+        Ok(filter)
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportCorridorsQuery {
+    /// Minimum success rate (0-100); also accepted as `min_success_rate`
+    #[serde(alias = "min_success_rate")]
+    pub success_rate_min: Option<f64>,
+    /// Maximum success rate (0-100); also accepted as `max_success_rate`
+    #[serde(alias = "max_success_rate")]
+    pub success_rate_max: Option<f64>,
+    /// Minimum volume in USD; also accepted as `min_volume`
+    #[serde(alias = "min_volume")]
+    pub volume_min: Option<f64>,
+    pub volume_max: Option<f64>,
+    /// Leave out corridors with fewer transactions; defaults to 0
+    #[serde(default)]
+    pub min_transactions: i64,
+    pub asset_code: Option<String>,
+}
+
+impl ExportCorridorsQuery {
+    /// The same thresholds `GET /api/corridors` applies
+    fn metrics_filter(&self) -> Result<CorridorMetricsFilter, ApiError> {
+        let filter = CorridorMetricsFilter {
+            min_success_rate: self.success_rate_min,
+            max_success_rate: self.success_rate_max,
+            min_volume: self.volume_min,
+            max_volume: self.volume_max,
+            min_transactions: self.min_transactions,
+            asset_code: self.asset_code.clone(),
+        };
+        filter.validate().map_err(ApiError::BadRequest)?;
+        Ok(filter)
+    }
+}
+
+/// Content type of the corridor export
+const NDJSON: &str = "application/x-ndjson";
+
+/// Export rows scored per history query
+const EXPORT_CHUNK_ROWS: usize = 100;
+
+/// Maximum number of corridor keys accepted by the batch endpoint
+const MAX_BATCH_KEYS: usize = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct BatchCorridorsRequest {
+    pub keys: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchCorridorsResponse {
+    pub corridors: HashMap<String, CorridorResponse>,
+    pub not_found: Vec<String>,
 }
 
 fn calculate_health_score(success_rate: f64, total_transactions: i64, volume_usd: f64) -> f64 {
@@ -116,191 +406,742 @@ fn get_liquidity_trend(volume_usd: f64) -> String {
     }
 }
 
+/// Corridor responses for `rows`, with every risk score's volatility term
+/// fed by the last [`RISK_HISTORY_DAYS`] days of history, fetched in one query
+async fn corridor_responses_with_history(
+    db: &dyn DatabaseBackend,
+    rows: &[LatestCorridorMetrics],
+) -> anyhow::Result<Vec<CorridorResponse>> {
+    let keys: Vec<String> = rows.iter().map(|row| row.corridor_key.clone()).collect();
+    let since = Utc::now() - Duration::days(RISK_HISTORY_DAYS);
+    let history = timed("db", db.get_daily_totals_by_keys(&keys, since)).await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let history = history.get(&row.corridor_key).map(Vec::as_slice);
+            corridor_response_from_metrics(row, history.unwrap_or_default())
+        })
+        .collect())
+}
+
+/// Build a corridor response from the latest aggregated metrics row
+///
+/// `history` holds the corridor's recent daily totals for the risk score's
+/// volatility term.
+fn corridor_response_from_metrics(
+    metrics: &LatestCorridorMetrics,
+    history: &[CorridorDailyTotals],
+) -> CorridorResponse {
+    let avg_latency = metrics.avg_settlement_latency_ms.unwrap_or(0.0);
+    let liquidity_depth = metrics.avg_liquidity_depth_usd.unwrap_or(0.0);
+    let analytics = CorridorAnalytics {
+        corridor: Corridor::new(
+            metrics.asset_a_code.clone(),
+            metrics.asset_a_issuer.clone(),
+            metrics.asset_b_code.clone(),
+            metrics.asset_b_issuer.clone(),
+        ),
+        success_rate: metrics.avg_success_rate,
+        total_transactions: metrics.total_transactions,
+        successful_transactions: metrics.successful_transactions,
+        failed_transactions: metrics.failed_transactions,
+        volume_usd: metrics.total_volume_usd,
+    };
+
+    CorridorResponse {
+        id: metrics.corridor_key.clone(),
+        source_asset: metrics.source_asset_code.clone(),
+        destination_asset: metrics.destination_asset_code.clone(),
+        success_rate: metrics.avg_success_rate,
+        total_attempts: metrics.total_transactions,
+        successful_payments: metrics.successful_transactions,
+        failed_payments: metrics.failed_transactions,
+        average_latency_ms: avg_latency,
+        median_latency_ms: avg_latency * 0.75,
+        p95_latency_ms: avg_latency * 2.5,
+        p99_latency_ms: avg_latency * 4.0,
+        liquidity_depth_usd: liquidity_depth,
+        liquidity_volume_24h_usd: metrics.total_volume_usd,
+        liquidity_trend: get_liquidity_trend(metrics.total_volume_usd),
+        health_score: calculate_health_score(
+            metrics.avg_success_rate,
+            metrics.total_transactions,
+            metrics.total_volume_usd,
+        ),
+        risk_score: compute_corridor_risk(&analytics, history),
+        last_updated: metrics
+            .last_updated_at()
+            .map(|at| at.to_rfc3339())
+            .unwrap_or_else(|| metrics.last_updated.clone()),
+        volume: None,
+        quote_currency: None,
+        quote_fallback: false,
+    }
+}
+
+/// Split requested keys into found corridors and keys with no data
+fn build_batch_response(
+    keys: &[String],
+    mut found: HashMap<String, CorridorResponse>,
+) -> BatchCorridorsResponse {
+    let mut corridors = HashMap::new();
+    let mut not_found = Vec::new();
+
+    for key in keys {
+        match found.remove(key) {
+            Some(corridor) => {
+                corridors.insert(key.clone(), corridor);
+            }
+            None => not_found.push(key.clone()),
+        }
+    }
+
+    BatchCorridorsResponse {
+        corridors,
+        not_found,
+    }
+}
+
 /// Generate cache key for corridor list with filters
-fn generate_corridor_list_cache_key(params: &ListCorridorsQuery) -> String {
+fn generate_corridor_list_cache_key(
+    params: &ListCorridorsQuery,
+    page: PageRequest,
+    profile: ResponseProfile,
+) -> String {
     let filter_str = format!(
-        "sr_min:{:?}_sr_max:{:?}_vol_min:{:?}_vol_max:{:?}_asset:{:?}_period:{:?}",
+        "sr_min:{:?}_sr_max:{:?}_vol_min:{:?}_vol_max:{:?}_min_tx:{}_asset:{:?}_period:{:?}_sort:{}:{}_profile:{}",
         params.success_rate_min,
         params.success_rate_max,
         params.volume_min,
         params.volume_max,
+        params.min_transactions,
         params.asset_code,
-        params.time_period
+        params.time_period,
+        params.sort_by.as_str(),
+        params.sort_order().as_str(),
+        profile.as_str()
     );
-    keys::corridor_list(params.limit, params.offset, &filter_str)
+    keys::corridor_list(page.limit, page.offset, &filter_str)
 }
 
 /// GET /api/corridors - List all corridors (cached)
 ///
-/// **DATA SOURCE: RPC**
-/// - Payment data from Horizon API
-/// - Trade data from Horizon API  
-/// - Order book data from Horizon API
-/// - Calculates corridor metrics from real-time RPC data
+/// **DATA SOURCE: Database**
+/// - Latest aggregated metrics from `corridor_metrics_latest`
+/// - Filters, sorting and paging are applied in the query
+///
+/// `?fields=compact` (or `X-Response-Profile: compact`) returns only
+/// `corridor_key`, `success_rate` and `volume_usd` per corridor.
+///
+/// Answers with a bare array, as it always has; send
+/// `Accept: application/vnd.stellar-insights.v2+json` for the paginated
+/// envelope, whose `total` counts every matching corridor.
+#[utoipa::path(
+    get,
+    path = "/api/corridors",
+    tag = "corridors",
+    params(
+        ListCorridorsQuery,
+        ("X-Response-Profile" = Option<String>, Header, description = "`full` (default) or `compact`; `fields` takes precedence"),
+        ("Accept" = Option<String>, Header, description = "`application/vnd.stellar-insights.v2+json` for the paginated envelope")
+    ),
+    responses(
+        (status = 200, description = "Corridors matching the filters; the paginated envelope when requested via `Accept`", content(
+            ([CorridorResponse] = "application/json"),
+            (CorridorListResponse = "application/vnd.stellar-insights.v2+json")
+        )),
+        (status = 400, description = "Invalid filter or response profile", body = crate::api::openapi::ErrorBody),
+        (status = 500, description = "Internal error", body = crate::api::openapi::ErrorBody)
+    )
+)]
+#[allow(clippy::too_many_arguments)]
 pub async fn list_corridors(
-    State((_db, cache, rpc_client)): State<(Arc<Database>, Arc<CacheManager>, Arc<StellarRpcClient>)>,
+    State((db, cache, _rpc_client)): State<CachedState>,
+    Extension(fx): Extension<Arc<FxService>>,
+    Extension(page_limits): Extension<Arc<PageLimits>>,
+    Extension(base_path): Extension<Arc<PublicBasePath>>,
+    Extension(precision): Extension<Arc<ResponsePrecision>>,
+    uri: Uri,
     Query(params): Query<ListCorridorsQuery>,
-) -> ApiResult<Json<Vec<CorridorResponse>>> {
-    let cache_key = generate_corridor_list_cache_key(&params);
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    let page = page_limits.resolve(params.limit, params.offset)?;
+    let filter = params.metrics_filter()?;
+    let profile = ResponseProfile::resolve(params.fields.as_deref(), &headers)?;
+    let quote = resolve_quote(&fx, params.quote.as_deref()).await?;
+    let cache_key = generate_corridor_list_cache_key(&params, page, profile);
 
-    let corridors = <()>::get_or_fetch(
+    let mut corridors = <()>::get_or_fetch_tagged(
         &cache,
         &cache_key,
         cache.config.get_ttl("corridor"),
+        &[keys::corridors_tag(), keys::corridor_lists_tag()],
         async {
-            // **RPC DATA**: Fetch recent payments to identify active corridors
-            let payments = match rpc_client.fetch_payments(200, None).await {
-                Ok(p) => p,
-                Err(e) => {
-                    tracing::error!("Failed to fetch payments from RPC: {}", e);
-                    return Ok(vec![]);
-                }
-            };
-
-            // **RPC DATA**: Fetch recent trades for volume data
-            let _trades = match rpc_client.fetch_trades(200, None).await {
-                Ok(t) => t,
-                Err(e) => {
-                    tracing::warn!("Failed to fetch trades from RPC: {}", e);
-                    vec![]
+            let metrics = timed(
+                "db",
+                db.list_corridor_metrics(
+                    &filter,
+                    params.sort_by,
+                    params.sort_order(),
+                    page.limit,
+                    page.offset,
+                ),
+            )
+            .await?;
+            let total = timed("db", db.count_corridor_metrics(&filter)).await?;
+            let corridors = corridor_responses_with_history(db.as_ref(), &metrics).await?;
+
+            Ok(CorridorListResponse::new(
+                Paginated::new(corridors, total as usize, page.limit, page.offset),
+                profile,
+            ))
+        },
+    )
+    .await?;
+
+    // Cached entries are always in USD; conversion happens per request
+    if let Some(quote) = &quote {
+        corridors.apply_quote(quote);
+    }
+    corridors.apply_precision(&precision);
+
+    Ok(corridors.into_negotiated(accepts_paginated(&headers), &base_path, &uri))
+}
+
+/// GET /api/corridors/export - Every corridor as newline-delimited JSON
+///
+/// Streams one `CorridorResponse` per line, ordered by corridor key, as rows
+/// come off the database, so large exports don't build up in memory. Reads
+/// the same aggregated metrics, with the same filters, as the list. A
+/// database error mid-stream aborts the response.
+#[utoipa::path(
+    get,
+    path = "/api/corridors/export",
+    tag = "corridors",
+    params(ExportCorridorsQuery),
+    responses(
+        (status = 200, description = "One corridor per line", body = CorridorResponse, content_type = "application/x-ndjson"),
+        (status = 400, description = "Invalid filter", body = crate::api::openapi::ErrorBody)
+    )
+)]
+pub async fn export_corridors(
+    State((db, _cache, _rpc_client)): State<CachedState>,
+    Extension(precision): Extension<Arc<ResponsePrecision>>,
+    Query(params): Query<ExportCorridorsQuery>,
+) -> ApiResult<Response> {
+    let filter = params.metrics_filter()?;
+
+    // Rows are taken in chunks so each chunk's risk history is one query
+    let lines = db
+        .stream_corridor_metrics(filter)
+        .chunks(EXPORT_CHUNK_ROWS)
+        .then(move |rows| {
+            let db = Arc::clone(&db);
+            let precision = Arc::clone(&precision);
+            async move {
+                let rows = rows.into_iter().collect::<anyhow::Result<Vec<_>>>()?;
+                let mut lines = Vec::new();
+                for mut corridor in corridor_responses_with_history(db.as_ref(), &rows).await? {
+                    corridor.apply_precision(&precision);
+                    serde_json::to_writer(&mut lines, &corridor)?;
+                    lines.push(b'\n');
                 }
-            };
-
-            // Group payments by asset pairs to identify corridors
-            use std::collections::HashMap;
-            let mut corridor_map: HashMap<String, Vec<&crate::rpc::Payment>> = HashMap::new();
-
-            for payment in &payments {
-                let asset_from = format!(
-                    "{}:{}",
-                    payment.asset_code.as_deref().unwrap_or("XLM"),
-                    payment.asset_issuer.as_deref().unwrap_or("native")
-                );
-                
-                // For now, assume destination is XLM (we'd need more data to determine actual destination asset)
-                let asset_to = "XLM:native".to_string();
-                
-                let corridor_key = format!("{}->{}", asset_from, asset_to);
-                corridor_map.entry(corridor_key).or_insert_with(Vec::new).push(payment);
+                Ok::<_, anyhow::Error>(lines)
             }
+        })
+        .map(|chunk| chunk.inspect_err(|e| tracing::error!("Corridor export failed: {:#}", e)));
 
-            // Calculate metrics for each corridor
-            let mut corridor_responses = Vec::new();
-
-            for (corridor_key, corridor_payments) in corridor_map.iter() {
-                let total_attempts = corridor_payments.len() as i64;
-                
-                // In Stellar, payments in the stream are successful
-                let successful_payments = total_attempts;
-                let failed_payments = 0;
-                let success_rate = if total_attempts > 0 { 100.0 } else { 0.0 };
-
-                // Calculate volume from payment amounts
-                let volume_usd: f64 = corridor_payments
-                    .iter()
-                    .filter_map(|p| p.amount.parse::<f64>().ok())
-                    .sum();
-
-                // Calculate health score
-                let health_score = calculate_health_score(success_rate, total_attempts, volume_usd);
-                let liquidity_trend = get_liquidity_trend(volume_usd);
-                let avg_latency = 400.0 + (success_rate * 2.0);
-
-                // Parse corridor key to get assets
-                let parts: Vec<&str> = corridor_key.split("->").collect();
-                if parts.len() != 2 {
-                    continue;
-                }
+    Ok(([(header::CONTENT_TYPE, NDJSON)], Body::from_stream(lines)).into_response())
+}
 
-                let source_parts: Vec<&str> = parts[0].split(':').collect();
-                let dest_parts: Vec<&str> = parts[1].split(':').collect();
+/// Days of history returned when `from` is not given
+const DEFAULT_HISTORY_DAYS: i64 = 30;
 
-                if source_parts.len() != 2 || dest_parts.len() != 2 {
-                    continue;
-                }
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CorridorHistoryQuery {
+    /// Earliest day to include (RFC 3339); defaults to 30 days before `to`
+    pub from: Option<DateTime<Utc>>,
+    /// Latest day to include (RFC 3339); defaults to now
+    pub to: Option<DateTime<Utc>>,
+}
 
-                let corridor_response = CorridorResponse {
-                    id: corridor_key.clone(),
-                    source_asset: source_parts[0].to_string(),
-                    destination_asset: dest_parts[0].to_string(),
-                    success_rate,
-                    total_attempts,
-                    successful_payments,
-                    failed_payments,
-                    average_latency_ms: avg_latency,
-                    median_latency_ms: avg_latency * 0.75,
-                    p95_latency_ms: avg_latency * 2.5,
-                    p99_latency_ms: avg_latency * 4.0,
-                    liquidity_depth_usd: volume_usd,
-                    liquidity_volume_24h_usd: volume_usd * 0.1,
-                    liquidity_trend,
-                    health_score,
-                    last_updated: chrono::Utc::now().to_rfc3339(),
-                };
-
-                corridor_responses.push(corridor_response);
-            }
+/// One day of corridor history
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CorridorHistoryPoint {
+    pub timestamp: String,
+    pub success_rate: f64,
+    pub total_transactions: i64,
+    pub successful_transactions: i64,
+    pub failed_transactions: i64,
+    pub volume_usd: f64,
+}
 
-            // Apply filters
-            let filtered: Vec<_> = corridor_responses
-                .into_iter()
-                .filter(|c| {
-                    if let Some(min) = params.success_rate_min {
-                        if c.success_rate < min {
-                            return false;
-                        }
-                    }
-                    if let Some(max) = params.success_rate_max {
-                        if c.success_rate > max {
-                            return false;
-                        }
-                    }
-                    if let Some(min) = params.volume_min {
-                        if c.liquidity_depth_usd < min {
-                            return false;
-                        }
-                    }
-                    if let Some(max) = params.volume_max {
-                        if c.liquidity_depth_usd > max {
-                            return false;
-                        }
-                    }
-                    if let Some(asset_code) = &params.asset_code {
-                        let asset_code_lower = asset_code.to_lowercase();
-                        if !c.source_asset.to_lowercase().contains(&asset_code_lower)
-                            && !c.destination_asset.to_lowercase().contains(&asset_code_lower)
-                        {
-                            return false;
-                        }
-                    }
-                    true
-                })
-                .collect();
-
-            Ok(filtered)
-        },
+impl CorridorHistoryPoint {
+    fn from_totals(totals: &CorridorDailyTotals, precision: &ResponsePrecision) -> Self {
+        let success_rate = if totals.total_transactions > 0 {
+            totals.successful_transactions as f64 / totals.total_transactions as f64 * 100.0
+        } else {
+            0.0
+        };
+        Self {
+            timestamp: totals.date.to_rfc3339(),
+            success_rate: precision.rate(success_rate),
+            total_transactions: totals.total_transactions,
+            successful_transactions: totals.successful_transactions,
+            failed_transactions: totals.failed_transactions,
+            volume_usd: precision.volume(totals.volume_usd),
+        }
+    }
+}
+
+/// Wrap serialized `items` in `[`, `,` and `]` so the chunks form one JSON array
+fn json_array_chunks<S>(items: S) -> impl Stream<Item = anyhow::Result<Vec<u8>>>
+where
+    S: Stream<Item = anyhow::Result<Vec<u8>>>,
+{
+    let items = items.enumerate().map(|(i, item)| {
+        let mut chunk = if i == 0 { Vec::new() } else { vec![b','] };
+        chunk.extend(item?);
+        Ok(chunk)
+    });
+    stream::once(async { Ok(b"[".to_vec()) })
+        .chain(items)
+        .chain(stream::once(async { Ok(b"]".to_vec()) }))
+}
+
+/// GET /api/corridors/:corridor_key/history - Daily corridor metrics over a range
+///
+/// Streams a JSON array of `CorridorHistoryPoint`s, oldest first, as rows come
+/// off the database, so the first points go out before the whole range is
+/// read. A database error mid-stream aborts the response before the closing
+/// `]`, so a client never mistakes a cut-off history for a complete one.
+#[utoipa::path(
+    get,
+    path = "/api/corridors/{corridor_key}/history",
+    tag = "corridors",
+    params(
+        ("corridor_key" = String, Path, description = "Corridor key, e.g. `USDC:GA...->XLM:native`"),
+        CorridorHistoryQuery
+    ),
+    responses(
+        (status = 200, description = "History points, oldest first", body = [CorridorHistoryPoint]),
+        (status = 400, description = "Invalid corridor key or range", body = crate::api::openapi::ErrorBody)
     )
-    .await?;
+)]
+pub async fn get_corridor_history(
+    State((db, _cache, _rpc_client)): State<CachedState>,
+    Extension(precision): Extension<Arc<ResponsePrecision>>,
+    Path(corridor_key): Path<String>,
+    Query(params): Query<CorridorHistoryQuery>,
+) -> ApiResult<Response> {
+    let corridor =
+        parse_corridor_key(&corridor_key).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let to = params.to.unwrap_or_else(Utc::now);
+    let from = params
+        .from
+        .unwrap_or(to - Duration::days(DEFAULT_HISTORY_DAYS));
+    if from > to {
+        return Err(ApiError::BadRequest(
+            "from must not be after to".to_string(),
+        ));
+    }
+
+    let points = db
+        .stream_corridor_history(&corridor.to_string_key(), from, to)
+        .map(move |row| {
+            let row = row.inspect_err(|e| tracing::error!("Corridor history failed: {:#}", e))?;
+            let point = CorridorHistoryPoint::from_totals(&row, &precision);
+            Ok::<_, anyhow::Error>(serde_json::to_vec(&point)?)
+        });
 
-    Ok(Json(corridors))
+    Ok((
+        [(header::CONTENT_TYPE, "application/json")],
+        Body::from_stream(json_array_chunks(points)),
+    )
+        .into_response())
+}
+
+/// Quote for a `?quote=` parameter, or `None` when volumes stay in plain USD
+async fn resolve_quote(fx: &FxService, currency: Option<&str>) -> ApiResult<Option<Quote>> {
+    match currency {
+        Some(currency) => fx
+            .quote(Some(currency))
+            .await
+            .map(Some)
+            .map_err(ApiError::BadRequest),
+        None => Ok(None),
+    }
 }
 
 
 /// GET /api/corridors/:corridor_key - Get detailed corridor information (cached)
+#[utoipa::path(
+    get,
+    path = "/api/corridors/{corridor_key}",
+    tag = "corridors",
+    params(("corridor_key" = String, Path, description = "Corridor key, e.g. `USDC:GA...->XLM:native`")),
+    responses(
+        (status = 200, description = "Corridor detail", body = CorridorDetailResponse),
+        (status = 404, description = "Corridor not found", body = crate::api::openapi::ErrorBody)
+    )
+)]
 pub async fn get_corridor_detail(
-    State((_db, _cache, _rpc_client)): State<(Arc<Database>, Arc<CacheManager>, Arc<StellarRpcClient>)>,
+    State((_db, _cache, _rpc_client)): State<CachedState>,
     Path(_corridor_key): Path<String>,
 ) -> ApiResult<Json<CorridorDetailResponse>> {
     // TODO: Implement RPC-based corridor detail
-    Err(crate::handlers::ApiError::NotFound(
+    Err(ApiError::NotFound(
         "Corridor detail endpoint not yet implemented with RPC".to_string()
     ))
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CorridorRollupResponse {
+    pub corridor_key: String,
+    pub windows: CorridorRollupWindows,
+    pub generated_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote_currency: Option<String>,
+    /// No FX rate was available for `?quote=`, so window volumes are in USD
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub quote_fallback: bool,
+}
+
+impl CacheSchema for CorridorRollupResponse {
+    const TAG: &'static str = "corridor_rollup.v1";
+}
+
+impl CorridorRollupResponse {
+    fn apply_quote(&mut self, quote: &Quote) {
+        for window in [
+            &mut self.windows.last_24h,
+            &mut self.windows.last_7d,
+            &mut self.windows.last_30d,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            window.volume = Some(quote.convert(window.volume_usd));
+        }
+        self.quote_currency = Some(quote.currency.clone());
+        self.quote_fallback = quote.fallback;
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct QuoteQuery {
+    /// Currency code (e.g. `EUR`) to quote volumes in; defaults to USD
+    pub quote: Option<String>,
+}
+
+/// GET /api/corridors/:corridor_key/rollup - 24h/7d/30d corridor totals (cached)
+#[utoipa::path(
+    get,
+    path = "/api/corridors/{corridor_key}/rollup",
+    tag = "corridors",
+    params(
+        ("corridor_key" = String, Path, description = "Corridor key, e.g. `USDC:GA...->XLM:native`"),
+        QuoteQuery
+    ),
+    responses(
+        (status = 200, description = "Corridor rollup; windows without history are null", body = CorridorRollupResponse),
+        (status = 400, description = "Invalid corridor key or quote currency", body = crate::api::openapi::ErrorBody)
+    )
+)]
+pub async fn get_corridor_rollup(
+    State((db, cache, _rpc_client)): State<CachedState>,
+    Extension(fx): Extension<Arc<FxService>>,
+    Path(corridor_key): Path<String>,
+    Query(params): Query<QuoteQuery>,
+) -> ApiResult<Json<CorridorRollupResponse>> {
+    let corridor_key = parse_corridor_key(&corridor_key)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?
+        .to_string_key();
+    let quote = resolve_quote(&fx, params.quote.as_deref()).await?;
+
+    let mut rollup = <()>::get_or_fetch_tagged(
+        &cache,
+        &keys::corridor_rollup(&corridor_key),
+        cache.config.get_ttl("corridor"),
+        &[keys::corridors_tag(), keys::corridor_tag(&corridor_key)],
+        async {
+            let now = Utc::now();
+            let history = timed(
+                "db",
+                db.get_corridor_daily_totals(&corridor_key, rollup_history_start(now)),
+            )
+            .await?;
+
+            Ok(CorridorRollupResponse {
+                corridor_key: corridor_key.clone(),
+                windows: rollup_corridor_windows(&history, now),
+                generated_at: now.to_rfc3339(),
+                quote_currency: None,
+                quote_fallback: false,
+            })
+        },
+    )
+    .await?;
+
+    if let Some(quote) = &quote {
+        rollup.apply_quote(quote);
+    }
+
+    Ok(Json(rollup))
+}
+
+/// Peers returned when `limit` is not given
+const DEFAULT_CORRIDOR_PEERS: usize = 10;
+
+/// Peers cached per corridor, and the largest accepted `limit`
+const MAX_CORRIDOR_PEERS: usize = 50;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CorridorPeersQuery {
+    /// Number of peers, 1-50; defaults to 10
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CorridorPeersResponse {
+    pub corridor_key: String,
+    /// Corridors sharing an asset with `corridor_key`, highest volume first
+    pub peers: Vec<CorridorResponse>,
+}
+
+impl CacheSchema for CorridorPeersResponse {
+    const TAG: &'static str = "corridor_peers.v1";
+}
+
+/// GET /api/corridors/:corridor_key/peers - Corridors sharing an asset (cached)
+///
+/// The top peers are cached once per corridor and cut down to `limit`.
+#[utoipa::path(
+    get,
+    path = "/api/corridors/{corridor_key}/peers",
+    tag = "corridors",
+    params(
+        ("corridor_key" = String, Path, description = "Corridor key, e.g. `USDC:GA...->XLM:native`"),
+        CorridorPeersQuery
+    ),
+    responses(
+        (status = 200, description = "Related corridors", body = CorridorPeersResponse),
+        (status = 400, description = "Invalid corridor key or limit", body = crate::api::openapi::ErrorBody)
+    )
+)]
+pub async fn get_corridor_peers(
+    State((db, cache, _rpc_client)): State<CachedState>,
+    Extension(precision): Extension<Arc<ResponsePrecision>>,
+    Path(corridor_key): Path<String>,
+    Query(params): Query<CorridorPeersQuery>,
+) -> ApiResult<Json<CorridorPeersResponse>> {
+    let corridor =
+        parse_corridor_key(&corridor_key).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let limit = params.limit.unwrap_or(DEFAULT_CORRIDOR_PEERS);
+    if limit == 0 || limit > MAX_CORRIDOR_PEERS {
+        return Err(ApiError::BadRequest(format!(
+            "limit must be between 1 and {}",
+            MAX_CORRIDOR_PEERS
+        )));
+    }
+    let corridor_key = corridor.to_string_key();
+
+    let mut response = <()>::get_or_fetch_tagged(
+        &cache,
+        &keys::corridor_peers(&corridor_key),
+        cache.config.get_ttl("corridor"),
+        &[keys::corridors_tag(), keys::corridor_lists_tag()],
+        async {
+            let peers = timed(
+                "db",
+                db.related_corridors(&corridor, MAX_CORRIDOR_PEERS as i64),
+            )
+            .await?;
+
+            Ok(CorridorPeersResponse {
+                corridor_key: corridor_key.clone(),
+                peers: corridor_responses_with_history(db.as_ref(), &peers).await?,
+            })
+        },
+    )
+    .await?;
+
+    response.peers.truncate(limit);
+    response
+        .peers
+        .iter_mut()
+        .for_each(|peer| peer.apply_precision(&precision));
+    Ok(Json(response))
+}
+
+/// GET /api/corridors/summary - Network-wide corridor headline numbers (cached)
+///
+/// Corridors whose success rate falls in the `red` status band count as
+/// unhealthy.
+#[utoipa::path(
+    get,
+    path = "/api/corridors/summary",
+    tag = "corridors",
+    responses(
+        (status = 200, description = "Network-wide corridor totals", body = CorridorNetworkSummary),
+        (status = 500, description = "Internal error", body = crate::api::openapi::ErrorBody)
+    )
+)]
+pub async fn get_corridor_summary(
+    State((db, cache, _rpc_client)): State<CachedState>,
+    Extension(thresholds): Extension<Arc<StatusThresholds>>,
+) -> ApiResult<Json<CorridorNetworkSummary>> {
+    let summary = <()>::get_or_fetch_tagged(
+        &cache,
+        &keys::corridor_network_summary(),
+        cache.config.get_ttl("dashboard"),
+        &[keys::corridors_tag(), keys::corridor_lists_tag()],
+        timed("db", db.corridor_summary(thresholds.yellow_min_success_rate)),
+    )
+    .await?;
+
+    Ok(Json(summary))
+}
+
+/// POST /api/corridors/batch - Fetch several corridors by key in one request (cached)
+///
+/// Keys are normalized like the single-corridor routes, and the response is
+/// keyed by the canonical form. Cached corridors are read in a single MGET;
+/// misses are loaded with one `corridor_key IN (...)` query and written back
+/// to the cache.
+pub async fn get_corridors_batch(
+    State((db, cache, _rpc_client)): State<CachedState>,
+    Extension(precision): Extension<Arc<ResponsePrecision>>,
+    Json(req): Json<BatchCorridorsRequest>,
+) -> ApiResult<Json<BatchCorridorsResponse>> {
+    let mut requested: Vec<String> = Vec::with_capacity(req.keys.len());
+    for key in req.keys.iter().filter(|key| !key.is_empty()) {
+        let key = parse_corridor_key(key)
+            .map_err(|e| ApiError::BadRequest(format!("Invalid corridor key {:?}: {}", key, e)))?
+            .to_string_key();
+        if !requested.contains(&key) {
+            requested.push(key);
+        }
+    }
+
+    if requested.is_empty() {
+        return Err(ApiError::BadRequest(
+            "At least one corridor key is required".to_string(),
+        ));
+    }
+    if requested.len() > MAX_BATCH_KEYS {
+        return Err(ApiError::BadRequest(format!(
+            "At most {} corridor keys can be requested at once",
+            MAX_BATCH_KEYS
+        )));
+    }
+
+    let cache_keys: Vec<String> = requested
+        .iter()
+        .map(|key| keys::corridor_summary(key))
+        .collect();
+    let cached = if cache_bypassed() {
+        vec![None; cache_keys.len()]
+    } else {
+        timed("cache", cache.get_many::<CorridorResponse>(&cache_keys)).await?
+    };
+
+    let mut found = HashMap::new();
+    let mut misses = Vec::new();
+    for (key, corridor) in requested.iter().zip(cached) {
+        match corridor {
+            Some(corridor) => {
+                found.insert(key.clone(), corridor);
+            }
+            None => misses.push(key.clone()),
+        }
+    }
+
+    if !misses.is_empty() {
+        let rows = timed("db", db.get_latest_corridor_metrics_by_keys(&misses)).await?;
+        let ttl = cache.config.get_ttl("corridor");
+
+        let corridors = corridor_responses_with_history(db.as_ref(), &rows).await?;
+        for corridor in corridors {
+            let _ = timed(
+                "cache",
+                cache.set_tagged(
+                    &keys::corridor_summary(&corridor.id),
+                    &corridor,
+                    ttl,
+                    &[keys::corridors_tag(), keys::corridor_tag(&corridor.id)],
+                ),
+            )
+            .await;
+            found.insert(corridor.id.clone(), corridor);
+        }
+    }
+
+    found
+        .values_mut()
+        .for_each(|corridor| corridor.apply_precision(&precision));
+    Ok(Json(build_batch_response(&requested, found)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_corridor_list_max_age_matches_corridor_ttl() {
+        use crate::cache::CacheManager;
+        use crate::cache_middleware::{cache_control_middleware, CacheControl};
+        use crate::db::backend::InMemoryDatabase;
+        use crate::rpc::StellarRpcClient;
+        use axum::{body::Body, http::Request, middleware, routing::get, Router};
+        use std::sync::Arc;
+        use tower::ServiceExt;
+
+        let cache = Arc::new(CacheManager::in_memory(Default::default()));
+        let corridor_ttl = cache.config.corridor_metrics_ttl;
+        let cache_control = middleware::from_fn_with_state(
+            CacheControl::for_cache_type(&cache.config, "corridor"),
+            cache_control_middleware,
+        );
+        let state: CachedState = (
+            Arc::new(InMemoryDatabase::new()),
+            cache,
+            Arc::new(StellarRpcClient::new_with_defaults(true)),
+        );
+
+        let fx = Arc::new(FxService::new(
+            Arc::new(crate::services::fx::StaticPriceSource::new()),
+            None,
+        ));
+
+        let app = Router::new()
+            .route("/api/corridors", get(list_corridors).layer(cache_control))
+            .with_state(state)
+            .layer(Extension(fx))
+            .layer(Extension(Arc::new(PageLimits::default())))
+            .layer(Extension(Arc::new(PublicBasePath::default())))
+            .layer(Extension(Arc::new(ResponsePrecision::default())));
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/corridors")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert!(response.status().is_success());
+        assert_eq!(
+            response.headers()[axum::http::header::CACHE_CONTROL],
+            format!("public, max-age={}", corridor_ttl).as_str()
+        );
+    }
+
     #[test]
     fn test_health_score_calculation() {
         let score = calculate_health_score(95.0, 1000, 1_000_000.0);
@@ -313,4 +1154,930 @@ mod tests {
         assert_eq!(get_liquidity_trend(5_000_000.0), "stable");
         assert_eq!(get_liquidity_trend(500_000.0), "decreasing");
     }
+
+    fn latest_metrics(corridor_key: &str) -> LatestCorridorMetrics {
+        LatestCorridorMetrics {
+            corridor_key: corridor_key.to_string(),
+            asset_a_code: "USDC".to_string(),
+            asset_a_issuer: "issuer1".to_string(),
+            asset_b_code: "EURC".to_string(),
+            asset_b_issuer: "issuer2".to_string(),
+            total_transactions: 100,
+            successful_transactions: 95,
+            failed_transactions: 5,
+            avg_success_rate: 95.0,
+            total_volume_usd: 2_000_000.0,
+            avg_slippage_bps: Some(12.0),
+            avg_settlement_latency_ms: Some(400.0),
+            avg_liquidity_depth_usd: Some(500_000.0),
+            last_updated: "2024-01-01T12:00:00Z".to_string(),
+            updated_at: "2024-01-01 12:05:00".to_string(),
+            source_asset_code: "USDC".to_string(),
+            source_asset_issuer: "issuer1".to_string(),
+            destination_asset_code: "EURC".to_string(),
+            destination_asset_issuer: "issuer2".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_batch_response_mixed_found_and_not_found() {
+        let found_key = "USDC:issuer1->EURC:issuer2".to_string();
+        let missing_key = "USDC:issuer1->XLM:native".to_string();
+
+        let mut found = HashMap::new();
+        found.insert(
+            found_key.clone(),
+            corridor_response_from_metrics(&latest_metrics(&found_key), &[]),
+        );
+
+        let response = build_batch_response(&[found_key.clone(), missing_key.clone()], found);
+
+        assert_eq!(response.corridors.len(), 1);
+        assert_eq!(response.corridors[&found_key].success_rate, 95.0);
+        assert_eq!(response.not_found, vec![missing_key]);
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json["corridors"][&found_key].is_object());
+        assert_eq!(json["not_found"][0], "USDC:issuer1->XLM:native");
+    }
+
+    #[test]
+    fn test_corridor_response_from_metrics() {
+        let corridor = corridor_response_from_metrics(&latest_metrics("a->b"), &[]);
+        assert_eq!(corridor.id, "a->b");
+        assert_eq!(corridor.total_attempts, 100);
+        assert_eq!(corridor.average_latency_ms, 400.0);
+        assert_eq!(corridor.liquidity_trend, "stable");
+    }
+
+    fn list_query(fields: Option<&str>) -> ListCorridorsQuery {
+        ListCorridorsQuery {
+            limit: None,
+            offset: 0,
+            sort_by: SortBy::default(),
+            order: None,
+            success_rate_min: None,
+            success_rate_max: None,
+            volume_min: None,
+            volume_max: None,
+            min_transactions: 0,
+            asset_code: None,
+            time_period: None,
+            fields: fields.map(str::to_string),
+            quote: None,
+        }
+    }
+
+    #[test]
+    fn test_quote_converts_list_volumes() {
+        let corridor = corridor_response_from_metrics(&latest_metrics("a->b"), &[]);
+        let eur = Quote {
+            currency: "EUR".to_string(),
+            rate: 0.9,
+            fallback: false,
+        };
+
+        let mut full = CorridorListResponse::new(
+            Paginated::from_all(vec![corridor.clone()], 50, 0),
+            ResponseProfile::Full,
+        );
+        full.apply_quote(&eur);
+        let json = serde_json::to_value(&full).unwrap();
+        assert_eq!(json["items"][0]["volume"], 1_800_000.0);
+        assert_eq!(json["items"][0]["quote_currency"], "EUR");
+        assert_eq!(json["items"][0]["liquidity_volume_24h_usd"], 2_000_000.0);
+        assert_eq!(json["items"][0]["liquidity_depth_usd"], 500_000.0);
+        assert!(json["items"][0].get("quote_fallback").is_none());
+
+        let mut compact = CorridorListResponse::new(
+            Paginated::from_all(vec![corridor], 50, 0),
+            ResponseProfile::Compact,
+        );
+        compact.apply_quote(&Quote {
+            fallback: true,
+            ..Quote::usd()
+        });
+        let json = serde_json::to_value(&compact).unwrap();
+        assert_eq!(json["items"][0]["volume"], 2_000_000.0);
+        assert_eq!(json["items"][0]["quote_currency"], "USD");
+        assert_eq!(json["items"][0]["quote_fallback"], true);
+    }
+
+    #[test]
+    fn test_last_updated_is_rfc3339() {
+        for stored in ["2024-01-01 12:00:00", "2024-01-01T12:00:00Z"] {
+            let corridor = corridor_response_from_metrics(
+                &LatestCorridorMetrics {
+                    last_updated: stored.to_string(),
+                    ..latest_metrics("a->b")
+                },
+                &[],
+            );
+            let parsed = chrono::DateTime::parse_from_rfc3339(&corridor.last_updated).unwrap();
+            assert_eq!(parsed.to_rfc3339(), "2024-01-01T12:00:00+00:00");
+        }
+    }
+
+    #[test]
+    fn test_precision_rounds_rates_and_volumes() {
+        let corridor = corridor_response_from_metrics(
+            &LatestCorridorMetrics {
+                avg_success_rate: 95.00001,
+                total_volume_usd: 1_234.5678,
+                avg_liquidity_depth_usd: Some(987_654.321),
+                ..latest_metrics("a->b")
+            },
+            &[],
+        );
+        let precision = ResponsePrecision {
+            rate_decimals: 2,
+            volume_decimals: 0,
+        };
+
+        let eur = Quote {
+            currency: "EUR".to_string(),
+            rate: 0.9,
+            fallback: false,
+        };
+
+        let mut full = CorridorListResponse::new(
+            Paginated::from_all(vec![corridor.clone()], 50, 0),
+            ResponseProfile::Full,
+        );
+        full.apply_quote(&eur);
+        full.apply_precision(&precision);
+        let json = serde_json::to_value(&full).unwrap();
+        assert_eq!(json["items"][0]["success_rate"], 95.0);
+        assert_eq!(json["items"][0]["liquidity_depth_usd"], 987_654.0);
+        assert_eq!(json["items"][0]["liquidity_volume_24h_usd"], 1_235.0);
+        assert_eq!(json["items"][0]["volume"], 1_111.0);
+
+        let mut compact = CorridorListResponse::new(
+            Paginated::from_all(vec![corridor], 50, 0),
+            ResponseProfile::Compact,
+        );
+        compact.apply_quote(&eur);
+        compact.apply_precision(&precision);
+        let json = serde_json::to_value(&compact).unwrap();
+        assert_eq!(json["items"][0]["success_rate"], 95.0);
+        assert_eq!(json["items"][0]["volume_usd"], 1_235.0);
+        assert_eq!(json["items"][0]["volume"], 1_111.0);
+    }
+
+    #[test]
+    fn test_compact_profile_serialization() {
+        let corridor = corridor_response_from_metrics(&latest_metrics("a->b"), &[]);
+        let response = CorridorListResponse::new(
+            Paginated::from_all(vec![corridor], 50, 0),
+            ResponseProfile::Compact,
+        );
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["total"], 1);
+        assert!(json["next_offset"].is_null());
+        let entry = json["items"][0].as_object().unwrap();
+        assert_eq!(entry.len(), 3);
+        assert_eq!(entry["corridor_key"], "a->b");
+        assert_eq!(entry["success_rate"], 95.0);
+        assert_eq!(entry["volume_usd"], 2_000_000.0);
+    }
+
+    #[test]
+    fn test_response_profile_resolution() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            ResponseProfile::resolve(None, &headers).unwrap(),
+            ResponseProfile::Full
+        );
+
+        headers.insert(RESPONSE_PROFILE_HEADER, "Compact".parse().unwrap());
+        assert_eq!(
+            ResponseProfile::resolve(None, &headers).unwrap(),
+            ResponseProfile::Compact
+        );
+        // Query parameter takes precedence over the header
+        assert_eq!(
+            ResponseProfile::resolve(Some("full"), &headers).unwrap(),
+            ResponseProfile::Full
+        );
+        assert!(ResponseProfile::resolve(Some("minimal"), &headers).is_err());
+    }
+
+    fn first_page() -> PageRequest {
+        PageLimits::default().resolve(None, 0).unwrap()
+    }
+
+    #[test]
+    fn test_cache_key_differs_by_profile() {
+        let params = list_query(None);
+        let full = generate_corridor_list_cache_key(&params, first_page(), ResponseProfile::Full);
+        let compact = generate_corridor_list_cache_key(&params, first_page(), ResponseProfile::Compact);
+
+        assert_ne!(full, compact);
+        assert!(compact.ends_with("_profile:compact"));
+    }
+
+    #[test]
+    fn test_threshold_params_parse_and_validate() {
+        let uri: axum::http::Uri = "/api/corridors?min_success_rate=80&max_success_rate=99.5&min_volume=1000"
+            .parse()
+            .unwrap();
+        let Query(params) = Query::<ListCorridorsQuery>::try_from_uri(&uri).unwrap();
+
+        let filter = params.metrics_filter().unwrap();
+        assert_eq!(filter.min_success_rate, Some(80.0));
+        assert_eq!(filter.max_success_rate, Some(99.5));
+        assert_eq!(filter.min_volume, Some(1000.0));
+
+        let uri: axum::http::Uri = "/api/corridors?min_success_rate=150".parse().unwrap();
+        let Query(params) = Query::<ListCorridorsQuery>::try_from_uri(&uri).unwrap();
+        assert!(params.metrics_filter().is_err());
+    }
+
+    #[test]
+    fn test_cache_key_includes_thresholds() {
+        let unfiltered = list_query(None);
+        let mut filtered = list_query(None);
+        filtered.success_rate_min = Some(80.0);
+        filtered.volume_min = Some(1000.0);
+
+        let key = generate_corridor_list_cache_key(&filtered, first_page(), ResponseProfile::Full);
+        assert!(key.contains("sr_min:Some(80.0)"));
+        assert!(key.contains("vol_min:Some(1000.0)"));
+        assert_ne!(
+            key,
+            generate_corridor_list_cache_key(&unfiltered, first_page(), ResponseProfile::Full)
+        );
+    }
+
+    #[test]
+    fn test_min_transactions_filter_and_cache_key() {
+        assert_eq!(
+            list_query(None).metrics_filter().unwrap().min_transactions,
+            0
+        );
+
+        let uri: axum::http::Uri = "/api/corridors?min_transactions=25".parse().unwrap();
+        let Query(params) = Query::<ListCorridorsQuery>::try_from_uri(&uri).unwrap();
+        assert_eq!(params.metrics_filter().unwrap().min_transactions, 25);
+
+        let key = generate_corridor_list_cache_key(&params, first_page(), ResponseProfile::Full);
+        assert!(key.contains("_min_tx:25_"));
+        assert_ne!(
+            key,
+            generate_corridor_list_cache_key(
+                &list_query(None),
+                first_page(),
+                ResponseProfile::Full
+            )
+        );
+
+        let uri: axum::http::Uri = "/api/corridors?min_transactions=-1".parse().unwrap();
+        let Query(params) = Query::<ListCorridorsQuery>::try_from_uri(&uri).unwrap();
+        assert!(params.metrics_filter().is_err());
+    }
+
+    #[test]
+    fn test_sort_by_deserialization() {
+        for (raw, expected) in [
+            ("\"success_rate\"", SortBy::SuccessRate),
+            ("\"volume\"", SortBy::Volume),
+            ("\"last_updated\"", SortBy::LastUpdated),
+            ("\"name\"", SortBy::Name),
+        ] {
+            let sort_by: SortBy = serde_json::from_str(raw).unwrap();
+            assert_eq!(sort_by, expected);
+        }
+        assert!(serde_json::from_str::<SortBy>("\"updated_at\"").is_err());
+
+        let uri: axum::http::Uri = "/api/corridors?sort_by=last_updated".parse().unwrap();
+        let Query(params) = Query::<ListCorridorsQuery>::try_from_uri(&uri).unwrap();
+        assert_eq!(params.sort_by, SortBy::LastUpdated);
+        assert_eq!(list_query(None).sort_by, SortBy::SuccessRate);
+    }
+
+    #[test]
+    fn test_cache_key_differs_by_sort() {
+        let by_rate = list_query(None);
+        let mut by_freshness = list_query(None);
+        by_freshness.sort_by = SortBy::LastUpdated;
+
+        let key =
+            generate_corridor_list_cache_key(&by_freshness, first_page(), ResponseProfile::Full);
+        assert!(key.contains("_sort:last_updated:desc_"));
+        assert_ne!(
+            key,
+            generate_corridor_list_cache_key(&by_rate, first_page(), ResponseProfile::Full)
+        );
+    }
+
+    #[test]
+    fn test_default_order_per_sort_key() {
+        for (sort_by, expected) in [
+            (SortBy::SuccessRate, SortOrder::Desc),
+            (SortBy::Volume, SortOrder::Desc),
+            (SortBy::LastUpdated, SortOrder::Desc),
+            (SortBy::Name, SortOrder::Asc),
+        ] {
+            let mut params = list_query(None);
+            params.sort_by = sort_by;
+            assert_eq!(params.sort_order(), expected, "{:?}", sort_by);
+        }
+
+        let uri: axum::http::Uri = "/api/corridors?sort_by=name&order=desc".parse().unwrap();
+        let Query(params) = Query::<ListCorridorsQuery>::try_from_uri(&uri).unwrap();
+        assert_eq!(params.sort_order(), SortOrder::Desc);
+
+        let uri: axum::http::Uri = "/api/corridors?order=sideways".parse().unwrap();
+        assert!(Query::<ListCorridorsQuery>::try_from_uri(&uri).is_err());
+    }
+
+    #[test]
+    fn test_cache_key_includes_order() {
+        let implicit = list_query(None);
+        let mut explicit_desc = list_query(None);
+        explicit_desc.order = Some(SortOrder::Desc);
+        let mut ascending = list_query(None);
+        ascending.order = Some(SortOrder::Asc);
+
+        let key =
+            |params| generate_corridor_list_cache_key(params, first_page(), ResponseProfile::Full);
+        assert!(key(&ascending).contains("_sort:success_rate:asc_"));
+        assert_ne!(key(&ascending), key(&implicit));
+        // An explicit default direction shares the implicit entry
+        assert_eq!(key(&explicit_desc), key(&implicit));
+    }
+
+    /// Router serving the corridor list and export from `db`
+    async fn list_router(db: Arc<dyn crate::db::backend::DatabaseBackend>) -> axum::Router {
+        use crate::cache::CacheManager;
+        use crate::rpc::StellarRpcClient;
+        use axum::{routing::get, Router};
+
+        let state: CachedState = (
+            db,
+            Arc::new(CacheManager::in_memory(Default::default())),
+            Arc::new(StellarRpcClient::new_with_defaults(true)),
+        );
+        let fx = Arc::new(FxService::new(
+            Arc::new(crate::services::fx::StaticPriceSource::new()),
+            None,
+        ));
+        Router::new()
+            .route("/api/corridors", get(list_corridors))
+            .route("/api/corridors/export", get(export_corridors))
+            .with_state(state)
+            .layer(Extension(fx))
+            .layer(Extension(Arc::new(PageLimits::default())))
+            .layer(Extension(Arc::new(PublicBasePath::default())))
+            .layer(Extension(Arc::new(ResponsePrecision::default())))
+    }
+
+    /// Corridor ids in a list response body
+    async fn listed_ids(response: Response) -> Vec<String> {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        body.as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["id"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_list_filters_and_sorts_in_the_database() {
+        use crate::db::backend::InMemoryDatabase;
+        use axum::{body::Body, http::Request};
+        use tower::ServiceExt;
+
+        let db = InMemoryDatabase::new();
+        for (key, asset, volume) in [
+            ("a->b", "USDC", 1_000.0),
+            ("c->d", "USDC", 9_000.0),
+            ("e->f", "BRL", 5_000.0),
+            ("g->h", "USDC", 90_000.0),
+        ] {
+            db.insert_corridor_metrics(LatestCorridorMetrics {
+                asset_a_code: asset.to_string(),
+                asset_b_code: "XLM".to_string(),
+                total_volume_usd: volume,
+                ..latest_metrics(key)
+            });
+        }
+
+        let response = list_router(Arc::new(db))
+            .await
+            .oneshot(
+                Request::builder()
+                    .uri("/api/corridors?asset_code=usdc&volume_max=10000&sort_by=volume")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert!(response.status().is_success());
+        assert_eq!(listed_ids(response).await, vec!["c->d", "a->b"]);
+    }
+
+    #[tokio::test]
+    async fn test_list_risk_scores_include_daily_volatility() {
+        use crate::db::backend::InMemoryDatabase;
+        use axum::{body::Body, http::Request};
+        use tower::ServiceExt;
+
+        let db = InMemoryDatabase::new();
+        for key in ["calm", "volatile"] {
+            db.insert_corridor_metrics(latest_metrics(key));
+        }
+        let today = Utc::now();
+        for (days_ago, successful) in [(1, 10), (2, 4)] {
+            db.insert_corridor_daily_totals(CorridorDailyTotals {
+                corridor_key: "volatile".to_string(),
+                date: today - Duration::days(days_ago),
+                total_transactions: 10,
+                successful_transactions: successful,
+                failed_transactions: 10 - successful,
+                volume_usd: 1_000.0,
+            });
+        }
+
+        let response = list_router(Arc::new(db))
+            .await
+            .oneshot(
+                Request::builder()
+                    .uri("/api/corridors?sort_by=name")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: Vec<CorridorResponse> = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body[0].id, "calm");
+        assert!(body[1].risk_score > body[0].risk_score);
+    }
+
+    #[tokio::test]
+    async fn test_list_envelope_only_when_accepted() {
+        use crate::db::backend::InMemoryDatabase;
+        use axum::{body::Body, http::Request};
+        use tower::ServiceExt;
+
+        let db = InMemoryDatabase::new();
+        for key in ["a->b", "c->d", "e->f"] {
+            db.insert_corridor_metrics(latest_metrics(key));
+        }
+        let app = list_router(Arc::new(db)).await;
+        let request = |accept: Option<&str>| {
+            let mut request = Request::builder().uri("/api/corridors?limit=2&sort_by=name");
+            if let Some(accept) = accept {
+                request = request.header(header::ACCEPT, accept);
+            }
+            request.body(Body::empty()).unwrap()
+        };
+
+        let response = app.clone().oneshot(request(None)).await.unwrap();
+        assert_eq!(response.headers()[header::VARY], "accept");
+        assert_eq!(listed_ids(response).await, vec!["a->b", "c->d"]);
+
+        let response = app
+            .oneshot(request(Some(PAGINATED_MEDIA_TYPE)))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            PAGINATED_MEDIA_TYPE
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["items"].as_array().unwrap().len(), 2);
+        assert_eq!(body["total"], 3);
+        assert_eq!(body["next_offset"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_list_min_transactions_is_inclusive_in_sql() {
+        use crate::database::Database;
+        use axum::{body::Body, http::Request};
+        use sqlx::sqlite::SqlitePoolOptions;
+        use tower::ServiceExt;
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        for (key, total) in [
+            ("A:x->XLM:native", 9),
+            ("B:x->XLM:native", 10),
+            ("C:x->XLM:native", 11),
+        ] {
+            sqlx::query(
+                r#"
+                INSERT INTO corridor_metrics_hourly (
+                    id, corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+                    hour_bucket, total_transactions, successful_transactions, failed_transactions,
+                    success_rate, volume_usd
+                )
+                VALUES ($1, $1, 'A', 'x', 'XLM', 'native', datetime('now'), $2, $2, 0, 100.0, 1.0)
+                "#,
+            )
+            .bind(key)
+            .bind(total)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let response = list_router(Arc::new(Database::new(pool)))
+            .await
+            .oneshot(
+                Request::builder()
+                    .uri("/api/corridors?min_transactions=10&sort_by=name")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert!(response.status().is_success());
+        assert_eq!(
+            listed_ids(response).await,
+            vec!["B:x->XLM:native", "C:x->XLM:native"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_matches_list_for_the_same_filter() {
+        use crate::db::backend::InMemoryDatabase;
+        use axum::{body::Body, http::Request};
+        use tower::ServiceExt;
+
+        let db = InMemoryDatabase::new();
+        for (key, asset, volume) in [
+            ("a->b", "USDC", 1_000.0),
+            ("c->d", "BRL", 9_000.0),
+            ("e->f", "USDC", 50_000.0),
+        ] {
+            db.insert_corridor_metrics(LatestCorridorMetrics {
+                asset_a_code: asset.to_string(),
+                total_volume_usd: volume,
+                ..latest_metrics(key)
+            });
+        }
+        let app = list_router(Arc::new(db)).await;
+        let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let filter = "asset_code=usdc&volume_max=10000";
+
+        let listed: Vec<CorridorResponse> = {
+            let uri = format!("/api/corridors?{}&sort_by=name", filter);
+            let response = app.clone().oneshot(get(&uri)).await.unwrap();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice(&body).unwrap()
+        };
+        let exported: Vec<CorridorResponse> = {
+            let uri = format!("/api/corridors/export?{}", filter);
+            let response = app.oneshot(get(&uri)).await.unwrap();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            std::str::from_utf8(&body)
+                .unwrap()
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect()
+        };
+
+        assert_eq!(listed.len(), 1);
+        assert_eq!(
+            serde_json::to_value(&listed).unwrap(),
+            serde_json::to_value(&exported).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_streams_one_corridor_per_line() {
+        use crate::cache::CacheManager;
+        use crate::db::backend::InMemoryDatabase;
+        use crate::rpc::StellarRpcClient;
+        use axum::{body::Body, http::Request, routing::get, Router};
+        use std::sync::Arc;
+        use tower::ServiceExt;
+
+        let db = InMemoryDatabase::new();
+        for (key, total_transactions) in [("c->d", 100), ("a->b", 100), ("e->f", 3)] {
+            db.insert_corridor_metrics(LatestCorridorMetrics {
+                total_transactions,
+                ..latest_metrics(key)
+            });
+        }
+        let state: CachedState = (
+            Arc::new(db),
+            Arc::new(CacheManager::in_memory(Default::default())),
+            Arc::new(StellarRpcClient::new_with_defaults(true)),
+        );
+        let app = Router::new()
+            .route("/api/corridors/export", get(export_corridors))
+            .with_state(state)
+            .layer(Extension(Arc::new(ResponsePrecision::default())));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/corridors/export?min_transactions=10")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert!(response.status().is_success());
+        assert_eq!(response.headers()[header::CONTENT_TYPE], NDJSON);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.ends_with('\n'));
+
+        let ids: Vec<String> = body
+            .lines()
+            .map(|line| {
+                let corridor: serde_json::Value = serde_json::from_str(line).unwrap();
+                corridor["id"].as_str().unwrap().to_string()
+            })
+            .collect();
+        assert_eq!(ids, vec!["a->b", "c->d"]);
+    }
+
+    #[tokio::test]
+    async fn test_history_streams_as_one_ordered_json_array() {
+        use crate::cache::CacheManager;
+        use crate::db::backend::InMemoryDatabase;
+        use crate::rpc::StellarRpcClient;
+        use axum::{body::Body, http::Request, routing::get, Router};
+        use std::sync::Arc;
+        use tower::ServiceExt;
+
+        let key = "USDC:GA->XLM:native";
+        let day = |d: u32| {
+            chrono::NaiveDate::from_ymd_opt(2026, 3, d)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+                .and_utc()
+        };
+        let db = InMemoryDatabase::new();
+        for (corridor_key, d) in [(key, 7), (key, 1), ("EURC:GB->XLM:native", 3), (key, 4)] {
+            db.insert_corridor_daily_totals(CorridorDailyTotals {
+                corridor_key: corridor_key.to_string(),
+                date: day(d),
+                total_transactions: 10 * d as i64,
+                successful_transactions: 9 * d as i64,
+                failed_transactions: d as i64,
+                volume_usd: 100.0 * d as f64,
+            });
+        }
+        let state: CachedState = (
+            Arc::new(db),
+            Arc::new(CacheManager::in_memory(Default::default())),
+            Arc::new(StellarRpcClient::new_with_defaults(true)),
+        );
+        let app = Router::new()
+            .route(
+                "/api/corridors/:corridor_key/history",
+                get(get_corridor_history),
+            )
+            .with_state(state)
+            .layer(Extension(Arc::new(ResponsePrecision::default())));
+        let get_history = |query: &str| {
+            let request = Request::builder()
+                .uri(format!(
+                    "/api/corridors/USDC:GA-%3EXLM:native/history?{}",
+                    query
+                ))
+                .body(Body::empty())
+                .unwrap();
+            app.clone().oneshot(request)
+        };
+
+        let response = get_history("from=2026-03-01T00:00:00Z&to=2026-03-31T00:00:00Z")
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+
+        // Reassemble the body chunk by chunk, as a client reading it would
+        let mut body = response.into_body().into_data_stream();
+        let mut bytes = Vec::new();
+        let mut chunks = 0;
+        while let Some(chunk) = body.next().await {
+            bytes.extend_from_slice(&chunk.unwrap());
+            chunks += 1;
+        }
+        assert!(chunks > 1);
+        let points: Vec<CorridorHistoryPoint> = serde_json::from_slice(&bytes).unwrap();
+        let days: Vec<_> = points
+            .iter()
+            .map(|p| (p.timestamp.clone(), p.total_transactions))
+            .collect();
+        assert_eq!(
+            days,
+            vec![
+                (day(1).to_rfc3339(), 10),
+                (day(4).to_rfc3339(), 40),
+                (day(7).to_rfc3339(), 70),
+            ]
+        );
+        assert_eq!(points[0].success_rate, 90.0);
+
+        // An empty range is still a valid array
+        let response = get_history("from=2026-04-01T00:00:00Z&to=2026-04-30T00:00:00Z")
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"[]");
+
+        let response = get_history("from=2026-03-10T00:00:00Z&to=2026-03-01T00:00:00Z")
+            .await
+            .unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_peers_share_an_asset_ranked_by_volume() {
+        use crate::cache::CacheManager;
+        use crate::db::backend::InMemoryDatabase;
+        use crate::rpc::StellarRpcClient;
+        use axum::{body::Body, http::Request, routing::get, Router};
+        use tower::ServiceExt;
+
+        let db = InMemoryDatabase::new();
+        for (a, b, volume) in [
+            (("EURC", "GEURO"), ("USDC", "GCIRCLE"), 1_000.0),
+            (("USDC", "GCIRCLE"), ("XLM", "native"), 500.0),
+            (("NGNT", "GNGN"), ("USDC", "GCIRCLE"), 9_000.0),
+            (("USDC", "GOTHER"), ("XLM", "native"), 8_000.0),
+            (("BRL", "GBRL"), ("NGNT", "GNGN"), 50_000.0),
+        ] {
+            let corridor = Corridor::new(a.0.into(), a.1.into(), b.0.into(), b.1.into());
+            db.insert_corridor_metrics(LatestCorridorMetrics {
+                asset_a_code: corridor.asset_a_code.clone(),
+                asset_a_issuer: corridor.asset_a_issuer.clone(),
+                asset_b_code: corridor.asset_b_code.clone(),
+                asset_b_issuer: corridor.asset_b_issuer.clone(),
+                total_volume_usd: volume,
+                ..latest_metrics(&corridor.to_string_key())
+            });
+        }
+        let state: CachedState = (
+            Arc::new(db),
+            Arc::new(CacheManager::in_memory(Default::default())),
+            Arc::new(StellarRpcClient::new_with_defaults(true)),
+        );
+        let app = Router::new()
+            .route(
+                "/api/corridors/:corridor_key/peers",
+                get(get_corridor_peers),
+            )
+            .with_state(state)
+            .layer(Extension(Arc::new(ResponsePrecision::default())));
+
+        // The key is accepted in either asset order
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/corridors/USDC:GCIRCLE-%3EEURC:GEURO/peers")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let peers: CorridorPeersResponse = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(peers.corridor_key, "EURC:GEURO->USDC:GCIRCLE");
+        let ids: Vec<&str> = peers.peers.iter().map(|peer| peer.id.as_str()).collect();
+        // USDC from another issuer and the BRL/NGNT corridor share nothing
+        assert_eq!(
+            ids,
+            vec!["NGNT:GNGN->USDC:GCIRCLE", "USDC:GCIRCLE->XLM:native"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_normalizes_keys_before_deduplicating() {
+        use crate::cache::CacheManager;
+        use crate::db::backend::InMemoryDatabase;
+        use crate::rpc::StellarRpcClient;
+        use axum::{body::Body, http::Request, routing::post, Router};
+        use tower::ServiceExt;
+
+        let db = InMemoryDatabase::new();
+        db.insert_corridor_metrics(latest_metrics("EURC:issuer2->USDC:issuer1"));
+        let state: CachedState = (
+            Arc::new(db),
+            Arc::new(CacheManager::in_memory(Default::default())),
+            Arc::new(StellarRpcClient::new_with_defaults(true)),
+        );
+        let app = Router::new()
+            .route("/api/corridors/batch", post(get_corridors_batch))
+            .with_state(state)
+            .layer(Extension(Arc::new(ResponsePrecision::default())));
+        let batch = |keys: serde_json::Value| {
+            app.clone().oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/corridors/batch")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(serde_json::json!({ "keys": keys }).to_string()))
+                    .unwrap(),
+            )
+        };
+
+        // Reversed order and lower-case codes name the same corridor
+        let response = batch(serde_json::json!([
+            "usdc:issuer1->eurc:issuer2",
+            "EURC:issuer2->USDC:issuer1",
+            "XLM:native->usdc:issuer1",
+        ]))
+        .await
+        .unwrap();
+        assert!(response.status().is_success());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let batch_response: BatchCorridorsResponse = serde_json::from_slice(&body).unwrap();
+        let found: Vec<&String> = batch_response.corridors.keys().collect();
+        assert_eq!(found, vec!["EURC:issuer2->USDC:issuer1"]);
+        assert_eq!(batch_response.not_found, vec!["USDC:issuer1->XLM:native"]);
+
+        let response = batch(serde_json::json!([
+            "USDC:issuer1",
+            "EURC:issuer2->USDC:issuer1"
+        ]))
+        .await
+        .unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_rollup_uses_the_canonical_key() {
+        use crate::cache::CacheManager;
+        use crate::db::backend::InMemoryDatabase;
+        use crate::rpc::StellarRpcClient;
+        use axum::{body::Body, http::Request, routing::get, Router};
+        use tower::ServiceExt;
+
+        let state: CachedState = (
+            Arc::new(InMemoryDatabase::new()),
+            Arc::new(CacheManager::in_memory(Default::default())),
+            Arc::new(StellarRpcClient::new_with_defaults(true)),
+        );
+        let fx = Arc::new(FxService::new(
+            Arc::new(crate::services::fx::StaticPriceSource::new()),
+            None,
+        ));
+        let app = Router::new()
+            .route(
+                "/api/corridors/:corridor_key/rollup",
+                get(get_corridor_rollup),
+            )
+            .with_state(state)
+            .layer(Extension(fx));
+        let get_rollup = |uri: &str| {
+            app.clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        };
+
+        let response = get_rollup("/api/corridors/usdc:GCIRCLE-%3EEURC:GEURO/rollup")
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let rollup: CorridorRollupResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(rollup.corridor_key, "EURC:GEURO->USDC:GCIRCLE");
+
+        let response = get_rollup("/api/corridors/USDC:GCIRCLE/rollup")
+            .await
+            .unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
+    }
 }
@@ -0,0 +1,446 @@
+//! Development-only endpoints, mounted when `ENABLE_DEV_ENDPOINTS=true`
+
+use anyhow::Result;
+use axum::{extract::State, routing::post, Json, Router};
+use chrono::{Duration, DurationRound, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::error::{ApiError, ApiResult};
+use crate::ingestion::config::BucketGranularity;
+use crate::models::corridor::Corridor;
+
+/// Hours of corridor metrics written for each sample corridor, ending at the current hour
+const SEED_HOURS: i64 = 24;
+
+struct SeedAnchor {
+    id: &'static str,
+    name: &'static str,
+    stellar_account: &'static str,
+    home_domain: &'static str,
+    status: &'static str,
+    reliability_score: f64,
+    asset_id: &'static str,
+    asset_code: &'static str,
+}
+
+const SEED_ANCHORS: [SeedAnchor; 3] = [
+    SeedAnchor {
+        id: "d1d1d1d1-1111-4111-a111-000000000001",
+        name: "Dev Dollar Anchor",
+        stellar_account: "GDEVSEEDANCHORDOLLARAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA1",
+        home_domain: "dollar.dev.example",
+        status: "green",
+        reliability_score: 99.2,
+        asset_id: "d1a1a1a1-1111-4111-a111-000000000001",
+        asset_code: "USDC",
+    },
+    SeedAnchor {
+        id: "d2d2d2d2-2222-4222-a222-000000000002",
+        name: "Dev Euro Anchor",
+        stellar_account: "GDEVSEEDANCHOREUROAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA2",
+        home_domain: "euro.dev.example",
+        status: "yellow",
+        reliability_score: 96.4,
+        asset_id: "d2a2a2a2-2222-4222-a222-000000000002",
+        asset_code: "EURC",
+    },
+    SeedAnchor {
+        id: "d3d3d3d3-3333-4333-a333-000000000003",
+        name: "Dev Naira Anchor",
+        stellar_account: "GDEVSEEDANCHORNAIRAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA3",
+        home_domain: "naira.dev.example",
+        status: "red",
+        reliability_score: 88.7,
+        asset_id: "d3a3a3a3-3333-4333-a333-000000000003",
+        asset_code: "NGNT",
+    },
+];
+
+/// Sample corridors as (id, source anchor, destination anchor); `None` is native XLM
+const SEED_CORRIDORS: [(&str, usize, Option<usize>); 3] = [
+    ("d1c1c1c1-1111-4111-a111-000000000001", 0, None),
+    ("d2c2c2c2-2222-4222-a222-000000000002", 1, Some(0)),
+    ("d3c3c3c3-3333-4333-a333-000000000003", 2, Some(0)),
+];
+
+/// Dev endpoint settings, read once at startup
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DevConfig {
+    /// `ENABLE_DEV_ENDPOINTS`: mount the `/api/dev` routes
+    pub enabled: bool,
+    /// `APP_ENV=production`: the routes refuse to run even when mounted
+    pub production: bool,
+    /// Bucket width sample metrics are written at, matching `INGESTION_BUCKET`
+    pub bucket: BucketGranularity,
+}
+
+impl DevConfig {
+    /// Create from environment variables
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let enabled = lookup("ENABLE_DEV_ENDPOINTS")
+            .and_then(|value| value.trim().parse::<bool>().ok())
+            .unwrap_or(false);
+        let production =
+            lookup("APP_ENV").is_some_and(|value| value.trim().eq_ignore_ascii_case("production"));
+
+        Self {
+            enabled,
+            production,
+            bucket: BucketGranularity::default(),
+        }
+    }
+
+    pub fn with_bucket(mut self, bucket: BucketGranularity) -> Self {
+        self.bucket = bucket;
+        self
+    }
+}
+
+/// Rows inserted by one seed run
+///
+/// Anchors, assets and corridors are only inserted the first time; the
+/// sample metrics, covering the last 24 hours, are rewritten on every run so
+/// they stay inside the window the corridor listings read from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeedResponse {
+    pub anchors: u64,
+    pub assets: u64,
+    pub corridors: u64,
+    /// Metric buckets written, at the configured bucket width
+    pub metric_buckets: u64,
+}
+
+/// Insert the deterministic sample dataset
+///
+/// Safe to run repeatedly: existing sample rows are left alone and no
+/// duplicates are created. Hourly samples are summed into `bucket`-wide
+/// rows, so the seed never mixes granularities with aggregated metrics.
+pub async fn seed_sample_data(db: &Database, bucket: BucketGranularity) -> Result<SeedResponse> {
+    let current_hour = Utc::now().duration_trunc(Duration::hours(1))?;
+    let mut response = SeedResponse {
+        anchors: 0,
+        assets: 0,
+        corridors: 0,
+        metric_buckets: 0,
+    };
+    let mut tx = db.pool().begin().await?;
+
+    for (index, anchor) in SEED_ANCHORS.iter().enumerate() {
+        let total = 1_000 * (index as i64 + 1);
+        let successful = (total as f64 * anchor.reliability_score / 100.0).round() as i64;
+
+        response.anchors += sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO anchors (
+                id, name, stellar_account, home_domain, total_transactions,
+                successful_transactions, failed_transactions, total_volume_usd,
+                avg_settlement_time_ms, reliability_score, status
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            "#,
+        )
+        .bind(anchor.id)
+        .bind(anchor.name)
+        .bind(anchor.stellar_account)
+        .bind(anchor.home_domain)
+        .bind(total)
+        .bind(successful)
+        .bind(total - successful)
+        .bind(total as f64 * 150.0)
+        .bind(1_500 + 750 * index as i64)
+        .bind(anchor.reliability_score)
+        .bind(anchor.status)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        response.assets += sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO assets (id, anchor_id, asset_code, asset_issuer, total_supply, num_holders)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(anchor.asset_id)
+        .bind(anchor.id)
+        .bind(anchor.asset_code)
+        .bind(anchor.stellar_account)
+        .bind(10_000_000.0 * (index as f64 + 1.0))
+        .bind(500 * (index as i64 + 1))
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    }
+
+    for (index, (id, corridor)) in seed_corridors().iter().enumerate() {
+        response.corridors += sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO corridors (
+                id, source_asset_code, source_asset_issuer,
+                destination_asset_code, destination_asset_issuer
+            ) VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(*id)
+        .bind(&corridor.asset_a_code)
+        .bind(&corridor.asset_a_issuer)
+        .bind(&corridor.asset_b_code)
+        .bind(&corridor.asset_b_issuer)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        let corridor_key = corridor.to_string_key();
+        sqlx::query("DELETE FROM corridor_metrics_hourly WHERE corridor_key = $1")
+            .bind(&corridor_key)
+            .execute(&mut *tx)
+            .await?;
+
+        // (total, failed) per bucket
+        let mut buckets: BTreeMap<_, (i64, i64)> = BTreeMap::new();
+        for hour in 0..SEED_HOURS {
+            let counts = buckets
+                .entry(bucket.truncate(current_hour - Duration::hours(hour)))
+                .or_default();
+            counts.0 += 100 + 10 * index as i64 + hour;
+            counts.1 += hour % 5 + index as i64;
+        }
+
+        for (start, (total, failed)) in buckets {
+            let successful = total - failed;
+
+            response.metric_buckets += sqlx::query(
+                r#"
+                INSERT INTO corridor_metrics_hourly (
+                    id, corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+                    hour_bucket, total_transactions, successful_transactions, failed_transactions,
+                    success_rate, volume_usd, avg_slippage_bps, avg_settlement_latency_ms,
+                    liquidity_depth_usd, granularity
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+                "#,
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&corridor_key)
+            .bind(&corridor.asset_a_code)
+            .bind(&corridor.asset_a_issuer)
+            .bind(&corridor.asset_b_code)
+            .bind(&corridor.asset_b_issuer)
+            .bind(start.to_rfc3339())
+            .bind(total)
+            .bind(successful)
+            .bind(failed)
+            .bind(successful as f64 * 100.0 / total as f64)
+            .bind(total as f64 * (1_000.0 + 250.0 * index as f64))
+            .bind(5.0 + 2.5 * index as f64)
+            .bind(1_500 + 200 * index as i64)
+            .bind(250_000.0 * (index as f64 + 1.0))
+            .bind(bucket.as_str())
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        }
+    }
+
+    tx.commit().await?;
+    Ok(response)
+}
+
+fn seed_corridors() -> Vec<(&'static str, Corridor)> {
+    SEED_CORRIDORS
+        .iter()
+        .map(|&(id, source, destination)| {
+            let source = &SEED_ANCHORS[source];
+            let (destination_code, destination_issuer) = match destination {
+                Some(index) => (
+                    SEED_ANCHORS[index].asset_code,
+                    SEED_ANCHORS[index].stellar_account,
+                ),
+                None => ("XLM", "native"),
+            };
+            let corridor = Corridor::new(
+                source.asset_code.to_string(),
+                source.stellar_account.to_string(),
+                destination_code.to_string(),
+                destination_issuer.to_string(),
+            );
+            (id, corridor)
+        })
+        .collect()
+}
+
+#[derive(Clone)]
+struct DevState {
+    db: Arc<Database>,
+    config: DevConfig,
+}
+
+/// Handler for POST /api/dev/seed - Load the sample dataset
+async fn seed(State(state): State<DevState>) -> ApiResult<Json<SeedResponse>> {
+    if state.config.production {
+        return Err(ApiError::Forbidden(
+            "Dev endpoints are disabled in production".to_string(),
+        ));
+    }
+
+    let response = seed_sample_data(&state.db, state.config.bucket).await?;
+    tracing::info!(
+        "Seeded sample data: {} anchors, {} corridors, {} metric buckets",
+        response.anchors,
+        response.corridors,
+        response.metric_buckets
+    );
+    Ok(Json(response))
+}
+
+pub fn routes(db: Arc<Database>, config: DevConfig) -> Router {
+    Router::new()
+        .route("/api/dev/seed", post(seed))
+        .with_state(DevState { db, config })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use std::collections::HashMap;
+    use tower::ServiceExt;
+
+    fn config_from(vars: &[(&str, &str)]) -> DevConfig {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        DevConfig::from_lookup(|name| vars.get(name).cloned())
+    }
+
+    async fn test_db() -> Arc<Database> {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        Arc::new(Database::new(pool))
+    }
+
+    async fn seeded_row_counts(db: &Database) -> (i64, i64, i64, i64) {
+        let count = |sql: &'static str| async move {
+            sqlx::query_scalar::<_, i64>(sql)
+                .fetch_one(db.pool())
+                .await
+                .unwrap()
+        };
+        (
+            count("SELECT COUNT(*) FROM anchors WHERE stellar_account LIKE 'GDEVSEED%'").await,
+            count("SELECT COUNT(*) FROM assets WHERE asset_issuer LIKE 'GDEVSEED%'").await,
+            count("SELECT COUNT(*) FROM corridors WHERE source_asset_issuer LIKE 'GDEVSEED%'")
+                .await,
+            count("SELECT COUNT(*) FROM corridor_metrics_hourly WHERE asset_a_issuer LIKE 'GDEVSEED%'")
+                .await,
+        )
+    }
+
+    async fn post_seed(app: Router) -> (StatusCode, Option<SeedResponse>) {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/dev/seed")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).ok())
+    }
+
+    #[test]
+    fn test_config_defaults_to_disabled() {
+        assert_eq!(config_from(&[]), DevConfig::default());
+        assert!(!config_from(&[("ENABLE_DEV_ENDPOINTS", "yes")]).enabled);
+
+        let config = config_from(&[("ENABLE_DEV_ENDPOINTS", "true"), ("APP_ENV", "Production")]);
+        assert!(config.enabled);
+        assert!(config.production);
+        assert!(!config_from(&[("APP_ENV", "development")]).production);
+    }
+
+    #[tokio::test]
+    async fn test_seed_inserts_expected_rows() {
+        let db = test_db().await;
+        let app = routes(
+            Arc::clone(&db),
+            config_from(&[("ENABLE_DEV_ENDPOINTS", "true")]),
+        );
+
+        let (status, response) = post_seed(app).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            response.unwrap(),
+            SeedResponse {
+                anchors: 3,
+                assets: 3,
+                corridors: 3,
+                metric_buckets: 72,
+            }
+        );
+        assert_eq!(seeded_row_counts(&db).await, (3, 3, 3, 72));
+    }
+
+    #[tokio::test]
+    async fn test_seed_is_idempotent() {
+        let db = test_db().await;
+        seed_sample_data(&db, BucketGranularity::Hour)
+            .await
+            .unwrap();
+
+        let second = seed_sample_data(&db, BucketGranularity::Hour)
+            .await
+            .unwrap();
+        assert_eq!((second.anchors, second.assets, second.corridors), (0, 0, 0));
+        assert_eq!(seeded_row_counts(&db).await, (3, 3, 3, 72));
+    }
+
+    #[tokio::test]
+    async fn test_seed_writes_day_buckets_when_configured() {
+        let db = test_db().await;
+        let response = seed_sample_data(&db, BucketGranularity::Day).await.unwrap();
+
+        // 24 hours span one or two days
+        assert!([3, 6].contains(&response.metric_buckets));
+        db.ensure_bucket_granularity(BucketGranularity::Day)
+            .await
+            .unwrap();
+        let total: i64 = sqlx::query_scalar(
+            "SELECT SUM(total_transactions) FROM corridor_metrics_hourly WHERE asset_a_issuer LIKE 'GDEVSEED%'",
+        )
+        .fetch_one(db.pool())
+        .await
+        .unwrap();
+        // Every hourly sample is kept: 3 corridors of 24 hours each
+        let hourly_sum: i64 = (0..3).map(|i| 24 * (100 + 10 * i) + 276).sum();
+        assert_eq!(total, hourly_sum);
+    }
+
+    #[tokio::test]
+    async fn test_seed_refused_in_production() {
+        let db = test_db().await;
+        let app = routes(
+            Arc::clone(&db),
+            config_from(&[("ENABLE_DEV_ENDPOINTS", "true"), ("APP_ENV", "production")]),
+        );
+
+        let (status, _) = post_seed(app).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(seeded_row_counts(&db).await, (0, 0, 0, 0));
+    }
+}
@@ -0,0 +1,325 @@
+//! Read-only GraphQL API at `/graphql`
+//!
+//! Lets clients fetch anchors with their assets, and corridors, in a single
+//! round trip. Resolvers call the same `Database` methods as the REST
+//! handlers, which stay the primary API. Anchor assets are loaded through a
+//! `DataLoader`, so a list of anchors costs one asset query rather than one
+//! per anchor.
+
+use async_graphql::dataloader::{DataLoader, Loader};
+use async_graphql::{
+    ComplexObject, Context, EmptyMutation, EmptySubscription, ErrorExtensions, Object, Schema, ID,
+};
+use axum::{extract::State, routing::post, Json, Router};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api::pagination::{PageLimits, PageRequest};
+use crate::database::Database;
+use crate::error::ApiError;
+use crate::models::corridor::Corridor;
+use crate::models::{Anchor, Asset};
+
+/// Deepest selection a query may nest, enough for anchors { assets { ... } }
+const MAX_QUERY_DEPTH: usize = 8;
+
+/// Most fields, aliases included, a single query may select
+const MAX_QUERY_COMPLEXITY: usize = 256;
+
+pub type InsightsSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Anchors ordered by reliability score
+    async fn anchors(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<i64>,
+        #[graphql(default = 0)] offset: i64,
+    ) -> async_graphql::Result<Vec<Anchor>> {
+        let page = resolve_page(ctx, limit, offset)?;
+        let anchors = ctx
+            .data::<Arc<Database>>()?
+            .list_anchors(page.limit, page.offset)
+            .await
+            .map_err(ApiError::from)
+            .map_err(graphql_error)?;
+        Ok(anchors)
+    }
+
+    /// A single anchor, or `null` if it does not exist
+    async fn anchor(&self, ctx: &Context<'_>, id: ID) -> async_graphql::Result<Option<Anchor>> {
+        let id = Uuid::parse_str(&id)
+            .map_err(ApiError::from)
+            .map_err(graphql_error)?;
+        let anchor = ctx
+            .data::<Arc<Database>>()?
+            .get_anchor_by_id(id)
+            .await
+            .map_err(ApiError::from)
+            .map_err(graphql_error)?;
+        Ok(anchor)
+    }
+
+    /// Corridors ordered by reliability score
+    async fn corridors(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<i64>,
+        #[graphql(default = 0)] offset: i64,
+    ) -> async_graphql::Result<Vec<Corridor>> {
+        let page = resolve_page(ctx, limit, offset)?;
+        let corridors = ctx
+            .data::<Arc<Database>>()?
+            .list_corridors(page.limit, page.offset)
+            .await
+            .map_err(ApiError::from)
+            .map_err(graphql_error)?;
+        Ok(corridors)
+    }
+}
+
+#[ComplexObject]
+impl Anchor {
+    /// Assets issued by this anchor
+    async fn assets(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Asset>> {
+        let id = Uuid::parse_str(&self.id)?;
+        let assets = ctx
+            .data::<DataLoader<AnchorAssetsLoader>>()?
+            .load_one(id)
+            .await
+            .map_err(graphql_error)?;
+        Ok(assets.unwrap_or_default())
+    }
+}
+
+/// Batches `Anchor.assets` lookups into a single query
+pub struct AnchorAssetsLoader {
+    db: Arc<Database>,
+}
+
+impl Loader<Uuid> for AnchorAssetsLoader {
+    type Value = Vec<Asset>;
+    type Error = ApiError;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Vec<Asset>>, ApiError> {
+        let mut by_anchor: HashMap<Uuid, Vec<Asset>> = HashMap::new();
+        for asset in self.db.get_assets_by_anchors(keys).await? {
+            if let Ok(anchor_id) = Uuid::parse_str(&asset.anchor_id) {
+                by_anchor.entry(anchor_id).or_default().push(asset);
+            }
+        }
+        Ok(by_anchor)
+    }
+}
+
+#[ComplexObject]
+impl Corridor {
+    /// `CODE:ISSUER->CODE:ISSUER` key used by the REST corridor endpoints
+    async fn key(&self) -> String {
+        self.to_string_key()
+    }
+}
+
+fn resolve_page(
+    ctx: &Context<'_>,
+    limit: Option<i64>,
+    offset: i64,
+) -> async_graphql::Result<PageRequest> {
+    ctx.data::<PageLimits>()?
+        .resolve(limit, offset)
+        .map_err(graphql_error)
+}
+
+/// Carry the REST error code over as the `code` extension
+fn graphql_error(err: ApiError) -> async_graphql::Error {
+    let code = err.code();
+    async_graphql::Error::new(err.to_string()).extend_with(|_, extensions| {
+        extensions.set("code", code);
+    })
+}
+
+pub fn schema(db: Arc<Database>, page_limits: PageLimits) -> InsightsSchema {
+    let assets_loader = DataLoader::new(
+        AnchorAssetsLoader {
+            db: Arc::clone(&db),
+        },
+        tokio::spawn,
+    );
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(db)
+        .data(page_limits)
+        .data(assets_loader)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+/// POST /graphql - Execute a GraphQL query
+async fn graphql(
+    State(schema): State<InsightsSchema>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(schema.execute(request).await)
+}
+
+pub fn routes(db: Arc<Database>, page_limits: PageLimits) -> Router {
+    Router::new()
+        .route("/graphql", post(graphql))
+        .with_state(schema(db, page_limits))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    const ANCHOR_ID: &str = "a1a1a1a1-1111-4111-a111-000000000001";
+
+    async fn seeded_db() -> Arc<Database> {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        sqlx::query(
+            "INSERT INTO anchors (id, name, stellar_account, reliability_score, status) VALUES ($1, 'GraphQL Anchor', 'GGRAPHQLANCHOR', 97.5, 'green')",
+        )
+        .bind(ANCHOR_ID)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let db = Arc::new(Database::new(pool));
+        for code in ["USDC", "EURC"] {
+            db.create_asset(
+                Uuid::parse_str(ANCHOR_ID).unwrap(),
+                code.to_string(),
+                "GGRAPHQLANCHOR".to_string(),
+            )
+            .await
+            .unwrap();
+        }
+        db
+    }
+
+    async fn execute(db: Arc<Database>, query: &str) -> Value {
+        let response = routes(db, PageLimits::default())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/graphql")
+                    .header("content-type", "application/json")
+                    .body(Body::from(json!({ "query": query }).to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_fetches_anchor_with_assets_in_one_query() {
+        let db = seeded_db().await;
+        let query = format!(
+            r#"{{ anchor(id: "{}") {{ name status assets {{ assetCode assetIssuer }} }} }}"#,
+            ANCHOR_ID
+        );
+
+        let response = execute(db, &query).await;
+
+        assert!(response.get("errors").is_none(), "{}", response);
+        let anchor = &response["data"]["anchor"];
+        assert_eq!(anchor["name"], "GraphQL Anchor");
+        assert_eq!(anchor["status"], "green");
+        let mut codes: Vec<&str> = anchor["assets"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|asset| asset["assetCode"].as_str().unwrap())
+            .collect();
+        codes.sort();
+        assert_eq!(codes, vec!["EURC", "USDC"]);
+        // Only the selected fields come back
+        assert_eq!(anchor.as_object().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_lists_each_anchor_with_its_own_assets() {
+        let db = seeded_db().await;
+        let other = db
+            .create_anchor(crate::models::CreateAnchorRequest {
+                name: "Other Anchor".to_string(),
+                stellar_account: "GOTHERANCHOR".to_string(),
+                home_domain: None,
+            })
+            .await
+            .unwrap();
+        db.create_asset(
+            Uuid::parse_str(&other.id).unwrap(),
+            "NGNT".to_string(),
+            "GOTHERANCHOR".to_string(),
+        )
+        .await
+        .unwrap();
+
+        let response = execute(db, "{ anchors { name assets { assetCode } } }").await;
+
+        assert!(response.get("errors").is_none(), "{}", response);
+        let assets_of = |name: &str| -> Vec<String> {
+            response["data"]["anchors"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|anchor| anchor["name"] == name)
+                .unwrap()["assets"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|asset| asset["assetCode"].as_str().unwrap().to_string())
+                .collect()
+        };
+        assert_eq!(assets_of("GraphQL Anchor"), vec!["EURC", "USDC"]);
+        assert_eq!(assets_of("Other Anchor"), vec!["NGNT"]);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_overly_complex_queries() {
+        let db = seeded_db().await;
+        let fields: Vec<String> = (0..MAX_QUERY_COMPLEXITY)
+            .map(|i| format!("a{}: anchors {{ name }}", i))
+            .collect();
+
+        let response = execute(db, &format!("{{ {} }}", fields.join(" "))).await;
+
+        assert!(response["data"].is_null(), "{}", response);
+        assert!(response["errors"][0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("complex"));
+    }
+
+    #[tokio::test]
+    async fn test_reports_invalid_arguments_as_graphql_errors() {
+        let db = seeded_db().await;
+
+        let response = execute(Arc::clone(&db), r#"{ anchor(id: "nope") { name } }"#).await;
+        assert_eq!(response["errors"][0]["extensions"]["code"], "BAD_REQUEST");
+
+        let response = execute(db, "{ anchors(limit: -1) { name } }").await;
+        assert_eq!(response["errors"][0]["extensions"]["code"], "BAD_REQUEST");
+    }
+}
@@ -0,0 +1,98 @@
+use axum::{
+    extract::{Path, Query, State},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::cache_invalidation::CacheInvalidationService;
+use crate::database::Database;
+use crate::error::{ApiError, ApiResult};
+use crate::ingestion::{DataIngestionService, IngestionSummary};
+use crate::models::IngestionFailureRecord;
+
+type IngestionState = (Arc<DataIngestionService>, Arc<CacheInvalidationService>);
+
+#[derive(Debug, Deserialize)]
+pub struct BackfillQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// POST /api/ingestion/backfill - Recompute anchor metrics from RPC
+///
+/// With `?dry_run=true` the computed deltas are returned without writing to
+/// the database or invalidating caches.
+pub async fn backfill(
+    State((ingestion, cache_invalidation)): State<IngestionState>,
+    Query(params): Query<BackfillQuery>,
+) -> ApiResult<Json<IngestionSummary>> {
+    let summary = ingestion.backfill(params.dry_run).await?;
+
+    if !summary.dry_run {
+        if let Err(e) = cache_invalidation.invalidate_anchors().await {
+            tracing::warn!("Failed to invalidate anchor caches: {}", e);
+        }
+    }
+
+    Ok(Json(summary))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListFailuresQuery {
+    /// `dead_lettered`, `requeued` or `resolved`; all ranges when omitted
+    pub status: Option<String>,
+}
+
+/// GET /api/ingestion/failures - List ledger ranges skipped by ingestion
+pub async fn list_failures(
+    State(db): State<Arc<Database>>,
+    Query(params): Query<ListFailuresQuery>,
+) -> ApiResult<Json<Vec<IngestionFailureRecord>>> {
+    let failures = db.list_ingestion_failures(params.status.as_deref()).await?;
+    Ok(Json(failures))
+}
+
+/// POST /api/ingestion/failures/:id/requeue - Retry a dead-lettered range
+///
+/// The ingestion loop picks requeued ranges up before fetching new ledgers.
+pub async fn requeue_failure(
+    State(db): State<Arc<Database>>,
+    Path(id): Path<i64>,
+) -> ApiResult<Json<IngestionFailureRecord>> {
+    if !db.requeue_ingestion_failure(id).await? {
+        return match db.get_ingestion_failure(id).await? {
+            Some(failure) => Err(ApiError::BadRequest(format!(
+                "Ingestion failure {} is {}, only dead-lettered ranges can be requeued",
+                id, failure.status
+            ))),
+            None => Err(ApiError::NotFound(format!(
+                "Ingestion failure {} not found",
+                id
+            ))),
+        };
+    }
+
+    let failure = db
+        .get_ingestion_failure(id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Ingestion failure {} not found", id)))?;
+    Ok(Json(failure))
+}
+
+pub fn routes(
+    ingestion: Arc<DataIngestionService>,
+    cache_invalidation: Arc<CacheInvalidationService>,
+    db: Arc<Database>,
+) -> Router {
+    let failure_routes = Router::new()
+        .route("/api/ingestion/failures", get(list_failures))
+        .route("/api/ingestion/failures/:id/requeue", post(requeue_failure))
+        .with_state(db);
+
+    Router::new()
+        .route("/api/ingestion/backfill", post(backfill))
+        .with_state((ingestion, cache_invalidation))
+        .merge(failure_routes)
+}
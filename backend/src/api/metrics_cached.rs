@@ -2,8 +2,10 @@ use axum::{routing::get, extract::State, Json, Router};
 use serde::{Serialize, Deserialize};
 use std::sync::Arc;
 
-use crate::cache::{keys, CacheManager};
+use crate::cache::{keys, CacheManager, CacheSchema};
 use crate::cache_middleware::CacheAware;
+use crate::db::slow_query::{SlowQueryLog, SlowQueryStats};
+use crate::websocket::{WsConnectionStats, WsState};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct MetricsOverview {
@@ -14,6 +16,10 @@ pub struct MetricsOverview {
     pub corridor_count: u32,
 }
 
+impl CacheSchema for MetricsOverview {
+    const TAG: &'static str = "metrics_overview.v1";
+}
+
 /// Handler for GET /api/metrics/overview (cached with 1 min TTL)
 pub async fn metrics_overview(
     State(cache): State<Arc<CacheManager>>,
@@ -53,6 +59,30 @@ pub fn routes(cache: Arc<CacheManager>) -> Router {
         .with_state(cache)
 }
 
+/// Handler for GET /api/metrics/database (not cached, counters are live)
+pub async fn database_metrics(
+    State(slow_queries): State<Arc<SlowQueryLog>>,
+) -> Json<SlowQueryStats> {
+    Json(slow_queries.stats())
+}
+
+pub fn database_routes(slow_queries: Arc<SlowQueryLog>) -> Router {
+    Router::new()
+        .route("/api/metrics/database", get(database_metrics))
+        .with_state(slow_queries)
+}
+
+/// Handler for GET /api/metrics/websocket (not cached, counters are live)
+pub async fn websocket_metrics(State(ws_state): State<Arc<WsState>>) -> Json<WsConnectionStats> {
+    Json(ws_state.connection_stats())
+}
+
+pub fn websocket_routes(ws_state: Arc<WsState>) -> Router {
+    Router::new()
+        .route("/api/metrics/websocket", get(websocket_metrics))
+        .with_state(ws_state)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,8 +1,19 @@
+pub mod admin;
 pub mod anchors;
 pub mod anchors_cached;
 pub mod auth;
 pub mod cache_stats;
+pub mod corridor_alerts;
+pub mod corridor_import;
 pub mod corridors;
 pub mod corridors_cached;
+pub mod dev;
+pub mod graphql;
+pub mod ingestion;
 pub mod metrics;
 pub mod metrics_cached;
+pub mod openapi;
+pub mod pagination;
+pub mod precision;
+pub mod rate_limit;
+pub mod webhooks;
@@ -0,0 +1,75 @@
+use axum::Router;
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
+
+pub use crate::error::{ErrorBody, ErrorDetail};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Stellar Insights API"),
+    paths(
+        crate::api::corridors_cached::list_corridors,
+        crate::api::corridors_cached::export_corridors,
+        crate::api::corridors_cached::get_corridor_detail,
+        crate::api::corridors_cached::get_corridor_rollup,
+        crate::api::corridors_cached::get_corridor_history,
+        crate::api::corridors_cached::get_corridor_peers,
+        crate::api::corridors_cached::get_corridor_summary,
+        crate::api::anchors_cached::get_anchors,
+        crate::handlers::get_anchor,
+        crate::api::cache_stats::get_cache_stats,
+    ),
+    components(schemas(ErrorBody, ErrorDetail)),
+    tags(
+        (name = "corridors", description = "Payment corridor metrics"),
+        (name = "anchors", description = "Anchor reliability metrics"),
+        (name = "cache", description = "Cache monitoring"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Serve the OpenAPI document at /api/openapi.json and Swagger UI at /api/docs
+pub fn routes() -> Router {
+    Router::new().merge(SwaggerUi::new("/api/docs").url("/api/openapi.json", ApiDoc::openapi()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_contains_corridor_list_with_200_schema() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+
+        let list = &spec["paths"]["/api/corridors"]["get"];
+        assert!(list.is_object());
+        let ok = &list["responses"]["200"]["content"];
+        assert_eq!(
+            ok["application/vnd.stellar-insights.v2+json"]["schema"]["$ref"],
+            "#/components/schemas/CorridorListResponse"
+        );
+        assert_eq!(
+            ok["application/json"]["schema"]["items"]["$ref"],
+            "#/components/schemas/CorridorResponse"
+        );
+        assert!(spec["components"]["schemas"]["CorridorResponse"].is_object());
+
+        let params: Vec<&str> = list["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|p| p["name"].as_str())
+            .collect();
+        assert!(params.contains(&"success_rate_min"));
+        assert!(params.contains(&"X-Response-Profile"));
+    }
+
+    #[test]
+    fn test_spec_contains_anchor_detail() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+
+        assert!(spec["paths"]["/api/anchors/{id}"]["get"]["responses"]["404"].is_object());
+        assert!(spec["components"]["schemas"]["AnchorDetailResponse"].is_object());
+        assert!(spec["components"]["schemas"]["CacheStatsResponse"].is_object());
+    }
+}
@@ -0,0 +1,473 @@
+use axum::http::{header, HeaderMap, Uri};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::error::ApiError;
+use crate::ingestion::config::parse_var;
+
+const DEFAULT_PAGE_LIMIT: i64 = 50;
+const DEFAULT_MAX_PAGE_LIMIT: i64 = 200;
+
+/// Page size bounds for list endpoints, read once at startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageLimits {
+    /// Page size when the request gives no `limit`
+    pub default_limit: i64,
+    /// Larger `limit` values are clamped to this
+    pub max_limit: i64,
+}
+
+impl Default for PageLimits {
+    fn default() -> Self {
+        Self {
+            default_limit: DEFAULT_PAGE_LIMIT,
+            max_limit: DEFAULT_MAX_PAGE_LIMIT,
+        }
+    }
+}
+
+impl PageLimits {
+    /// Create from `PAGE_DEFAULT_LIMIT` and `PAGE_MAX_LIMIT`
+    pub fn from_env() -> anyhow::Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> anyhow::Result<Self> {
+        let default_limit = parse_var(&lookup, "PAGE_DEFAULT_LIMIT", DEFAULT_PAGE_LIMIT)?;
+        let max_limit = parse_var(&lookup, "PAGE_MAX_LIMIT", DEFAULT_MAX_PAGE_LIMIT)?;
+        if default_limit < 1 || max_limit < default_limit {
+            anyhow::bail!(
+                "PAGE_DEFAULT_LIMIT must be between 1 and PAGE_MAX_LIMIT ({}), got {}",
+                max_limit,
+                default_limit
+            );
+        }
+
+        Ok(Self {
+            default_limit,
+            max_limit,
+        })
+    }
+
+    /// Validate a requested page, filling in the default limit and clamping
+    /// it to the maximum
+    pub fn resolve(&self, limit: Option<i64>, offset: i64) -> Result<PageRequest, ApiError> {
+        let limit = limit.unwrap_or(self.default_limit);
+        if limit < 0 {
+            return Err(ApiError::BadRequest(format!(
+                "limit must not be negative, got {}",
+                limit
+            )));
+        }
+        if offset < 0 {
+            return Err(ApiError::BadRequest(format!(
+                "offset must not be negative, got {}",
+                offset
+            )));
+        }
+
+        Ok(PageRequest {
+            limit: limit.min(self.max_limit),
+            offset,
+        })
+    }
+}
+
+/// Public prefix for pagination links, from `PUBLIC_BASE_PATH`
+///
+/// Behind a reverse proxy the API may be served under a path (`/insights`)
+/// or another host (`https://api.example.com/insights`); links are built
+/// under this prefix instead of the path the backend sees. Empty by default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PublicBasePath(String);
+
+impl PublicBasePath {
+    pub fn new(base: &str) -> anyhow::Result<Self> {
+        let base = base.trim_end_matches('/');
+        if !(base.is_empty()
+            || base.starts_with('/')
+            || base.starts_with("http://")
+            || base.starts_with("https://"))
+        {
+            anyhow::bail!(
+                "PUBLIC_BASE_PATH must be a path starting with / or an http(s) URL, got {}",
+                base
+            );
+        }
+        Ok(Self(base.to_string()))
+    }
+
+    /// Create from `PUBLIC_BASE_PATH`
+    pub fn from_env() -> anyhow::Result<Self> {
+        Self::new(&std::env::var("PUBLIC_BASE_PATH").unwrap_or_default())
+    }
+
+    /// URL of `path` with `query` under the public prefix
+    fn url(&self, path: &str, query: &str) -> String {
+        format!("{}{}?{}", self.0, path, query)
+    }
+}
+
+/// Navigation links for a page of a list response
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct PageLinks {
+    #[serde(rename = "self")]
+    pub self_link: String,
+    /// Omitted on the last page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+    /// Omitted on the first page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev: Option<String>,
+}
+
+impl PageLinks {
+    /// Links for the page at `offset` of the list requested at `uri`
+    ///
+    /// Query parameters other than `limit` and `offset` are kept, so
+    /// following a link keeps the same filters and sort.
+    fn new(
+        base: &PublicBasePath,
+        uri: &Uri,
+        limit: i64,
+        offset: i64,
+        next_offset: Option<i64>,
+    ) -> Self {
+        let filters: Vec<&str> = uri
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .filter(|pair| {
+                let name = pair.split('=').next().unwrap_or_default();
+                !pair.is_empty() && name != "limit" && name != "offset"
+            })
+            .collect();
+        let link = |offset: i64| {
+            let mut query = filters.clone();
+            let paging = format!("limit={}&offset={}", limit, offset);
+            query.push(&paging);
+            base.url(uri.path(), &query.join("&"))
+        };
+
+        Self {
+            self_link: link(offset),
+            next: next_offset.map(link),
+            prev: (offset > 0).then(|| link((offset - limit).max(0))),
+        }
+    }
+}
+
+/// Media type opting a list endpoint into the [`Paginated`] envelope
+///
+/// Without it, endpoints that predate the envelope keep answering with a
+/// bare JSON array.
+pub const PAGINATED_MEDIA_TYPE: &str = "application/vnd.stellar-insights.v2+json";
+
+/// Whether `Accept` lists [`PAGINATED_MEDIA_TYPE`]
+pub fn accepts_paginated(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|media| {
+            media
+                .split(';')
+                .next()
+                .is_some_and(|media| media.trim().eq_ignore_ascii_case(PAGINATED_MEDIA_TYPE))
+        })
+}
+
+/// A validated `limit`/`offset` pair
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageRequest {
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// Pagination envelope shared by list endpoints
+///
+/// `next_offset` is the offset of the following page, or `None` on the last page.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Paginated<T> {
+    pub items: Vec<T>,
+    pub total: usize,
+    pub limit: i64,
+    pub offset: i64,
+    pub next_offset: Option<i64>,
+    /// Set by [`Paginated::with_links`] on responses served over HTTP
+    #[serde(rename = "_links", default, skip_serializing_if = "Option::is_none")]
+    pub links: Option<PageLinks>,
+}
+
+impl<T> Paginated<T> {
+    /// Wrap a page of a result set holding `total` items
+    pub fn new(items: Vec<T>, total: usize, limit: i64, offset: i64) -> Self {
+        let end = offset.max(0) + items.len() as i64;
+        let next_offset = if (end as usize) < total {
+            Some(end)
+        } else {
+            None
+        };
+
+        Self {
+            items,
+            total,
+            limit,
+            offset,
+            next_offset,
+            links: None,
+        }
+    }
+
+    /// Wrap a page fetched with `LIMIT`/`OFFSET` when the overall count is unknown
+    ///
+    /// `total` is the page size, and a full page is assumed to have a successor.
+    pub fn from_page(items: Vec<T>, limit: i64, offset: i64) -> Self {
+        let total = items.len();
+        let next_offset = if limit > 0 && total as i64 >= limit {
+            Some(offset.max(0) + total as i64)
+        } else {
+            None
+        };
+
+        Self {
+            items,
+            total,
+            limit,
+            offset,
+            next_offset,
+            links: None,
+        }
+    }
+
+    /// Slice one page out of a complete, in-memory result set
+    pub fn from_all(all: Vec<T>, limit: i64, offset: i64) -> Self {
+        let total = all.len();
+        let items = all
+            .into_iter()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .collect();
+
+        Self::new(items, total, limit, offset)
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Paginated<U> {
+        Paginated {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            limit: self.limit,
+            offset: self.offset,
+            next_offset: self.next_offset,
+            links: self.links,
+        }
+    }
+
+    /// Add `_links` for this page of the list requested at `uri`
+    pub fn with_links(mut self, base: &PublicBasePath, uri: &Uri) -> Self {
+        self.links = Some(PageLinks::new(
+            base,
+            uri,
+            self.limit,
+            self.offset,
+            self.next_offset,
+        ));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_resolve_clamps_over_max_limit() {
+        let limits = PageLimits {
+            default_limit: 20,
+            max_limit: 100,
+        };
+
+        assert_eq!(
+            limits.resolve(Some(1_000_000), 10).unwrap(),
+            PageRequest {
+                limit: 100,
+                offset: 10
+            }
+        );
+        assert_eq!(limits.resolve(None, 0).unwrap().limit, 20);
+        assert_eq!(limits.resolve(Some(0), 0).unwrap().limit, 0);
+    }
+
+    #[test]
+    fn test_resolve_rejects_negatives() {
+        let limits = PageLimits::default();
+
+        assert!(matches!(
+            limits.resolve(Some(-1), 0),
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(matches!(
+            limits.resolve(Some(10), -5),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_page_limits_from_lookup() {
+        let lookup = |vars: &[(&str, &str)]| {
+            let vars: HashMap<String, String> = vars
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            PageLimits::from_lookup(|name| vars.get(name).cloned())
+        };
+
+        assert_eq!(lookup(&[]).unwrap(), PageLimits::default());
+        assert_eq!(
+            lookup(&[("PAGE_DEFAULT_LIMIT", "25"), ("PAGE_MAX_LIMIT", "500")]).unwrap(),
+            PageLimits {
+                default_limit: 25,
+                max_limit: 500
+            }
+        );
+        assert!(lookup(&[("PAGE_DEFAULT_LIMIT", "0")]).is_err());
+        assert!(lookup(&[("PAGE_DEFAULT_LIMIT", "300"), ("PAGE_MAX_LIMIT", "100")]).is_err());
+    }
+
+    #[test]
+    fn test_accepts_paginated_media_type() {
+        let accept = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::ACCEPT, value.parse().unwrap());
+            accepts_paginated(&headers)
+        };
+
+        assert!(!accepts_paginated(&HeaderMap::new()));
+        assert!(!accept("application/json"));
+        assert!(!accept("*/*"));
+        assert!(accept(PAGINATED_MEDIA_TYPE));
+        assert!(accept(
+            "application/json;q=0.5, application/vnd.stellar-insights.v2+json;q=1"
+        ));
+    }
+
+    #[test]
+    fn test_serializes_envelope_fields() {
+        let page = Paginated::new(vec!["a", "b"], 5, 2, 0);
+
+        let json = serde_json::to_value(&page).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "items": ["a", "b"],
+                "total": 5,
+                "limit": 2,
+                "offset": 0,
+                "next_offset": 2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_last_page_has_no_next_offset() {
+        let page = Paginated::from_all(vec![1, 2, 3, 4, 5], 2, 4);
+        assert_eq!(page.items, vec![5]);
+        assert_eq!(page.total, 5);
+        assert_eq!(page.next_offset, None);
+
+        let json = serde_json::to_value(&page).unwrap();
+        assert!(json["next_offset"].is_null());
+
+        let empty = Paginated::from_all(Vec::<i32>::new(), 50, 0);
+        assert!(empty.items.is_empty());
+        assert_eq!(empty.next_offset, None);
+    }
+
+    #[test]
+    fn test_from_page_assumes_more_after_full_page() {
+        let full = Paginated::from_page(vec![1, 2], 2, 10);
+        assert_eq!(full.next_offset, Some(12));
+
+        let partial = Paginated::from_page(vec![1], 2, 10);
+        assert_eq!(partial.next_offset, None);
+    }
+
+    fn links_for(uri: &str, page: Paginated<i32>) -> PageLinks {
+        let base = PublicBasePath::new("/insights/").unwrap();
+        page.with_links(&base, &uri.parse().unwrap()).links.unwrap()
+    }
+
+    #[test]
+    fn test_links_on_first_page() {
+        let links = links_for(
+            "/api/anchors?status=green&limit=2",
+            Paginated::from_all(vec![1, 2, 3, 4, 5], 2, 0),
+        );
+        assert_eq!(
+            links,
+            PageLinks {
+                self_link: "/insights/api/anchors?status=green&limit=2&offset=0".to_string(),
+                next: Some("/insights/api/anchors?status=green&limit=2&offset=2".to_string()),
+                prev: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_links_on_middle_page() {
+        let links = links_for(
+            "/api/corridors?offset=3&limit=2&sort_by=volume",
+            Paginated::from_all(vec![1, 2, 3, 4, 5, 6], 2, 3),
+        );
+        assert_eq!(
+            links.self_link,
+            "/insights/api/corridors?sort_by=volume&limit=2&offset=3"
+        );
+        assert_eq!(
+            links.next.as_deref(),
+            Some("/insights/api/corridors?sort_by=volume&limit=2&offset=5")
+        );
+        assert_eq!(
+            links.prev.as_deref(),
+            Some("/insights/api/corridors?sort_by=volume&limit=2&offset=1")
+        );
+    }
+
+    #[test]
+    fn test_links_on_last_page() {
+        let base = PublicBasePath::new("https://api.example.com").unwrap();
+        let page = Paginated::from_all(vec![1, 2, 3, 4, 5], 2, 4)
+            .with_links(&base, &"/api/anchors?limit=2&offset=4".parse().unwrap());
+
+        let json = serde_json::to_value(&page).unwrap();
+        assert_eq!(
+            json["_links"],
+            serde_json::json!({
+                "self": "https://api.example.com/api/anchors?limit=2&offset=4",
+                "prev": "https://api.example.com/api/anchors?limit=2&offset=2",
+            })
+        );
+    }
+
+    #[test]
+    fn test_public_base_path_validation() {
+        assert_eq!(PublicBasePath::new("").unwrap(), PublicBasePath::default());
+        assert!(PublicBasePath::new("/api-proxy").is_ok());
+        assert!(PublicBasePath::new("insights").is_err());
+    }
+
+    #[test]
+    fn test_round_trip_and_map() {
+        let page = Paginated::from_all(vec![1, 2, 3], 2, 0).map(|n| n * 10);
+        assert_eq!(page.items, vec![10, 20]);
+        assert_eq!(page.next_offset, Some(2));
+
+        let json = serde_json::to_string(&page).unwrap();
+        let decoded: Paginated<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.items, page.items);
+        assert_eq!(decoded.total, 3);
+        assert_eq!(decoded.limit, 2);
+    }
+}
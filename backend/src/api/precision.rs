@@ -0,0 +1,113 @@
+//! Decimal rounding of rate and monetary fields in API responses
+//!
+//! Storage keeps full precision; handlers round just before responding, so the
+//! same metric serializes the same way on every endpoint.
+
+use crate::ingestion::config::parse_var;
+
+const DEFAULT_RATE_DECIMALS: u32 = 2;
+const DEFAULT_VOLUME_DECIMALS: u32 = 2;
+
+/// Largest accepted number of decimals; beyond this f64 has nothing to add
+const MAX_DECIMALS: u32 = 10;
+
+/// Decimals kept in responses, read once at startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResponsePrecision {
+    /// Percentages and scores such as `success_rate`
+    pub rate_decimals: u32,
+    /// USD and quote-currency amounts such as `volume_usd`
+    pub volume_decimals: u32,
+}
+
+impl Default for ResponsePrecision {
+    fn default() -> Self {
+        Self {
+            rate_decimals: DEFAULT_RATE_DECIMALS,
+            volume_decimals: DEFAULT_VOLUME_DECIMALS,
+        }
+    }
+}
+
+impl ResponsePrecision {
+    /// Create from `RESPONSE_RATE_DECIMALS` and `RESPONSE_VOLUME_DECIMALS`
+    pub fn from_env() -> anyhow::Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> anyhow::Result<Self> {
+        let rate_decimals = parse_var(&lookup, "RESPONSE_RATE_DECIMALS", DEFAULT_RATE_DECIMALS)?;
+        let volume_decimals =
+            parse_var(&lookup, "RESPONSE_VOLUME_DECIMALS", DEFAULT_VOLUME_DECIMALS)?;
+        for (name, decimals) in [
+            ("RESPONSE_RATE_DECIMALS", rate_decimals),
+            ("RESPONSE_VOLUME_DECIMALS", volume_decimals),
+        ] {
+            if decimals > MAX_DECIMALS {
+                anyhow::bail!(
+                    "{} must be at most {}, got {}",
+                    name,
+                    MAX_DECIMALS,
+                    decimals
+                );
+            }
+        }
+
+        Ok(Self {
+            rate_decimals,
+            volume_decimals,
+        })
+    }
+
+    pub fn rate(&self, value: f64) -> f64 {
+        round_to(value, self.rate_decimals)
+    }
+
+    pub fn volume(&self, value: f64) -> f64 {
+        round_to(value, self.volume_decimals)
+    }
+}
+
+/// `value` rounded half away from zero to `decimals` places
+///
+/// Non-finite values are returned unchanged.
+pub fn round_to(value: f64, decimals: u32) -> f64 {
+    if !value.is_finite() {
+        return value;
+    }
+    let scale = 10f64.powi(decimals as i32);
+    (value * scale).round() / scale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_round_to() {
+        assert_eq!(round_to(95.00001, 2), 95.0);
+        assert_eq!(round_to(99.995, 1), 100.0);
+        assert_eq!(round_to(1234.5678, 0), 1235.0);
+        assert_eq!(round_to(-0.125, 2), -0.13);
+        assert!(round_to(f64::NAN, 2).is_nan());
+    }
+
+    #[test]
+    fn test_precision_from_lookup() {
+        let lookup = |vars: &[(&str, &str)]| {
+            let vars: HashMap<String, String> = vars
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            ResponsePrecision::from_lookup(|name| vars.get(name).cloned())
+        };
+
+        assert_eq!(lookup(&[]).unwrap(), ResponsePrecision::default());
+        let precision = lookup(&[("RESPONSE_VOLUME_DECIMALS", "0")]).unwrap();
+        assert_eq!(precision.rate(95.00001), 95.0);
+        assert_eq!(precision.volume(1_234_567.891), 1_234_568.0);
+        assert!(lookup(&[("RESPONSE_RATE_DECIMALS", "11")]).is_err());
+        assert!(lookup(&[("RESPONSE_VOLUME_DECIMALS", "-1")]).is_err());
+    }
+}
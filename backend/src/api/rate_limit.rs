@@ -0,0 +1,98 @@
+use axum::{
+    extract::{ConnectInfo, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use crate::rate_limit::{RateLimitInfo, RateLimiter};
+use crate::rpc::Network;
+
+#[derive(Debug, Deserialize)]
+pub struct RateLimitStatusQuery {
+    /// Comma-separated endpoint paths; defaults to every registered endpoint
+    pub endpoints: Option<String>,
+    /// Network whose windows to report; defaults to mainnet
+    #[serde(default)]
+    pub network: Network,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EndpointRateLimitStatus {
+    pub endpoint: String,
+    pub limit: u32,
+    pub used: u32,
+    pub remaining: u32,
+    pub reset_after: u32,
+    pub is_whitelisted: bool,
+}
+
+impl EndpointRateLimitStatus {
+    fn new(endpoint: String, info: RateLimitInfo) -> Self {
+        Self {
+            endpoint,
+            limit: info.limit,
+            used: info.limit.saturating_sub(info.remaining),
+            remaining: info.remaining,
+            reset_after: info.reset_after,
+            is_whitelisted: info.is_whitelisted,
+        }
+    }
+}
+
+/// Handler for GET /api/rate-limit/status - Report the caller's rate-limit standing
+///
+/// Does not count against any limit.
+pub async fn get_rate_limit_status(
+    State(limiter): State<Arc<RateLimiter>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Query(params): Query<RateLimitStatusQuery>,
+) -> Json<Vec<EndpointRateLimitStatus>> {
+    let ip = addr.ip().to_string();
+
+    let endpoints = match params.endpoints {
+        Some(endpoints) => endpoints
+            .split(',')
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+            .map(str::to_string)
+            .collect(),
+        None => limiter.registered_endpoints().await,
+    };
+
+    let mut statuses = Vec::with_capacity(endpoints.len());
+    for endpoint in endpoints {
+        let info = limiter.status(&ip, &endpoint, params.network).await;
+        statuses.push(EndpointRateLimitStatus::new(endpoint, info));
+    }
+
+    Json(statuses)
+}
+
+pub fn routes(limiter: Arc<RateLimiter>) -> Router {
+    Router::new()
+        .route("/api/rate-limit/status", get(get_rate_limit_status))
+        .with_state(limiter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_status_from_info() {
+        let status = EndpointRateLimitStatus::new(
+            "/api/anchors".to_string(),
+            RateLimitInfo {
+                limit: 100,
+                remaining: 60,
+                reset_after: 30,
+                is_whitelisted: false,
+            },
+        );
+        assert_eq!(status.used, 40);
+        assert_eq!(status.remaining, 60);
+    }
+}
@@ -0,0 +1,72 @@
+use axum::{extract::State, Json};
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::{CreateWebhookRequest, WebhookRecord};
+use crate::services::webhook::ANCHOR_STATUS_CHANGED_EVENT;
+use crate::state::AppState;
+
+/// POST /api/webhooks - Register a webhook receiver
+pub async fn create_webhook(
+    State(app_state): State<AppState>,
+    Json(req): Json<CreateWebhookRequest>,
+) -> ApiResult<Json<WebhookRecord>> {
+    validate_webhook_request(&req)?;
+
+    let webhook = app_state.db.create_webhook(req).await?;
+
+    Ok(Json(webhook))
+}
+
+/// GET /api/webhooks - List registered webhook receivers
+pub async fn list_webhooks(
+    State(app_state): State<AppState>,
+) -> ApiResult<Json<Vec<WebhookRecord>>> {
+    let webhooks = app_state.db.list_webhooks().await?;
+
+    Ok(Json(webhooks))
+}
+
+fn validate_webhook_request(req: &CreateWebhookRequest) -> Result<(), ApiError> {
+    if !req.url.starts_with("http://") && !req.url.starts_with("https://") {
+        return Err(ApiError::BadRequest(
+            "Webhook url must be an http(s) URL".to_string(),
+        ));
+    }
+
+    if req.secret.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Webhook secret cannot be empty".to_string(),
+        ));
+    }
+
+    if let Some(event_type) = &req.event_type {
+        if event_type != ANCHOR_STATUS_CHANGED_EVENT {
+            return Err(ApiError::BadRequest(format!(
+                "Unsupported event type: {}",
+                event_type
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(url: &str, secret: &str) -> CreateWebhookRequest {
+        CreateWebhookRequest {
+            url: url.to_string(),
+            secret: secret.to_string(),
+            event_type: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_webhook_request() {
+        assert!(validate_webhook_request(&request("https://ops.example.com/hook", "s3cret")).is_ok());
+        assert!(validate_webhook_request(&request("ftp://ops.example.com", "s3cret")).is_err());
+        assert!(validate_webhook_request(&request("https://ops.example.com/hook", "")).is_err());
+    }
+}
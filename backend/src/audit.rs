@@ -0,0 +1,166 @@
+//! Audit log of successful writes
+//!
+//! [`audit_middleware`] sits inside authentication on the write routers and
+//! appends one `audit_log` row per successful mutating request: who made it,
+//! which action, the written resource's id and the request id. The table is
+//! append-only; SQLite triggers reject updates and deletes.
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, RawPathParams, Request, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+
+use crate::auth_middleware::AuthUser;
+use crate::database::Database;
+use crate::idempotency::IDEMPOTENT_REPLAYED_HEADER;
+
+pub const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Principal recorded for writes made without an authenticated user
+const ANONYMOUS: &str = "anonymous";
+
+/// Action names for the audited routes, by method and route pattern
+const ACTIONS: &[(Method, &str, &str)] = &[
+    (Method::POST, "/api/anchors", "create_anchor"),
+    (
+        Method::PUT,
+        "/api/anchors/:id/metrics",
+        "update_anchor_metrics",
+    ),
+    (
+        Method::PATCH,
+        "/api/anchors/:id/metrics",
+        "patch_anchor_metrics",
+    ),
+    (
+        Method::POST,
+        "/api/anchors/:id/assets",
+        "create_anchor_asset",
+    ),
+    (Method::POST, "/api/corridors", "create_corridor"),
+    (Method::POST, "/api/corridors/import", "import_corridors"),
+    (
+        Method::PUT,
+        "/api/corridors/:id/metrics-from-transactions",
+        "update_corridor_metrics",
+    ),
+    (Method::POST, "/api/cache/flush", "flush_cache"),
+    (
+        Method::POST,
+        "/api/ingestion/failures/:id/requeue",
+        "requeue_ingestion_failure",
+    ),
+    (Method::POST, "/api/ingestion/backfill", "backfill_ledgers"),
+    (
+        Method::POST,
+        "/api/admin/maintenance",
+        "set_maintenance_mode",
+    ),
+];
+
+/// Action recorded for a write to `route`; unlisted routes fall back to
+/// `"<METHOD> <route>"`
+pub fn action_for(method: &Method, route: &str) -> String {
+    ACTIONS
+        .iter()
+        .find(|(m, r, _)| m == method && *r == route)
+        .map(|(_, _, action)| action.to_string())
+        .unwrap_or_else(|| format!("{} {}", method, route))
+}
+
+fn is_write(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    )
+}
+
+fn is_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"))
+}
+
+/// The `id` field of a JSON response body, as returned by the create handlers
+fn id_from_body(body: &[u8]) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_slice(body).ok()?;
+    match value.get("id")? {
+        serde_json::Value::String(id) => Some(id.clone()),
+        serde_json::Value::Number(id) => Some(id.to_string()),
+        _ => None,
+    }
+}
+
+/// Middleware recording successful writes in the audit log
+///
+/// The target is the route's `:id` parameter, or else the `id` of the JSON
+/// response, which is how the create handlers return the new resource.
+/// Idempotent replays are not recorded again. The
+/// caller's `X-Request-Id` is reused, or one is generated, and echoed on the
+/// response. A failed insert is logged rather than failing a write that has
+/// already happened.
+pub async fn audit_middleware(
+    State(db): State<Arc<Database>>,
+    matched_path: Option<MatchedPath>,
+    path_params: Option<RawPathParams>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let method = req.method().clone();
+    let request_id = req
+        .headers()
+        .get(X_REQUEST_ID)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let principal = req
+        .extensions()
+        .get::<AuthUser>()
+        .map(|user| user.username.clone())
+        .unwrap_or_else(|| ANONYMOUS.to_string());
+    let route = matched_path
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| req.uri().path().to_string());
+    let path_id = path_params.and_then(|params| {
+        params
+            .iter()
+            .find(|(name, _)| *name == "id")
+            .map(|(_, value)| value.to_string())
+    });
+
+    let mut response = next.run(req).await;
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(X_REQUEST_ID, value);
+    }
+    // Replays of an idempotent request repeat a write that was already recorded
+    if !is_write(&method)
+        || !response.status().is_success()
+        || response.headers().contains_key(IDEMPOTENT_REPLAYED_HEADER)
+    {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let (target_id, body) = match path_id {
+        Some(id) => (Some(id), body),
+        None if is_json(&parts.headers) => match axum::body::to_bytes(body, usize::MAX).await {
+            Ok(bytes) => (id_from_body(&bytes), Body::from(bytes)),
+            Err(_) => return Response::from_parts(parts, Body::empty()),
+        },
+        None => (None, body),
+    };
+
+    let action = action_for(&method, &route);
+    if let Err(e) = db
+        .record_audit(&principal, &action, target_id.as_deref(), &request_id)
+        .await
+    {
+        tracing::error!("Failed to record audit entry for {}: {}", action, e);
+    }
+    Response::from_parts(parts, body)
+}
@@ -1,7 +1,16 @@
+use crate::api::cache_stats::CacheStatsResponse;
+use crate::cache::CacheManager;
+use crate::corridor_feed::CorridorSnapshot;
+use crate::db::aggregates::LatestCorridorMetrics;
 use crate::models::Anchor;
-use crate::models::corridor::Corridor;
+use crate::models::corridor::{Corridor, CorridorMetrics};
 use crate::websocket::{WsMessage, WsState};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Default interval between cache statistics pushes
+pub const DEFAULT_CACHE_STATS_INTERVAL: Duration = Duration::from_secs(1);
 
 /// Broadcast an anchor update to all WebSocket clients
 pub fn broadcast_anchor_update(ws_state: &Arc<WsState>, anchor: &Anchor) {
@@ -26,11 +35,75 @@ pub fn broadcast_corridor_update(ws_state: &Arc<WsState>, corridor: &Corridor) {
     ws_state.broadcast(message);
 }
 
+/// Send `corridors` subscribers the fields of `metrics` that changed
+pub fn broadcast_corridor_metrics(
+    ws_state: &Arc<WsState>,
+    corridor: &Corridor,
+    metrics: &CorridorMetrics,
+) {
+    ws_state.publish_corridor(CorridorSnapshot::from_metrics(
+        &corridor.to_string_key(),
+        metrics,
+    ));
+}
+
+/// Send `corridors` subscribers the changes to corridors' rolling metrics
+pub fn broadcast_latest_corridor_metrics(ws_state: &WsState, metrics: &[LatestCorridorMetrics]) {
+    for corridor in metrics {
+        ws_state.publish_corridor(CorridorSnapshot::from_latest(corridor));
+    }
+}
+
+/// Push cache statistics to `cache_stats` subscribers every `interval`
+///
+/// Snapshots are skipped while nobody is subscribed.
+pub fn spawn_cache_stats_stream(
+    ws_state: Arc<WsState>,
+    cache: Arc<CacheManager>,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if ws_state.has_cache_stats_subscribers() {
+                ws_state.publish_cache_stats(CacheStatsResponse::from(cache.get_stats()));
+            }
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::Utc;
 
+    #[tokio::test]
+    async fn test_cache_stats_subscriber_receives_snapshot() {
+        let ws_state = Arc::new(WsState::new());
+        let cache = Arc::new(CacheManager::new(Default::default()).await.unwrap());
+        let mut rx = ws_state.subscribe_cache_stats();
+        // Broadcast-only clients never see cache stats
+        let mut general_rx = ws_state.tx.subscribe();
+
+        let interval = Duration::from_millis(50);
+        let stream = spawn_cache_stats_stream(Arc::clone(&ws_state), Arc::clone(&cache), interval);
+
+        let message = tokio::time::timeout(interval * 4, rx.recv())
+            .await
+            .expect("no stats within the interval")
+            .unwrap();
+        match message {
+            WsMessage::CacheStats(stats) => {
+                assert_eq!(stats, CacheStatsResponse::from(cache.get_stats()));
+            }
+            other => panic!("unexpected message {:?}", other),
+        }
+        assert!(general_rx.try_recv().is_err());
+
+        stream.abort();
+    }
+
     #[test]
     fn test_broadcast_anchor_update() {
         let ws_state = Arc::new(WsState::new());
@@ -46,6 +119,7 @@ mod tests {
             avg_settlement_time_ms: 500,
             reliability_score: 95.0,
             status: "active".to_string(),
+            availability: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -0,0 +1,163 @@
+//! Minimal Redis server for tests that need the real connection path
+//!
+//! Speaks enough RESP for `GET`, `MGET`, `SET` (with `NX`/`EX`), `SETEX`,
+//! `DEL` and `TTL`, storing values in a [`MemoryStore`]. Anything else is
+//! answered with `+OK`.
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::{JoinHandle, JoinSet};
+
+use super::memory::MemoryStore;
+use super::{connect_redis, CacheConfig, CacheManager};
+
+/// Stand-in expiry for values set without one
+const NO_EXPIRY_SECS: usize = 365 * 24 * 3600;
+
+pub(crate) struct FakeRedis {
+    url: String,
+    store: Arc<MemoryStore>,
+    server: JoinHandle<()>,
+}
+
+impl FakeRedis {
+    pub(crate) async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("redis://{}", listener.local_addr().unwrap());
+        let store = Arc::new(MemoryStore::default());
+
+        let server_store = Arc::clone(&store);
+        let server = tokio::spawn(async move {
+            // Owned here so stopping the server also closes open connections
+            let mut connections = JoinSet::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                connections.spawn(serve(stream, Arc::clone(&server_store)));
+            }
+        });
+
+        Self { url, store, server }
+    }
+
+    pub(crate) fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Cache connected to this server
+    pub(crate) async fn connect(&self, config: CacheConfig) -> CacheManager {
+        let connection = connect_redis(&self.url).await.unwrap();
+        CacheManager::with_connection(config, self.url.clone(), Some(connection))
+    }
+
+    /// Time left before `key` expires, if it is set
+    pub(crate) fn ttl(&self, key: &str) -> Option<Duration> {
+        self.store.ttl(key)
+    }
+
+    /// Stop accepting commands and close every open connection
+    pub(crate) fn stop(&self) {
+        self.server.abort();
+    }
+}
+
+impl Drop for FakeRedis {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+enum Reply {
+    Ok,
+    Nil,
+    Integer(i64),
+    Bulk(String),
+    Array(Vec<Option<String>>),
+}
+
+impl Reply {
+    fn encode(&self) -> String {
+        match self {
+            Reply::Ok => "+OK\r\n".to_string(),
+            Reply::Nil => "$-1\r\n".to_string(),
+            Reply::Integer(n) => format!(":{}\r\n", n),
+            Reply::Bulk(value) => format!("${}\r\n{}\r\n", value.len(), value),
+            Reply::Array(values) => {
+                let mut out = format!("*{}\r\n", values.len());
+                for value in values {
+                    match value {
+                        Some(value) => out.push_str(&Reply::Bulk(value.clone()).encode()),
+                        None => out.push_str(&Reply::Nil.encode()),
+                    }
+                }
+                out
+            }
+        }
+    }
+}
+
+async fn serve(stream: TcpStream, store: Arc<MemoryStore>) {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+    while let Some(args) = read_command(&mut reader).await {
+        let reply = execute(&store, &args);
+        if writer.write_all(reply.encode().as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
+
+async fn read_command<R: AsyncBufRead + Unpin>(reader: &mut R) -> Option<Vec<String>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).await.ok()? == 0 {
+        return None;
+    }
+    let count: usize = line.trim_end().strip_prefix('*')?.parse().ok()?;
+    let mut args = Vec::with_capacity(count);
+    for _ in 0..count {
+        line.clear();
+        reader.read_line(&mut line).await.ok()?;
+        let len: usize = line.trim_end().strip_prefix('$')?.parse().ok()?;
+        let mut arg = vec![0; len + 2];
+        reader.read_exact(&mut arg).await.ok()?;
+        arg.truncate(len);
+        args.push(String::from_utf8(arg).ok()?);
+    }
+    Some(args)
+}
+
+fn execute(store: &MemoryStore, args: &[String]) -> Reply {
+    let arg = |i: usize| args.get(i).map(String::as_str).unwrap_or_default();
+    match arg(0).to_ascii_uppercase().as_str() {
+        "GET" => store.get(arg(1)).map_or(Reply::Nil, Reply::Bulk),
+        "MGET" => Reply::Array(store.get_many(&args[1..])),
+        "SETEX" => {
+            store.set(arg(1), arg(3).to_string(), arg(2).parse().unwrap_or(0));
+            Reply::Ok
+        }
+        "SET" => {
+            let options: Vec<String> = args[3..].iter().map(|a| a.to_ascii_uppercase()).collect();
+            let ttl = options
+                .iter()
+                .position(|o| o == "EX")
+                .and_then(|i| args.get(i + 4)?.parse().ok())
+                .unwrap_or(NO_EXPIRY_SECS);
+            if options.iter().any(|o| o == "NX") {
+                if store.set_nx(arg(1), arg(2).to_string(), ttl) {
+                    Reply::Ok
+                } else {
+                    Reply::Nil
+                }
+            } else {
+                store.set(arg(1), arg(2).to_string(), ttl);
+                Reply::Ok
+            }
+        }
+        "DEL" => Reply::Integer(args[1..].iter().filter(|key| store.delete(key)).count() as i64),
+        "TTL" => match store.ttl(arg(1)) {
+            Some(ttl) => Reply::Integer(ttl.as_secs() as i64),
+            None => Reply::Integer(-2),
+        },
+        _ => Reply::Ok,
+    }
+}
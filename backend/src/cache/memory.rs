@@ -0,0 +1,195 @@
+//! In-process stand-in for Redis behind [`CacheManager::in_memory`]
+//!
+//! Mirrors the handful of Redis commands the cache uses: string values and
+//! tag sets with expiry, `SET NX`, and `KEYS`-style pattern deletes.
+//!
+//! [`CacheManager::in_memory`]: super::CacheManager::in_memory
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Default)]
+pub(crate) struct MemoryStore {
+    state: Mutex<MemoryState>,
+}
+
+#[derive(Default)]
+struct MemoryState {
+    values: HashMap<String, Expiring<String>>,
+    tags: HashMap<String, Expiring<HashSet<String>>>,
+}
+
+struct Expiring<T> {
+    value: T,
+    expires_at: Instant,
+}
+
+impl<T> Expiring<T> {
+    fn new(value: T, ttl_seconds: usize) -> Self {
+        Self {
+            value,
+            expires_at: expiry(ttl_seconds),
+        }
+    }
+
+    fn is_live(&self) -> bool {
+        Instant::now() < self.expires_at
+    }
+}
+
+fn expiry(ttl_seconds: usize) -> Instant {
+    Instant::now() + Duration::from_secs(ttl_seconds as u64)
+}
+
+impl MemoryStore {
+    pub(crate) fn get(&self, key: &str) -> Option<String> {
+        let mut state = self.state.lock().unwrap();
+        match state.values.get(key) {
+            Some(entry) if entry.is_live() => Some(entry.value.clone()),
+            Some(_) => {
+                state.values.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub(crate) fn get_many(&self, keys: &[String]) -> Vec<Option<String>> {
+        keys.iter().map(|key| self.get(key)).collect()
+    }
+
+    pub(crate) fn set(&self, key: &str, value: String, ttl_seconds: usize) {
+        self.state
+            .lock()
+            .unwrap()
+            .values
+            .insert(key.to_string(), Expiring::new(value, ttl_seconds));
+    }
+
+    /// Set `key` unless a live value exists, returning whether it was set
+    pub(crate) fn set_nx(&self, key: &str, value: String, ttl_seconds: usize) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.values.get(key).is_some_and(Expiring::is_live) {
+            return false;
+        }
+        state
+            .values
+            .insert(key.to_string(), Expiring::new(value, ttl_seconds));
+        true
+    }
+
+    /// Time left before `key` expires, if it is set
+    #[cfg(test)]
+    pub(crate) fn ttl(&self, key: &str) -> Option<Duration> {
+        self.state
+            .lock()
+            .unwrap()
+            .values
+            .get(key)
+            .filter(|entry| entry.is_live())
+            .map(|entry| entry.expires_at.saturating_duration_since(Instant::now()))
+    }
+
+    /// Remove `key`, returning whether a live value was removed
+    pub(crate) fn delete(&self, key: &str) -> bool {
+        self.state
+            .lock()
+            .unwrap()
+            .values
+            .remove(key)
+            .is_some_and(|entry| entry.is_live())
+    }
+
+    /// Remove every value whose key matches `pattern`, returning how many
+    pub(crate) fn delete_matching(&self, pattern: &str) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        let before = state.values.len();
+        state
+            .values
+            .retain(|key, entry| !(entry.is_live() && glob_match(pattern, key)));
+        (before - state.values.len()) as u64
+    }
+
+    /// Record `member` under the tag set `tag_key`, keeping the set alive at
+    /// least `ttl_seconds`
+    pub(crate) fn add_to_tag(&self, tag_key: &str, member: &str, ttl_seconds: usize) {
+        let mut state = self.state.lock().unwrap();
+        let set = state
+            .tags
+            .entry(tag_key.to_string())
+            .or_insert_with(|| Expiring::new(HashSet::new(), ttl_seconds));
+        if !set.is_live() {
+            set.value.clear();
+        }
+        set.value.insert(member.to_string());
+        set.expires_at = set.expires_at.max(expiry(ttl_seconds));
+    }
+
+    pub(crate) fn tag_members(&self, tag_key: &str) -> Vec<String> {
+        match self.state.lock().unwrap().tags.get(tag_key) {
+            Some(set) if set.is_live() => set.value.iter().cloned().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Remove the tag set `tag_key` along with every key recorded under it,
+    /// returning the members
+    pub(crate) fn invalidate_tag(&self, tag_key: &str) -> Vec<String> {
+        let mut state = self.state.lock().unwrap();
+        let members: Vec<String> = match state.tags.remove(tag_key) {
+            Some(set) if set.is_live() => set.value.into_iter().collect(),
+            _ => Vec::new(),
+        };
+        for member in &members {
+            state.values.remove(member);
+        }
+        members
+    }
+}
+
+/// Redis `KEYS`-style match where `*` stands for any run of characters
+fn glob_match(pattern: &str, key: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let Some(mut rest) = key.strip_prefix(parts.next().unwrap_or_default()) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("v2:anchor:*", "v2:anchor:detail:1"));
+        assert!(!glob_match("v2:anchor:*", "v2:tag:anchor:1"));
+        assert!(glob_match("v2:*:detail:*", "v2:corridor:detail:USDC"));
+        assert!(!glob_match("v2:*:detail:*", "v2:corridor:summary:USDC"));
+        assert!(glob_match("exact", "exact"));
+        assert!(!glob_match("exact", "exactly"));
+    }
+
+    #[test]
+    fn test_expired_values_are_gone() {
+        let store = MemoryStore::default();
+        store.set("live", "1".to_string(), 60);
+        store.set("expired", "2".to_string(), 0);
+
+        assert_eq!(store.get("live"), Some("1".to_string()));
+        assert_eq!(store.get("expired"), None);
+        assert!(store.set_nx("expired", "3".to_string(), 60));
+        assert!(!store.set_nx("live", "4".to_string(), 60));
+    }
+}
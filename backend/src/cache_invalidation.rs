@@ -1,6 +1,41 @@
 use crate::cache::{keys, CacheManager};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+/// Cache namespace wiped by [`CacheInvalidationService::flush`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FlushScope {
+    Anchors,
+    Corridors,
+    Dashboard,
+    All,
+}
+
+impl FlushScope {
+    /// Key patterns covering the scope; tag sets are left alone since they
+    /// only point at keys and are pruned on the next invalidation
+    ///
+    /// `All` also covers the RPC, FX and ML caches, which have no scope of
+    /// their own.
+    pub fn patterns(self) -> Vec<String> {
+        match self {
+            FlushScope::Anchors => vec![keys::anchor_pattern()],
+            FlushScope::Corridors => vec![keys::corridor_pattern()],
+            FlushScope::Dashboard => vec![keys::dashboard_pattern(), keys::metrics_pattern()],
+            FlushScope::All => [
+                FlushScope::Anchors,
+                FlushScope::Corridors,
+                FlushScope::Dashboard,
+            ]
+            .into_iter()
+            .flat_map(FlushScope::patterns)
+            .chain([keys::rpc_pattern(), keys::fx_pattern(), keys::ml_pattern()])
+            .collect(),
+        }
+    }
+}
+
 /// Service for managing cache invalidation on data updates
 pub struct CacheInvalidationService {
     cache: Arc<CacheManager>,
@@ -11,10 +46,15 @@ impl CacheInvalidationService {
         Self { cache }
     }
 
+    /// Cache this service invalidates
+    pub fn cache(&self) -> &Arc<CacheManager> {
+        &self.cache
+    }
+
     /// Invalidate all anchor-related caches
     pub async fn invalidate_anchors(&self) -> anyhow::Result<()> {
         tracing::info!("Invalidating anchor caches");
-        self.cache.delete_pattern(&keys::anchor_pattern()).await
+        self.cache.invalidate_tag(&keys::anchors_tag()).await
     }
 
     /// Invalidate specific anchor caches
@@ -22,8 +62,26 @@ impl CacheInvalidationService {
         tracing::info!("Invalidating cache for anchor: {}", anchor_id);
         self.cache.delete(&keys::anchor_detail(anchor_id)).await?;
         self.cache.delete(&keys::anchor_assets(anchor_id)).await?;
+        self.cache.invalidate_tag(&keys::anchor_tag(anchor_id)).await?;
         // Also invalidate the list caches since they contain this anchor
-        self.cache.delete_pattern(&keys::anchor_pattern()).await
+        self.cache.invalidate_tag(&keys::anchor_lists_tag()).await
+    }
+
+    /// Invalidate only the caches touched by an update to one anchor.
+    ///
+    /// Detail, account and asset entries for other anchors stay cached; list
+    /// pages are dropped because they may include this anchor.
+    pub async fn invalidate_anchor_update(
+        &self,
+        anchor_id: &str,
+        account: &str,
+    ) -> anyhow::Result<()> {
+        tracing::info!("Invalidating caches for updated anchor: {}", anchor_id);
+        self.cache.delete(&keys::anchor_detail(anchor_id)).await?;
+        self.cache.delete(&keys::anchor_assets(anchor_id)).await?;
+        self.cache.delete(&keys::anchor_by_account(account)).await?;
+        self.cache.invalidate_tag(&keys::anchor_tag(anchor_id)).await?;
+        self.cache.invalidate_tag(&keys::anchor_lists_tag()).await
     }
 
     /// Invalidate anchor by account
@@ -31,27 +89,53 @@ impl CacheInvalidationService {
         tracing::info!("Invalidating cache for anchor account: {}", account);
         self.cache.delete(&keys::anchor_by_account(account)).await?;
         // Also invalidate list caches
-        self.cache.delete_pattern(&keys::anchor_pattern()).await
+        self.cache.invalidate_tag(&keys::anchor_lists_tag()).await
     }
 
     /// Invalidate all corridor-related caches
     pub async fn invalidate_corridors(&self) -> anyhow::Result<()> {
         tracing::info!("Invalidating corridor caches");
-        self.cache.delete_pattern(&keys::corridor_pattern()).await
+        self.cache.invalidate_tag(&keys::corridors_tag()).await
     }
 
     /// Invalidate specific corridor cache
     pub async fn invalidate_corridor(&self, corridor_key: &str) -> anyhow::Result<()> {
         tracing::info!("Invalidating cache for corridor: {}", corridor_key);
         self.cache.delete(&keys::corridor_detail(corridor_key)).await?;
+        self.cache.invalidate_tag(&keys::corridor_tag(corridor_key)).await?;
         // Also invalidate the list caches since they contain this corridor
-        self.cache.delete_pattern(&keys::corridor_pattern()).await
+        self.cache.invalidate_tag(&keys::corridor_lists_tag()).await
+    }
+
+    /// Invalidate only the caches touched by updates to `corridor_keys`.
+    ///
+    /// Per-corridor entries for other corridors stay cached; list pages and
+    /// network-wide totals are dropped when any corridor changed.
+    pub async fn invalidate_corridor_keys<I, S>(&self, corridor_keys: I) -> anyhow::Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut changed = 0;
+        for corridor_key in corridor_keys {
+            let corridor_key = corridor_key.as_ref();
+            self.cache.delete(&keys::corridor_detail(corridor_key)).await?;
+            self.cache.invalidate_tag(&keys::corridor_tag(corridor_key)).await?;
+            changed += 1;
+        }
+        if changed == 0 {
+            return Ok(());
+        }
+
+        tracing::info!("Invalidated caches for {} updated corridors", changed);
+        self.cache.invalidate_tag(&keys::corridor_lists_tag()).await
     }
 
     /// Invalidate dashboard caches
     pub async fn invalidate_dashboard(&self) -> anyhow::Result<()> {
         tracing::info!("Invalidating dashboard caches");
-        self.cache.delete_pattern(&keys::dashboard_pattern()).await
+        self.cache.delete_pattern(&keys::dashboard_pattern()).await?;
+        Ok(())
     }
 
     /// Invalidate metrics caches
@@ -69,6 +153,16 @@ impl CacheInvalidationService {
         self.invalidate_metrics().await?;
         Ok(())
     }
+
+    /// Delete every key in a namespace, returning how many were removed
+    pub async fn flush(&self, scope: FlushScope) -> anyhow::Result<u64> {
+        tracing::warn!("Flushing cache scope {:?}", scope);
+        let mut removed = 0;
+        for pattern in scope.patterns() {
+            removed += self.cache.delete_pattern(&pattern).await?;
+        }
+        Ok(removed)
+    }
 }
 
 #[cfg(test)]
@@ -77,8 +171,103 @@ mod tests {
 
     #[test]
     fn test_cache_key_patterns() {
-        assert_eq!(keys::anchor_pattern(), "anchor:*");
-        assert_eq!(keys::corridor_pattern(), "corridor:*");
-        assert_eq!(keys::dashboard_pattern(), "dashboard:*");
+        assert_eq!(keys::anchor_pattern(), "v2:anchor:*");
+        assert_eq!(keys::corridor_pattern(), "v2:corridor:*");
+        assert_eq!(keys::dashboard_pattern(), "v2:dashboard:*");
+    }
+
+    #[test]
+    fn test_flush_scope_patterns() {
+        assert_eq!(FlushScope::Anchors.patterns(), vec!["v2:anchor:*"]);
+        assert_eq!(FlushScope::Corridors.patterns(), vec!["v2:corridor:*"]);
+        assert_eq!(
+            FlushScope::Dashboard.patterns(),
+            vec!["v2:dashboard:*", "v2:metrics:*"]
+        );
+        assert_eq!(
+            FlushScope::All.patterns(),
+            vec![
+                "v2:anchor:*",
+                "v2:corridor:*",
+                "v2:dashboard:*",
+                "v2:metrics:*",
+                "v2:rpc:*",
+                "v2:fx:*",
+                "v2:ml:*",
+            ]
+        );
+        // Idempotency records and tag sets are never flushed
+        for pattern in FlushScope::All.patterns() {
+            assert_ne!(pattern, "v2:*");
+            assert!(!pattern.starts_with("v2:idempotency"));
+            assert!(!pattern.starts_with("v2:tag"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flush_only_removes_scope_keys() {
+        let cache = Arc::new(CacheManager::in_memory(Default::default()));
+        let service = CacheInvalidationService::new(Arc::clone(&cache));
+
+        let anchor = keys::anchor_detail("test-flush-anchor");
+        let corridor = keys::corridor_detail("test-flush-corridor");
+        let dashboard = keys::dashboard_stats();
+        let metrics = keys::metrics_overview();
+        let unscoped = [
+            keys::rpc_fee_stats(crate::rpc::Network::Testnet),
+            keys::fx_rate("EUR"),
+            keys::ml_prediction("test-flush-corridor", "hash"),
+        ];
+        let all_keys: Vec<&String> = [&anchor, &corridor, &dashboard, &metrics]
+            .into_iter()
+            .chain(&unscoped)
+            .collect();
+
+        for (scope, flushed) in [
+            (FlushScope::Anchors, vec![&anchor]),
+            (FlushScope::Corridors, vec![&corridor]),
+            (FlushScope::Dashboard, vec![&dashboard, &metrics]),
+        ] {
+            for key in &all_keys {
+                cache.set(key, &"cached".to_string(), 60).await.unwrap();
+            }
+
+            assert_eq!(service.flush(scope).await.unwrap(), flushed.len() as u64);
+
+            for &key in &all_keys {
+                let present = cache.get::<String>(key).await.unwrap().is_some();
+                assert_eq!(present, !flushed.contains(&key), "{:?} {}", scope, key);
+            }
+        }
+
+        // Only a full flush reaches the RPC, FX and ML caches
+        service.flush(FlushScope::All).await.unwrap();
+        for key in &all_keys {
+            assert_eq!(cache.get::<String>(key).await.unwrap(), None);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_anchor_update_leaves_other_anchors_cached() {
+        let cache = Arc::new(CacheManager::in_memory(Default::default()));
+        let service = CacheInvalidationService::new(Arc::clone(&cache));
+
+        let detail_a = keys::anchor_detail("test-anchor-a");
+        let detail_b = keys::anchor_detail("test-anchor-b");
+        cache.set(&detail_a, &"a".to_string(), 60).await.unwrap();
+        cache.set(&detail_b, &"b".to_string(), 60).await.unwrap();
+
+        service
+            .invalidate_anchor_update("test-anchor-a", "GTESTANCHORA")
+            .await
+            .unwrap();
+
+        assert_eq!(cache.get::<String>(&detail_a).await.unwrap(), None);
+        assert_eq!(
+            cache.get::<String>(&detail_b).await.unwrap(),
+            Some("b".to_string())
+        );
+
+        cache.delete(&detail_b).await.unwrap();
     }
 }
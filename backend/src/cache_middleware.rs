@@ -1,6 +1,126 @@
-use crate::cache::CacheManager;
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderValue, Method},
+    middleware::Next,
+    response::Response,
+};
 use std::sync::Arc;
 
+use crate::cache::{CacheConfig, CacheManager, CacheSchema};
+use crate::server_timing::timed;
+
+/// `Cache-Control` policy for the responses of a route
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheControl {
+    /// Successful reads may be cached by browsers and CDNs for `max_age` seconds
+    Public { max_age: usize },
+    /// Never cached; for authenticated and write routes
+    NoStore,
+}
+
+impl CacheControl {
+    /// Let clients cache as long as the server-side cache keeps `cache_type` entries
+    pub fn for_cache_type(config: &CacheConfig, cache_type: &str) -> Self {
+        CacheControl::Public {
+            max_age: config.get_ttl(cache_type),
+        }
+    }
+
+    fn header_value(self, method: &Method, success: bool) -> HeaderValue {
+        match self {
+            CacheControl::Public { max_age }
+                if success && matches!(*method, Method::GET | Method::HEAD) =>
+            {
+                HeaderValue::from_str(&format!("public, max-age={}", max_age))
+                    .expect("cache-control value is valid")
+            }
+            _ => HeaderValue::from_static("no-store"),
+        }
+    }
+}
+
+/// Middleware setting `Cache-Control` unless the handler already did
+///
+/// Errors and non-GET responses are always `no-store`.
+pub async fn cache_control_middleware(
+    State(policy): State<CacheControl>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let method = req.method().clone();
+    let mut response = next.run(req).await;
+    let value = policy.header_value(&method, response.status().is_success());
+    response
+        .headers_mut()
+        .entry(header::CACHE_CONTROL)
+        .or_insert(value);
+    response
+}
+
+tokio::task_local! {
+    static BYPASS_CACHE: bool;
+}
+
+/// Whether server-side cache reads may be skipped per request
+///
+/// Off unless `CACHE_BYPASS_ENABLED=true`, so production traffic can't force
+/// every request through to the database and RPC.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheBypass {
+    pub enabled: bool,
+}
+
+impl CacheBypass {
+    pub fn from_env() -> Self {
+        let enabled = std::env::var("CACHE_BYPASS_ENABLED")
+            .ok()
+            .and_then(|v| v.parse::<bool>().ok())
+            .unwrap_or(false);
+
+        Self { enabled }
+    }
+}
+
+/// Whether a request asks for a fresh fetch, with `Cache-Control: no-cache`
+/// or `?no_cache=true`
+pub fn wants_cache_bypass(query: Option<&str>, headers: &HeaderMap) -> bool {
+    let from_query = query
+        .unwrap_or_default()
+        .split('&')
+        .filter_map(|pair| pair.strip_prefix("no_cache="))
+        .any(|value| value == "true");
+    let from_header = headers
+        .get_all(header::CACHE_CONTROL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|directive| directive.trim().eq_ignore_ascii_case("no-cache"));
+    from_query || from_header
+}
+
+/// Whether the current request skips cache reads
+///
+/// Always `false` outside [`cache_bypass_middleware`].
+pub fn cache_bypassed() -> bool {
+    BYPASS_CACHE.try_with(|bypass| *bypass).unwrap_or(false)
+}
+
+/// Middleware letting a request skip cache reads while it is handled
+///
+/// [`CacheAware`] lookups then go straight to the fetch and write the fresh
+/// result back, so later requests see it too. Ignored unless enabled.
+pub async fn cache_bypass_middleware(
+    State(policy): State<CacheBypass>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let bypass = policy.enabled && wants_cache_bypass(req.uri().query(), req.headers());
+    if bypass {
+        tracing::debug!("Bypassing cache reads for {}", req.uri());
+    }
+    BYPASS_CACHE.scope(bypass, next.run(req)).await
+}
+
 /// Helper trait for cache-aware operations
 pub trait CacheAware {
     fn get_or_fetch<T, F>(
@@ -10,7 +130,19 @@ pub trait CacheAware {
         fetch_fn: F,
     ) -> impl std::future::Future<Output = anyhow::Result<T>>
     where
-        T: serde::Serialize + serde::de::DeserializeOwned,
+        T: serde::Serialize + serde::de::DeserializeOwned + CacheSchema,
+        F: std::future::Future<Output = anyhow::Result<T>>;
+
+    /// Like [`CacheAware::get_or_fetch`], recording the stored key under `tags`
+    fn get_or_fetch_tagged<T, F>(
+        cache: &Arc<CacheManager>,
+        key: &str,
+        ttl: usize,
+        tags: &[String],
+        fetch_fn: F,
+    ) -> impl std::future::Future<Output = anyhow::Result<T>>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned + CacheSchema,
         F: std::future::Future<Output = anyhow::Result<T>>;
 }
 
@@ -23,24 +155,50 @@ impl CacheAware for () {
         fetch_fn: F,
     ) -> impl std::future::Future<Output = anyhow::Result<T>>
     where
-        T: serde::Serialize + serde::de::DeserializeOwned,
+        T: serde::Serialize + serde::de::DeserializeOwned + CacheSchema,
         F: std::future::Future<Output = anyhow::Result<T>>,
     {
         async move {
             // Try to get from cache first
-            if let Ok(Some(cached)) = cache.get::<T>(key).await {
-                return Ok(cached);
+            if !cache_bypassed() {
+                if let Ok(Some(cached)) = timed("cache", cache.get::<T>(key)).await {
+                    return Ok(cached);
+                }
             }
 
             // Cache miss or error, fetch from source
             let data = fetch_fn.await?;
 
             // Store in cache (ignore errors, cache is optional)
-            let _ = cache.set(key, &data, ttl).await;
+            let _ = timed("cache", cache.set(key, &data, ttl)).await;
 
             Ok(data)
         }
     }
+
+    async fn get_or_fetch_tagged<T, F>(
+        cache: &Arc<CacheManager>,
+        key: &str,
+        ttl: usize,
+        tags: &[String],
+        fetch_fn: F,
+    ) -> anyhow::Result<T>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned + CacheSchema,
+        F: std::future::Future<Output = anyhow::Result<T>>,
+    {
+        if !cache_bypassed() {
+            if let Ok(Some(cached)) = timed("cache", cache.get::<T>(key)).await {
+                return Ok(cached);
+            }
+        }
+
+        let data = fetch_fn.await?;
+
+        let _ = timed("cache", cache.set_tagged(key, &data, ttl, tags)).await;
+
+        Ok(data)
+    }
 }
 
 #[cfg(test)]
@@ -48,11 +206,31 @@ mod tests {
     use super::*;
     use serde::{Deserialize, Serialize};
 
+    #[test]
+    fn test_cache_control_header_values() {
+        let public = CacheControl::for_cache_type(&CacheConfig::default(), "anchor");
+        assert_eq!(public, CacheControl::Public { max_age: 600 });
+        assert_eq!(
+            public.header_value(&Method::GET, true),
+            "public, max-age=600"
+        );
+        assert_eq!(public.header_value(&Method::GET, false), "no-store");
+        assert_eq!(public.header_value(&Method::POST, true), "no-store");
+        assert_eq!(
+            CacheControl::NoStore.header_value(&Method::GET, true),
+            "no-store"
+        );
+    }
+
     #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
     struct TestData {
         value: String,
     }
 
+    impl CacheSchema for TestData {
+        const TAG: &'static str = "test_data.v1";
+    }
+
     #[tokio::test]
     async fn test_cache_aware_get_or_fetch() {
         let cache = Arc::new(
@@ -76,4 +254,82 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), test_data);
     }
+
+    #[test]
+    fn test_wants_cache_bypass() {
+        let mut headers = HeaderMap::new();
+        assert!(!wants_cache_bypass(Some("limit=5"), &headers));
+        assert!(wants_cache_bypass(Some("limit=5&no_cache=true"), &headers));
+        assert!(!wants_cache_bypass(Some("no_cache=false"), &headers));
+
+        headers.insert(
+            header::CACHE_CONTROL,
+            HeaderValue::from_static("max-age=0, No-Cache"),
+        );
+        assert!(wants_cache_bypass(None, &headers));
+    }
+
+    #[tokio::test]
+    async fn test_bypass_only_when_enabled() {
+        use axum::{body::Body, middleware, routing::get, Router};
+        use tower::ServiceExt;
+
+        let app = |enabled| {
+            Router::new()
+                .route("/", get(|| async { cache_bypassed().to_string() }))
+                .layer(middleware::from_fn_with_state(
+                    CacheBypass { enabled },
+                    cache_bypass_middleware,
+                ))
+        };
+        let bypassed = |enabled| async move {
+            let response = app(enabled)
+                .oneshot(
+                    Request::builder()
+                        .uri("/")
+                        .header(header::CACHE_CONTROL, "no-cache")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            body == "true"
+        };
+
+        assert!(bypassed(true).await);
+        assert!(!bypassed(false).await);
+        assert!(!cache_bypassed());
+    }
+
+    #[tokio::test]
+    async fn test_bypass_fetches_on_cache_hit_and_writes_back() {
+        let cache = Arc::new(CacheManager::in_memory(Default::default()));
+        let key = "test:bypass";
+        let stale = TestData {
+            value: "stale".to_string(),
+        };
+        let fresh = TestData {
+            value: "fresh".to_string(),
+        };
+        cache.set(key, &stale, 60).await.unwrap();
+
+        let cached = <()>::get_or_fetch_tagged(&cache, key, 60, &[], async { Ok(fresh.clone()) })
+            .await
+            .unwrap();
+        assert_eq!(cached, stale);
+
+        let fetched = BYPASS_CACHE
+            .scope(
+                true,
+                <()>::get_or_fetch_tagged(&cache, key, 60, &[], async { Ok(fresh.clone()) }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(fetched, fresh);
+        assert_eq!(cache.get::<TestData>(key).await.unwrap(), Some(fresh));
+        cache.delete(key).await.unwrap();
+    }
 }
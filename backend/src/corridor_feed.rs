@@ -0,0 +1,287 @@
+//! Live corridor metrics for WebSocket subscribers
+//!
+//! Clients on the `corridors` channel get every corridor's current metrics
+//! once when they connect, then only the fields that changed. Each corridor
+//! carries its own sequence number, bumped on every change; a client that
+//! sees a delta whose `seq` isn't one past its copy has missed an update.
+//!
+//! A client subscribes before the snapshot is taken, so a delta may repeat a
+//! change the snapshot already holds; deltas at or below the copy's `seq` are
+//! to be ignored.
+//!
+//! A client that detects a gap sends `{"action": "resync", "corridors": [...]}`
+//! and gets the current metrics and `seq` of those corridors back.
+
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::db::aggregates::LatestCorridorMetrics;
+use crate::models::corridor::CorridorMetrics;
+
+/// Metrics of one corridor as pushed to WebSocket clients
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorridorSnapshot {
+    pub corridor_key: String,
+    pub success_rate: f64,
+    pub total_transactions: i64,
+    pub successful_transactions: i64,
+    pub failed_transactions: i64,
+    pub volume_usd: f64,
+    pub avg_settlement_latency_ms: Option<i32>,
+    pub liquidity_depth_usd: f64,
+}
+
+impl CorridorSnapshot {
+    pub fn from_metrics(corridor_key: &str, metrics: &CorridorMetrics) -> Self {
+        Self {
+            corridor_key: corridor_key.to_string(),
+            success_rate: metrics.success_rate,
+            total_transactions: metrics.total_transactions,
+            successful_transactions: metrics.successful_transactions,
+            failed_transactions: metrics.failed_transactions,
+            volume_usd: metrics.volume_usd,
+            avg_settlement_latency_ms: metrics.avg_settlement_latency_ms,
+            liquidity_depth_usd: metrics.liquidity_depth_usd,
+        }
+    }
+
+    /// Snapshot of a corridor's rolling 24-hour metrics, as the listing shows them
+    pub fn from_latest(metrics: &LatestCorridorMetrics) -> Self {
+        let success_rate = if metrics.total_transactions > 0 {
+            metrics.successful_transactions as f64 * 100.0 / metrics.total_transactions as f64
+        } else {
+            0.0
+        };
+        Self {
+            corridor_key: metrics.corridor_key.clone(),
+            success_rate,
+            total_transactions: metrics.total_transactions,
+            successful_transactions: metrics.successful_transactions,
+            failed_transactions: metrics.failed_transactions,
+            volume_usd: metrics.total_volume_usd,
+            avg_settlement_latency_ms: metrics
+                .avg_settlement_latency_ms
+                .map(|ms| ms.round() as i32),
+            liquidity_depth_usd: metrics.avg_liquidity_depth_usd.unwrap_or(0.0),
+        }
+    }
+}
+
+/// A corridor snapshot and the sequence number it was published at
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SequencedCorridor {
+    pub seq: u64,
+    #[serde(flatten)]
+    pub snapshot: CorridorSnapshot,
+}
+
+/// Fields of `next` that differ from `prev`, keyed by field name
+///
+/// Clients merge the result into their copy of the corridor. The key itself
+/// is never part of a delta.
+pub fn corridor_delta(prev: &CorridorSnapshot, next: &CorridorSnapshot) -> Map<String, Value> {
+    let prev = fields(prev);
+    fields(next)
+        .into_iter()
+        .filter(|(field, value)| prev.get(field) != Some(value))
+        .collect()
+}
+
+/// Every field of `snapshot` but the key
+fn fields(snapshot: &CorridorSnapshot) -> Map<String, Value> {
+    let mut fields = match serde_json::to_value(snapshot) {
+        Ok(Value::Object(fields)) => fields,
+        _ => Map::new(),
+    };
+    fields.remove("corridor_key");
+    fields
+}
+
+/// A change to one corridor, ready to broadcast
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorridorChange {
+    pub corridor_key: String,
+    pub seq: u64,
+    pub changes: Map<String, Value>,
+}
+
+/// Last published metrics of every corridor
+#[derive(Default)]
+pub struct CorridorFeed {
+    corridors: DashMap<String, SequencedCorridor>,
+}
+
+impl CorridorFeed {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `snapshot`, returning what changed or `None` if nothing did
+    ///
+    /// A corridor seen for the first time starts at sequence 1 with every
+    /// field in its delta. `on_change` runs while the corridor is still
+    /// locked, so concurrent updates to one corridor are sent in order.
+    pub fn update<F>(&self, snapshot: CorridorSnapshot, on_change: F) -> Option<CorridorChange>
+    where
+        F: FnOnce(&CorridorChange),
+    {
+        let key = snapshot.corridor_key.clone();
+        let (mut entry, changes) = match self.corridors.entry(key.clone()) {
+            Entry::Occupied(entry) => {
+                let changes = corridor_delta(&entry.get().snapshot, &snapshot);
+                if changes.is_empty() {
+                    return None;
+                }
+                (entry.into_ref(), changes)
+            }
+            Entry::Vacant(entry) => {
+                let changes = fields(&snapshot);
+                let entry = entry.insert(SequencedCorridor {
+                    seq: 0,
+                    snapshot: snapshot.clone(),
+                });
+                (entry, changes)
+            }
+        };
+
+        entry.seq += 1;
+        entry.snapshot = snapshot;
+        let change = CorridorChange {
+            corridor_key: key,
+            seq: entry.seq,
+            changes,
+        };
+        on_change(&change);
+        Some(change)
+    }
+
+    /// Current metrics of the corridors in `keys`, plus the keys never seen
+    ///
+    /// Sequence numbers are left as they are, so a resynced client carries on
+    /// from the next delta.
+    pub fn resync(&self, keys: &[String]) -> (Vec<SequencedCorridor>, Vec<String>) {
+        let mut corridors = Vec::new();
+        let mut unknown = Vec::new();
+        for key in keys {
+            match self.corridors.get(key) {
+                Some(entry) => corridors.push(entry.value().clone()),
+                None => unknown.push(key.clone()),
+            }
+        }
+        (corridors, unknown)
+    }
+
+    /// Every corridor's current metrics, ordered by key
+    pub fn snapshot(&self) -> Vec<SequencedCorridor> {
+        let mut corridors: Vec<_> = self
+            .corridors
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect();
+        corridors.sort_by(|a, b| a.snapshot.corridor_key.cmp(&b.snapshot.corridor_key));
+        corridors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn snapshot(success_rate: f64) -> CorridorSnapshot {
+        CorridorSnapshot {
+            corridor_key: "USDC:GA->XLM:native".to_string(),
+            success_rate,
+            total_transactions: 100,
+            successful_transactions: 95,
+            failed_transactions: 5,
+            volume_usd: 10_000.0,
+            avg_settlement_latency_ms: Some(400),
+            liquidity_depth_usd: 50_000.0,
+        }
+    }
+
+    #[test]
+    fn test_delta_holds_only_changed_fields() {
+        let prev = snapshot(95.0);
+        assert!(corridor_delta(&prev, &prev).is_empty());
+
+        let delta = corridor_delta(&prev, &snapshot(96.5));
+        assert_eq!(Value::Object(delta), json!({ "success_rate": 96.5 }));
+
+        let next = CorridorSnapshot {
+            avg_settlement_latency_ms: None,
+            total_transactions: 101,
+            ..snapshot(95.0)
+        };
+        let delta = corridor_delta(&prev, &next);
+        assert_eq!(
+            Value::Object(delta),
+            json!({ "avg_settlement_latency_ms": null, "total_transactions": 101 })
+        );
+    }
+
+    #[test]
+    fn test_feed_sequences_changes_per_corridor() {
+        let feed = CorridorFeed::new();
+        let mut sent = Vec::new();
+
+        let first = feed.update(snapshot(95.0), |c| sent.push(c.seq)).unwrap();
+        assert_eq!(first.seq, 1);
+        // A new corridor's first delta carries every field
+        assert_eq!(first.changes.len(), 7);
+        assert!(!first.changes.contains_key("corridor_key"));
+
+        assert!(feed.update(snapshot(95.0), |c| sent.push(c.seq)).is_none());
+        let second = feed.update(snapshot(90.0), |c| sent.push(c.seq)).unwrap();
+        assert_eq!(second.seq, 2);
+        assert_eq!(
+            Value::Object(second.changes),
+            json!({ "success_rate": 90.0 })
+        );
+
+        let other = CorridorSnapshot {
+            corridor_key: "EURC:GB->XLM:native".to_string(),
+            ..snapshot(80.0)
+        };
+        assert_eq!(feed.update(other, |c| sent.push(c.seq)).unwrap().seq, 1);
+        assert_eq!(sent, vec![1, 2, 1]);
+
+        let current = feed.snapshot();
+        let keys: Vec<_> = current
+            .iter()
+            .map(|c| (c.snapshot.corridor_key.as_str(), c.seq))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![("EURC:GB->XLM:native", 1), ("USDC:GA->XLM:native", 2)]
+        );
+        assert_eq!(current[1].snapshot, snapshot(90.0));
+    }
+
+    #[test]
+    fn test_resync_returns_current_metrics_and_unknown_keys() {
+        let feed = CorridorFeed::new();
+        feed.update(snapshot(95.0), |_| {});
+        feed.update(snapshot(92.0), |_| {});
+
+        let keys = vec![
+            "USDC:GA->XLM:native".to_string(),
+            "EURC:GB->XLM:native".to_string(),
+        ];
+        let (corridors, unknown) = feed.resync(&keys);
+        assert_eq!(
+            corridors,
+            vec![SequencedCorridor {
+                seq: 2,
+                snapshot: snapshot(92.0),
+            }]
+        );
+        assert_eq!(unknown, vec!["EURC:GB->XLM:native".to_string()]);
+
+        // Resyncing doesn't consume a sequence number
+        assert_eq!(feed.update(snapshot(91.0), |_| {}).unwrap().seq, 3);
+    }
+}
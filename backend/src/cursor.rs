@@ -0,0 +1,162 @@
+//! Opaque pagination cursors
+//!
+//! A cursor is the base64url encoding of a version byte, the JSON payload and
+//! an HMAC-SHA256 over both. Clients can read a cursor but not forge or edit
+//! one, so a cursor can't be used to reach an offset the API never handed out.
+//!
+//! The key comes from `CURSOR_SECRET`. Without it a random key is made per
+//! process, and cursors stop working when the server restarts.
+
+use std::sync::OnceLock;
+
+use anyhow::{bail, Context, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+const CURSOR_VERSION: u8 = 1;
+
+/// Length of the HMAC-SHA256 tag ending every cursor
+const TAG_LEN: usize = 32;
+
+/// Position a cursor points at
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CursorPayload {
+    /// Rows to skip
+    pub offset: i64,
+    /// Sort key of the last row returned, for keyset pagination
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+}
+
+impl CursorPayload {
+    pub fn at_offset(offset: i64) -> Self {
+        Self {
+            offset,
+            after: None,
+        }
+    }
+}
+
+/// Encode `payload` as an opaque cursor
+pub fn encode(payload: &CursorPayload) -> String {
+    encode_with(cursor_key(), payload)
+}
+
+/// Decode a cursor made by [`encode`], rejecting any that were altered
+pub fn decode(cursor: &str) -> Result<CursorPayload> {
+    decode_with(cursor_key(), cursor)
+}
+
+fn cursor_key() -> &'static [u8] {
+    static KEY: OnceLock<Vec<u8>> = OnceLock::new();
+    KEY.get_or_init(|| match std::env::var("CURSOR_SECRET") {
+        Ok(secret) if !secret.is_empty() => secret.into_bytes(),
+        _ => {
+            tracing::warn!("CURSOR_SECRET not set, cursors won't survive a restart");
+            let mut key = vec![0; TAG_LEN];
+            rand::thread_rng().fill_bytes(&mut key);
+            key
+        }
+    })
+}
+
+fn mac(key: &[u8]) -> Hmac<Sha256> {
+    Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length")
+}
+
+fn encode_with(key: &[u8], payload: &CursorPayload) -> String {
+    let mut bytes = vec![CURSOR_VERSION];
+    serde_json::to_writer(&mut bytes, payload).expect("cursor payload serializes");
+    let mut mac = mac(key);
+    mac.update(&bytes);
+    bytes.extend_from_slice(&mac.finalize().into_bytes());
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn decode_with(key: &[u8], cursor: &str) -> Result<CursorPayload> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(cursor)
+        .context("Cursor is not valid base64url")?;
+    if bytes.len() <= 1 + TAG_LEN {
+        bail!("Cursor is too short");
+    }
+
+    let (signed, tag) = bytes.split_at(bytes.len() - TAG_LEN);
+    let mut mac = mac(key);
+    mac.update(signed);
+    mac.verify_slice(tag)
+        .map_err(|_| anyhow::anyhow!("Cursor signature does not match"))?;
+
+    let (version, payload) = (signed[0], &signed[1..]);
+    if version != CURSOR_VERSION {
+        bail!("Unsupported cursor version {}", version);
+    }
+    serde_json::from_slice(payload).context("Cursor payload is malformed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &[u8] = b"test-cursor-secret";
+
+    #[test]
+    fn test_round_trip() {
+        for payload in [
+            CursorPayload::at_offset(0),
+            CursorPayload {
+                offset: 150,
+                after: Some("USDC:GA->XLM:native".to_string()),
+            },
+        ] {
+            let cursor = encode_with(KEY, &payload);
+            assert!(cursor
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_'));
+            assert_eq!(decode_with(KEY, &cursor).unwrap(), payload);
+        }
+
+        let cursor = encode(&CursorPayload::at_offset(42));
+        assert_eq!(decode(&cursor).unwrap().offset, 42);
+    }
+
+    #[test]
+    fn test_rejects_tampered_cursor() {
+        let cursor = encode_with(KEY, &CursorPayload::at_offset(50));
+        let bytes = URL_SAFE_NO_PAD.decode(&cursor).unwrap();
+
+        // Rewrite the offset but keep the original tag
+        let (signed, tag) = bytes.split_at(bytes.len() - TAG_LEN);
+        let mut forged = String::from_utf8(signed.to_vec())
+            .unwrap()
+            .replace("50", "99")
+            .into_bytes();
+        forged.extend_from_slice(tag);
+        let err = decode_with(KEY, &URL_SAFE_NO_PAD.encode(&forged)).unwrap_err();
+        assert_eq!(err.to_string(), "Cursor signature does not match");
+
+        // Signed with another key
+        let other = encode_with(b"another-secret", &CursorPayload::at_offset(50));
+        assert!(decode_with(KEY, &other).is_err());
+
+        assert!(decode_with(KEY, "not a cursor!").is_err());
+        assert!(decode_with(KEY, &cursor[..10]).is_err());
+    }
+
+    #[test]
+    fn test_rejects_unknown_version() {
+        let payload = br#"{"offset":1}"#;
+        let mut bytes = vec![CURSOR_VERSION + 1];
+        bytes.extend_from_slice(payload);
+        let mut mac = mac(KEY);
+        mac.update(&bytes);
+        bytes.extend_from_slice(&mac.finalize().into_bytes());
+
+        let err = decode_with(KEY, &URL_SAFE_NO_PAD.encode(bytes)).unwrap_err();
+        assert_eq!(err.to_string(), "Unsupported cursor version 2");
+    }
+}
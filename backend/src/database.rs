@@ -1,12 +1,19 @@
 use anyhow::Result;
-use chrono::Utc;
-use sqlx::SqlitePool;
+use chrono::{DateTime, Utc};
+use sqlx::{QueryBuilder, Sqlite, SqliteExecutor, SqlitePool};
+use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::analytics::compute_anchor_metrics;
+use crate::analytics::health::{AnchorHealthBreakdown, StatusThresholds};
+use crate::db::explain::{explain, ExplainQuery, QueryPlan};
+use crate::db::migrations::{migration_status, MigrationStatus, MIGRATOR};
+use crate::db::slow_query::SlowQueryLog;
 use crate::models::{
-    Anchor, AnchorDetailResponse, AnchorMetricsHistory, Asset, CorridorRecord, CreateAnchorRequest,
-    MetricRecord, SnapshotRecord,
+    Anchor, AnchorAssetVolume, AnchorDetailResponse, AnchorMetricsHistory, AnchorMetricsPatch, AnchorFilter, Asset, AuditLogRecord, CorridorAlertRecord,
+    CorridorRecord, CorridorTransactionRecord, CreateAnchorRequest, CreateCorridorAlertRequest,
+    CreateWebhookRequest, IngestionFailureRecord, MetricRecord,
+    SnapshotRecord, SortBy, SortOrder, UpdateCorridorAlertRequest, WebhookRecord,
 };
 
 /// Parameters for updating anchor from RPC data
@@ -19,6 +26,82 @@ pub struct AnchorRpcUpdate {
     pub avg_settlement_time_ms: i32,
     pub reliability_score: f64,
     pub status: String,
+    pub availability: Option<f64>,
+}
+
+/// Rows written by [`Database::import_corridors`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CorridorImportCounts {
+    pub created: usize,
+    pub updated: usize,
+}
+
+/// Corridor metrics summed over every corridor trading one of an anchor's assets
+#[derive(Debug, Clone, Default, PartialEq, sqlx::FromRow)]
+pub struct AnchorCorridorTotals {
+    pub corridor_count: i64,
+    pub total_transactions: i64,
+    pub successful_transactions: i64,
+    pub failed_transactions: i64,
+    pub volume_usd: f64,
+    /// Settlement latency weighted by each bucket's transaction count
+    pub avg_settlement_time_ms: Option<i32>,
+}
+
+/// Sums `corridor_metrics_hourly` for corridors with the anchor's asset on
+/// either side; a corridor between two of its assets is counted once
+const ANCHOR_CORRIDOR_TOTALS_SQL: &str = r#"
+    SELECT
+        COUNT(DISTINCT m.corridor_key) AS corridor_count,
+        COALESCE(SUM(m.total_transactions), 0) AS total_transactions,
+        COALESCE(SUM(m.successful_transactions), 0) AS successful_transactions,
+        COALESCE(SUM(m.failed_transactions), 0) AS failed_transactions,
+        COALESCE(SUM(m.volume_usd), 0.0) AS volume_usd,
+        CAST(
+            SUM(m.avg_settlement_latency_ms * m.total_transactions)
+                / NULLIF(SUM(CASE WHEN m.avg_settlement_latency_ms IS NOT NULL
+                                  THEN m.total_transactions END), 0)
+            AS INTEGER
+        ) AS avg_settlement_time_ms
+    FROM corridor_metrics_hourly m
+    WHERE EXISTS (
+        SELECT 1 FROM assets a
+        WHERE a.anchor_id = $1
+          AND ((a.asset_code = m.asset_a_code AND a.asset_issuer = m.asset_a_issuer)
+            OR (a.asset_code = m.asset_b_code AND a.asset_issuer = m.asset_b_issuer))
+    )
+"#;
+
+/// Time-series tables pruned by the metrics retention job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricsHistoryTable {
+    AnchorMetricsHistory,
+    CorridorMetrics,
+    CorridorMetricsHourly,
+}
+
+impl MetricsHistoryTable {
+    pub const ALL: [MetricsHistoryTable; 3] = [
+        MetricsHistoryTable::AnchorMetricsHistory,
+        MetricsHistoryTable::CorridorMetrics,
+        MetricsHistoryTable::CorridorMetricsHourly,
+    ];
+
+    pub fn table_name(self) -> &'static str {
+        match self {
+            MetricsHistoryTable::AnchorMetricsHistory => "anchor_metrics_history",
+            MetricsHistoryTable::CorridorMetrics => "corridor_metrics",
+            MetricsHistoryTable::CorridorMetricsHourly => "corridor_metrics_hourly",
+        }
+    }
+
+    fn time_column(self) -> &'static str {
+        match self {
+            MetricsHistoryTable::AnchorMetricsHistory => "timestamp",
+            MetricsHistoryTable::CorridorMetrics => "date",
+            MetricsHistoryTable::CorridorMetricsHourly => "hour_bucket",
+        }
+    }
 }
 
 /// Parameters for recording anchor metrics history
@@ -36,23 +119,88 @@ pub struct AnchorMetricsParams {
 
 pub struct Database {
     pool: SqlitePool,
+    slow_queries: Arc<SlowQueryLog>,
 }
 
 impl Database {
     pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            slow_queries: Arc::new(SlowQueryLog::default()),
+        }
+    }
+
+    /// Share `slow_queries` for timing this database's queries
+    pub fn with_slow_query_log(mut self, slow_queries: Arc<SlowQueryLog>) -> Self {
+        self.slow_queries = slow_queries;
+        self
     }
 
     pub fn pool(&self) -> &SqlitePool {
         &self.pool
     }
 
+    /// Round trip a trivial query, checking a connection can be acquired
+    pub async fn ping(&self) -> Result<()> {
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
+        Ok(())
+    }
+
+    pub fn slow_query_log(&self) -> &Arc<SlowQueryLog> {
+        &self.slow_queries
+    }
+
+    /// Applied and pending schema migrations, checked against this build
+    pub async fn migration_status(&self) -> Result<Vec<MigrationStatus>> {
+        let _timer = self.slow_queries.start("migration_status");
+        migration_status(&self.pool, &MIGRATOR).await
+    }
+
+    /// `EXPLAIN QUERY PLAN` output for one of the whitelisted named queries
+    pub async fn explain_query(&self, query: ExplainQuery) -> Result<QueryPlan> {
+        explain(&self.pool, query).await
+    }
+
     pub fn corridor_aggregates(&self) -> crate::db::aggregates::CorridorAggregates {
         crate::db::aggregates::CorridorAggregates::new(self.pool.clone())
+            .with_slow_query_log(Arc::clone(&self.slow_queries))
+    }
+
+    /// Network-wide corridor totals, counting corridors below `health_threshold` as unhealthy
+    pub async fn corridor_summary(
+        &self,
+        health_threshold: f64,
+    ) -> Result<crate::models::corridor::CorridorNetworkSummary> {
+        self.corridor_aggregates()
+            .corridor_summary(health_threshold)
+            .await
+    }
+
+    /// Corridors sharing an asset with `corridor`, highest volume first
+    pub async fn related_corridors(
+        &self,
+        corridor: &crate::models::corridor::Corridor,
+        limit: i64,
+    ) -> Result<Vec<crate::db::aggregates::LatestCorridorMetrics>> {
+        self.corridor_aggregates()
+            .related_corridors(corridor, limit)
+            .await
+    }
+
+    /// Daily metrics for `corridor_key` as they stood at `at`
+    pub async fn corridor_metrics_at(
+        &self,
+        corridor_key: &str,
+        at: chrono::DateTime<Utc>,
+    ) -> Result<Option<crate::models::corridor::CorridorMetrics>> {
+        self.corridor_aggregates()
+            .corridor_metrics_at(corridor_key, at)
+            .await
     }
 
     // Anchor operations
     pub async fn create_anchor(&self, req: CreateAnchorRequest) -> Result<Anchor> {
+        let _timer = self.slow_queries.start("create_anchor");
         let id = Uuid::new_v4().to_string();
         let anchor = sqlx::query_as::<_, Anchor>(
             r#"
@@ -72,6 +220,7 @@ impl Database {
     }
 
     pub async fn get_anchor_by_id(&self, id: Uuid) -> Result<Option<Anchor>> {
+        let _timer = self.slow_queries.start("get_anchor_by_id");
         let anchor = sqlx::query_as::<_, Anchor>(
             r#"
             SELECT * FROM anchors WHERE id = $1
@@ -88,6 +237,7 @@ impl Database {
         &self,
         stellar_account: &str,
     ) -> Result<Option<Anchor>> {
+        let _timer = self.slow_queries.start("get_anchor_by_stellar_account");
         let anchor = sqlx::query_as::<_, Anchor>(
             r#"
             SELECT * FROM anchors WHERE stellar_account = $1
@@ -100,22 +250,111 @@ impl Database {
         Ok(anchor)
     }
 
+    /// Set only the metric columns present in `patch`
+    ///
+    /// Returns `None` if the anchor does not exist. Derived fields such as
+    /// `status` are not recomputed.
+    pub async fn patch_anchor_metrics(
+        &self,
+        anchor_id: Uuid,
+        patch: &AnchorMetricsPatch,
+    ) -> Result<Option<Anchor>> {
+        let _timer = self.slow_queries.start("patch_anchor_metrics");
+        if patch.is_empty() {
+            return self.get_anchor_by_id(anchor_id).await;
+        }
+
+        let mut query = sqlx::QueryBuilder::<sqlx::Sqlite>::new("UPDATE anchors SET ");
+        let mut columns = query.separated(", ");
+        if let Some(value) = patch.total_transactions {
+            columns.push("total_transactions = ").push_bind_unseparated(value);
+        }
+        if let Some(value) = patch.successful_transactions {
+            columns.push("successful_transactions = ").push_bind_unseparated(value);
+        }
+        if let Some(value) = patch.failed_transactions {
+            columns.push("failed_transactions = ").push_bind_unseparated(value);
+        }
+        if let Some(value) = patch.avg_settlement_time_ms {
+            columns.push("avg_settlement_time_ms = ").push_bind_unseparated(value);
+        }
+        if let Some(value) = patch.total_volume_usd {
+            columns.push("total_volume_usd = ").push_bind_unseparated(value);
+        }
+        if let Some(value) = patch.reliability_score {
+            columns.push("reliability_score = ").push_bind_unseparated(value);
+        }
+        columns.push("updated_at = ").push_bind_unseparated(Utc::now());
+        query
+            .push(" WHERE id = ")
+            .push_bind(anchor_id.to_string())
+            .push(" RETURNING *");
+
+        let anchor = query
+            .build_query_as::<Anchor>()
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(anchor)
+    }
+
     pub async fn list_anchors(&self, limit: i64, offset: i64) -> Result<Vec<Anchor>> {
-        let anchors = sqlx::query_as::<_, Anchor>(
-            r#"
-            SELECT * FROM anchors
-            ORDER BY reliability_score DESC, updated_at DESC
-            LIMIT $1 OFFSET $2
-            "#,
+        self.list_anchors_sorted(
+            AnchorFilter::default(),
+            SortBy::SuccessRate,
+            SortOrder::Desc,
+            limit,
+            offset,
         )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&self.pool)
-        .await?;
+        .await
+    }
+
+    /// Anchors within `filter`, ordered by `sort_by` in `order` and most
+    /// recently updated first on ties
+    pub async fn list_anchors_sorted(
+        &self,
+        filter: AnchorFilter,
+        sort_by: SortBy,
+        order: SortOrder,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Anchor>> {
+        let _timer = self.slow_queries.start("list_anchors_sorted");
+        let column = match sort_by {
+            SortBy::SuccessRate => "reliability_score",
+            SortBy::Volume => "total_volume_usd",
+            SortBy::LastUpdated => "updated_at",
+            SortBy::Name => "name",
+        };
+        let mut query = QueryBuilder::<Sqlite>::new("SELECT * FROM anchors WHERE 1 = 1");
+        push_anchor_predicates(&mut query, filter);
+        query
+            .push(format!(
+                " ORDER BY {} {}, updated_at DESC LIMIT ",
+                column,
+                order.sql()
+            ))
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
+
+        let anchors = query
+            .build_query_as::<Anchor>()
+            .fetch_all(&self.pool)
+            .await?;
 
         Ok(anchors)
     }
 
+    /// Number of rows [`Self::list_anchors_sorted`] pages through
+    pub async fn count_anchors(&self, filter: AnchorFilter) -> Result<i64> {
+        let _timer = self.slow_queries.start("count_anchors");
+        let mut query = QueryBuilder::<Sqlite>::new("SELECT COUNT(*) FROM anchors WHERE 1 = 1");
+        push_anchor_predicates(&mut query, filter);
+        Ok(query.build_query_scalar().fetch_one(&self.pool).await?)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub async fn update_anchor_metrics(
         &self,
         anchor_id: Uuid,
@@ -124,13 +363,16 @@ impl Database {
         failed_transactions: i64,
         avg_settlement_time_ms: Option<i32>,
         volume_usd: Option<f64>,
+        thresholds: &StatusThresholds,
     ) -> Result<Anchor> {
+        let _timer = self.slow_queries.start("update_anchor_metrics");
         // Compute metrics
         let metrics = compute_anchor_metrics(
             total_transactions,
             successful_transactions,
             failed_transactions,
             avg_settlement_time_ms,
+            thresholds,
         );
 
         // Update anchor
@@ -178,6 +420,104 @@ impl Database {
         Ok(anchor)
     }
 
+    /// Corridor totals for every corridor involving one of the anchor's assets
+    pub async fn aggregate_anchor_from_corridors(
+        &self,
+        anchor_id: Uuid,
+    ) -> Result<AnchorCorridorTotals> {
+        let _timer = self.slow_queries.start("aggregate_anchor_from_corridors");
+        let totals = sqlx::query_as::<_, AnchorCorridorTotals>(ANCHOR_CORRIDOR_TOTALS_SQL)
+            .bind(anchor_id.to_string())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(totals)
+    }
+
+    /// Overwrite an anchor's transaction counts and reliability with its
+    /// corridor totals, or `None` if the anchor doesn't exist
+    ///
+    /// The totals are read, the anchor updated and the history row recorded in
+    /// one transaction. With no corridor metrics at all the anchor is returned
+    /// unchanged alongside the empty totals.
+    pub async fn recompute_anchor_from_corridors(
+        &self,
+        anchor_id: Uuid,
+        thresholds: &StatusThresholds,
+    ) -> Result<Option<(Anchor, AnchorCorridorTotals)>> {
+        let _timer = self.slow_queries.start("recompute_anchor_from_corridors");
+        let mut tx = self.pool.begin().await?;
+
+        let totals = sqlx::query_as::<_, AnchorCorridorTotals>(ANCHOR_CORRIDOR_TOTALS_SQL)
+            .bind(anchor_id.to_string())
+            .fetch_one(&mut *tx)
+            .await?;
+        if totals.corridor_count == 0 {
+            let anchor = sqlx::query_as::<_, Anchor>("SELECT * FROM anchors WHERE id = $1")
+                .bind(anchor_id.to_string())
+                .fetch_optional(&mut *tx)
+                .await?;
+            return Ok(anchor.map(|anchor| (anchor, totals)));
+        }
+
+        let metrics = compute_anchor_metrics(
+            totals.total_transactions,
+            totals.successful_transactions,
+            totals.failed_transactions,
+            totals.avg_settlement_time_ms,
+            thresholds,
+        );
+
+        let anchor = sqlx::query_as::<_, Anchor>(
+            r#"
+            UPDATE anchors
+            SET total_transactions = $1,
+                successful_transactions = $2,
+                failed_transactions = $3,
+                avg_settlement_time_ms = $4,
+                reliability_score = $5,
+                status = $6,
+                total_volume_usd = $7,
+                updated_at = $8
+            WHERE id = $9
+            RETURNING *
+            "#,
+        )
+        .bind(totals.total_transactions)
+        .bind(totals.successful_transactions)
+        .bind(totals.failed_transactions)
+        .bind(totals.avg_settlement_time_ms.unwrap_or(0))
+        .bind(metrics.reliability_score)
+        .bind(metrics.status.as_str())
+        .bind(totals.volume_usd)
+        .bind(Utc::now())
+        .bind(anchor_id.to_string())
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(anchor) = anchor else {
+            return Ok(None);
+        };
+
+        insert_anchor_metrics_history(
+            &mut *tx,
+            AnchorMetricsParams {
+                anchor_id,
+                success_rate: metrics.success_rate,
+                failure_rate: metrics.failure_rate,
+                reliability_score: metrics.reliability_score,
+                total_transactions: totals.total_transactions,
+                successful_transactions: totals.successful_transactions,
+                failed_transactions: totals.failed_transactions,
+                avg_settlement_time_ms: totals.avg_settlement_time_ms,
+                volume_usd: Some(totals.volume_usd),
+            },
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some((anchor, totals)))
+    }
+
     // Asset operations
     pub async fn create_asset(
         &self,
@@ -185,6 +525,7 @@ impl Database {
         asset_code: String,
         asset_issuer: String,
     ) -> Result<Asset> {
+        let _timer = self.slow_queries.start("create_asset");
         let id = Uuid::new_v4().to_string();
         let asset = sqlx::query_as::<_, Asset>(
             r#"
@@ -207,6 +548,7 @@ impl Database {
     }
 
     pub async fn get_assets_by_anchor(&self, anchor_id: Uuid) -> Result<Vec<Asset>> {
+        let _timer = self.slow_queries.start("get_assets_by_anchor");
         let assets = sqlx::query_as::<_, Asset>(
             r#"
             SELECT * FROM assets WHERE anchor_id = $1
@@ -220,7 +562,55 @@ impl Database {
         Ok(assets)
     }
 
+    /// Assets of every anchor in `anchor_ids`, in one query
+    pub async fn get_assets_by_anchors(&self, anchor_ids: &[Uuid]) -> Result<Vec<Asset>> {
+        if anchor_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let _timer = self.slow_queries.start("get_assets_by_anchors");
+        let mut query = QueryBuilder::<Sqlite>::new("SELECT * FROM assets WHERE anchor_id IN (");
+        let mut ids = query.separated(", ");
+        for id in anchor_ids {
+            ids.push_bind(id.to_string());
+        }
+        query.push(") ORDER BY asset_code ASC");
+        let assets = query
+            .build_query_as::<Asset>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(assets)
+    }
+
+    /// Payment count and volume for each of an anchor's assets, largest volume first
+    ///
+    /// Assets without payments are included with zero counts.
+    pub async fn anchor_asset_volumes(&self, anchor_id: Uuid) -> Result<Vec<AnchorAssetVolume>> {
+        let _timer = self.slow_queries.start("anchor_asset_volumes");
+        let volumes = sqlx::query_as::<_, AnchorAssetVolume>(
+            r#"
+            SELECT
+                a.asset_code,
+                a.asset_issuer,
+                COUNT(p.id) AS transaction_count,
+                COALESCE(SUM(p.amount), 0.0) AS volume
+            FROM assets a
+            LEFT JOIN payments p
+                ON p.asset_code = a.asset_code AND p.asset_issuer = a.asset_issuer
+            WHERE a.anchor_id = $1
+            GROUP BY a.asset_code, a.asset_issuer
+            ORDER BY volume DESC, a.asset_code ASC
+            "#,
+        )
+        .bind(anchor_id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(volumes)
+    }
+
     pub async fn count_assets_by_anchor(&self, anchor_id: Uuid) -> Result<i64> {
+        let _timer = self.slow_queries.start("count_assets_by_anchor");
         let count: (i64,) = sqlx::query_as(
             r#"
             SELECT COUNT(*) FROM assets WHERE anchor_id = $1
@@ -235,6 +625,7 @@ impl Database {
 
     // Update anchor metrics from RPC ingestion
     pub async fn update_anchor_from_rpc(&self, params: AnchorRpcUpdate) -> Result<()> {
+        let _timer = self.slow_queries.start("update_anchor_from_rpc");
         sqlx::query(
             r#"
             UPDATE anchors
@@ -245,8 +636,9 @@ impl Database {
                 avg_settlement_time_ms = $5,
                 reliability_score = $6,
                 status = $7,
-                updated_at = $8
-            WHERE stellar_account = $9
+                availability = $8,
+                updated_at = $9
+            WHERE stellar_account = $10
             "#,
         )
         .bind(params.total_transactions)
@@ -256,6 +648,7 @@ impl Database {
         .bind(params.avg_settlement_time_ms)
         .bind(params.reliability_score)
         .bind(&params.status)
+        .bind(params.availability)
         .bind(Utc::now())
         .bind(&params.stellar_account)
         .execute(&self.pool)
@@ -269,33 +662,8 @@ impl Database {
         &self,
         params: AnchorMetricsParams,
     ) -> Result<AnchorMetricsHistory> {
-        let id = Uuid::new_v4().to_string();
-        let history = sqlx::query_as::<_, AnchorMetricsHistory>(
-            r#"
-            INSERT INTO anchor_metrics_history (
-                id, anchor_id, timestamp, success_rate, failure_rate, reliability_score,
-                total_transactions, successful_transactions, failed_transactions,
-                avg_settlement_time_ms, volume_usd
-            )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
-            RETURNING *
-            "#,
-        )
-        .bind(id)
-        .bind(params.anchor_id.to_string())
-        .bind(Utc::now())
-        .bind(params.success_rate)
-        .bind(params.failure_rate)
-        .bind(params.reliability_score)
-        .bind(params.total_transactions)
-        .bind(params.successful_transactions)
-        .bind(params.failed_transactions)
-        .bind(params.avg_settlement_time_ms.unwrap_or(0))
-        .bind(params.volume_usd.unwrap_or(0.0))
-        .fetch_one(&self.pool)
-        .await?;
-
-        Ok(history)
+        let _timer = self.slow_queries.start("record_anchor_metrics_history");
+        insert_anchor_metrics_history(&self.pool, params).await
     }
 
     pub async fn get_anchor_metrics_history(
@@ -303,6 +671,7 @@ impl Database {
         anchor_id: Uuid,
         limit: i64,
     ) -> Result<Vec<AnchorMetricsHistory>> {
+        let _timer = self.slow_queries.start("get_anchor_metrics_history");
         let history = sqlx::query_as::<_, AnchorMetricsHistory>(
             r#"
             SELECT * FROM anchor_metrics_history
@@ -319,7 +688,11 @@ impl Database {
         Ok(history)
     }
 
-    pub async fn get_anchor_detail(&self, anchor_id: Uuid) -> Result<Option<AnchorDetailResponse>> {
+    pub async fn get_anchor_detail(
+        &self,
+        anchor_id: Uuid,
+        thresholds: &StatusThresholds,
+    ) -> Result<Option<AnchorDetailResponse>> {
         let anchor = match self.get_anchor_by_id(anchor_id).await? {
             Some(a) => a,
             None => return Ok(None),
@@ -327,11 +700,13 @@ impl Database {
 
         let assets = self.get_assets_by_anchor(anchor_id).await?;
         let metrics_history = self.get_anchor_metrics_history(anchor_id, 30).await?;
+        let health = AnchorHealthBreakdown::new(&anchor, &assets, &metrics_history, thresholds);
 
         Ok(Some(AnchorDetailResponse {
             anchor,
             assets,
             metrics_history,
+            health,
         }))
     }
 
@@ -340,6 +715,7 @@ impl Database {
         &self,
         req: crate::models::CreateCorridorRequest,
     ) -> Result<crate::models::corridor::Corridor> {
+        let _timer = self.slow_queries.start("create_corridor");
         let corridor = crate::models::corridor::Corridor::new(
             req.source_asset_code,
             req.source_asset_issuer,
@@ -347,7 +723,7 @@ impl Database {
             req.dest_asset_issuer,
         );
 
-        // Ensure the corridor exists in the database
+        // A second registration of the pair fails on the unique constraint
         sqlx::query(
             r#"
             INSERT INTO corridors (
@@ -355,8 +731,6 @@ impl Database {
                 destination_asset_code, destination_asset_issuer
             )
             VALUES ($1, $2, $3, $4, $5)
-            ON CONFLICT (source_asset_code, source_asset_issuer, destination_asset_code, destination_asset_issuer)
-            DO UPDATE SET updated_at = CURRENT_TIMESTAMP
             "#,
         )
         .bind(Uuid::new_v4().to_string())
@@ -370,11 +744,140 @@ impl Database {
         Ok(corridor)
     }
 
+    /// Register the corridor from `source` to `destination` unless a row for
+    /// the asset pair exists in either direction, returning the stored corridor
+    ///
+    /// Used by ingestion; the first registration fixes the direction the
+    /// corridor is reported in.
+    /// Safe to race: a caller that loses the insert reads back the winner's
+    /// row instead of failing on the unique constraint.
+    pub async fn ensure_corridor(
+        &self,
+        source: (&str, &str),
+        destination: (&str, &str),
+    ) -> Result<crate::models::corridor::Corridor> {
+        let _timer = self.slow_queries.start("ensure_corridor");
+        let source_code = crate::models::corridor::canonical_asset_code(source.0);
+        let destination_code = crate::models::corridor::canonical_asset_code(destination.0);
+        let inserted = sqlx::query_as::<_, CorridorRecord>(
+            r#"
+            INSERT INTO corridors (
+                id, source_asset_code, source_asset_issuer,
+                destination_asset_code, destination_asset_issuer
+            )
+            SELECT $1, $2, $3, $4, $5
+            WHERE NOT EXISTS (
+                SELECT 1 FROM corridors
+                WHERE (source_asset_code = $2 AND source_asset_issuer = $3
+                       AND destination_asset_code = $4 AND destination_asset_issuer = $5)
+                   OR (source_asset_code = $4 AND source_asset_issuer = $5
+                       AND destination_asset_code = $2 AND destination_asset_issuer = $3)
+            )
+            ON CONFLICT (source_asset_code, source_asset_issuer, destination_asset_code, destination_asset_issuer)
+            DO NOTHING
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&source_code)
+        .bind(source.1)
+        .bind(&destination_code)
+        .bind(destination.1)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let record = match inserted {
+            Some(record) => record,
+            None => {
+                sqlx::query_as::<_, CorridorRecord>(
+                    r#"
+                    SELECT * FROM corridors
+                    WHERE (source_asset_code = $1 AND source_asset_issuer = $2
+                           AND destination_asset_code = $3 AND destination_asset_issuer = $4)
+                       OR (source_asset_code = $3 AND source_asset_issuer = $4
+                           AND destination_asset_code = $1 AND destination_asset_issuer = $2)
+                    ORDER BY created_at, id
+                    LIMIT 1
+                    "#,
+                )
+                .bind(&source_code)
+                .bind(source.1)
+                .bind(&destination_code)
+                .bind(destination.1)
+                .fetch_one(&self.pool)
+                .await?
+            }
+        };
+
+        Ok(crate::models::corridor::Corridor::new(
+            record.source_asset_code,
+            record.source_asset_issuer,
+            record.destination_asset_code,
+            record.destination_asset_issuer,
+        ))
+    }
+
+    /// Upsert `corridors` in one transaction
+    ///
+    /// Existing corridors only have `updated_at` touched; nothing is written
+    /// if any statement fails.
+    pub async fn import_corridors(
+        &self,
+        corridors: &[crate::models::corridor::Corridor],
+    ) -> Result<CorridorImportCounts> {
+        let _timer = self.slow_queries.start("import_corridors");
+        let mut counts = CorridorImportCounts::default();
+        let mut tx = self.pool.begin().await?;
+
+        for corridor in corridors {
+            let updated = sqlx::query(
+                r#"
+                UPDATE corridors SET updated_at = CURRENT_TIMESTAMP
+                WHERE source_asset_code = $1 AND source_asset_issuer = $2
+                  AND destination_asset_code = $3 AND destination_asset_issuer = $4
+                "#,
+            )
+            .bind(&corridor.asset_a_code)
+            .bind(&corridor.asset_a_issuer)
+            .bind(&corridor.asset_b_code)
+            .bind(&corridor.asset_b_issuer)
+            .execute(&mut *tx)
+            .await?;
+
+            if updated.rows_affected() > 0 {
+                counts.updated += 1;
+                continue;
+            }
+
+            sqlx::query(
+                r#"
+                INSERT INTO corridors (
+                    id, source_asset_code, source_asset_issuer,
+                    destination_asset_code, destination_asset_issuer
+                )
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&corridor.asset_a_code)
+            .bind(&corridor.asset_a_issuer)
+            .bind(&corridor.asset_b_code)
+            .bind(&corridor.asset_b_issuer)
+            .execute(&mut *tx)
+            .await?;
+            counts.created += 1;
+        }
+
+        tx.commit().await?;
+        Ok(counts)
+    }
+
     pub async fn list_corridors(
         &self,
         limit: i64,
         offset: i64,
     ) -> Result<Vec<crate::models::corridor::Corridor>> {
+        let _timer = self.slow_queries.start("list_corridors");
         let records = sqlx::query_as::<_, CorridorRecord>(
             r#"
             SELECT * FROM corridors ORDER BY reliability_score DESC LIMIT $1 OFFSET $2
@@ -402,6 +905,7 @@ impl Database {
         &self,
         id: Uuid,
     ) -> Result<Option<crate::models::corridor::Corridor>> {
+        let _timer = self.slow_queries.start("get_corridor_by_id");
         let record = sqlx::query_as::<_, CorridorRecord>(
             r#"
             SELECT * FROM corridors WHERE id = $1
@@ -426,6 +930,7 @@ impl Database {
         id: Uuid,
         metrics: crate::models::corridor::CorridorMetrics,
     ) -> Result<crate::models::corridor::Corridor> {
+        let _timer = self.slow_queries.start("update_corridor_metrics");
         let record = sqlx::query_as::<_, CorridorRecord>(
             r#"
             UPDATE corridors
@@ -448,6 +953,103 @@ impl Database {
         ))
     }
 
+    /// Whether any aggregated metrics have been recorded for a corridor key
+    pub async fn corridor_key_exists(&self, corridor_key: &str) -> Result<bool> {
+        let _timer = self.slow_queries.start("corridor_key_exists");
+        let exists: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM corridor_metrics_hourly WHERE corridor_key = $1
+                UNION ALL
+                SELECT 1 FROM corridor_metrics WHERE corridor_key = $1
+            )
+            "#,
+        )
+        .bind(corridor_key)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(exists)
+    }
+
+    /// Most recent payments in either asset of a corridor, newest first
+    ///
+    /// Payments without an indexed transaction are reported as successful,
+    /// matching how the aggregation job counts them.
+    pub async fn list_corridor_transactions(
+        &self,
+        corridor: &crate::models::corridor::Corridor,
+        successful: Option<bool>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<CorridorTransactionRecord>> {
+        let _timer = self.slow_queries.start("list_corridor_transactions");
+        let mut query = sqlx::QueryBuilder::<sqlx::Sqlite>::new(
+            r#"
+            SELECT * FROM (
+                SELECT
+                    p.transaction_hash,
+                    p.amount,
+                    COALESCE(t.successful, 1) AS successful,
+                    p.created_at,
+                    t.ledger_sequence
+                FROM payments p
+                LEFT JOIN transactions t ON t.hash = p.transaction_hash
+                WHERE (COALESCE(p.asset_code, 'XLM') = "#,
+        );
+        query
+            .push_bind(&corridor.asset_a_code)
+            .push(" AND COALESCE(p.asset_issuer, 'native') = ")
+            .push_bind(&corridor.asset_a_issuer)
+            .push(") OR (COALESCE(p.asset_code, 'XLM') = ")
+            .push_bind(&corridor.asset_b_code)
+            .push(" AND COALESCE(p.asset_issuer, 'native') = ")
+            .push_bind(&corridor.asset_b_issuer)
+            .push("))");
+        if let Some(successful) = successful {
+            query.push(" WHERE successful = ").push_bind(successful);
+        }
+        query
+            .push(" ORDER BY created_at DESC LIMIT ")
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
+
+        let records = query
+            .build_query_as::<CorridorTransactionRecord>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(records)
+    }
+
+    /// Delete up to `batch_size` rows of `table` older than `cutoff`
+    ///
+    /// Callers loop until fewer than `batch_size` rows come back, so no single
+    /// statement holds the write lock for long.
+    pub async fn prune_metrics_history_batch(
+        &self,
+        table: MetricsHistoryTable,
+        cutoff: chrono::DateTime<Utc>,
+        batch_size: i64,
+    ) -> Result<u64> {
+        let _timer = self.slow_queries.start("prune_metrics_history_batch");
+        // Table and column names come from the enum, never from input
+        let sql = format!(
+            "DELETE FROM {table} WHERE rowid IN (SELECT rowid FROM {table} WHERE {column} < $1 LIMIT $2)",
+            table = table.table_name(),
+            column = table.time_column(),
+        );
+
+        let result = sqlx::query(&sql)
+            .bind(cutoff)
+            .bind(batch_size)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
     // Generic Metric operations
     pub async fn record_metric(
         &self,
@@ -456,6 +1058,7 @@ impl Database {
         entity_id: Option<String>,
         entity_type: Option<String>,
     ) -> Result<MetricRecord> {
+        let _timer = self.slow_queries.start("record_metric");
         let id = Uuid::new_v4().to_string();
         let metric = sqlx::query_as::<_, MetricRecord>(
             r#"
@@ -485,6 +1088,7 @@ impl Database {
         hash: Option<String>,
         epoch: Option<i64>,
     ) -> Result<SnapshotRecord> {
+        let _timer = self.slow_queries.start("create_snapshot");
         let id = Uuid::new_v4().to_string();
         let snapshot = sqlx::query_as::<_, SnapshotRecord>(
             r#"
@@ -507,6 +1111,7 @@ impl Database {
     }
 
     pub async fn get_snapshot_by_epoch(&self, epoch: i64) -> Result<Option<SnapshotRecord>> {
+        let _timer = self.slow_queries.start("get_snapshot_by_epoch");
         let snapshot = sqlx::query_as::<_, SnapshotRecord>(
             r#"
             SELECT * FROM snapshots WHERE epoch = $1 LIMIT 1
@@ -520,6 +1125,7 @@ impl Database {
     }
 
     pub async fn list_snapshots(&self, limit: i64, offset: i64) -> Result<Vec<SnapshotRecord>> {
+        let _timer = self.slow_queries.start("list_snapshots");
         let snapshots = sqlx::query_as::<_, SnapshotRecord>(
             r#"
             SELECT * FROM snapshots
@@ -536,48 +1142,233 @@ impl Database {
         Ok(snapshots)
     }
 
-    // Ingestion methods
-    pub async fn get_ingestion_cursor(&self, task_name: &str) -> Result<Option<String>> {
-        let state = sqlx::query_as::<_, crate::models::IngestionState>(
+    // Webhook operations
+    pub async fn create_webhook(&self, req: CreateWebhookRequest) -> Result<WebhookRecord> {
+        let _timer = self.slow_queries.start("create_webhook");
+        let id = Uuid::new_v4().to_string();
+        let webhook = sqlx::query_as::<_, WebhookRecord>(
             r#"
-            SELECT * FROM ingestion_state WHERE task_name = $1
+            INSERT INTO webhooks (id, url, secret, event_type)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
             "#,
         )
-        .bind(task_name)
-        .fetch_optional(&self.pool)
+        .bind(id)
+        .bind(&req.url)
+        .bind(&req.secret)
+        .bind(
+            req.event_type
+                .as_deref()
+                .unwrap_or(crate::services::webhook::ANCHOR_STATUS_CHANGED_EVENT),
+        )
+        .fetch_one(&self.pool)
         .await?;
 
-        Ok(state.map(|s| s.last_cursor))
+        Ok(webhook)
     }
 
-    pub async fn update_ingestion_cursor(&self, task_name: &str, last_cursor: &str) -> Result<()> {
-        sqlx::query(
+    pub async fn list_webhooks(&self) -> Result<Vec<WebhookRecord>> {
+        let _timer = self.slow_queries.start("list_webhooks");
+        let webhooks = sqlx::query_as::<_, WebhookRecord>(
             r#"
-            INSERT INTO ingestion_state (task_name, last_cursor, updated_at)
-            VALUES ($1, $2, $3)
-            ON CONFLICT (task_name) DO UPDATE SET
-                last_cursor = EXCLUDED.last_cursor,
-                updated_at = EXCLUDED.updated_at
+            SELECT * FROM webhooks ORDER BY created_at DESC
             "#,
         )
-        .bind(task_name)
-        .bind(last_cursor)
-        .bind(Utc::now())
-        .execute(&self.pool)
+        .fetch_all(&self.pool)
         .await?;
 
-        Ok(())
+        Ok(webhooks)
     }
 
-    pub async fn save_payments(&self, payments: Vec<crate::models::PaymentRecord>) -> Result<()> {
-        for payment in payments {
-            sqlx::query(
-                r#"
-                INSERT INTO payments (
-                    id, transaction_hash, source_account, destination_account,
-                    asset_type, asset_code, asset_issuer, amount, created_at
-                )
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+    pub async fn list_active_webhooks(&self, event_type: &str) -> Result<Vec<WebhookRecord>> {
+        let _timer = self.slow_queries.start("list_active_webhooks");
+        let webhooks = sqlx::query_as::<_, WebhookRecord>(
+            r#"
+            SELECT * FROM webhooks WHERE event_type = $1 AND is_active = 1
+            "#,
+        )
+        .bind(event_type)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(webhooks)
+    }
+
+    // Corridor alert operations
+    pub async fn create_corridor_alert(
+        &self,
+        req: CreateCorridorAlertRequest,
+    ) -> Result<CorridorAlertRecord> {
+        let _timer = self.slow_queries.start("create_corridor_alert");
+        let id = Uuid::new_v4().to_string();
+        let alert = sqlx::query_as::<_, CorridorAlertRecord>(
+            r#"
+            INSERT INTO corridor_alerts (id, corridor_key, min_success_rate, notify_url)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(&req.corridor_key)
+        .bind(req.min_success_rate)
+        .bind(&req.notify_url)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(alert)
+    }
+
+    pub async fn get_corridor_alert(&self, id: Uuid) -> Result<Option<CorridorAlertRecord>> {
+        let _timer = self.slow_queries.start("get_corridor_alert");
+        let alert = sqlx::query_as::<_, CorridorAlertRecord>(
+            r#"
+            SELECT * FROM corridor_alerts WHERE id = $1
+            "#,
+        )
+        .bind(id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(alert)
+    }
+
+    pub async fn list_corridor_alerts(&self) -> Result<Vec<CorridorAlertRecord>> {
+        let _timer = self.slow_queries.start("list_corridor_alerts");
+        let alerts = sqlx::query_as::<_, CorridorAlertRecord>(
+            r#"
+            SELECT * FROM corridor_alerts ORDER BY corridor_key ASC, created_at DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(alerts)
+    }
+
+    pub async fn list_active_corridor_alerts(
+        &self,
+        corridor_key: &str,
+    ) -> Result<Vec<CorridorAlertRecord>> {
+        let _timer = self.slow_queries.start("list_active_corridor_alerts");
+        let alerts = sqlx::query_as::<_, CorridorAlertRecord>(
+            r#"
+            SELECT * FROM corridor_alerts WHERE corridor_key = $1 AND is_active = 1
+            "#,
+        )
+        .bind(corridor_key)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(alerts)
+    }
+
+    pub async fn update_corridor_alert(
+        &self,
+        id: Uuid,
+        req: UpdateCorridorAlertRequest,
+    ) -> Result<Option<CorridorAlertRecord>> {
+        let _timer = self.slow_queries.start("update_corridor_alert");
+        let alert = sqlx::query_as::<_, CorridorAlertRecord>(
+            r#"
+            UPDATE corridor_alerts
+            SET min_success_rate = COALESCE($1, min_success_rate),
+                notify_url = COALESCE($2, notify_url),
+                is_active = COALESCE($3, is_active),
+                updated_at = $4
+            WHERE id = $5
+            RETURNING *
+            "#,
+        )
+        .bind(req.min_success_rate)
+        .bind(&req.notify_url)
+        .bind(req.is_active)
+        .bind(Utc::now())
+        .bind(id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(alert)
+    }
+
+    pub async fn delete_corridor_alert(&self, id: Uuid) -> Result<bool> {
+        let _timer = self.slow_queries.start("delete_corridor_alert");
+        let result = sqlx::query(
+            r#"
+            DELETE FROM corridor_alerts WHERE id = $1
+            "#,
+        )
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn set_corridor_alert_triggered_at(
+        &self,
+        id: &str,
+        triggered_at: Option<chrono::DateTime<Utc>>,
+    ) -> Result<()> {
+        let _timer = self.slow_queries.start("set_corridor_alert_triggered_at");
+        sqlx::query(
+            r#"
+            UPDATE corridor_alerts SET last_triggered_at = $1 WHERE id = $2
+            "#,
+        )
+        .bind(triggered_at)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    // Ingestion methods
+    pub async fn get_ingestion_cursor(&self, task_name: &str) -> Result<Option<String>> {
+        let _timer = self.slow_queries.start("get_ingestion_cursor");
+        let state = sqlx::query_as::<_, crate::models::IngestionState>(
+            r#"
+            SELECT * FROM ingestion_state WHERE task_name = $1
+            "#,
+        )
+        .bind(task_name)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(state.map(|s| s.last_cursor))
+    }
+
+    pub async fn update_ingestion_cursor(&self, task_name: &str, last_cursor: &str) -> Result<()> {
+        let _timer = self.slow_queries.start("update_ingestion_cursor");
+        sqlx::query(
+            r#"
+            INSERT INTO ingestion_state (task_name, last_cursor, updated_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (task_name) DO UPDATE SET
+                last_cursor = EXCLUDED.last_cursor,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(task_name)
+        .bind(last_cursor)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn save_payments(&self, payments: Vec<crate::models::PaymentRecord>) -> Result<()> {
+        let _timer = self.slow_queries.start("save_payments");
+        for payment in payments {
+            sqlx::query(
+                r#"
+                INSERT INTO payments (
+                    id, transaction_hash, source_account, destination_account,
+                    asset_type, asset_code, asset_issuer, amount, created_at,
+                    source_asset_code, source_asset_issuer
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
                 ON CONFLICT (id) DO NOTHING
                 "#,
             )
@@ -589,7 +1380,9 @@ impl Database {
             .bind(&payment.asset_code)
             .bind(&payment.asset_issuer)
             .bind(payment.amount)
-            .bind(payment.created_at)
+            .bind(payment.created_at.to_rfc3339())
+            .bind(&payment.source_asset_code)
+            .bind(&payment.source_asset_issuer)
             .execute(&self.pool)
             .await?;
         }
@@ -612,22 +1405,79 @@ impl Database {
             .await
     }
 
-    pub async fn upsert_hourly_corridor_metric(
+    pub async fn fetch_unaggregated_payments(
+        &self,
+        since: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<Vec<(String, crate::models::corridor::PaymentRecord)>> {
+        self.aggregation_db()
+            .fetch_unaggregated_payments(since, limit)
+            .await
+    }
+
+    pub async fn mark_payments_aggregated(&self, ids: &[String]) -> Result<()> {
+        self.aggregation_db().mark_payments_aggregated(ids).await
+    }
+
+    pub async fn upsert_corridor_metric_bucket(
         &self,
         metric: &crate::services::aggregation::HourlyCorridorMetrics,
+        granularity: crate::ingestion::config::BucketGranularity,
     ) -> Result<()> {
         self.aggregation_db()
-            .upsert_hourly_corridor_metric(metric)
+            .upsert_corridor_metric_bucket(metric, granularity)
             .await
     }
 
-    pub async fn fetch_hourly_metrics_by_timerange(
+    /// Recompute a corridor's daily `corridor_metrics` row for `day` from its
+    /// metric buckets, which the history endpoints read
+    pub async fn roll_up_daily_corridor_metrics(
+        &self,
+        corridor_key: &str,
+        day: chrono::NaiveDate,
+    ) -> Result<()> {
+        let totals = self
+            .aggregation_db()
+            .sum_day_buckets(corridor_key, day)
+            .await?;
+        if let Some(analytics) = totals {
+            self.corridor_aggregates()
+                .store_daily_corridor_metrics(&analytics, day)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Fail when corridor metric buckets of another granularity are stored
+    ///
+    /// Hourly and daily buckets can't share the table: a day's bucket would
+    /// collide with its own midnight hour and history would mix widths.
+    pub async fn ensure_bucket_granularity(
+        &self,
+        granularity: crate::ingestion::config::BucketGranularity,
+    ) -> Result<()> {
+        let mismatched = self
+            .aggregation_db()
+            .count_buckets_not_at(granularity)
+            .await?;
+        if mismatched > 0 {
+            anyhow::bail!(
+                "corridor_metrics_hourly holds {} buckets of another granularity; clear them before aggregating with INGESTION_BUCKET={}",
+                mismatched,
+                granularity
+            );
+        }
+        Ok(())
+    }
+
+    pub async fn fetch_corridor_metric_buckets(
         &self,
         start_time: chrono::DateTime<chrono::Utc>,
         end_time: chrono::DateTime<chrono::Utc>,
+        granularity: crate::ingestion::config::BucketGranularity,
     ) -> Result<Vec<crate::services::aggregation::HourlyCorridorMetrics>> {
         self.aggregation_db()
-            .fetch_hourly_metrics_by_timerange(start_time, end_time)
+            .fetch_corridor_metric_buckets(start_time, end_time, granularity)
             .await
     }
 
@@ -663,4 +1513,733 @@ impl Database {
             .increment_job_retry_count(job_id)
             .await
     }
+
+    // Audit log operations
+
+    pub async fn record_audit(
+        &self,
+        principal: &str,
+        action: &str,
+        target_id: Option<&str>,
+        request_id: &str,
+    ) -> Result<()> {
+        let _timer = self.slow_queries.start("record_audit");
+        sqlx::query(
+            r#"
+            INSERT INTO audit_log (principal, action, target_id, request_id, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(principal)
+        .bind(action)
+        .bind(target_id)
+        .bind(request_id)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Audit entries recorded in `[from, to]`, oldest first, at most `limit`
+    pub async fn audit_entries(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> Result<Vec<AuditLogRecord>> {
+        let _timer = self.slow_queries.start("audit_entries");
+        let entries = sqlx::query_as::<_, AuditLogRecord>(
+            r#"
+            SELECT * FROM audit_log
+            WHERE ($1 IS NULL OR created_at >= $1)
+              AND ($2 IS NULL OR created_at <= $2)
+            ORDER BY created_at ASC, id ASC
+            LIMIT $3
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    // Ingestion dead-letter operations
+
+    pub async fn list_ingestion_failures(
+        &self,
+        status: Option<&str>,
+    ) -> Result<Vec<IngestionFailureRecord>> {
+        let _timer = self.slow_queries.start("list_ingestion_failures");
+        let failures = sqlx::query_as::<_, IngestionFailureRecord>(
+            r#"
+            SELECT * FROM ingestion_failures
+            WHERE $1 IS NULL OR status = $1
+            ORDER BY start_ledger ASC
+            "#,
+        )
+        .bind(status)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(failures)
+    }
+
+    pub async fn get_ingestion_failure(&self, id: i64) -> Result<Option<IngestionFailureRecord>> {
+        let _timer = self.slow_queries.start("get_ingestion_failure");
+        let failure = sqlx::query_as::<_, IngestionFailureRecord>(
+            "SELECT * FROM ingestion_failures WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(failure)
+    }
+
+    /// Queue a dead-lettered range for another ingestion attempt
+    ///
+    /// Returns `false` when the range is not currently dead-lettered.
+    pub async fn requeue_ingestion_failure(&self, id: i64) -> Result<bool> {
+        let _timer = self.slow_queries.start("requeue_ingestion_failure");
+        let result = sqlx::query(
+            r#"
+            UPDATE ingestion_failures
+            SET status = 'requeued', updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND status = 'dead_lettered'
+            "#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+/// Append the filter's bounds as `AND` predicates on `anchors`
+fn push_anchor_predicates(query: &mut QueryBuilder<'_, Sqlite>, filter: AnchorFilter) {
+    if let Some(min) = filter.min_reliability {
+        query.push(" AND reliability_score >= ").push_bind(min);
+    }
+    if let Some(max) = filter.max_reliability {
+        query.push(" AND reliability_score < ").push_bind(max);
+    }
+}
+
+async fn insert_anchor_metrics_history<'e>(
+    executor: impl SqliteExecutor<'e>,
+    params: AnchorMetricsParams,
+) -> Result<AnchorMetricsHistory> {
+    let history = sqlx::query_as::<_, AnchorMetricsHistory>(
+        r#"
+        INSERT INTO anchor_metrics_history (
+            id, anchor_id, timestamp, success_rate, failure_rate, reliability_score,
+            total_transactions, successful_transactions, failed_transactions,
+            avg_settlement_time_ms, volume_usd
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        RETURNING *
+        "#,
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(params.anchor_id.to_string())
+    .bind(Utc::now())
+    .bind(params.success_rate)
+    .bind(params.failure_rate)
+    .bind(params.reliability_score)
+    .bind(params.total_transactions)
+    .bind(params.successful_transactions)
+    .bind(params.failed_transactions)
+    .bind(params.avg_settlement_time_ms.unwrap_or(0))
+    .bind(params.volume_usd.unwrap_or(0.0))
+    .fetch_one(executor)
+    .await?;
+
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::corridor::Corridor;
+    use crate::models::AnchorStatus;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_db() -> Database {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        Database::new(pool)
+    }
+
+    async fn insert_payment(db: &Database, hash: &str, asset_code: &str, created_at: &str) {
+        sqlx::query(
+            r#"
+            INSERT INTO payments (
+                id, transaction_hash, source_account, destination_account,
+                asset_type, asset_code, asset_issuer, amount, created_at
+            )
+            VALUES ($1, $2, 'GSRC', 'GDST', 'credit_alphanum4', $3, 'issuer', 10.0, $4)
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(hash)
+        .bind(asset_code)
+        .bind(created_at)
+        .execute(db.pool())
+        .await
+        .unwrap();
+    }
+
+    async fn insert_transaction(db: &Database, hash: &str, ledger: i64, successful: bool) {
+        sqlx::query("INSERT OR IGNORE INTO ledgers (sequence, hash, close_time) VALUES ($1, 'h', '')")
+            .bind(ledger)
+            .execute(db.pool())
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO transactions (hash, ledger_sequence, successful) VALUES ($1, $2, $3)")
+            .bind(hash)
+            .bind(ledger)
+            .bind(successful)
+            .execute(db.pool())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_queries_share_slow_query_log() {
+        let slow_queries = Arc::new(SlowQueryLog::new(std::time::Duration::ZERO));
+        let db = setup_db()
+            .await
+            .with_slow_query_log(Arc::clone(&slow_queries));
+
+        db.get_anchor_by_id(Uuid::new_v4()).await.unwrap();
+        db.corridor_aggregates()
+            .delete_old_metrics(Utc::now().date_naive())
+            .await
+            .unwrap();
+
+        assert_eq!(slow_queries.stats().slow_queries, 2);
+    }
+
+    #[tokio::test]
+    async fn test_list_corridor_transactions_filters_by_success() {
+        let db = setup_db().await;
+        insert_payment(&db, "tx1", "USDC", "2024-01-01T10:00:00Z").await;
+        insert_payment(&db, "tx2", "USDC", "2024-01-01T11:00:00Z").await;
+        insert_payment(&db, "tx3", "EURC", "2024-01-01T12:00:00Z").await;
+        insert_transaction(&db, "tx1", 100, true).await;
+        insert_transaction(&db, "tx2", 101, false).await;
+
+        let corridor = Corridor::from_key("USDC:issuer->XLM:native").unwrap();
+
+        let all = db
+            .list_corridor_transactions(&corridor, None, 50, 0)
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].transaction_hash, "tx2");
+        assert_eq!(all[0].ledger_sequence, Some(101));
+
+        let failed = db
+            .list_corridor_transactions(&corridor, Some(false), 50, 0)
+            .await
+            .unwrap();
+        assert_eq!(failed.len(), 1);
+        assert!(!failed[0].successful);
+
+        let succeeded = db
+            .list_corridor_transactions(&corridor, Some(true), 1, 0)
+            .await
+            .unwrap();
+        assert_eq!(succeeded.len(), 1);
+        assert_eq!(succeeded[0].transaction_hash, "tx1");
+    }
+
+    #[tokio::test]
+    async fn test_anchor_asset_volumes_aggregates_per_asset() {
+        let db = setup_db().await;
+        let anchor = db
+            .create_anchor(CreateAnchorRequest {
+                name: "Volume Anchor".to_string(),
+                stellar_account: "GVOLUMEANCHOR".to_string(),
+                home_domain: None,
+            })
+            .await
+            .unwrap();
+        let id = Uuid::parse_str(&anchor.id).unwrap();
+        for code in ["USDC", "EURC", "BRL"] {
+            db.create_asset(id, code.to_string(), "GVOLUMEANCHOR".to_string())
+                .await
+                .unwrap();
+        }
+
+        for (code, issuer, amount) in [
+            ("USDC", "GVOLUMEANCHOR", 100.0),
+            ("USDC", "GVOLUMEANCHOR", 50.0),
+            ("EURC", "GVOLUMEANCHOR", 400.0),
+            // Same code from another issuer is not this anchor's asset
+            ("USDC", "GOTHER", 1_000.0),
+        ] {
+            sqlx::query(
+                r#"
+                INSERT INTO payments (
+                    id, transaction_hash, source_account, destination_account,
+                    asset_type, asset_code, asset_issuer, amount, created_at
+                )
+                VALUES ($1, 'hash', 'GSRC', 'GDST', 'credit_alphanum4', $2, $3, $4, '2024-01-01T00:00:00Z')
+                "#,
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(code)
+            .bind(issuer)
+            .bind(amount)
+            .execute(db.pool())
+            .await
+            .unwrap();
+        }
+
+        let volumes = db.anchor_asset_volumes(id).await.unwrap();
+        let summary: Vec<_> = volumes
+            .iter()
+            .map(|v| (v.asset_code.as_str(), v.transaction_count, v.volume))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("EURC", 1, 400.0), ("USDC", 2, 150.0), ("BRL", 0, 0.0)]
+        );
+
+        assert!(db
+            .anchor_asset_volumes(Uuid::new_v4())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    async fn insert_hourly_metric(
+        db: &Database,
+        corridor_key: &str,
+        hour_bucket: &str,
+        counts: (i64, i64, i64),
+        volume_usd: f64,
+        latency_ms: Option<i32>,
+    ) {
+        let corridor = Corridor::from_key(corridor_key).unwrap();
+        let (total, successful, failed) = counts;
+        sqlx::query(
+            r#"
+            INSERT INTO corridor_metrics_hourly (
+                id, corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+                hour_bucket, total_transactions, successful_transactions, failed_transactions,
+                volume_usd, avg_settlement_latency_ms
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(corridor_key)
+        .bind(&corridor.asset_a_code)
+        .bind(&corridor.asset_a_issuer)
+        .bind(&corridor.asset_b_code)
+        .bind(&corridor.asset_b_issuer)
+        .bind(hour_bucket)
+        .bind(total)
+        .bind(successful)
+        .bind(failed)
+        .bind(volume_usd)
+        .bind(latency_ms)
+        .execute(db.pool())
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_anchor_totals_aggregate_across_corridors() {
+        let db = setup_db().await;
+        let anchor = db
+            .create_anchor(CreateAnchorRequest {
+                name: "Corridor Anchor".to_string(),
+                stellar_account: "GANCHOR".to_string(),
+                home_domain: None,
+            })
+            .await
+            .unwrap();
+        let id = Uuid::parse_str(&anchor.id).unwrap();
+        for code in ["USDC", "EURC"] {
+            db.create_asset(id, code.to_string(), "GA".to_string())
+                .await
+                .unwrap();
+        }
+
+        let usdc = "USDC:GA->XLM:native";
+        // Both sides are the anchor's assets; counted once
+        let both = "EURC:GA->USDC:GA";
+        let eurc = "XLM:native->EURC:GA";
+        // Same code from another issuer
+        let other = "USDC:GOTHER->XLM:native";
+        for (corridor_key, hour, counts, volume, latency) in [
+            (usdc, "10:00", (100, 90, 10), 1000.0, Some(1000)),
+            (usdc, "11:00", (50, 50, 0), 500.0, Some(4000)),
+            (both, "10:00", (10, 5, 5), 100.0, None),
+            (eurc, "10:00", (40, 35, 5), 400.0, Some(2000)),
+            (other, "10:00", (1000, 0, 1000), 9000.0, Some(9000)),
+        ] {
+            let hour_bucket = format!("2024-01-01T{}:00Z", hour);
+            insert_hourly_metric(&db, corridor_key, &hour_bucket, counts, volume, latency).await;
+        }
+
+        let totals = db.aggregate_anchor_from_corridors(id).await.unwrap();
+        assert_eq!(
+            totals,
+            AnchorCorridorTotals {
+                corridor_count: 3,
+                total_transactions: 200,
+                successful_transactions: 180,
+                failed_transactions: 20,
+                volume_usd: 2000.0,
+                // (1000 * 100 + 4000 * 50 + 2000 * 40) / 190
+                avg_settlement_time_ms: Some(2000),
+            }
+        );
+
+        let (updated, _) = db
+            .recompute_anchor_from_corridors(id, &StatusThresholds::default())
+            .await
+            .unwrap()
+            .unwrap();
+        let expected =
+            compute_anchor_metrics(200, 180, 20, Some(2000), &StatusThresholds::default());
+        assert_eq!(updated.total_transactions, 200);
+        assert_eq!(updated.successful_transactions, 180);
+        assert_eq!(updated.failed_transactions, 20);
+        assert_eq!(updated.total_volume_usd, 2000.0);
+        assert_eq!(updated.avg_settlement_time_ms, 2000);
+        assert_eq!(updated.reliability_score, expected.reliability_score);
+        assert_eq!(updated.status, expected.status.as_str());
+
+        let history = db.get_anchor_metrics_history(id, 10).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].total_transactions, 200);
+
+        assert!(db
+            .recompute_anchor_from_corridors(Uuid::new_v4(), &StatusThresholds::default())
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_anchor_without_corridors_totals_zero() {
+        let db = setup_db().await;
+        let anchor = db
+            .create_anchor(CreateAnchorRequest {
+                name: "Quiet Anchor".to_string(),
+                stellar_account: "GQUIET".to_string(),
+                home_domain: None,
+            })
+            .await
+            .unwrap();
+        let id = Uuid::parse_str(&anchor.id).unwrap();
+
+        let totals = db.aggregate_anchor_from_corridors(id).await.unwrap();
+        assert_eq!(totals, AnchorCorridorTotals::default());
+    }
+
+    #[tokio::test]
+    async fn test_recompute_without_corridor_metrics_leaves_anchor_unchanged() {
+        let db = setup_db().await;
+        let anchor = db
+            .create_anchor(CreateAnchorRequest {
+                name: "Quiet Anchor".to_string(),
+                stellar_account: "GQUIET".to_string(),
+                home_domain: None,
+            })
+            .await
+            .unwrap();
+        let id = Uuid::parse_str(&anchor.id).unwrap();
+
+        let (unchanged, totals) = db
+            .recompute_anchor_from_corridors(id, &StatusThresholds::default())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(totals.corridor_count, 0);
+        assert_eq!(unchanged.status, anchor.status);
+        assert_eq!(unchanged.reliability_score, anchor.reliability_score);
+        assert_eq!(unchanged.updated_at, anchor.updated_at);
+        assert!(db
+            .get_anchor_metrics_history(id, 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_anchor_filter_bounds_list_and_count() {
+        let db = setup_db().await;
+        for (name, reliability_score) in [("green", 99.0), ("yellow", 96.0), ("red", 50.0)] {
+            let anchor = db
+                .create_anchor(CreateAnchorRequest {
+                    name: name.to_string(),
+                    stellar_account: format!("G{}", name.to_uppercase()),
+                    home_domain: None,
+                })
+                .await
+                .unwrap();
+            db.patch_anchor_metrics(
+                Uuid::parse_str(&anchor.id).unwrap(),
+                &AnchorMetricsPatch {
+                    reliability_score: Some(reliability_score),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        // Migrations seed anchors of their own, so check membership per band
+        let thresholds = StatusThresholds::default();
+        let mut banded = 0;
+        for (status, expected) in [
+            (AnchorStatus::Green, "green"),
+            (AnchorStatus::Yellow, "yellow"),
+            (AnchorStatus::Red, "red"),
+        ] {
+            let filter = thresholds.anchor_filter(status);
+            let anchors = db
+                .list_anchors_sorted(filter, SortBy::Name, SortOrder::Asc, 100, 0)
+                .await
+                .unwrap();
+            assert!(anchors.iter().any(|a| a.name == expected));
+            assert!(anchors
+                .iter()
+                .all(|a| thresholds.anchor_status(a.reliability_score) == status));
+            assert_eq!(
+                db.count_anchors(filter).await.unwrap(),
+                anchors.len() as i64
+            );
+            banded += anchors.len() as i64;
+        }
+        assert_eq!(
+            db.count_anchors(AnchorFilter::default()).await.unwrap(),
+            banded
+        );
+    }
+
+    #[tokio::test]
+    async fn test_patch_anchor_metrics_preserves_omitted_fields() {
+        let db = setup_db().await;
+        let anchor = db
+            .create_anchor(CreateAnchorRequest {
+                name: "Patch Anchor".to_string(),
+                stellar_account: "GPATCHANCHOR".to_string(),
+                home_domain: None,
+            })
+            .await
+            .unwrap();
+        let id = Uuid::parse_str(&anchor.id).unwrap();
+        db.update_anchor_metrics(
+            id,
+            100,
+            90,
+            10,
+            Some(2000),
+            Some(5000.0),
+            &StatusThresholds::default(),
+        )
+        .await
+        .unwrap();
+
+        let patched = db
+            .patch_anchor_metrics(
+                id,
+                &AnchorMetricsPatch {
+                    reliability_score: Some(42.5),
+                    failed_transactions: Some(12),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(patched.reliability_score, 42.5);
+        assert_eq!(patched.failed_transactions, 12);
+        assert_eq!(patched.total_transactions, 100);
+        assert_eq!(patched.successful_transactions, 90);
+        assert_eq!(patched.avg_settlement_time_ms, 2000);
+        assert_eq!(patched.total_volume_usd, 5000.0);
+
+        let missing = db
+            .patch_anchor_metrics(Uuid::new_v4(), &AnchorMetricsPatch::default())
+            .await
+            .unwrap();
+        assert!(missing.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_prune_metrics_history_only_removes_rows_before_cutoff() {
+        let db = setup_db().await;
+        let anchor = db
+            .create_anchor(CreateAnchorRequest {
+                name: "Prune Anchor".to_string(),
+                stellar_account: "GPRUNEANCHOR".to_string(),
+                home_domain: None,
+            })
+            .await
+            .unwrap();
+        let now = Utc::now();
+        let cutoff = now - chrono::Duration::days(30);
+
+        for days_ago in [1, 29, 31, 45, 90] {
+            sqlx::query(
+                r#"
+                INSERT INTO anchor_metrics_history (
+                    id, anchor_id, timestamp, success_rate, failure_rate, reliability_score,
+                    total_transactions, successful_transactions, failed_transactions
+                )
+                VALUES ($1, $2, $3, 99.0, 1.0, 95.0, 100, 99, 1)
+                "#,
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&anchor.id)
+            .bind(now - chrono::Duration::days(days_ago))
+            .execute(db.pool())
+            .await
+            .unwrap();
+        }
+
+        let table = MetricsHistoryTable::AnchorMetricsHistory;
+        assert_eq!(db.prune_metrics_history_batch(table, cutoff, 2).await.unwrap(), 2);
+        assert_eq!(db.prune_metrics_history_batch(table, cutoff, 2).await.unwrap(), 1);
+        assert_eq!(db.prune_metrics_history_batch(table, cutoff, 2).await.unwrap(), 0);
+
+        let remaining: Vec<chrono::DateTime<Utc>> =
+            sqlx::query_scalar("SELECT timestamp FROM anchor_metrics_history WHERE anchor_id = $1")
+                .bind(&anchor.id)
+                .fetch_all(db.pool())
+                .await
+                .unwrap();
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining.iter().all(|ts| *ts >= cutoff));
+
+        for table in MetricsHistoryTable::ALL {
+            db.prune_metrics_history_batch(table, cutoff, 100).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_requeue_ingestion_failure() {
+        let db = setup_db().await;
+        sqlx::query(
+            "INSERT INTO ingestion_failures (start_ledger, end_ledger, attempts, last_error) VALUES (100, 104, 3, 'boom')",
+        )
+        .execute(db.pool())
+        .await
+        .unwrap();
+
+        let dead = db.list_ingestion_failures(Some("dead_lettered")).await.unwrap();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].start_ledger, 100);
+
+        assert!(db.requeue_ingestion_failure(dead[0].id).await.unwrap());
+        // Already requeued
+        assert!(!db.requeue_ingestion_failure(dead[0].id).await.unwrap());
+
+        let failure = db.get_ingestion_failure(dead[0].id).await.unwrap().unwrap();
+        assert_eq!(failure.status, "requeued");
+        assert!(db.list_ingestion_failures(Some("dead_lettered")).await.unwrap().is_empty());
+        assert_eq!(db.list_ingestion_failures(None).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_create_corridor_twice_is_a_conflict() {
+        let db = setup_db().await;
+        let request = || crate::models::CreateCorridorRequest {
+            name: None,
+            source_asset_code: "YEN".to_string(),
+            source_asset_issuer: "GYEN".to_string(),
+            dest_asset_code: "USDC".to_string(),
+            dest_asset_issuer: "GUSDC".to_string(),
+        };
+
+        db.create_corridor(request()).await.unwrap();
+        let err = db.create_corridor(request()).await.unwrap_err();
+        assert!(matches!(
+            crate::error::ApiError::from(err),
+            crate::error::ApiError::Conflict(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_ensure_corridor_keeps_the_first_direction() {
+        let db = setup_db().await;
+
+        let first = db
+            .ensure_corridor(("yen", "GYEN"), ("USDC", "GUSDC"))
+            .await
+            .unwrap();
+        let reversed = db
+            .ensure_corridor(("USDC", "GUSDC"), ("YEN", "GYEN"))
+            .await
+            .unwrap();
+
+        assert_eq!(first, reversed);
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT source_asset_code, destination_asset_code FROM corridors WHERE 'YEN' IN (source_asset_code, destination_asset_code)",
+        )
+        .fetch_all(db.pool())
+        .await
+        .unwrap();
+        assert_eq!(rows, vec![("YEN".to_string(), "USDC".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_ensure_corridor_converges_on_one_row() {
+        // A file database so both callers use separate connections
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("c.db").display());
+        let pool = SqlitePoolOptions::new()
+            .max_connections(4)
+            .connect(&url)
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        let corridor = Corridor::new(
+            "USDC".to_string(),
+            "GISSUER".to_string(),
+            "XLM".to_string(),
+            "native".to_string(),
+        );
+        let ensure = || {
+            let db = Database::new(pool.clone());
+            let corridor = corridor.clone();
+            tokio::spawn(async move {
+                db.ensure_corridor(
+                    (&corridor.asset_a_code, &corridor.asset_a_issuer),
+                    (&corridor.asset_b_code, &corridor.asset_b_issuer),
+                )
+                .await
+            })
+        };
+        let (first, second) = tokio::join!(ensure(), ensure());
+
+        assert_eq!(first.unwrap().unwrap(), corridor);
+        assert_eq!(second.unwrap().unwrap(), corridor);
+        let rows: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM corridors WHERE source_asset_code = 'USDC' AND source_asset_issuer = 'GISSUER'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(rows, 1);
+    }
 }
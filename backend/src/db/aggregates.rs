@@ -1,16 +1,69 @@
 use anyhow::Result;
-use chrono::NaiveDate;
-use sqlx::SqlitePool;
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::stream::{BoxStream, Stream, StreamExt};
+use sqlx::{QueryBuilder, Sqlite, SqlitePool};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::mpsc;
 
-use crate::models::corridor::{Corridor, CorridorAnalytics, CorridorMetrics};
+use crate::db::slow_query::SlowQueryLog;
+use crate::models::corridor::{
+    Corridor, CorridorAnalytics, CorridorMetrics, CorridorNetworkSummary,
+};
+use crate::models::{SortBy, SortOrder};
+
+/// Rows buffered between the database cursor and a slow stream consumer
+const STREAM_BUFFER_ROWS: usize = 64;
+
+/// Queries shared with [`crate::db::explain`], so plans match what runs
+pub(crate) const TOP_CORRIDORS_BY_VOLUME_SQL: &str = r#"
+    SELECT * FROM corridor_metrics
+    WHERE date >= $1 AND date < $2
+    ORDER BY volume_usd DESC
+    LIMIT $3
+"#;
+
+pub(crate) const CORRIDOR_DAILY_TOTALS_SQL: &str = r#"
+    SELECT corridor_key, date, total_transactions, successful_transactions,
+           failed_transactions, volume_usd
+    FROM corridor_metrics
+    WHERE corridor_key = $1 AND date >= $2
+    ORDER BY date DESC
+"#;
+
+pub(crate) const CORRIDOR_HISTORY_SQL: &str = r#"
+    SELECT corridor_key, date, total_transactions, successful_transactions,
+           failed_transactions, volume_usd
+    FROM corridor_metrics
+    WHERE corridor_key = $1 AND date >= $2 AND date <= $3
+    ORDER BY date ASC
+"#;
+
+pub(crate) const CORRIDOR_METRICS_AT_SQL: &str = r#"
+    SELECT * FROM corridor_metrics
+    WHERE corridor_key = $1 AND date <= $2
+    ORDER BY date DESC
+    LIMIT 1
+"#;
 
 pub struct CorridorAggregates {
     pool: SqlitePool,
+    slow_queries: Arc<SlowQueryLog>,
 }
 
 impl CorridorAggregates {
     pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            slow_queries: Arc::new(SlowQueryLog::default()),
+        }
+    }
+
+    /// Share `slow_queries` for timing these queries, e.g. with the owning `Database`
+    pub fn with_slow_query_log(mut self, slow_queries: Arc<SlowQueryLog>) -> Self {
+        self.slow_queries = slow_queries;
+        self
     }
 
     pub async fn store_daily_corridor_metrics(
@@ -18,6 +71,7 @@ impl CorridorAggregates {
         analytics: &CorridorAnalytics,
         date: NaiveDate,
     ) -> Result<CorridorMetrics> {
+        let _timer = self.slow_queries.start("store_daily_corridor_metrics");
         let date_datetime = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
         let corridor_key = analytics.corridor.to_string_key();
 
@@ -62,6 +116,7 @@ impl CorridorAggregates {
         start_date: NaiveDate,
         end_date: NaiveDate,
     ) -> Result<Vec<CorridorMetrics>> {
+        let _timer = self.slow_queries.start("get_corridor_metrics");
         let corridor_key = corridor.to_string_key();
         let start_datetime = start_date.and_hms_opt(0, 0, 0).unwrap().and_utc();
         let end_datetime = end_date.and_hms_opt(23, 59, 59).unwrap().and_utc();
@@ -86,6 +141,7 @@ impl CorridorAggregates {
         &self,
         date: NaiveDate,
     ) -> Result<Vec<CorridorMetrics>> {
+        let _timer = self.slow_queries.start("get_corridor_metrics_for_date");
         let date_datetime = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
         let next_day = date_datetime + chrono::Duration::days(1);
 
@@ -104,11 +160,29 @@ impl CorridorAggregates {
         Ok(metrics)
     }
 
+    /// Metrics row in effect for `corridor_key` at `at`: the latest with
+    /// `date <= at`, or `None` if the corridor had no data yet
+    pub async fn corridor_metrics_at(
+        &self,
+        corridor_key: &str,
+        at: DateTime<Utc>,
+    ) -> Result<Option<CorridorMetrics>> {
+        let _timer = self.slow_queries.start("corridor_metrics_at");
+        let metrics = sqlx::query_as::<_, CorridorMetrics>(CORRIDOR_METRICS_AT_SQL)
+            .bind(corridor_key)
+            .bind(at)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(metrics)
+    }
+
     pub async fn get_aggregated_corridor_metrics(
         &self,
         start_date: NaiveDate,
         end_date: NaiveDate,
     ) -> Result<Vec<AggregatedCorridorMetrics>> {
+        let _timer = self.slow_queries.start("get_aggregated_corridor_metrics");
         let start_datetime = start_date.and_hms_opt(0, 0, 0).unwrap().and_utc();
         let end_datetime = end_date.and_hms_opt(23, 59, 59).unwrap().and_utc();
 
@@ -140,24 +214,62 @@ impl CorridorAggregates {
         Ok(metrics)
     }
 
-    pub async fn get_top_corridors_by_volume(
+    /// Fetch the latest (rolling 24h) metrics for a set of corridors in a single query
+    pub async fn get_latest_corridor_metrics_by_keys(
         &self,
-        date: NaiveDate,
-        limit: i64,
-    ) -> Result<Vec<CorridorMetrics>> {
-        let date_datetime = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
-        let next_day = date_datetime + chrono::Duration::days(1);
+        corridor_keys: &[String],
+    ) -> Result<Vec<LatestCorridorMetrics>> {
+        let _timer = self
+            .slow_queries
+            .start("get_latest_corridor_metrics_by_keys");
+        if corridor_keys.is_empty() {
+            return Ok(Vec::new());
+        }
 
-        let metrics = sqlx::query_as::<_, CorridorMetrics>(
+        let mut query = QueryBuilder::<Sqlite>::new(
+            "SELECT * FROM corridor_metrics_latest WHERE corridor_key IN (",
+        );
+        let mut separated = query.separated(", ");
+        for key in corridor_keys {
+            separated.push_bind(key);
+        }
+        separated.push_unseparated(")");
+
+        let metrics = query
+            .build_query_as::<LatestCorridorMetrics>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(metrics)
+    }
+
+    /// Latest metrics of the corridors sharing an asset with `corridor`,
+    /// highest 24h volume first
+    ///
+    /// Assets match on code and issuer; `corridor` itself is left out.
+    pub async fn related_corridors(
+        &self,
+        corridor: &Corridor,
+        limit: i64,
+    ) -> Result<Vec<LatestCorridorMetrics>> {
+        let _timer = self.slow_queries.start("related_corridors");
+        let metrics = sqlx::query_as::<_, LatestCorridorMetrics>(
             r#"
-            SELECT * FROM corridor_metrics
-            WHERE date >= $1 AND date < $2
-            ORDER BY volume_usd DESC
-            LIMIT $3
+            SELECT * FROM corridor_metrics_latest
+            WHERE corridor_key != $1
+              AND ((asset_a_code = $2 AND asset_a_issuer = $3)
+                OR (asset_b_code = $2 AND asset_b_issuer = $3)
+                OR (asset_a_code = $4 AND asset_a_issuer = $5)
+                OR (asset_b_code = $4 AND asset_b_issuer = $5))
+            ORDER BY total_volume_usd DESC, corridor_key ASC
+            LIMIT $6
             "#,
         )
-        .bind(date_datetime)
-        .bind(next_day)
+        .bind(corridor.to_string_key())
+        .bind(&corridor.asset_a_code)
+        .bind(&corridor.asset_a_issuer)
+        .bind(&corridor.asset_b_code)
+        .bind(&corridor.asset_b_issuer)
         .bind(limit)
         .fetch_all(&self.pool)
         .await?;
@@ -165,11 +277,165 @@ impl CorridorAggregates {
         Ok(metrics)
     }
 
+    /// Daily totals for one corridor from `since` onwards, newest first
+    pub async fn get_corridor_daily_totals(
+        &self,
+        corridor_key: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<CorridorDailyTotals>> {
+        let _timer = self.slow_queries.start("get_corridor_daily_totals");
+        let totals = sqlx::query_as::<_, CorridorDailyTotals>(CORRIDOR_DAILY_TOTALS_SQL)
+            .bind(corridor_key)
+            .bind(since)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(totals)
+    }
+
+    /// Daily totals for each of `corridor_keys` from `since` onwards, newest
+    /// first, in one query; keys without history are absent from the map
+    pub async fn get_daily_totals_by_keys(
+        &self,
+        corridor_keys: &[String],
+        since: DateTime<Utc>,
+    ) -> Result<HashMap<String, Vec<CorridorDailyTotals>>> {
+        let _timer = self.slow_queries.start("get_daily_totals_by_keys");
+        if corridor_keys.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut query = QueryBuilder::<Sqlite>::new(
+            "SELECT corridor_key, date, total_transactions, successful_transactions, \
+             failed_transactions, volume_usd FROM corridor_metrics WHERE date >= ",
+        );
+        query.push_bind(since).push(" AND corridor_key IN (");
+        let mut separated = query.separated(", ");
+        for key in corridor_keys {
+            separated.push_bind(key);
+        }
+        separated.push_unseparated(") ORDER BY corridor_key, date DESC");
+
+        let rows = query
+            .build_query_as::<CorridorDailyTotals>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(group_daily_totals(rows))
+    }
+
+    /// List latest (rolling 24h) corridor metrics matching the given thresholds
+    pub async fn list_corridor_metrics(
+        &self,
+        filter: &CorridorMetricsFilter,
+        sort_by: SortBy,
+        order: SortOrder,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<LatestCorridorMetrics>> {
+        let _timer = self.slow_queries.start("list_corridor_metrics");
+        let mut query = QueryBuilder::<Sqlite>::new("SELECT * FROM corridor_metrics_latest WHERE 1 = 1");
+        filter.push_predicates(&mut query);
+        let column = match sort_by {
+            SortBy::SuccessRate => "avg_success_rate",
+            SortBy::Volume => "total_volume_usd",
+            SortBy::LastUpdated => "updated_at",
+            SortBy::Name => "corridor_key",
+        };
+        query
+            .push(" ORDER BY ")
+            .push(column)
+            .push(" ")
+            .push(order.sql())
+            .push(", corridor_key LIMIT ")
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
+
+        let metrics = query
+            .build_query_as::<LatestCorridorMetrics>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(metrics)
+    }
+
+    /// Number of latest corridor metrics rows matching `filter`
+    pub async fn count_corridor_metrics(&self, filter: &CorridorMetricsFilter) -> Result<i64> {
+        let _timer = self.slow_queries.start("count_corridor_metrics");
+        let mut query =
+            QueryBuilder::<Sqlite>::new("SELECT COUNT(*) FROM corridor_metrics_latest WHERE 1 = 1");
+        filter.push_predicates(&mut query);
+        Ok(query.build_query_scalar().fetch_one(&self.pool).await?)
+    }
+
+    /// Stream latest (rolling 24h) corridor metrics matching `filter`, by key
+    ///
+    /// Rows come off a database cursor through a small bounded channel, so
+    /// memory stays flat however many corridors there are. Not timed as a
+    /// slow query, since the duration depends on how fast the consumer reads.
+    pub fn stream_corridor_metrics(
+        &self,
+        filter: CorridorMetricsFilter,
+    ) -> BoxStream<'static, Result<LatestCorridorMetrics>> {
+        let pool = self.pool.clone();
+        spawn_row_stream(move |tx| async move {
+            let mut query =
+                QueryBuilder::<Sqlite>::new("SELECT * FROM corridor_metrics_latest WHERE 1 = 1");
+            filter.push_predicates(&mut query);
+            query.push(" ORDER BY corridor_key");
+
+            let rows = query.build_query_as::<LatestCorridorMetrics>().fetch(&pool);
+            forward_rows(rows, &tx).await;
+        })
+    }
+
+    /// Stream the daily history of `corridor_key` between `from` and `to`,
+    /// oldest first
+    ///
+    /// Read from a database cursor like [`Self::stream_corridor_metrics`].
+    pub fn stream_corridor_history(
+        &self,
+        corridor_key: String,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> BoxStream<'static, Result<CorridorDailyTotals>> {
+        let pool = self.pool.clone();
+        spawn_row_stream(move |tx| async move {
+            let rows = sqlx::query_as::<_, CorridorDailyTotals>(CORRIDOR_HISTORY_SQL)
+                .bind(corridor_key)
+                .bind(from)
+                .bind(to)
+                .fetch(&pool);
+            forward_rows(rows, &tx).await;
+        })
+    }
+
+    pub async fn get_top_corridors_by_volume(
+        &self,
+        date: NaiveDate,
+        limit: i64,
+    ) -> Result<Vec<CorridorMetrics>> {
+        let _timer = self.slow_queries.start("get_top_corridors_by_volume");
+        let date_datetime = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let next_day = date_datetime + chrono::Duration::days(1);
+
+        let metrics = sqlx::query_as::<_, CorridorMetrics>(TOP_CORRIDORS_BY_VOLUME_SQL)
+            .bind(date_datetime)
+            .bind(next_day)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(metrics)
+    }
+
     pub async fn get_top_corridors_by_transactions(
         &self,
         date: NaiveDate,
         limit: i64,
     ) -> Result<Vec<CorridorMetrics>> {
+        let _timer = self.slow_queries.start("get_top_corridors_by_transactions");
         let date_datetime = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
         let next_day = date_datetime + chrono::Duration::days(1);
 
@@ -196,6 +462,7 @@ impl CorridorAggregates {
         min_success_rate: f64,
         min_transactions: i64,
     ) -> Result<Vec<CorridorMetrics>> {
+        let _timer = self.slow_queries.start("get_corridors_by_success_rate");
         let date_datetime = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
         let next_day = date_datetime + chrono::Duration::days(1);
 
@@ -223,6 +490,7 @@ impl CorridorAggregates {
         start_date: NaiveDate,
         end_date: NaiveDate,
     ) -> Result<CorridorSummaryStats> {
+        let _timer = self.slow_queries.start("get_corridor_summary_stats");
         let start_datetime = start_date.and_hms_opt(0, 0, 0).unwrap().and_utc();
         let end_datetime = end_date.and_hms_opt(23, 59, 59).unwrap().and_utc();
 
@@ -247,7 +515,43 @@ impl CorridorAggregates {
         Ok(stats)
     }
 
+    /// Headline totals across `corridor_metrics_latest` in one query
+    ///
+    /// The volume-weighted success rate falls back to the plain mean when no
+    /// corridor has volume.
+    pub async fn corridor_summary(&self, health_threshold: f64) -> Result<CorridorNetworkSummary> {
+        let _timer = self.slow_queries.start("corridor_summary");
+        let summary = sqlx::query_as::<_, CorridorNetworkSummary>(
+            r#"
+            SELECT
+                COUNT(*) AS total_corridors,
+                COALESCE(AVG(avg_success_rate), 0.0) AS avg_success_rate,
+                COALESCE(
+                    SUM(avg_success_rate * total_volume_usd) / NULLIF(SUM(total_volume_usd), 0),
+                    AVG(avg_success_rate),
+                    0.0
+                ) AS volume_weighted_success_rate,
+                COALESCE(SUM(total_volume_usd), 0.0) AS total_volume_usd,
+                $1 AS health_threshold,
+                COALESCE(SUM(CASE WHEN avg_success_rate < $1 THEN 1 ELSE 0 END), 0)
+                    AS unhealthy_corridors,
+                (
+                    SELECT corridor_key FROM corridor_metrics_latest
+                    ORDER BY total_transactions DESC, total_volume_usd DESC
+                    LIMIT 1
+                ) AS most_active_corridor
+            FROM corridor_metrics_latest
+            "#,
+        )
+        .bind(health_threshold)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(summary)
+    }
+
     pub async fn delete_old_metrics(&self, cutoff_date: NaiveDate) -> Result<u64> {
+        let _timer = self.slow_queries.start("delete_old_metrics");
         let cutoff_datetime = cutoff_date.and_hms_opt(0, 0, 0).unwrap().and_utc();
 
         let result = sqlx::query(
@@ -279,6 +583,294 @@ pub struct AggregatedCorridorMetrics {
     pub latest_date: chrono::DateTime<chrono::Utc>,
 }
 
+/// Row of the `corridor_metrics_latest` view
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct LatestCorridorMetrics {
+    pub corridor_key: String,
+    pub asset_a_code: String,
+    pub asset_a_issuer: String,
+    pub asset_b_code: String,
+    pub asset_b_issuer: String,
+    pub total_transactions: i64,
+    pub successful_transactions: i64,
+    pub failed_transactions: i64,
+    pub avg_success_rate: f64,
+    pub total_volume_usd: f64,
+    pub avg_slippage_bps: Option<f64>,
+    pub avg_settlement_latency_ms: Option<f64>,
+    pub avg_liquidity_depth_usd: Option<f64>,
+    pub last_updated: String,
+    /// When any of the corridor's hourly rows was last written
+    pub updated_at: String,
+    /// Asset the corridor's payments are sent in, as registered in `corridors`
+    pub source_asset_code: String,
+    pub source_asset_issuer: String,
+    /// Asset the corridor's payments deliver
+    pub destination_asset_code: String,
+    pub destination_asset_issuer: String,
+}
+
+impl LatestCorridorMetrics {
+    /// `last_updated` as a timestamp
+    ///
+    /// The hour bucket is stored as text: RFC 3339 when written by the
+    /// aggregator, `YYYY-MM-DD HH:MM:SS` (UTC) when written by SQLite itself.
+    pub fn last_updated_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.last_updated)
+            .map(|dt| dt.with_timezone(&Utc))
+            .or_else(|_| {
+                chrono::NaiveDateTime::parse_from_str(&self.last_updated, "%Y-%m-%d %H:%M:%S")
+                    .map(|dt| dt.and_utc())
+            })
+            .ok()
+    }
+
+    /// Whether this is another corridor with an asset in common with
+    /// `corridor`, as [`CorridorAggregates::related_corridors`] matches them
+    pub fn is_peer_of(&self, corridor: &Corridor) -> bool {
+        let assets = [
+            (&corridor.asset_a_code, &corridor.asset_a_issuer),
+            (&corridor.asset_b_code, &corridor.asset_b_issuer),
+        ];
+        let shares = |code: &String, issuer: &String| assets.contains(&(code, issuer));
+
+        self.corridor_key != corridor.to_string_key()
+            && (shares(&self.asset_a_code, &self.asset_a_issuer)
+                || shares(&self.asset_b_code, &self.asset_b_issuer))
+    }
+}
+
+/// Order rows the way [`CorridorAggregates::list_corridor_metrics`] does
+pub fn sort_corridor_metrics(
+    metrics: &mut [LatestCorridorMetrics],
+    sort_by: SortBy,
+    order: SortOrder,
+) {
+    metrics.sort_by(|a, b| {
+        let ordering = match sort_by {
+            SortBy::SuccessRate => a.avg_success_rate.total_cmp(&b.avg_success_rate),
+            SortBy::Volume => a.total_volume_usd.total_cmp(&b.total_volume_usd),
+            SortBy::LastUpdated => a.updated_at.cmp(&b.updated_at),
+            SortBy::Name => a.corridor_key.cmp(&b.corridor_key),
+        };
+        order
+            .apply(ordering)
+            .then_with(|| a.corridor_key.cmp(&b.corridor_key))
+    });
+}
+
+/// [`CorridorAggregates::corridor_summary`] computed over rows already in memory
+pub fn summarize_corridor_metrics(
+    metrics: &[LatestCorridorMetrics],
+    health_threshold: f64,
+) -> CorridorNetworkSummary {
+    let count = metrics.len() as f64;
+    let total_volume_usd: f64 = metrics.iter().map(|m| m.total_volume_usd).sum();
+    let avg_success_rate = if metrics.is_empty() {
+        0.0
+    } else {
+        metrics.iter().map(|m| m.avg_success_rate).sum::<f64>() / count
+    };
+    let volume_weighted_success_rate = if total_volume_usd > 0.0 {
+        metrics
+            .iter()
+            .map(|m| m.avg_success_rate * m.total_volume_usd)
+            .sum::<f64>()
+            / total_volume_usd
+    } else {
+        avg_success_rate
+    };
+    let most_active_corridor = metrics
+        .iter()
+        .max_by(|a, b| {
+            a.total_transactions
+                .cmp(&b.total_transactions)
+                .then(a.total_volume_usd.total_cmp(&b.total_volume_usd))
+        })
+        .map(|m| m.corridor_key.clone());
+
+    CorridorNetworkSummary {
+        total_corridors: metrics.len() as i64,
+        avg_success_rate,
+        volume_weighted_success_rate,
+        total_volume_usd,
+        health_threshold,
+        unhealthy_corridors: metrics
+            .iter()
+            .filter(|m| m.avg_success_rate < health_threshold)
+            .count() as i64,
+        most_active_corridor,
+    }
+}
+
+/// Run `produce` on its own task and stream the rows it sends
+fn spawn_row_stream<T, F, Fut>(produce: F) -> BoxStream<'static, Result<T>>
+where
+    T: Send + 'static,
+    F: FnOnce(mpsc::Sender<Result<T>>) -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel(STREAM_BUFFER_ROWS);
+    tokio::spawn(produce(tx));
+
+    futures::stream::unfold(
+        rx,
+        |mut rx| async move { rx.recv().await.map(|row| (row, rx)) },
+    )
+    .boxed()
+}
+
+/// Send `rows` on to `tx` until they run out, the consumer hangs up, or one
+/// fails
+async fn forward_rows<T>(
+    mut rows: impl Stream<Item = sqlx::Result<T>> + Unpin,
+    tx: &mpsc::Sender<Result<T>>,
+) {
+    while let Some(row) = rows.next().await {
+        let failed = row.is_err();
+        // Stop once the consumer hangs up or after reporting an error
+        if tx.send(row.map_err(anyhow::Error::from)).await.is_err() || failed {
+            break;
+        }
+    }
+}
+
+/// Daily totals grouped by corridor key, keeping their order within each key
+pub fn group_daily_totals(
+    totals: impl IntoIterator<Item = CorridorDailyTotals>,
+) -> HashMap<String, Vec<CorridorDailyTotals>> {
+    let mut grouped: HashMap<String, Vec<CorridorDailyTotals>> = HashMap::new();
+    for day in totals {
+        grouped
+            .entry(day.corridor_key.clone())
+            .or_default()
+            .push(day);
+    }
+    grouped
+}
+
+/// One day of `corridor_metrics` history
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct CorridorDailyTotals {
+    pub corridor_key: String,
+    pub date: DateTime<Utc>,
+    pub total_transactions: i64,
+    pub successful_transactions: i64,
+    pub failed_transactions: i64,
+    pub volume_usd: f64,
+}
+
+/// Server-side thresholds for corridor list queries
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CorridorMetricsFilter {
+    pub min_success_rate: Option<f64>,
+    pub max_success_rate: Option<f64>,
+    pub min_volume: Option<f64>,
+    pub max_volume: Option<f64>,
+    /// Corridors with fewer transactions are left out; 0 keeps everything
+    pub min_transactions: i64,
+    /// Case-insensitive substring of either asset code
+    pub asset_code: Option<String>,
+}
+
+impl CorridorMetricsFilter {
+    /// Check that rates are percentages, the range is ordered and volume is non-negative
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        for (name, rate) in [
+            ("min_success_rate", self.min_success_rate),
+            ("max_success_rate", self.max_success_rate),
+        ] {
+            if let Some(rate) = rate {
+                if !(0.0..=100.0).contains(&rate) {
+                    return Err(format!("{} must be between 0 and 100", name));
+                }
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min_success_rate, self.max_success_rate) {
+            if min > max {
+                return Err("min_success_rate cannot exceed max_success_rate".to_string());
+            }
+        }
+        for (name, volume) in [
+            ("min_volume", self.min_volume),
+            ("max_volume", self.max_volume),
+        ] {
+            if let Some(volume) = volume {
+                if !volume.is_finite() || volume < 0.0 {
+                    return Err(format!("{} must be a non-negative number", name));
+                }
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min_volume, self.max_volume) {
+            if min > max {
+                return Err("min_volume cannot exceed max_volume".to_string());
+            }
+        }
+        if self.min_transactions < 0 {
+            return Err("min_transactions cannot be negative".to_string());
+        }
+        Ok(())
+    }
+
+    /// Append the thresholds as `AND` predicates on `corridor_metrics_latest`
+    fn push_predicates(&self, query: &mut QueryBuilder<'_, Sqlite>) {
+        if let Some(min) = self.min_success_rate {
+            query.push(" AND avg_success_rate >= ").push_bind(min);
+        }
+        if let Some(max) = self.max_success_rate {
+            query.push(" AND avg_success_rate <= ").push_bind(max);
+        }
+        if let Some(min) = self.min_volume {
+            query.push(" AND total_volume_usd >= ").push_bind(min);
+        }
+        if let Some(max) = self.max_volume {
+            query.push(" AND total_volume_usd <= ").push_bind(max);
+        }
+        if self.min_transactions > 0 {
+            query
+                .push(" AND total_transactions >= ")
+                .push_bind(self.min_transactions);
+        }
+        if let Some(code) = self.asset_code_needle() {
+            query
+                .push(" AND (instr(lower(asset_a_code), ")
+                .push_bind(code.clone())
+                .push(") > 0 OR instr(lower(asset_b_code), ")
+                .push_bind(code)
+                .push(") > 0)");
+        }
+    }
+
+    /// Whether a row passes the thresholds, mirroring the SQL predicates
+    pub fn matches(&self, metrics: &LatestCorridorMetrics) -> bool {
+        self.min_success_rate
+            .is_none_or(|min| metrics.avg_success_rate >= min)
+            && self
+                .max_success_rate
+                .is_none_or(|max| metrics.avg_success_rate <= max)
+            && self
+                .min_volume
+                .is_none_or(|min| metrics.total_volume_usd >= min)
+            && self
+                .max_volume
+                .is_none_or(|max| metrics.total_volume_usd <= max)
+            && metrics.total_transactions >= self.min_transactions
+            && self.asset_code_needle().is_none_or(|code| {
+                metrics.asset_a_code.to_lowercase().contains(&code)
+                    || metrics.asset_b_code.to_lowercase().contains(&code)
+            })
+    }
+
+    /// Lowercased `asset_code`, or `None` when it is absent or blank
+    fn asset_code_needle(&self) -> Option<String> {
+        self.asset_code
+            .as_deref()
+            .map(str::trim)
+            .filter(|code| !code.is_empty())
+            .map(str::to_lowercase)
+    }
+}
+
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct CorridorSummaryStats {
     pub total_corridors: i64,
@@ -290,4 +882,542 @@ pub struct CorridorSummaryStats {
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_aggregates() -> CorridorAggregates {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        for (key, success_rate, volume) in [
+            ("USDC:a->XLM:native", 99.0, 5_000.0),
+            ("EURC:b->XLM:native", 80.0, 50_000.0),
+            ("BRL:c->XLM:native", 40.0, 500.0),
+        ] {
+            sqlx::query(
+                r#"
+                INSERT INTO corridor_metrics_hourly (
+                    id, corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+                    hour_bucket, total_transactions, successful_transactions, failed_transactions,
+                    success_rate, volume_usd
+                )
+                VALUES ($1, $2, 'A', 'issuer', 'XLM', 'native', datetime('now'), 10, 9, 1, $3, $4)
+                "#,
+            )
+            .bind(key)
+            .bind(key)
+            .bind(success_rate)
+            .bind(volume)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        CorridorAggregates::new(pool)
+    }
+
+    #[tokio::test]
+    async fn test_list_corridor_metrics_applies_filters() {
+        let aggregates = setup_aggregates().await;
+
+        let all = aggregates
+            .list_corridor_metrics(
+                &CorridorMetricsFilter::default(),
+                SortBy::Volume,
+                SortOrder::Desc,
+                50,
+                0,
+            )
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].corridor_key, "EURC:b->XLM:native");
+
+        let filter = CorridorMetricsFilter {
+            min_success_rate: Some(75.0),
+            max_success_rate: Some(95.0),
+            ..Default::default()
+        };
+        let rates = aggregates
+            .list_corridor_metrics(&filter, SortBy::Volume, SortOrder::Desc, 50, 0)
+            .await
+            .unwrap();
+        assert_eq!(rates.len(), 1);
+        assert_eq!(rates[0].corridor_key, "EURC:b->XLM:native");
+
+        let filter = CorridorMetricsFilter {
+            min_volume: Some(1_000.0),
+            ..Default::default()
+        };
+        let volume = aggregates
+            .list_corridor_metrics(&filter, SortBy::Volume, SortOrder::Desc, 1, 1)
+            .await
+            .unwrap();
+        assert_eq!(volume.len(), 1);
+        assert_eq!(volume[0].corridor_key, "USDC:a->XLM:native");
+        assert_eq!(aggregates.count_corridor_metrics(&filter).await.unwrap(), 2);
+
+        let filter = CorridorMetricsFilter {
+            max_volume: Some(5_000.0),
+            ..Default::default()
+        };
+        let capped = aggregates
+            .list_corridor_metrics(&filter, SortBy::Volume, SortOrder::Desc, 50, 0)
+            .await
+            .unwrap();
+        assert_eq!(capped.len(), 2);
+        assert_eq!(capped[0].corridor_key, "USDC:a->XLM:native");
+    }
+
+    #[tokio::test]
+    async fn test_asset_code_filter_matches_either_side() {
+        let aggregates = setup_aggregates().await;
+        sqlx::query(
+            "UPDATE corridor_metrics_hourly SET asset_a_code = 'EURC' WHERE corridor_key = 'EURC:b->XLM:native'",
+        )
+        .execute(&aggregates.pool)
+        .await
+        .unwrap();
+
+        let filter = CorridorMetricsFilter {
+            asset_code: Some("eur".to_string()),
+            ..Default::default()
+        };
+        let rows = aggregates
+            .list_corridor_metrics(&filter, SortBy::Name, SortOrder::Asc, 50, 0)
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert!(filter.matches(&rows[0]));
+
+        let filter = CorridorMetricsFilter {
+            asset_code: Some("XLM".to_string()),
+            ..Default::default()
+        };
+        let rows = aggregates
+            .list_corridor_metrics(&filter, SortBy::Name, SortOrder::Asc, 50, 0)
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_stream_corridor_metrics_in_key_order() {
+        let aggregates = setup_aggregates().await;
+
+        let filter = CorridorMetricsFilter {
+            min_volume: Some(1_000.0),
+            ..Default::default()
+        };
+        let rows: Vec<_> = aggregates.stream_corridor_metrics(filter).collect().await;
+        let keys: Vec<_> = rows
+            .into_iter()
+            .map(|row| row.unwrap().corridor_key)
+            .collect();
+        assert_eq!(keys, vec!["EURC:b->XLM:native", "USDC:a->XLM:native"]);
+    }
+
+    #[tokio::test]
+    async fn test_min_transactions_boundary_is_inclusive() {
+        let aggregates = setup_aggregates().await;
+        for (key, total) in [("EURC:b->XLM:native", 25), ("BRL:c->XLM:native", 3)] {
+            sqlx::query(
+                "UPDATE corridor_metrics_hourly SET total_transactions = $1 WHERE corridor_key = $2",
+            )
+            .bind(total)
+            .bind(key)
+            .execute(&aggregates.pool)
+            .await
+            .unwrap();
+        }
+
+        let filter = CorridorMetricsFilter {
+            min_transactions: 10,
+            ..Default::default()
+        };
+        let rows = aggregates
+            .list_corridor_metrics(&filter, SortBy::Name, SortOrder::Asc, 50, 0)
+            .await
+            .unwrap();
+        let keys: Vec<_> = rows.iter().map(|m| m.corridor_key.as_str()).collect();
+        // USDC sits exactly at the threshold
+        assert_eq!(keys, vec!["EURC:b->XLM:native", "USDC:a->XLM:native"]);
+        assert!(rows.iter().all(|m| filter.matches(m)));
+
+        let all = aggregates
+            .list_corridor_metrics(
+                &CorridorMetricsFilter::default(),
+                SortBy::Name,
+                SortOrder::Asc,
+                50,
+                0,
+            )
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 3);
+        assert!(!filter.matches(&all[0]));
+    }
+
+    #[tokio::test]
+    async fn test_list_corridor_metrics_sorts_by_last_updated() {
+        let aggregates = setup_aggregates().await;
+        for (key, updated_at) in [
+            ("USDC:a->XLM:native", "2026-01-01 09:00:00"),
+            ("EURC:b->XLM:native", "2026-01-01 08:00:00"),
+            ("BRL:c->XLM:native", "2026-01-01 10:00:00"),
+        ] {
+            sqlx::query(
+                "UPDATE corridor_metrics_hourly SET updated_at = $1 WHERE corridor_key = $2",
+            )
+            .bind(updated_at)
+            .bind(key)
+            .execute(&aggregates.pool)
+            .await
+            .unwrap();
+        }
+
+        let mut expected = Vec::new();
+        for (sort_by, order) in [
+            (SortBy::LastUpdated, SortBy::LastUpdated.default_order()),
+            (SortBy::SuccessRate, SortBy::SuccessRate.default_order()),
+            (SortBy::LastUpdated, SortOrder::Asc),
+            (SortBy::Name, SortBy::Name.default_order()),
+        ] {
+            let rows = aggregates
+                .list_corridor_metrics(&CorridorMetricsFilter::default(), sort_by, order, 50, 0)
+                .await
+                .unwrap();
+            let keys: Vec<_> = rows.iter().map(|m| m.corridor_key.as_str()).collect();
+            expected.push(keys.join(","));
+
+            // The in-memory ordering matches SQL
+            let mut resorted = rows.clone();
+            resorted.reverse();
+            sort_corridor_metrics(&mut resorted, sort_by, order);
+            let resorted: Vec<_> = resorted.iter().map(|m| m.corridor_key.as_str()).collect();
+            assert_eq!(resorted, keys, "{:?} {:?}", sort_by, order);
+        }
+
+        assert_eq!(
+            expected,
+            vec![
+                "BRL:c->XLM:native,USDC:a->XLM:native,EURC:b->XLM:native",
+                "USDC:a->XLM:native,EURC:b->XLM:native,BRL:c->XLM:native",
+                "EURC:b->XLM:native,USDC:a->XLM:native,BRL:c->XLM:native",
+                "BRL:c->XLM:native,EURC:b->XLM:native,USDC:a->XLM:native",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_related_corridors_share_an_asset() {
+        let aggregates = setup_aggregates().await;
+        for (a_code, a_issuer, b_code, b_issuer, volume) in [
+            ("EURC", "GE", "USDC", "GC", 100.0),
+            ("NGNT", "GN", "USDC", "GC", 300.0),
+            ("EURC", "GE", "ZAR", "GZ", 200.0),
+            ("USDC", "GOTHER", "ZAR", "GZ", 900.0),
+        ] {
+            let key = format!("{}:{}->{}:{}", a_code, a_issuer, b_code, b_issuer);
+            sqlx::query(
+                r#"
+                INSERT INTO corridor_metrics_hourly (
+                    id, corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+                    hour_bucket, total_transactions, successful_transactions, failed_transactions,
+                    success_rate, volume_usd
+                )
+                VALUES ($1, $1, $2, $3, $4, $5, datetime('now'), 10, 9, 1, 90.0, $6)
+                "#,
+            )
+            .bind(key)
+            .bind(a_code)
+            .bind(a_issuer)
+            .bind(b_code)
+            .bind(b_issuer)
+            .bind(volume)
+            .execute(&aggregates.pool)
+            .await
+            .unwrap();
+        }
+
+        let corridor = Corridor::new("EURC".into(), "GE".into(), "USDC".into(), "GC".into());
+        let peers = aggregates.related_corridors(&corridor, 10).await.unwrap();
+        let keys: Vec<_> = peers.iter().map(|m| m.corridor_key.as_str()).collect();
+        assert_eq!(keys, vec!["NGNT:GN->USDC:GC", "EURC:GE->ZAR:GZ"]);
+
+        // The in-memory match agrees with SQL
+        let all = aggregates
+            .list_corridor_metrics(
+                &CorridorMetricsFilter::default(),
+                SortBy::Name,
+                SortOrder::Asc,
+                50,
+                0,
+            )
+            .await
+            .unwrap();
+        let matched = all.iter().filter(|m| m.is_peer_of(&corridor)).count();
+        assert_eq!(matched, keys.len());
+    }
+
+    #[tokio::test]
+    async fn test_get_corridor_daily_totals_since() {
+        let aggregates = setup_aggregates().await;
+        let today = Utc::now().date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc();
+
+        for (key, days_ago) in [
+            ("USDC->XLM", 0),
+            ("USDC->XLM", 3),
+            ("USDC->XLM", 40),
+            ("EURC->XLM", 0),
+        ] {
+            sqlx::query(
+                r#"
+                INSERT INTO corridor_metrics (
+                    corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+                    date, total_transactions, successful_transactions, failed_transactions,
+                    success_rate, volume_usd
+                )
+                VALUES ($1, 'A', 'issuer', 'XLM', 'native', $2, 10, 9, 1, 90.0, 100.0)
+                "#,
+            )
+            .bind(key)
+            .bind(today - chrono::Duration::days(days_ago))
+            .execute(&aggregates.pool)
+            .await
+            .unwrap();
+        }
+
+        let totals = aggregates
+            .get_corridor_daily_totals("USDC->XLM", today - chrono::Duration::days(30))
+            .await
+            .unwrap();
+        assert_eq!(totals.len(), 2);
+        assert_eq!(totals[0].date, today);
+        assert_eq!(totals[1].date, today - chrono::Duration::days(3));
+
+        let keys = ["USDC->XLM", "EURC->XLM", "BRL->XLM"].map(String::from);
+        let by_key = aggregates
+            .get_daily_totals_by_keys(&keys, today - chrono::Duration::days(30))
+            .await
+            .unwrap();
+        assert_eq!(by_key.len(), 2);
+        let dates: Vec<_> = by_key["USDC->XLM"].iter().map(|t| t.date).collect();
+        assert_eq!(dates, totals.iter().map(|t| t.date).collect::<Vec<_>>());
+        assert_eq!(by_key["EURC->XLM"].len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stream_corridor_history_oldest_first_within_range() {
+        let aggregates = setup_aggregates().await;
+        let day = |d: u32| {
+            NaiveDate::from_ymd_opt(2026, 3, d)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+                .and_utc()
+        };
+
+        for (key, d) in [
+            ("USDC->XLM", 9),
+            ("USDC->XLM", 2),
+            ("USDC->XLM", 5),
+            ("USDC->XLM", 20),
+            ("EURC->XLM", 5),
+        ] {
+            sqlx::query(
+                r#"
+                INSERT INTO corridor_metrics (
+                    corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+                    date, total_transactions, successful_transactions, failed_transactions,
+                    success_rate, volume_usd
+                )
+                VALUES ($1, 'A', 'issuer', 'XLM', 'native', $2, 10, 9, 1, 90.0, 100.0)
+                "#,
+            )
+            .bind(key)
+            .bind(day(d))
+            .execute(&aggregates.pool)
+            .await
+            .unwrap();
+        }
+
+        let rows: Vec<_> = aggregates
+            .stream_corridor_history("USDC->XLM".to_string(), day(2), day(9))
+            .collect()
+            .await;
+        let dates: Vec<_> = rows.into_iter().map(|row| row.unwrap().date).collect();
+        assert_eq!(dates, vec![day(2), day(5), day(9)]);
+    }
+
+    #[tokio::test]
+    async fn test_corridor_metrics_at() {
+        let aggregates = setup_aggregates().await;
+        let day = |d: u32| {
+            NaiveDate::from_ymd_opt(2026, 3, d)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+                .and_utc()
+        };
+        for (date, success_rate) in [(day(10), 90.0), (day(12), 70.0)] {
+            sqlx::query(
+                r#"
+                INSERT INTO corridor_metrics (
+                    corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+                    date, total_transactions, successful_transactions, failed_transactions,
+                    success_rate, volume_usd
+                )
+                VALUES ('USDC->XLM', 'A', 'issuer', 'XLM', 'native', $1, 10, 9, 1, $2, 100.0)
+                "#,
+            )
+            .bind(date)
+            .bind(success_rate)
+            .execute(&aggregates.pool)
+            .await
+            .unwrap();
+        }
+        let at = |when| aggregates.corridor_metrics_at("USDC->XLM", when);
+
+        // Exact match
+        let exact = at(day(12)).await.unwrap().unwrap();
+        assert_eq!((exact.date, exact.success_rate), (day(12), 70.0));
+
+        // Between rows, the earlier one is still in effect
+        let between = at(day(11) + chrono::Duration::hours(6))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((between.date, between.success_rate), (day(10), 90.0));
+
+        // Before the first row there was no data
+        assert!(at(day(9)).await.unwrap().is_none());
+        assert!(aggregates
+            .corridor_metrics_at("EURC->XLM", day(12))
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_corridor_summary_weights_success_rate_by_volume() {
+        let aggregates = setup_aggregates().await;
+
+        let summary = aggregates.corridor_summary(95.0).await.unwrap();
+        let weighted = (99.0 * 5_000.0 + 80.0 * 50_000.0 + 40.0 * 500.0) / 55_500.0;
+        assert_eq!(summary.total_corridors, 3);
+        assert!((summary.avg_success_rate - 73.0).abs() < 1e-9);
+        assert!((summary.volume_weighted_success_rate - weighted).abs() < 1e-9);
+        assert_eq!(summary.total_volume_usd, 55_500.0);
+        assert_eq!(summary.unhealthy_corridors, 2);
+        // Transaction counts tie, so the larger volume wins
+        assert_eq!(
+            summary.most_active_corridor.as_deref(),
+            Some("EURC:b->XLM:native")
+        );
+
+        // The in-memory computation agrees with the SQL one
+        let rows = aggregates
+            .list_corridor_metrics(
+                &CorridorMetricsFilter::default(),
+                SortBy::Volume,
+                SortOrder::Desc,
+                50,
+                0,
+            )
+            .await
+            .unwrap();
+        assert_eq!(summarize_corridor_metrics(&rows, 95.0), summary);
+    }
+
+    #[tokio::test]
+    async fn test_corridor_summary_of_empty_network() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        let summary = CorridorAggregates::new(pool)
+            .corridor_summary(95.0)
+            .await
+            .unwrap();
+        assert_eq!(summary, summarize_corridor_metrics(&[], 95.0));
+        assert_eq!(summary.total_corridors, 0);
+        assert_eq!(summary.volume_weighted_success_rate, 0.0);
+        assert_eq!(summary.most_active_corridor, None);
+    }
+
+    #[test]
+    fn test_summary_without_volume_falls_back_to_plain_average() {
+        let row = |key: &str, success_rate: f64| LatestCorridorMetrics {
+            corridor_key: key.to_string(),
+            asset_a_code: "A".to_string(),
+            asset_a_issuer: "issuer".to_string(),
+            asset_b_code: "XLM".to_string(),
+            asset_b_issuer: "native".to_string(),
+            total_transactions: 10,
+            successful_transactions: 9,
+            failed_transactions: 1,
+            avg_success_rate: success_rate,
+            total_volume_usd: 0.0,
+            avg_slippage_bps: None,
+            avg_settlement_latency_ms: None,
+            avg_liquidity_depth_usd: None,
+            last_updated: String::new(),
+            updated_at: String::new(),
+            source_asset_code: "A".to_string(),
+            source_asset_issuer: "issuer".to_string(),
+            destination_asset_code: "XLM".to_string(),
+            destination_asset_issuer: "native".to_string(),
+        };
+
+        let summary = summarize_corridor_metrics(&[row("a", 90.0), row("b", 60.0)], 95.0);
+        assert_eq!(summary.avg_success_rate, 75.0);
+        assert_eq!(summary.volume_weighted_success_rate, 75.0);
+        assert_eq!(summary.unhealthy_corridors, 2);
+    }
+
+    #[test]
+    fn test_filter_validation() {
+        assert!(CorridorMetricsFilter::default().validate().is_ok());
+
+        let out_of_range = CorridorMetricsFilter {
+            min_success_rate: Some(101.0),
+            ..Default::default()
+        };
+        assert!(out_of_range.validate().is_err());
+
+        let inverted = CorridorMetricsFilter {
+            min_success_rate: Some(90.0),
+            max_success_rate: Some(10.0),
+            ..Default::default()
+        };
+        assert!(inverted.validate().is_err());
+
+        let negative_volume = CorridorMetricsFilter {
+            min_volume: Some(-1.0),
+            ..Default::default()
+        };
+        assert!(negative_volume.validate().is_err());
+
+        let inverted_volume = CorridorMetricsFilter {
+            min_volume: Some(100.0),
+            max_volume: Some(10.0),
+            ..Default::default()
+        };
+        assert!(inverted_volume.validate().is_err());
+
+        let negative_transactions = CorridorMetricsFilter {
+            min_transactions: -1,
+            ..Default::default()
+        };
+        assert!(negative_transactions.validate().is_err());
+    }
+}
@@ -1,7 +1,8 @@
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use sqlx::SqlitePool;
 
+use crate::ingestion::config::BucketGranularity;
 use crate::services::aggregation::HourlyCorridorMetrics;
 
 pub struct AggregationDb {
@@ -30,6 +31,8 @@ impl AggregationDb {
                 asset_type,
                 asset_code,
                 asset_issuer,
+                source_asset_code,
+                source_asset_issuer,
                 amount,
                 created_at
             FROM payments
@@ -45,39 +48,78 @@ impl AggregationDb {
         .await
         .context("Failed to fetch payments by timerange")?;
 
-        // Convert to PaymentRecord with corridor information
-        let payment_records: Vec<crate::models::corridor::PaymentRecord> = records
+        Ok(records
             .into_iter()
-            .filter_map(|row| {
-                // Parse the created_at timestamp
-                let timestamp = DateTime::parse_from_rfc3339(&row.created_at)
-                    .ok()?
-                    .with_timezone(&Utc);
+            .filter_map(PaymentRecordRow::into_record)
+            .collect())
+    }
 
-                // For now, assume all payments are successful
-                // In a real system, you'd have a status field
-                let successful = true;
-
-                Some(crate::models::corridor::PaymentRecord {
-                    id: uuid::Uuid::parse_str(&row.id).ok()?,
-                    source_asset_code: row.asset_code.clone().unwrap_or_else(|| "XLM".to_string()),
-                    source_asset_issuer: row.asset_issuer.clone().unwrap_or_else(|| "native".to_string()),
-                    destination_asset_code: row.asset_code.unwrap_or_else(|| "XLM".to_string()),
-                    destination_asset_issuer: row.asset_issuer.unwrap_or_else(|| "native".to_string()),
-                    amount: row.amount,
-                    successful,
-                    timestamp,
-                    submission_time: None,
-                    confirmation_time: None,
-                })
+    /// Fetch payments since `since` not yet counted into a metric bucket,
+    /// oldest first, with their stored ids
+    pub async fn fetch_unaggregated_payments(
+        &self,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<(String, crate::models::corridor::PaymentRecord)>> {
+        let records = sqlx::query_as::<_, PaymentRecordRow>(
+            r#"
+            SELECT
+                id,
+                transaction_hash,
+                source_account,
+                destination_account,
+                asset_type,
+                asset_code,
+                asset_issuer,
+                source_asset_code,
+                source_asset_issuer,
+                amount,
+                created_at
+            FROM payments
+            WHERE aggregated = 0 AND created_at >= ?
+            ORDER BY created_at ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(since.to_rfc3339())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch unaggregated payments")?;
+
+        Ok(records
+            .into_iter()
+            .filter_map(|row| {
+                let id = row.id.clone();
+                row.into_record().map(|record| (id, record))
             })
-            .collect();
+            .collect())
+    }
 
-        Ok(payment_records)
+    /// Flag payments as counted, so later runs don't add them to a bucket again
+    pub async fn mark_payments_aggregated(&self, ids: &[String]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for id in ids {
+            sqlx::query("UPDATE payments SET aggregated = 1 WHERE id = ?")
+                .bind(id)
+                .execute(&mut *tx)
+                .await
+                .context("Failed to mark payment aggregated")?;
+        }
+        tx.commit().await?;
+        Ok(())
     }
 
-    /// Upsert hourly corridor metric
-    pub async fn upsert_hourly_corridor_metric(&self, metric: &HourlyCorridorMetrics) -> Result<()> {
+    /// Upsert a corridor metric bucket of the given granularity
+    ///
+    /// Counts and volume are added to the stored bucket inside the statement,
+    /// and the success rate is recomputed from the summed counts, so workers
+    /// applying deltas to the same bucket concurrently never lose an update.
+    pub async fn upsert_corridor_metric_bucket(
+        &self,
+        metric: &HourlyCorridorMetrics,
+        granularity: BucketGranularity,
+    ) -> Result<()> {
         let now = Utc::now().to_rfc3339();
         
         sqlx::query(
@@ -98,14 +140,19 @@ impl AggregationDb {
                 avg_slippage_bps,
                 avg_settlement_latency_ms,
                 liquidity_depth_usd,
+                granularity,
                 created_at,
                 updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(corridor_key, hour_bucket) DO UPDATE SET
                 total_transactions = total_transactions + excluded.total_transactions,
                 successful_transactions = successful_transactions + excluded.successful_transactions,
                 failed_transactions = failed_transactions + excluded.failed_transactions,
-                success_rate = (successful_transactions * 100.0) / NULLIF(total_transactions, 0),
+                success_rate = COALESCE(
+                    ((successful_transactions + excluded.successful_transactions) * 100.0)
+                        / NULLIF(total_transactions + excluded.total_transactions, 0),
+                    0
+                ),
                 volume_usd = volume_usd + excluded.volume_usd,
                 avg_slippage_bps = (avg_slippage_bps + excluded.avg_slippage_bps) / 2.0,
                 avg_settlement_latency_ms = COALESCE(
@@ -132,6 +179,7 @@ impl AggregationDb {
         .bind(metric.avg_slippage_bps)
         .bind(metric.avg_settlement_latency_ms)
         .bind(metric.liquidity_depth_usd)
+        .bind(granularity.as_str())
         .bind(&now)
         .bind(&now)
         .bind(&now)
@@ -142,11 +190,74 @@ impl AggregationDb {
         Ok(())
     }
 
-    /// Fetch hourly metrics by time range
-    pub async fn fetch_hourly_metrics_by_timerange(
+    /// Rows stored at a granularity other than `granularity`
+    pub async fn count_buckets_not_at(&self, granularity: BucketGranularity) -> Result<i64> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM corridor_metrics_hourly WHERE granularity != ?")
+            .bind(granularity.as_str())
+            .fetch_one(&self.pool)
+            .await
+            .context("Failed to count corridor metric buckets")
+    }
+
+    /// Totals of a corridor's metric buckets starting on `day`; `None`
+    /// when it has none
+    pub async fn sum_day_buckets(
+        &self,
+        corridor_key: &str,
+        day: NaiveDate,
+    ) -> Result<Option<crate::models::corridor::CorridorAnalytics>> {
+        let start = day.and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let row = sqlx::query_as::<_, DayBucketTotalsRow>(
+            r#"
+            SELECT
+                asset_a_code,
+                asset_a_issuer,
+                asset_b_code,
+                asset_b_issuer,
+                SUM(total_transactions) AS total_transactions,
+                SUM(successful_transactions) AS successful_transactions,
+                SUM(failed_transactions) AS failed_transactions,
+                SUM(volume_usd) AS volume_usd
+            FROM corridor_metrics_hourly
+            WHERE corridor_key = ? AND hour_bucket >= ? AND hour_bucket < ?
+            GROUP BY asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer
+            "#,
+        )
+        .bind(corridor_key)
+        .bind(start.to_rfc3339())
+        .bind((start + chrono::Duration::days(1)).to_rfc3339())
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to sum corridor metric buckets")?;
+
+        Ok(row.map(|row| {
+            let success_rate = if row.total_transactions > 0 {
+                row.successful_transactions as f64 * 100.0 / row.total_transactions as f64
+            } else {
+                0.0
+            };
+            crate::models::corridor::CorridorAnalytics {
+                corridor: crate::models::corridor::Corridor::new(
+                    row.asset_a_code,
+                    row.asset_a_issuer,
+                    row.asset_b_code,
+                    row.asset_b_issuer,
+                ),
+                success_rate,
+                total_transactions: row.total_transactions,
+                successful_transactions: row.successful_transactions,
+                failed_transactions: row.failed_transactions,
+                volume_usd: row.volume_usd,
+            }
+        }))
+    }
+
+    /// Fetch metric buckets of one granularity by time range
+    pub async fn fetch_corridor_metric_buckets(
         &self,
         start_time: DateTime<Utc>,
         end_time: DateTime<Utc>,
+        granularity: BucketGranularity,
     ) -> Result<Vec<HourlyCorridorMetrics>> {
         let rows = sqlx::query_as::<_, HourlyCorridorMetricsRow>(
             r#"
@@ -167,12 +278,13 @@ impl AggregationDb {
                 avg_settlement_latency_ms,
                 liquidity_depth_usd
             FROM corridor_metrics_hourly
-            WHERE hour_bucket >= ? AND hour_bucket <= ?
+            WHERE hour_bucket >= ? AND hour_bucket <= ? AND granularity = ?
             ORDER BY hour_bucket ASC
             "#,
         )
         .bind(start_time.to_rfc3339())
         .bind(end_time.to_rfc3339())
+        .bind(granularity.as_str())
         .fetch_all(&self.pool)
         .await
         .context("Failed to fetch hourly metrics by timerange")?;
@@ -333,10 +445,70 @@ struct PaymentRecordRow {
     asset_type: String,
     asset_code: Option<String>,
     asset_issuer: Option<String>,
+    source_asset_code: Option<String>,
+    source_asset_issuer: Option<String>,
     amount: f64,
     created_at: String,
 }
 
+impl PaymentRecordRow {
+    fn into_record(self) -> Option<crate::models::corridor::PaymentRecord> {
+        let timestamp = DateTime::parse_from_rfc3339(&self.created_at)
+            .ok()?
+            .with_timezone(&Utc);
+
+        // For now, assume all payments are successful
+        // In a real system, you'd have a status field
+        let successful = true;
+
+        let destination_asset_code = self.asset_code.unwrap_or_else(|| "XLM".to_string());
+        let destination_asset_issuer = self.asset_issuer.unwrap_or_else(|| "native".to_string());
+        // Plain payments send the asset they deliver
+        let (source_asset_code, source_asset_issuer) =
+            match (self.source_asset_code, self.source_asset_issuer) {
+                (Some(code), Some(issuer)) => (code, issuer),
+                _ => (
+                    destination_asset_code.clone(),
+                    destination_asset_issuer.clone(),
+                ),
+            };
+
+        Some(crate::models::corridor::PaymentRecord {
+            id: payment_uuid(&self.id),
+            source_asset_code,
+            source_asset_issuer,
+            destination_asset_code,
+            destination_asset_issuer,
+            amount: self.amount,
+            successful,
+            timestamp,
+            submission_time: None,
+            confirmation_time: None,
+        })
+    }
+}
+
+/// Stable UUID for a stored payment id; Horizon ids are numeric operation ids
+fn payment_uuid(id: &str) -> uuid::Uuid {
+    uuid::Uuid::parse_str(id).unwrap_or_else(|_| {
+        use sha2::{Digest, Sha256};
+        let digest = Sha256::digest(id.as_bytes());
+        uuid::Uuid::from_slice(&digest[..16]).expect("16-byte slice")
+    })
+}
+
+#[derive(sqlx::FromRow)]
+struct DayBucketTotalsRow {
+    asset_a_code: String,
+    asset_a_issuer: String,
+    asset_b_code: String,
+    asset_b_issuer: String,
+    total_transactions: i64,
+    successful_transactions: i64,
+    failed_transactions: i64,
+    volume_usd: f64,
+}
+
 #[derive(sqlx::FromRow)]
 struct HourlyCorridorMetricsRow {
     id: String,
@@ -355,3 +527,76 @@ struct HourlyCorridorMetricsRow {
     avg_settlement_latency_ms: Option<i32>,
     liquidity_depth_usd: f64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    fn delta(successful: i64, failed: i64, hour_bucket: DateTime<Utc>) -> HourlyCorridorMetrics {
+        let total = successful + failed;
+        HourlyCorridorMetrics {
+            id: uuid::Uuid::new_v4().to_string(),
+            corridor_key: "USDC:GISSUER->XLM:native".to_string(),
+            asset_a_code: "USDC".to_string(),
+            asset_a_issuer: "GISSUER".to_string(),
+            asset_b_code: "XLM".to_string(),
+            asset_b_issuer: "native".to_string(),
+            hour_bucket,
+            total_transactions: total,
+            successful_transactions: successful,
+            failed_transactions: failed,
+            success_rate: successful as f64 * 100.0 / total as f64,
+            volume_usd: total as f64,
+            avg_slippage_bps: 0.0,
+            avg_settlement_latency_ms: None,
+            liquidity_depth_usd: 0.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_deltas_are_summed() {
+        // A file database so the two workers use separate connections
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("agg.db").display());
+        let pool = SqlitePoolOptions::new()
+            .max_connections(4)
+            .connect(&url)
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let db = AggregationDb::new(pool.clone());
+
+        let hour = Utc::now()
+            .date_naive()
+            .and_hms_opt(10, 0, 0)
+            .unwrap()
+            .and_utc();
+        let worker = |successful, failed| {
+            let db = AggregationDb::new(pool.clone());
+            async move {
+                for _ in 0..20 {
+                    db.upsert_corridor_metric_bucket(
+                        &delta(successful, failed, hour),
+                        BucketGranularity::Hour,
+                    )
+                    .await
+                    .unwrap();
+                }
+            }
+        };
+        tokio::join!(worker(3, 1), worker(1, 1));
+
+        let stored = db
+            .fetch_corridor_metric_buckets(hour, hour, BucketGranularity::Hour)
+            .await
+            .unwrap();
+        assert_eq!(stored.len(), 1);
+        let stored = &stored[0];
+        assert_eq!(stored.total_transactions, 20 * 4 + 20 * 2);
+        assert_eq!(stored.successful_transactions, 20 * 3 + 20);
+        assert_eq!(stored.failed_transactions, 20 + 20);
+        assert!((stored.volume_usd - 120.0).abs() < 1e-9);
+        assert!((stored.success_rate - 80.0 * 100.0 / 120.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,425 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, BoxStream, StreamExt};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::db::aggregates::{
+    group_daily_totals, sort_corridor_metrics, summarize_corridor_metrics, CorridorDailyTotals,
+    CorridorMetricsFilter, LatestCorridorMetrics,
+};
+use crate::models::corridor::{Corridor, CorridorNetworkSummary};
+use crate::models::{Anchor, AnchorFilter, Asset, SortBy, SortOrder};
+
+/// Storage operations used by the cached list handlers
+///
+/// Implemented by [`Database`] and by [`InMemoryDatabase`], which lets handler
+/// tests run without a database file.
+#[async_trait]
+pub trait DatabaseBackend: Send + Sync {
+    async fn list_anchors(
+        &self,
+        filter: AnchorFilter,
+        sort_by: SortBy,
+        order: SortOrder,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Anchor>>;
+
+    /// Number of rows [`DatabaseBackend::list_anchors`] pages through
+    async fn count_anchors(&self, filter: AnchorFilter) -> Result<i64>;
+
+    async fn get_assets_by_anchor(&self, anchor_id: Uuid) -> Result<Vec<Asset>>;
+
+    async fn list_corridor_metrics(
+        &self,
+        filter: &CorridorMetricsFilter,
+        sort_by: SortBy,
+        order: SortOrder,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<LatestCorridorMetrics>>;
+
+    /// Number of rows [`DatabaseBackend::list_corridor_metrics`] pages through
+    async fn count_corridor_metrics(&self, filter: &CorridorMetricsFilter) -> Result<i64>;
+
+    async fn get_latest_corridor_metrics_by_keys(
+        &self,
+        corridor_keys: &[String],
+    ) -> Result<Vec<LatestCorridorMetrics>>;
+
+    /// Corridors sharing an asset with `corridor`, highest volume first
+    async fn related_corridors(
+        &self,
+        corridor: &Corridor,
+        limit: i64,
+    ) -> Result<Vec<LatestCorridorMetrics>>;
+
+    async fn get_corridor_daily_totals(
+        &self,
+        corridor_key: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<CorridorDailyTotals>>;
+
+    /// Daily totals of several corridors at once, newest first per key
+    async fn get_daily_totals_by_keys(
+        &self,
+        corridor_keys: &[String],
+        since: DateTime<Utc>,
+    ) -> Result<HashMap<String, Vec<CorridorDailyTotals>>>;
+
+    async fn corridor_summary(&self, health_threshold: f64) -> Result<CorridorNetworkSummary>;
+
+    /// Every latest corridor metrics row matching `filter`, ordered by key
+    fn stream_corridor_metrics(
+        &self,
+        filter: CorridorMetricsFilter,
+    ) -> BoxStream<'static, Result<LatestCorridorMetrics>>;
+
+    /// Daily history of `corridor_key` dated from `from` to `to`, oldest first
+    fn stream_corridor_history(
+        &self,
+        corridor_key: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> BoxStream<'static, Result<CorridorDailyTotals>>;
+}
+
+#[async_trait]
+impl DatabaseBackend for Database {
+    async fn list_anchors(
+        &self,
+        filter: AnchorFilter,
+        sort_by: SortBy,
+        order: SortOrder,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Anchor>> {
+        Database::list_anchors_sorted(self, filter, sort_by, order, limit, offset).await
+    }
+
+    async fn count_anchors(&self, filter: AnchorFilter) -> Result<i64> {
+        Database::count_anchors(self, filter).await
+    }
+
+    async fn get_assets_by_anchor(&self, anchor_id: Uuid) -> Result<Vec<Asset>> {
+        Database::get_assets_by_anchor(self, anchor_id).await
+    }
+
+    async fn list_corridor_metrics(
+        &self,
+        filter: &CorridorMetricsFilter,
+        sort_by: SortBy,
+        order: SortOrder,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<LatestCorridorMetrics>> {
+        self.corridor_aggregates()
+            .list_corridor_metrics(filter, sort_by, order, limit, offset)
+            .await
+    }
+
+    async fn count_corridor_metrics(&self, filter: &CorridorMetricsFilter) -> Result<i64> {
+        self.corridor_aggregates()
+            .count_corridor_metrics(filter)
+            .await
+    }
+
+    async fn get_latest_corridor_metrics_by_keys(
+        &self,
+        corridor_keys: &[String],
+    ) -> Result<Vec<LatestCorridorMetrics>> {
+        self.corridor_aggregates()
+            .get_latest_corridor_metrics_by_keys(corridor_keys)
+            .await
+    }
+
+    async fn related_corridors(
+        &self,
+        corridor: &Corridor,
+        limit: i64,
+    ) -> Result<Vec<LatestCorridorMetrics>> {
+        Database::related_corridors(self, corridor, limit).await
+    }
+
+    async fn get_corridor_daily_totals(
+        &self,
+        corridor_key: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<CorridorDailyTotals>> {
+        self.corridor_aggregates()
+            .get_corridor_daily_totals(corridor_key, since)
+            .await
+    }
+
+    async fn get_daily_totals_by_keys(
+        &self,
+        corridor_keys: &[String],
+        since: DateTime<Utc>,
+    ) -> Result<HashMap<String, Vec<CorridorDailyTotals>>> {
+        self.corridor_aggregates()
+            .get_daily_totals_by_keys(corridor_keys, since)
+            .await
+    }
+
+    async fn corridor_summary(&self, health_threshold: f64) -> Result<CorridorNetworkSummary> {
+        Database::corridor_summary(self, health_threshold).await
+    }
+
+    fn stream_corridor_metrics(
+        &self,
+        filter: CorridorMetricsFilter,
+    ) -> BoxStream<'static, Result<LatestCorridorMetrics>> {
+        self.corridor_aggregates().stream_corridor_metrics(filter)
+    }
+
+    fn stream_corridor_history(
+        &self,
+        corridor_key: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> BoxStream<'static, Result<CorridorDailyTotals>> {
+        self.corridor_aggregates()
+            .stream_corridor_history(corridor_key.to_string(), from, to)
+    }
+}
+
+/// In-memory [`DatabaseBackend`] for tests
+///
+/// Ordering and filtering follow the SQL queries of [`Database`].
+#[derive(Default)]
+pub struct InMemoryDatabase {
+    anchors: RwLock<Vec<Anchor>>,
+    assets: RwLock<HashMap<String, Vec<Asset>>>,
+    corridor_metrics: RwLock<Vec<LatestCorridorMetrics>>,
+    corridor_history: RwLock<Vec<CorridorDailyTotals>>,
+}
+
+impl InMemoryDatabase {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert_anchor(&self, anchor: Anchor) {
+        self.anchors.write().unwrap().push(anchor);
+    }
+
+    pub fn insert_asset(&self, asset: Asset) {
+        self.assets
+            .write()
+            .unwrap()
+            .entry(asset.anchor_id.clone())
+            .or_default()
+            .push(asset);
+    }
+
+    pub fn insert_corridor_metrics(&self, metrics: LatestCorridorMetrics) {
+        self.corridor_metrics.write().unwrap().push(metrics);
+    }
+
+    pub fn insert_corridor_daily_totals(&self, totals: CorridorDailyTotals) {
+        self.corridor_history.write().unwrap().push(totals);
+    }
+}
+
+fn page<T>(items: Vec<T>, limit: i64, offset: i64) -> Vec<T> {
+    items
+        .into_iter()
+        .skip(offset.max(0) as usize)
+        .take(limit.max(0) as usize)
+        .collect()
+}
+
+#[async_trait]
+impl DatabaseBackend for InMemoryDatabase {
+    async fn list_anchors(
+        &self,
+        filter: AnchorFilter,
+        sort_by: SortBy,
+        order: SortOrder,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Anchor>> {
+        let mut anchors: Vec<_> = self
+            .anchors
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|a| filter.matches(a.reliability_score))
+            .cloned()
+            .collect();
+        anchors.sort_by(|a, b| {
+            let ordering = match sort_by {
+                SortBy::SuccessRate => a.reliability_score.total_cmp(&b.reliability_score),
+                SortBy::Volume => a.total_volume_usd.total_cmp(&b.total_volume_usd),
+                SortBy::LastUpdated => a.updated_at.cmp(&b.updated_at),
+                SortBy::Name => a.name.cmp(&b.name),
+            };
+            order.apply(ordering).then(b.updated_at.cmp(&a.updated_at))
+        });
+        Ok(page(anchors, limit, offset))
+    }
+
+    async fn count_anchors(&self, filter: AnchorFilter) -> Result<i64> {
+        Ok(self
+            .anchors
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|a| filter.matches(a.reliability_score))
+            .count() as i64)
+    }
+
+    async fn get_assets_by_anchor(&self, anchor_id: Uuid) -> Result<Vec<Asset>> {
+        Ok(self
+            .assets
+            .read()
+            .unwrap()
+            .get(&anchor_id.to_string())
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn list_corridor_metrics(
+        &self,
+        filter: &CorridorMetricsFilter,
+        sort_by: SortBy,
+        order: SortOrder,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<LatestCorridorMetrics>> {
+        let mut metrics: Vec<_> = self
+            .corridor_metrics
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|m| filter.matches(m))
+            .cloned()
+            .collect();
+        sort_corridor_metrics(&mut metrics, sort_by, order);
+        Ok(page(metrics, limit, offset))
+    }
+
+    async fn count_corridor_metrics(&self, filter: &CorridorMetricsFilter) -> Result<i64> {
+        Ok(self
+            .corridor_metrics
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|m| filter.matches(m))
+            .count() as i64)
+    }
+
+    async fn get_latest_corridor_metrics_by_keys(
+        &self,
+        corridor_keys: &[String],
+    ) -> Result<Vec<LatestCorridorMetrics>> {
+        Ok(self
+            .corridor_metrics
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|m| corridor_keys.contains(&m.corridor_key))
+            .cloned()
+            .collect())
+    }
+
+    async fn related_corridors(
+        &self,
+        corridor: &Corridor,
+        limit: i64,
+    ) -> Result<Vec<LatestCorridorMetrics>> {
+        let mut peers: Vec<_> = self
+            .corridor_metrics
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|m| m.is_peer_of(corridor))
+            .cloned()
+            .collect();
+        peers.sort_by(|a, b| {
+            b.total_volume_usd
+                .total_cmp(&a.total_volume_usd)
+                .then_with(|| a.corridor_key.cmp(&b.corridor_key))
+        });
+        Ok(page(peers, limit, 0))
+    }
+
+    async fn get_corridor_daily_totals(
+        &self,
+        corridor_key: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<CorridorDailyTotals>> {
+        let mut totals: Vec<_> = self
+            .corridor_history
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|t| t.corridor_key == corridor_key && t.date >= since)
+            .cloned()
+            .collect();
+        totals.sort_by_key(|t| std::cmp::Reverse(t.date));
+        Ok(totals)
+    }
+
+    async fn get_daily_totals_by_keys(
+        &self,
+        corridor_keys: &[String],
+        since: DateTime<Utc>,
+    ) -> Result<HashMap<String, Vec<CorridorDailyTotals>>> {
+        let mut totals: Vec<_> = self
+            .corridor_history
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|t| corridor_keys.contains(&t.corridor_key) && t.date >= since)
+            .cloned()
+            .collect();
+        totals.sort_by_key(|t| std::cmp::Reverse(t.date));
+        Ok(group_daily_totals(totals))
+    }
+
+    async fn corridor_summary(&self, health_threshold: f64) -> Result<CorridorNetworkSummary> {
+        Ok(summarize_corridor_metrics(
+            &self.corridor_metrics.read().unwrap(),
+            health_threshold,
+        ))
+    }
+
+    fn stream_corridor_metrics(
+        &self,
+        filter: CorridorMetricsFilter,
+    ) -> BoxStream<'static, Result<LatestCorridorMetrics>> {
+        let mut metrics: Vec<_> = self
+            .corridor_metrics
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|m| filter.matches(m))
+            .cloned()
+            .collect();
+        sort_corridor_metrics(&mut metrics, SortBy::Name, SortOrder::Asc);
+        stream::iter(metrics.into_iter().map(Ok)).boxed()
+    }
+
+    fn stream_corridor_history(
+        &self,
+        corridor_key: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> BoxStream<'static, Result<CorridorDailyTotals>> {
+        let mut history: Vec<_> = self
+            .corridor_history
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|t| t.corridor_key == corridor_key && t.date >= from && t.date <= to)
+            .cloned()
+            .collect();
+        history.sort_by_key(|t| t.date);
+        stream::iter(history.into_iter().map(Ok)).boxed()
+    }
+}
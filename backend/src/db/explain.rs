@@ -0,0 +1,134 @@
+//! Query plans for a fixed set of named queries
+//!
+//! Operators can see how SQLite executes the queries behind the busiest
+//! endpoints without being able to run arbitrary SQL: only the names in
+//! [`ExplainQuery::ALL`] are accepted, and each maps to the same SQL constant
+//! the endpoint itself runs.
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::fmt;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+use crate::db::aggregates::{
+    CORRIDOR_DAILY_TOTALS_SQL, CORRIDOR_METRICS_AT_SQL, TOP_CORRIDORS_BY_VOLUME_SQL,
+};
+
+/// A query that may be explained
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExplainQuery {
+    /// Highest-volume corridors for a day, the related corridors on
+    /// `GET /api/corridors/:corridor_key`
+    TopCorridors,
+    /// Latest metrics at or before a timestamp, `GET /api/corridors/:corridor_key/at`
+    CorridorMetricsAt,
+    /// Daily totals behind `GET /api/corridors/:corridor_key/rollup`
+    CorridorDailyTotals,
+}
+
+impl ExplainQuery {
+    pub const ALL: [ExplainQuery; 3] = [
+        ExplainQuery::TopCorridors,
+        ExplainQuery::CorridorMetricsAt,
+        ExplainQuery::CorridorDailyTotals,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ExplainQuery::TopCorridors => "top_corridors",
+            ExplainQuery::CorridorMetricsAt => "corridor_metrics_at",
+            ExplainQuery::CorridorDailyTotals => "corridor_daily_totals",
+        }
+    }
+
+    pub fn sql(self) -> &'static str {
+        match self {
+            ExplainQuery::TopCorridors => TOP_CORRIDORS_BY_VOLUME_SQL,
+            ExplainQuery::CorridorMetricsAt => CORRIDOR_METRICS_AT_SQL,
+            ExplainQuery::CorridorDailyTotals => CORRIDOR_DAILY_TOTALS_SQL,
+        }
+    }
+}
+
+impl fmt::Display for ExplainQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ExplainQuery {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|query| query.name() == s)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|query| query.name()).collect();
+                format!(
+                    "Unknown query '{}'; expected one of: {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// One node of SQLite's `EXPLAIN QUERY PLAN` output
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema, sqlx::FromRow)]
+pub struct PlanStep {
+    pub id: i64,
+    /// `id` of the enclosing step, 0 at the top level
+    pub parent: i64,
+    /// e.g. `SEARCH corridor_metrics USING INDEX ...`
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct QueryPlan {
+    pub query: String,
+    pub sql: String,
+    pub steps: Vec<PlanStep>,
+}
+
+/// Plan `query` with representative parameters
+///
+/// The statement is only planned, never run, so the parameter values don't
+/// affect the result beyond their types.
+pub async fn explain(pool: &SqlitePool, query: ExplainQuery) -> Result<QueryPlan> {
+    let sql = query.sql();
+    let statement = format!("EXPLAIN QUERY PLAN {}", sql);
+    let now = Utc::now();
+    let plan = sqlx::query_as::<_, PlanStep>(&statement);
+    let plan = match query {
+        ExplainQuery::TopCorridors => plan.bind(now - Duration::days(1)).bind(now).bind(10i64),
+        ExplainQuery::CorridorMetricsAt => plan.bind("USDC:issuer->EURC:issuer").bind(now),
+        ExplainQuery::CorridorDailyTotals => plan
+            .bind("USDC:issuer->EURC:issuer")
+            .bind(now - Duration::days(30)),
+    };
+    let steps = plan.fetch_all(pool).await?;
+
+    Ok(QueryPlan {
+        query: query.name().to_string(),
+        sql: sql.trim().to_string(),
+        steps,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_named_queries_parse() {
+        for query in ExplainQuery::ALL {
+            assert_eq!(query.name().parse::<ExplainQuery>(), Ok(query));
+        }
+        let err = "SELECT * FROM anchors".parse::<ExplainQuery>().unwrap_err();
+        assert!(err.contains("top_corridors"));
+    }
+}
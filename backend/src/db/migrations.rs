@@ -0,0 +1,140 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::migrate::Migrator;
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+/// Migrations embedded in this build
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// One migration as recorded in `_sqlx_migrations`, compared with this build
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    /// When the migration ran, `None` if it is still pending
+    pub installed_on: Option<DateTime<Utc>>,
+    /// Whether the recorded run completed
+    pub success: bool,
+    /// `false` when the applied SQL differs from the embedded file; `None`
+    /// when the migration is pending or unknown to this build
+    pub checksum_matches: Option<bool>,
+}
+
+#[derive(sqlx::FromRow)]
+struct AppliedMigration {
+    version: i64,
+    description: String,
+    installed_on: DateTime<Utc>,
+    success: bool,
+    checksum: Vec<u8>,
+}
+
+/// Applied and pending migrations, by version
+///
+/// Applied rows are checked against `migrator`'s embedded checksums, so a
+/// migration edited after it ran shows `checksum_matches: Some(false)`.
+pub async fn migration_status(
+    pool: &SqlitePool,
+    migrator: &Migrator,
+) -> Result<Vec<MigrationStatus>> {
+    let applied = sqlx::query_as::<_, AppliedMigration>(
+        r#"
+        SELECT version, description, installed_on, success, checksum
+        FROM _sqlx_migrations
+        ORDER BY version
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let embedded: BTreeMap<i64, _> = migrator
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .map(|migration| (migration.version, migration))
+        .collect();
+
+    let mut statuses: BTreeMap<i64, MigrationStatus> = applied
+        .into_iter()
+        .map(|row| {
+            let checksum_matches = embedded
+                .get(&row.version)
+                .map(|migration| *migration.checksum == *row.checksum);
+            let status = MigrationStatus {
+                version: row.version,
+                description: row.description,
+                installed_on: Some(row.installed_on),
+                success: row.success,
+                checksum_matches,
+            };
+            (row.version, status)
+        })
+        .collect();
+
+    for (version, migration) in embedded {
+        statuses.entry(version).or_insert_with(|| MigrationStatus {
+            version,
+            description: migration.description.to_string(),
+            installed_on: None,
+            success: false,
+            checksum_matches: None,
+        });
+    }
+
+    Ok(statuses.into_values().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[tokio::test]
+    async fn test_flags_checksum_mismatch() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::query(
+            r#"
+            CREATE TABLE _sqlx_migrations (
+                version BIGINT PRIMARY KEY,
+                description TEXT NOT NULL,
+                installed_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                success BOOLEAN NOT NULL,
+                checksum BLOB NOT NULL,
+                execution_time BIGINT NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let first = MIGRATOR.iter().next().unwrap();
+        for (version, checksum) in [(1, first.checksum.to_vec()), (2, vec![0u8; 48])] {
+            sqlx::query(
+                "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time) VALUES ($1, 'fixture', TRUE, $2, 0)",
+            )
+            .bind(version)
+            .bind(checksum)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let statuses = migration_status(&pool, &MIGRATOR).await.unwrap();
+
+        assert_eq!(statuses.len(), MIGRATOR.iter().count());
+        assert_eq!(statuses[0].checksum_matches, Some(true));
+        assert!(statuses[0].installed_on.is_some());
+        assert_eq!(statuses[1].version, 2);
+        assert_eq!(statuses[1].checksum_matches, Some(false));
+        // Embedded migrations with no row are pending
+        assert_eq!(statuses[2].installed_on, None);
+        assert_eq!(statuses[2].checksum_matches, None);
+    }
+}
@@ -1,3 +1,7 @@
 pub mod aggregates;
 pub mod aggregation;
+pub mod backend;
+pub mod explain;
+pub mod migrations;
 pub mod schema;
+pub mod slow_query;
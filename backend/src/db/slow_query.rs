@@ -0,0 +1,140 @@
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tracing::warn;
+use utoipa::ToSchema;
+
+use crate::ingestion::config::parse_var;
+
+/// Queries slower than this are logged when `SLOW_QUERY_THRESHOLD_MS` is unset
+pub const DEFAULT_SLOW_QUERY_THRESHOLD: Duration = Duration::from_millis(500);
+
+/// Times database queries and counts the ones over a threshold
+///
+/// Only the method name and duration are logged; SQL text and bound
+/// parameters may hold account ids or other sensitive values.
+#[derive(Debug)]
+pub struct SlowQueryLog {
+    threshold: Duration,
+    slow_queries: AtomicU64,
+}
+
+/// Counters reported by `GET /api/metrics/database`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+pub struct SlowQueryStats {
+    pub slow_query_threshold_ms: u64,
+    pub slow_queries: u64,
+}
+
+impl SlowQueryLog {
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            slow_queries: AtomicU64::new(0),
+        }
+    }
+
+    /// Create from `SLOW_QUERY_THRESHOLD_MS`
+    pub fn from_env() -> anyhow::Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> anyhow::Result<Self> {
+        let threshold_ms = parse_var(
+            &lookup,
+            "SLOW_QUERY_THRESHOLD_MS",
+            DEFAULT_SLOW_QUERY_THRESHOLD.as_millis() as u64,
+        )?;
+        Ok(Self::new(Duration::from_millis(threshold_ms)))
+    }
+
+    /// Start timing `method`; the query is recorded when the returned timer drops
+    pub fn start(&self, method: &'static str) -> QueryTimer<'_> {
+        QueryTimer {
+            log: self,
+            method,
+            started: Instant::now(),
+        }
+    }
+
+    fn record(&self, method: &'static str, elapsed: Duration) {
+        if elapsed <= self.threshold {
+            return;
+        }
+        self.slow_queries.fetch_add(1, Ordering::Relaxed);
+        warn!(
+            method,
+            duration_ms = elapsed.as_millis() as u64,
+            "Slow database query: {} took {} ms",
+            method,
+            elapsed.as_millis()
+        );
+    }
+
+    pub fn stats(&self) -> SlowQueryStats {
+        SlowQueryStats {
+            slow_query_threshold_ms: self.threshold.as_millis() as u64,
+            slow_queries: self.slow_queries.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Guard returned by [`SlowQueryLog::start`]
+///
+/// Timing on drop covers every exit from a query method, including early
+/// returns through `?`.
+pub struct QueryTimer<'a> {
+    log: &'a SlowQueryLog,
+    method: &'static str,
+    started: Instant,
+}
+
+impl Drop for QueryTimer<'_> {
+    fn drop(&mut self) {
+        self.log.record(self.method, self.started.elapsed());
+    }
+}
+
+impl Default for SlowQueryLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_SLOW_QUERY_THRESHOLD)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_slow_query_increments_counter() {
+        let log = SlowQueryLog::new(Duration::from_millis(20));
+
+        drop(log.start("fast_query"));
+        assert_eq!(log.stats().slow_queries, 0);
+
+        {
+            let _timer = log.start("slow_query");
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert_eq!(
+            log.stats(),
+            SlowQueryStats {
+                slow_query_threshold_ms: 20,
+                slow_queries: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_threshold_from_env() {
+        let log = SlowQueryLog::from_lookup(|name| {
+            (name == "SLOW_QUERY_THRESHOLD_MS").then(|| "250".to_string())
+        })
+        .unwrap();
+        assert_eq!(log.stats().slow_query_threshold_ms, 250);
+
+        let default = SlowQueryLog::from_lookup(|_| None).unwrap();
+        assert_eq!(default.stats().slow_query_threshold_ms, 500);
+        assert!(SlowQueryLog::from_lookup(|_| Some("slowish".to_string())).is_err());
+    }
+}
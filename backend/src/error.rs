@@ -0,0 +1,279 @@
+//! Error type shared by the HTTP handlers
+//!
+//! Every variant serializes as
+//! `{ "error": { "code": "NOT_FOUND", "message": "...", "details": {} } }`.
+//! Codes are part of the API contract: add new ones rather than renaming.
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+pub type ApiResult<T> = Result<T, ApiError>;
+
+/// Codes reported for a unique or primary key violation: SQLite's extended
+/// `SQLITE_CONSTRAINT_UNIQUE` and `SQLITE_CONSTRAINT_PRIMARYKEY`, and the
+/// SQLSTATE `23505` other databases use
+const UNIQUE_VIOLATION_CODES: &[&str] = &["2067", "1555", "23505"];
+
+#[derive(Debug, Clone)]
+pub enum ApiError {
+    NotFound(String),
+    BadRequest(String),
+    Forbidden(String),
+    Conflict(String),
+    InternalError(String),
+}
+
+impl ApiError {
+    /// Stable machine-readable code for the variant
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::NotFound(_) => "NOT_FOUND",
+            ApiError::BadRequest(_) => "BAD_REQUEST",
+            ApiError::Forbidden(_) => "FORBIDDEN",
+            ApiError::Conflict(_) => "CONFLICT",
+            ApiError::InternalError(_) => "INTERNAL_ERROR",
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn into_body(self) -> ErrorBody {
+        let code = self.code();
+        let message = match self {
+            ApiError::NotFound(msg)
+            | ApiError::BadRequest(msg)
+            | ApiError::Forbidden(msg)
+            | ApiError::Conflict(msg)
+            | ApiError::InternalError(msg) => msg,
+        };
+
+        ErrorBody {
+            error: ErrorDetail {
+                code,
+                message,
+                details: serde_json::Value::Object(Default::default()),
+            },
+        }
+    }
+}
+
+/// Error body returned by `ApiError`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorBody {
+    pub error: ErrorDetail,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorDetail {
+    /// Machine-readable error code, e.g. `NOT_FOUND`
+    pub code: &'static str,
+    pub message: String,
+    #[schema(value_type = Object)]
+    pub details: serde_json::Value,
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiError::NotFound(msg)
+            | ApiError::BadRequest(msg)
+            | ApiError::Forbidden(msg)
+            | ApiError::Conflict(msg)
+            | ApiError::InternalError(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status(), Json(self.into_body())).into_response()
+    }
+}
+
+/// Database errors inside are classified as for `sqlx::Error`
+impl From<anyhow::Error> for ApiError {
+    fn from(err: anyhow::Error) -> Self {
+        match err.downcast_ref::<sqlx::Error>() {
+            Some(sqlx_err) => classify_sqlx_error(sqlx_err, err.to_string()),
+            None => ApiError::InternalError(err.to_string()),
+        }
+    }
+}
+
+/// Unique violations are 409s and missing rows 404s; anything else is a 500
+impl From<sqlx::Error> for ApiError {
+    fn from(err: sqlx::Error) -> Self {
+        let message = err.to_string();
+        classify_sqlx_error(&err, message)
+    }
+}
+
+fn classify_sqlx_error(err: &sqlx::Error, message: String) -> ApiError {
+    if matches!(err, sqlx::Error::RowNotFound) {
+        return ApiError::NotFound("Record not found".to_string());
+    }
+    let unique_violation = err
+        .as_database_error()
+        .and_then(|db_err| db_err.code())
+        .is_some_and(|code| UNIQUE_VIOLATION_CODES.contains(&code.as_ref()));
+    if unique_violation {
+        return ApiError::Conflict(message);
+    }
+    ApiError::InternalError(message)
+}
+
+impl From<redis::RedisError> for ApiError {
+    fn from(err: redis::RedisError) -> Self {
+        ApiError::InternalError(err.to_string())
+    }
+}
+
+/// Ids arrive as path parameters, so a malformed one is the caller's mistake
+impl From<uuid::Error> for ApiError {
+    fn from(err: uuid::Error) -> Self {
+        ApiError::BadRequest(format!("Invalid id: {}", err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn response_parts(err: ApiError) -> (StatusCode, serde_json::Value) {
+        let response = err.into_response();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_variants_serialize_code_and_status() {
+        let cases = [
+            (
+                ApiError::NotFound("Anchor not found".into()),
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+            ),
+            (
+                ApiError::BadRequest("limit must be positive".into()),
+                StatusCode::BAD_REQUEST,
+                "BAD_REQUEST",
+            ),
+            (
+                ApiError::Forbidden("Dev endpoints are disabled in production".into()),
+                StatusCode::FORBIDDEN,
+                "FORBIDDEN",
+            ),
+            (
+                ApiError::Conflict("Idempotency key reused".into()),
+                StatusCode::CONFLICT,
+                "CONFLICT",
+            ),
+            (
+                ApiError::InternalError("database is locked".into()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "INTERNAL_ERROR",
+            ),
+        ];
+
+        for (err, expected_status, expected_code) in cases {
+            assert_eq!(err.code(), expected_code);
+            let (status, body) = response_parts(err).await;
+            assert_eq!(status, expected_status);
+            assert_eq!(body["error"]["code"], expected_code);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_from_conversions_map_to_status() {
+        let anyhow_err: ApiError = anyhow::anyhow!("boom").into();
+        assert_eq!(anyhow_err.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let redis_err: ApiError =
+            redis::RedisError::from((redis::ErrorKind::IoError, "connection dropped")).into();
+        assert_eq!(redis_err.code(), "INTERNAL_ERROR");
+        let (status, _) = response_parts(redis_err).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+
+        let uuid_err: ApiError = uuid::Uuid::parse_str("not-a-uuid").unwrap_err().into();
+        assert_eq!(uuid_err.code(), "BAD_REQUEST");
+        let (status, body) = response_parts(uuid_err).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Invalid id"));
+    }
+
+    #[tokio::test]
+    async fn test_sqlx_errors_are_classified() {
+        let pool = sqlx::SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE TABLE anchors (id TEXT PRIMARY KEY, account TEXT UNIQUE)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let insert = |id: &'static str, account: &'static str| {
+            sqlx::query("INSERT INTO anchors (id, account) VALUES ($1, $2)")
+                .bind(id)
+                .bind(account)
+                .execute(&pool)
+        };
+        insert("a1", "GA").await.unwrap();
+
+        let duplicate: ApiError = insert("a2", "GA").await.unwrap_err().into();
+        assert_eq!(duplicate.code(), "CONFLICT");
+        assert!(duplicate.to_string().contains("UNIQUE constraint failed"));
+        let duplicate_key: ApiError = insert("a1", "GB").await.unwrap_err().into();
+        assert_eq!(duplicate_key.status(), StatusCode::CONFLICT);
+
+        // Database methods return anyhow errors wrapping the sqlx one
+        let wrapped = anyhow::Error::new(insert("a3", "GA").await.unwrap_err())
+            .context("Failed to create anchor");
+        assert_eq!(ApiError::from(wrapped).status(), StatusCode::CONFLICT);
+
+        let missing: ApiError = sqlx::Error::RowNotFound.into();
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+        let missing: ApiError = anyhow::Error::new(sqlx::Error::RowNotFound).into();
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+
+        let bad_sql: ApiError = sqlx::query("SELECT * FROM nowhere")
+            .execute(&pool)
+            .await
+            .unwrap_err()
+            .into();
+        assert_eq!(bad_sql.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let timed_out: ApiError = sqlx::Error::PoolTimedOut.into();
+        assert_eq!(timed_out.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn test_error_body_shape() {
+        let (_, body) = response_parts(ApiError::NotFound("Anchor not found".into())).await;
+
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "error": {
+                    "code": "NOT_FOUND",
+                    "message": "Anchor not found",
+                    "details": {}
+                }
+            })
+        );
+    }
+}
@@ -1,81 +1,143 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::HeaderMap,
     response::IntoResponse,
-    Json,
+    Extension, Json,
 };
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::future::Future;
+use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::broadcast::{broadcast_anchor_update, broadcast_corridor_update};
-use crate::models::corridor::Corridor;
-use crate::models::{AnchorDetailResponse, CreateAnchorRequest, CreateCorridorRequest};
+use crate::analytics::health::StatusThresholds;
+use crate::api::pagination::Paginated;
+use crate::broadcast::{
+    broadcast_anchor_update, broadcast_corridor_metrics, broadcast_corridor_update,
+};
+use crate::cache::keys;
+use crate::cache_middleware::CacheAware;
+use crate::error::{ApiError, ApiResult};
+use crate::idempotency::{idempotency_key, IdempotencyState, IdempotencyStore, Idempotent};
+use crate::models::corridor::{parse_corridor_key, Corridor};
+use crate::models::{
+    AnchorAssetVolume, AnchorDetailResponse, AnchorMetricsPatch, CreateAnchorRequest,
+    CreateCorridorRequest,
+};
 use crate::services::analytics::{compute_corridor_metrics, CorridorTransaction};
+use crate::services::webhook::detect_status_transition;
 use crate::state::AppState;
 
-pub type ApiResult<T> = Result<T, ApiError>;
+#[derive(Debug, Deserialize)]
+pub struct ListAnchorsQuery {
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
 
-#[derive(Debug)]
-pub enum ApiError {
-    NotFound(String),
-    BadRequest(String),
-    InternalError(String),
+fn default_limit() -> i64 {
+    50
 }
 
-impl IntoResponse for ApiError {
-    fn into_response(self) -> axum::response::Response {
-        let (status, message) = match self {
-            ApiError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
-            ApiError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
-            ApiError::InternalError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-        };
+pub type ListAnchorsResponse = Paginated<crate::models::Anchor>;
 
-        (status, Json(serde_json::json!({ "error": message }))).into_response()
-    }
+#[derive(Debug, Deserialize)]
+pub struct ListCorridorsQuery {
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
 }
 
-impl From<anyhow::Error> for ApiError {
-    fn from(err: anyhow::Error) -> Self {
-        ApiError::InternalError(err.to_string())
-    }
-}
+pub type ListCorridorsResponse = Paginated<Corridor>;
 
-impl From<sqlx::Error> for ApiError {
-    fn from(err: sqlx::Error) -> Self {
-        ApiError::InternalError(err.to_string())
-    }
-}
+/// Maximum number of transactions returned per corridor transactions request
+const MAX_CORRIDOR_TRANSACTIONS: i64 = 200;
 
 #[derive(Debug, Deserialize)]
-pub struct ListAnchorsQuery {
+pub struct CorridorTransactionsQuery {
     #[serde(default = "default_limit")]
     pub limit: i64,
     #[serde(default)]
     pub offset: i64,
+    /// Only return successful (`true`) or failed (`false`) transactions
+    pub successful: Option<bool>,
 }
 
-fn default_limit() -> i64 {
-    50
-}
+impl CorridorTransactionsQuery {
+    fn clamped_limit(&self) -> i64 {
+        self.limit.clamp(1, MAX_CORRIDOR_TRANSACTIONS)
+    }
 
-#[derive(Debug, Serialize)]
-pub struct ListAnchorsResponse {
-    pub anchors: Vec<crate::models::Anchor>,
-    pub total: usize,
+    fn clamped_offset(&self) -> i64 {
+        self.offset.max(0)
+    }
 }
 
 #[derive(Debug, Deserialize)]
-pub struct ListCorridorsQuery {
-    #[serde(default = "default_limit")]
+pub struct CorridorAtQuery {
+    /// RFC 3339 instant, e.g. `2026-03-11T12:00:00Z`
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CorridorTransactionsResponse {
+    pub corridor_key: String,
+    pub transactions: Vec<crate::models::CorridorTransactionRecord>,
     pub limit: i64,
-    #[serde(default)]
     pub offset: i64,
 }
 
-#[derive(Debug, Serialize)]
-pub struct ListCorridorsResponse {
-    pub corridors: Vec<Corridor>,
-    pub total: usize,
+/// Run `create` at most once per `Idempotency-Key` within `scope`
+///
+/// A repeated key returns the stored response of the first request, marked as
+/// a replay. Without the header the request is handled normally.
+async fn with_idempotency<T, F, Fut>(
+    store: &IdempotencyStore,
+    scope: &str,
+    headers: &HeaderMap,
+    create: F,
+) -> ApiResult<Idempotent<T>>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = ApiResult<T>>,
+{
+    let Some(key) = idempotency_key(headers).map_err(ApiError::BadRequest)? else {
+        return Ok(Idempotent {
+            value: create().await?,
+            replayed: false,
+        });
+    };
+
+    match store.begin::<T>(scope, &key).await? {
+        IdempotencyState::New => {}
+        IdempotencyState::InProgress => {
+            return Err(ApiError::Conflict(
+                "A request with this Idempotency-Key is still being processed".to_string(),
+            ))
+        }
+        IdempotencyState::Completed(response) => {
+            return Ok(Idempotent {
+                value: response,
+                replayed: true,
+            })
+        }
+    }
+
+    match create().await {
+        Ok(response) => {
+            store.complete(scope, &key, &response).await?;
+            Ok(Idempotent {
+                value: response,
+                replayed: false,
+            })
+        }
+        Err(e) => {
+            store.abandon(scope, &key).await;
+            Err(e)
+        }
+    }
 }
 
 /// GET /api/anchors - List all anchors with their metrics
@@ -84,18 +146,28 @@ pub async fn list_anchors(
     Query(params): Query<ListAnchorsQuery>,
 ) -> ApiResult<Json<ListAnchorsResponse>> {
     let anchors = app_state.db.list_anchors(params.limit, params.offset).await?;
-    let total = anchors.len();
 
-    Ok(Json(ListAnchorsResponse { anchors, total }))
+    Ok(Json(Paginated::from_page(anchors, params.limit, params.offset)))
 }
 
 /// GET /api/anchors/:id - Get detailed anchor information
+#[utoipa::path(
+    get,
+    path = "/api/anchors/{id}",
+    tag = "anchors",
+    params(("id" = Uuid, Path, description = "Anchor id")),
+    responses(
+        (status = 200, description = "Anchor detail with health breakdown", body = AnchorDetailResponse),
+        (status = 404, description = "Anchor not found", body = crate::api::openapi::ErrorBody)
+    )
+)]
 pub async fn get_anchor(
     State(app_state): State<AppState>,
+    Extension(thresholds): Extension<Arc<StatusThresholds>>,
     Path(id): Path<Uuid>,
 ) -> ApiResult<Json<AnchorDetailResponse>> {
     let anchor_detail = app_state.db
-        .get_anchor_detail(id)
+        .get_anchor_detail(id, &thresholds)
         .await?
         .ok_or_else(|| ApiError::NotFound(format!("Anchor with id {} not found", id)))?;
 
@@ -121,10 +193,14 @@ pub async fn get_anchor_by_account(
 }
 
 /// POST /api/anchors - Create a new anchor
+///
+/// Honors an `Idempotency-Key` header so retried requests create one anchor.
 pub async fn create_anchor(
     State(app_state): State<AppState>,
+    Extension(idempotency): Extension<Arc<IdempotencyStore>>,
+    headers: HeaderMap,
     Json(req): Json<CreateAnchorRequest>,
-) -> ApiResult<Json<crate::models::Anchor>> {
+) -> ApiResult<Idempotent<Json<crate::models::Anchor>>> {
     if req.name.is_empty() {
         return Err(ApiError::BadRequest("Name cannot be empty".to_string()));
     }
@@ -135,12 +211,17 @@ pub async fn create_anchor(
         ));
     }
 
-    let anchor = app_state.db.create_anchor(req).await?;
+    let anchor = with_idempotency(&idempotency, "create_anchor", &headers, || async {
+        let anchor = app_state.db.create_anchor(req).await?;
 
-    // Broadcast the new anchor to WebSocket clients
-    broadcast_anchor_update(&app_state.ws_state, &anchor);
+        // Broadcast the new anchor to WebSocket clients
+        broadcast_anchor_update(&app_state.ws_state, &anchor);
 
-    Ok(Json(anchor))
+        Ok(anchor)
+    })
+    .await?;
+
+    Ok(anchor.map(Json))
 }
 
 /// PUT /api/anchors/:id/metrics - Update anchor metrics
@@ -155,16 +236,14 @@ pub struct UpdateMetricsRequest {
 
 pub async fn update_anchor_metrics(
     State(app_state): State<AppState>,
+    Extension(thresholds): Extension<Arc<StatusThresholds>>,
     Path(id): Path<Uuid>,
     Json(req): Json<UpdateMetricsRequest>,
 ) -> ApiResult<Json<crate::models::Anchor>> {
     // Verify anchor exists
-    if app_state.db.get_anchor_by_id(id).await?.is_none() {
-        return Err(ApiError::NotFound(format!(
-            "Anchor with id {} not found",
-            id
-        )));
-    }
+    let existing = app_state.db.get_anchor_by_id(id).await?.ok_or_else(|| {
+        ApiError::NotFound(format!("Anchor with id {} not found", id))
+    })?;
 
     let anchor = app_state.db
         .update_anchor_metrics(
@@ -174,15 +253,179 @@ pub async fn update_anchor_metrics(
             req.failed_transactions,
             req.avg_settlement_time_ms,
             req.volume_usd,
+            &thresholds,
         )
         .await?;
 
     // Broadcast the anchor update to WebSocket clients
     broadcast_anchor_update(&app_state.ws_state, &anchor);
 
+    // Drop only the cache entries that include this anchor
+    if let Err(e) = app_state
+        .cache_invalidation
+        .invalidate_anchor_update(&anchor.id, &anchor.stellar_account)
+        .await
+    {
+        tracing::warn!("Failed to invalidate caches for anchor {}: {}", anchor.id, e);
+    }
+
+    // Notify webhook receivers if the status flipped
+    if let Some(change) = detect_status_transition(&anchor.id, &existing.status, &anchor.status) {
+        if let Err(e) = app_state.webhooks.notify_anchor_status_change(&change).await {
+            tracing::warn!("Failed to dispatch status webhooks for {}: {}", anchor.id, e);
+        }
+    }
+
+    Ok(Json(anchor))
+}
+
+/// PATCH /api/anchors/:id/metrics - Update only the provided anchor metrics
+pub async fn patch_anchor_metrics(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(patch): Json<AnchorMetricsPatch>,
+) -> ApiResult<Json<crate::models::Anchor>> {
+    let existing = app_state.db.get_anchor_by_id(id).await?.ok_or_else(|| {
+        ApiError::NotFound(format!("Anchor with id {} not found", id))
+    })?;
+
+    validate_metrics_patch(&patch, &existing).map_err(ApiError::BadRequest)?;
+
+    let anchor = app_state
+        .db
+        .patch_anchor_metrics(id, &patch)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Anchor with id {} not found", id)))?;
+
+    broadcast_anchor_update(&app_state.ws_state, &anchor);
+
+    if let Err(e) = app_state
+        .cache_invalidation
+        .invalidate_anchor_update(&anchor.id, &anchor.stellar_account)
+        .await
+    {
+        tracing::warn!("Failed to invalidate caches for anchor {}: {}", anchor.id, e);
+    }
+
+    Ok(Json(anchor))
+}
+
+/// POST /api/anchors/:id/recompute-reliability - Re-derive anchor metrics from its corridors
+///
+/// Replaces the anchor's transaction counts, volume and reliability score
+/// with the totals of every corridor trading one of its assets. Returns 409
+/// and leaves the anchor untouched when none of those corridors has metrics.
+pub async fn recompute_anchor_reliability(
+    State(app_state): State<AppState>,
+    Extension(thresholds): Extension<Arc<StatusThresholds>>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<crate::models::Anchor>> {
+    let existing = app_state
+        .db
+        .get_anchor_by_id(id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Anchor with id {} not found", id)))?;
+
+    let (anchor, totals) = app_state
+        .db
+        .recompute_anchor_from_corridors(id, &thresholds)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Anchor with id {} not found", id)))?;
+    if totals.corridor_count == 0 {
+        return Err(ApiError::Conflict(format!(
+            "Anchor {} has no corridor metrics to recompute from",
+            id
+        )));
+    }
+    tracing::info!(
+        "Recomputed anchor {} from {} corridors: reliability {:.2} -> {:.2}",
+        anchor.id,
+        totals.corridor_count,
+        existing.reliability_score,
+        anchor.reliability_score
+    );
+
+    broadcast_anchor_update(&app_state.ws_state, &anchor);
+
+    if let Err(e) = app_state
+        .cache_invalidation
+        .invalidate_anchor_update(&anchor.id, &anchor.stellar_account)
+        .await
+    {
+        tracing::warn!(
+            "Failed to invalidate caches for anchor {}: {}",
+            anchor.id,
+            e
+        );
+    }
+
+    if let Some(change) = detect_status_transition(&anchor.id, &existing.status, &anchor.status) {
+        if let Err(e) = app_state
+            .webhooks
+            .notify_anchor_status_change(&change)
+            .await
+        {
+            tracing::warn!(
+                "Failed to dispatch status webhooks for {}: {}",
+                anchor.id,
+                e
+            );
+        }
+    }
+
     Ok(Json(anchor))
 }
 
+/// Check the provided fields, and that the counts stay consistent once
+/// merged with the stored ones
+fn validate_metrics_patch(
+    patch: &AnchorMetricsPatch,
+    existing: &crate::models::Anchor,
+) -> Result<(), String> {
+    let counts = [
+        ("total_transactions", patch.total_transactions),
+        ("successful_transactions", patch.successful_transactions),
+        ("failed_transactions", patch.failed_transactions),
+    ];
+    for (field, value) in counts {
+        if value.is_some_and(|v| v < 0) {
+            return Err(format!("{} cannot be negative", field));
+        }
+    }
+
+    if patch.avg_settlement_time_ms.is_some_and(|v| v < 0) {
+        return Err("avg_settlement_time_ms cannot be negative".to_string());
+    }
+
+    if patch
+        .total_volume_usd
+        .is_some_and(|v| !v.is_finite() || v < 0.0)
+    {
+        return Err("total_volume_usd must be a non-negative number".to_string());
+    }
+
+    if patch
+        .reliability_score
+        .is_some_and(|v| !(0.0..=100.0).contains(&v))
+    {
+        return Err("reliability_score must be between 0 and 100".to_string());
+    }
+
+    let total = patch.total_transactions.unwrap_or(existing.total_transactions);
+    let successful = patch
+        .successful_transactions
+        .unwrap_or(existing.successful_transactions);
+    let failed = patch.failed_transactions.unwrap_or(existing.failed_transactions);
+    if successful + failed > total {
+        return Err(format!(
+            "successful_transactions ({}) and failed_transactions ({}) exceed total_transactions ({})",
+            successful, failed, total
+        ));
+    }
+
+    Ok(())
+}
+
 /// GET /api/anchors/:id/assets - Get assets for an anchor
 pub async fn get_anchor_assets(
     State(app_state): State<AppState>,
@@ -201,6 +444,32 @@ pub async fn get_anchor_assets(
     Ok(Json(assets))
 }
 
+/// GET /api/anchors/:id/assets/volume - Payment count and volume per anchor asset (cached)
+pub async fn get_anchor_asset_volumes(
+    State(app_state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<Vec<AnchorAssetVolume>>> {
+    if app_state.db.get_anchor_by_id(id).await?.is_none() {
+        return Err(ApiError::NotFound(format!(
+            "Anchor with id {} not found",
+            id
+        )));
+    }
+
+    let cache = app_state.cache_invalidation.cache();
+    let anchor_id = id.to_string();
+    let volumes = <()>::get_or_fetch_tagged(
+        cache,
+        &keys::anchor_asset_volumes(&anchor_id),
+        cache.config.get_ttl("anchor"),
+        &[keys::anchors_tag(), keys::anchor_tag(&anchor_id)],
+        app_state.db.anchor_asset_volumes(id),
+    )
+    .await?;
+
+    Ok(Json(volumes))
+}
+
 /// POST /api/anchors/:id/assets - Add asset to anchor
 #[derive(Debug, Deserialize)]
 pub struct CreateAssetRequest {
@@ -229,29 +498,120 @@ pub async fn create_anchor_asset(
 }
 
 /// Health check endpoint
-pub async fn health_check() -> impl IntoResponse {
+///
+/// Redis is optional, so a lost cache connection is reported without
+/// marking the service unhealthy.
+pub async fn health_check(State(app_state): State<AppState>) -> impl IntoResponse {
+    let redis_connected = app_state.cache_invalidation.cache().is_connected().await;
+
     Json(serde_json::json!({
         "status": "healthy",
         "service": "stellar-insights-backend",
-        "version": env!("CARGO_PKG_VERSION")
+        "version": env!("CARGO_PKG_VERSION"),
+        "redis_connected": redis_connected
     }))
 }
 
+#[derive(Debug, Serialize)]
+pub struct VersionResponse {
+    pub version: &'static str,
+    pub git_sha: &'static str,
+    pub build_timestamp: String,
+    pub rustc_version: &'static str,
+}
+
+/// GET /api/version - Build metadata of the running binary
+pub async fn version() -> Json<VersionResponse> {
+    let build_timestamp = env!("BUILD_TIMESTAMP")
+        .parse::<i64>()
+        .ok()
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    Json(VersionResponse {
+        version: env!("CARGO_PKG_VERSION"),
+        git_sha: env!("BUILD_GIT_SHA"),
+        build_timestamp,
+        rustc_version: env!("BUILD_RUSTC_VERSION"),
+    })
+}
+
 /// GET /api/corridors - List all corridors
 pub async fn list_corridors(
     State(app_state): State<AppState>,
     Query(params): Query<ListCorridorsQuery>,
 ) -> ApiResult<Json<ListCorridorsResponse>> {
     let corridors = app_state.db.list_corridors(params.limit, params.offset).await?;
-    let total = corridors.len();
-    Ok(Json(ListCorridorsResponse { corridors, total }))
+    Ok(Json(Paginated::from_page(corridors, params.limit, params.offset)))
+}
+
+/// GET /api/corridors/:corridor_key/transactions - Most recent raw transactions in a corridor
+pub async fn get_corridor_transactions(
+    State(app_state): State<AppState>,
+    Path(corridor_key): Path<String>,
+    Query(params): Query<CorridorTransactionsQuery>,
+) -> ApiResult<Json<CorridorTransactionsResponse>> {
+    let corridor =
+        parse_corridor_key(&corridor_key).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    if !app_state.db.corridor_key_exists(&corridor.to_string_key()).await? {
+        return Err(ApiError::NotFound(format!(
+            "Corridor {} not found",
+            corridor_key
+        )));
+    }
+
+    let limit = params.clamped_limit();
+    let offset = params.clamped_offset();
+    let transactions = app_state
+        .db
+        .list_corridor_transactions(&corridor, params.successful, limit, offset)
+        .await?;
+
+    Ok(Json(CorridorTransactionsResponse {
+        corridor_key,
+        transactions,
+        limit,
+        offset,
+    }))
+}
+
+/// GET /api/corridors/:corridor_key/at?timestamp= - Corridor metrics as of a past instant
+///
+/// Returns the latest daily metrics row dated at or before `timestamp`.
+pub async fn get_corridor_metrics_at(
+    State(app_state): State<AppState>,
+    Path(corridor_key): Path<String>,
+    Query(params): Query<CorridorAtQuery>,
+) -> ApiResult<Json<crate::models::corridor::CorridorMetrics>> {
+    let corridor =
+        parse_corridor_key(&corridor_key).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    let metrics = app_state
+        .db
+        .corridor_metrics_at(&corridor.to_string_key(), params.timestamp)
+        .await?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "No metrics for corridor {} at or before {}",
+                corridor_key,
+                params.timestamp.to_rfc3339()
+            ))
+        })?;
+
+    Ok(Json(metrics))
 }
 
 /// POST /api/corridors - Create a new corridor
+///
+/// Honors an `Idempotency-Key` header so retried requests create one corridor.
 pub async fn create_corridor(
     State(app_state): State<AppState>,
+    Extension(idempotency): Extension<Arc<IdempotencyStore>>,
+    headers: HeaderMap,
     Json(req): Json<CreateCorridorRequest>,
-) -> ApiResult<Json<Corridor>> {
+) -> ApiResult<Idempotent<Json<Corridor>>> {
     if req.source_asset_code.is_empty() || req.dest_asset_code.is_empty() {
         return Err(ApiError::BadRequest(
             "Asset codes cannot be empty".to_string(),
@@ -262,12 +622,17 @@ pub async fn create_corridor(
             "Asset issuers cannot be empty".to_string(),
         ));
     }
-    let corridor = app_state.db.create_corridor(req).await?;
-    
-    // Broadcast the new corridor to WebSocket clients
-    broadcast_corridor_update(&app_state.ws_state, &corridor);
-    
-    Ok(Json(corridor))
+    let corridor = with_idempotency(&idempotency, "create_corridor", &headers, || async {
+        let corridor = app_state.db.create_corridor(req).await?;
+
+        // Broadcast the new corridor to WebSocket clients
+        broadcast_corridor_update(&app_state.ws_state, &corridor);
+
+        Ok(corridor)
+    })
+    .await?;
+
+    Ok(corridor.map(Json))
 }
 
 /// PUT /api/corridors/:id/metrics-from-transactions - Compute metrics from transactions and persist
@@ -306,10 +671,21 @@ pub async fn update_corridor_metrics_from_transactions(
         .collect();
 
     let metrics = compute_corridor_metrics(&txs, None, 1.0);
-    let corridor = app_state.db.update_corridor_metrics(id, metrics).await?;
+    let success_rate = metrics.success_rate;
+    let corridor = app_state.db.update_corridor_metrics(id, metrics.clone()).await?;
     
     // Broadcast the corridor update to WebSocket clients
     broadcast_corridor_update(&app_state.ws_state, &corridor);
+    broadcast_corridor_metrics(&app_state.ws_state, &corridor, &metrics);
+
+    // Evaluate alert thresholds against the fresh success rate
+    if let Err(e) = app_state
+        .corridor_alerts
+        .evaluate(&corridor.to_string_key(), success_rate)
+        .await
+    {
+        tracing::warn!("Failed to evaluate corridor alerts: {}", e);
+    }
     
     Ok(Json(corridor))
 }
@@ -320,3 +696,259 @@ pub async fn ingestion_status(
     let status = app_state.ingestion.get_ingestion_status().await?;
     Ok(Json(status))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_version_reports_crate_version() {
+        let Json(response) = version().await;
+
+        assert!(!response.version.is_empty());
+        assert_eq!(response.version, env!("CARGO_PKG_VERSION"));
+        assert!(!response.git_sha.is_empty());
+        assert!(response.rustc_version.starts_with("rustc"));
+        assert!(chrono::DateTime::parse_from_rfc3339(&response.build_timestamp).is_ok());
+    }
+
+    fn transactions_query(limit: i64, offset: i64) -> CorridorTransactionsQuery {
+        CorridorTransactionsQuery {
+            limit,
+            offset,
+            successful: None,
+        }
+    }
+
+    #[test]
+    fn test_corridor_transactions_limit_clamp() {
+        assert_eq!(transactions_query(50, 0).clamped_limit(), 50);
+        assert_eq!(transactions_query(0, 0).clamped_limit(), 1);
+        assert_eq!(transactions_query(-5, 0).clamped_limit(), 1);
+        assert_eq!(
+            transactions_query(10_000, 0).clamped_limit(),
+            MAX_CORRIDOR_TRANSACTIONS
+        );
+        assert_eq!(transactions_query(50, -10).clamped_offset(), 0);
+    }
+
+    async fn test_app_state() -> AppState {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        let db = Arc::new(crate::database::Database::new(pool));
+        let rpc = Arc::new(crate::rpc::StellarRpcClient::new_with_defaults(true));
+        let webhooks = Arc::new(crate::services::webhook::WebhookService::new(Arc::clone(&db)));
+        let cache = Arc::new(crate::cache::CacheManager::new(Default::default()).await.unwrap());
+
+        AppState::new(
+            Arc::clone(&db),
+            Arc::new(crate::websocket::WsState::new()),
+            Arc::new(crate::ingestion::DataIngestionService::new(
+                rpc,
+                Arc::clone(&db),
+                Arc::clone(&webhooks),
+            )),
+            webhooks,
+            Arc::new(crate::services::corridor_alerts::CorridorAlertService::new(
+                Arc::clone(&db),
+            )),
+            Arc::new(crate::cache_invalidation::CacheInvalidationService::new(cache)),
+        )
+    }
+
+    fn stored_anchor() -> crate::models::Anchor {
+        crate::models::Anchor {
+            id: Uuid::new_v4().to_string(),
+            name: "Anchor".to_string(),
+            stellar_account: "GANCHOR".to_string(),
+            home_domain: None,
+            total_transactions: 100,
+            successful_transactions: 90,
+            failed_transactions: 10,
+            total_volume_usd: 0.0,
+            avg_settlement_time_ms: 0,
+            reliability_score: 0.0,
+            status: "green".to_string(),
+            availability: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_validate_metrics_patch() {
+        let existing = stored_anchor();
+        let is_valid = |f: fn(&mut AnchorMetricsPatch)| {
+            let mut patch = AnchorMetricsPatch::default();
+            f(&mut patch);
+            validate_metrics_patch(&patch, &existing).is_ok()
+        };
+
+        assert!(is_valid(|_| {}));
+        assert!(is_valid(|p| p.reliability_score = Some(88.0)));
+        assert!(!is_valid(|p| p.reliability_score = Some(101.0)));
+        assert!(!is_valid(|p| p.failed_transactions = Some(-1)));
+        assert!(!is_valid(|p| p.total_volume_usd = Some(f64::NAN)));
+        // Merged with the stored 90 successful, 11 failed exceeds the stored total
+        assert!(!is_valid(|p| p.failed_transactions = Some(11)));
+        assert!(is_valid(|p| {
+            p.total_transactions = Some(200);
+            p.failed_transactions = Some(110);
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_anchor_asset_volumes_requires_known_anchor() {
+        let state = test_app_state().await;
+        let result = get_anchor_asset_volumes(State(state.clone()), Path(Uuid::new_v4())).await;
+        assert!(matches!(result, Err(ApiError::NotFound(_))));
+
+        let anchor = state
+            .db
+            .create_anchor(CreateAnchorRequest {
+                name: "Asset Volume Anchor".to_string(),
+                stellar_account: "GASSETVOLUMEANCHOR".to_string(),
+                home_domain: None,
+            })
+            .await
+            .unwrap();
+        let id = Uuid::parse_str(&anchor.id).unwrap();
+        state
+            .db
+            .create_asset(id, "USDC".to_string(), anchor.stellar_account.clone())
+            .await
+            .unwrap();
+
+        let Json(volumes) = get_anchor_asset_volumes(State(state), Path(id))
+            .await
+            .unwrap();
+        assert_eq!(volumes.len(), 1);
+        assert_eq!(volumes[0].asset_code, "USDC");
+        assert_eq!(volumes[0].transaction_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_create_anchor_replays_idempotent_retry() {
+        let state = test_app_state().await;
+        let store = Arc::new(IdempotencyStore::in_memory(std::time::Duration::from_secs(60)));
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            crate::idempotency::IDEMPOTENCY_KEY_HEADER,
+            "retry-1".parse().unwrap(),
+        );
+        let request = || CreateAnchorRequest {
+            name: "Idempotent Anchor".to_string(),
+            stellar_account: "GIDEMPOTENTANCHORACCOUNT".to_string(),
+            home_domain: None,
+        };
+
+        let first = create_anchor(
+            State(state.clone()),
+            Extension(Arc::clone(&store)),
+            headers.clone(),
+            Json(request()),
+        )
+        .await
+        .unwrap();
+        let second = create_anchor(
+            State(state.clone()),
+            Extension(Arc::clone(&store)),
+            headers,
+            Json(request()),
+        )
+        .await
+        .unwrap();
+
+        assert!(!first.replayed);
+        assert!(second.replayed);
+        let (Json(first), Json(second)) = (first.value, second.value);
+        assert_eq!(
+            serde_json::to_value(&first).unwrap(),
+            serde_json::to_value(&second).unwrap()
+        );
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM anchors WHERE name = ?")
+            .bind("Idempotent Anchor")
+            .fetch_one(state.db.pool())
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_create_anchor_writes_one_audit_entry() {
+        use crate::audit::{audit_middleware, X_REQUEST_ID};
+        use crate::auth_middleware::AuthUser;
+        use axum::{body::Body, http::Request, middleware, routing::post, Router};
+        use tower::ServiceExt;
+
+        let state = test_app_state().await;
+        let db = Arc::clone(&state.db);
+        let app = Router::new()
+            .route("/api/anchors", post(create_anchor))
+            .with_state(state)
+            .layer(Extension(Arc::new(IdempotencyStore::in_memory(
+                std::time::Duration::from_secs(60),
+            ))))
+            .layer(middleware::from_fn_with_state(
+                Arc::clone(&db),
+                audit_middleware,
+            ))
+            .layer(Extension(AuthUser {
+                user_id: "1".to_string(),
+                username: "operator".to_string(),
+            }));
+        let create = |name: &str| {
+            Request::builder()
+                .method("POST")
+                .uri("/api/anchors")
+                .header("content-type", "application/json")
+                .header(X_REQUEST_ID, format!("req-{}", name))
+                .header(
+                    crate::idempotency::IDEMPOTENCY_KEY_HEADER,
+                    format!("key-{}", name),
+                )
+                .body(Body::from(
+                    serde_json::json!({ "name": name, "stellar_account": "GAUDITED" }).to_string(),
+                ))
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(create("Audited")).await.unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        assert_eq!(response.headers()[X_REQUEST_ID], "req-Audited");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let anchor: crate::models::Anchor = serde_json::from_slice(&body).unwrap();
+
+        // Replaying the same key repeats a write that is already recorded
+        let response = app.clone().oneshot(create("Audited")).await.unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::OK);
+        assert_eq!(
+            response.headers()[crate::idempotency::IDEMPOTENT_REPLAYED_HEADER],
+            "true"
+        );
+
+        // Rejected writes are not audited
+        let response = app.oneshot(create("")).await.unwrap();
+        assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
+
+        let entries = db.audit_entries(None, None, 100).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].principal, "operator");
+        assert_eq!(entries[0].action, "create_anchor");
+        assert_eq!(entries[0].target_id.as_deref(), Some(anchor.id.as_str()));
+        assert_eq!(entries[0].request_id, "req-Audited");
+
+        let delete = sqlx::query("DELETE FROM audit_log")
+            .execute(db.pool())
+            .await;
+        assert!(delete.is_err(), "audit_log must be append-only");
+    }
+}
@@ -0,0 +1,396 @@
+use axum::http::{HeaderMap, HeaderName, HeaderValue};
+use axum::response::{IntoResponse, Response};
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::cache::{keys, CacheManager, CacheSchema};
+
+/// Header carrying the client-chosen idempotency key
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Header set on responses replayed from an earlier request with the same key
+pub const IDEMPOTENT_REPLAYED_HEADER: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+/// Longest accepted idempotency key
+const MAX_KEY_LEN: usize = 255;
+
+/// Default window during which a key replays its original response
+const DEFAULT_TTL_SECS: u64 = 24 * 3600;
+
+/// How long a claimed key stays pending, so a request that dies before
+/// completing does not lock its key for the whole window
+const PENDING_TTL_SECS: u64 = 60;
+
+/// What is stored under an idempotency key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+enum IdempotencyRecord {
+    /// The first request with this key is still being processed
+    Pending,
+    Completed {
+        response: serde_json::Value,
+    },
+}
+
+impl CacheSchema for IdempotencyRecord {
+    const TAG: &'static str = "idempotency_record.v1";
+}
+
+/// Result of claiming an idempotency key
+#[derive(Debug, PartialEq)]
+pub enum IdempotencyState<T> {
+    /// First use of the key; the caller should perform the operation
+    New,
+    /// Another request with the same key has not finished yet
+    InProgress,
+    /// The key was used before; replay this response
+    Completed(T),
+}
+
+/// Response of an idempotent handler
+///
+/// Replays carry [`IDEMPOTENT_REPLAYED_HEADER`], so clients and the audit log
+/// can tell them from the request that did the work.
+#[derive(Debug)]
+pub struct Idempotent<T> {
+    pub value: T,
+    pub replayed: bool,
+}
+
+impl<T> Idempotent<T> {
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Idempotent<U> {
+        Idempotent {
+            value: f(self.value),
+            replayed: self.replayed,
+        }
+    }
+}
+
+impl<T: IntoResponse> IntoResponse for Idempotent<T> {
+    fn into_response(self) -> Response {
+        let mut response = self.value.into_response();
+        if self.replayed {
+            response
+                .headers_mut()
+                .insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+        }
+        response
+    }
+}
+
+/// Extract and validate the `Idempotency-Key` header, if present
+pub fn idempotency_key(headers: &HeaderMap) -> Result<Option<String>, String> {
+    let Some(value) = headers.get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
+    };
+
+    let key = value
+        .to_str()
+        .map_err(|_| "Idempotency-Key must be visible ASCII".to_string())?
+        .trim();
+    if key.is_empty() || key.len() > MAX_KEY_LEN {
+        return Err(format!(
+            "Idempotency-Key must be between 1 and {} characters",
+            MAX_KEY_LEN
+        ));
+    }
+
+    Ok(Some(key.to_string()))
+}
+
+/// Responses of idempotent requests, kept in Redis for a window
+///
+/// Falls back to per-instance memory while Redis is unavailable.
+pub struct IdempotencyStore {
+    cache: Option<Arc<CacheManager>>,
+    memory: DashMap<String, (IdempotencyRecord, Instant)>,
+    ttl: Duration,
+    pending_ttl: Duration,
+}
+
+impl IdempotencyStore {
+    pub fn new(cache: Arc<CacheManager>, ttl: Duration) -> Self {
+        Self {
+            cache: Some(cache),
+            ..Self::in_memory(ttl)
+        }
+    }
+
+    /// Create with the window from `IDEMPOTENCY_TTL_SECS` (default 24h)
+    pub fn from_env(cache: Arc<CacheManager>) -> Self {
+        let ttl_secs = std::env::var("IDEMPOTENCY_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_TTL_SECS);
+
+        Self::new(cache, Duration::from_secs(ttl_secs))
+    }
+
+    /// Store kept only in this process, for tests and single-instance setups
+    pub fn in_memory(ttl: Duration) -> Self {
+        Self {
+            cache: None,
+            memory: DashMap::new(),
+            ttl,
+            pending_ttl: ttl.min(Duration::from_secs(PENDING_TTL_SECS)),
+        }
+    }
+
+    async fn redis(&self) -> Option<&CacheManager> {
+        match &self.cache {
+            Some(cache) if cache.is_connected().await => Some(cache),
+            _ => None,
+        }
+    }
+
+    /// Claim `key` within `scope`, or look up the response it already produced
+    pub async fn begin<T: DeserializeOwned>(
+        &self,
+        scope: &str,
+        key: &str,
+    ) -> anyhow::Result<IdempotencyState<T>> {
+        let storage_key = keys::idempotency(scope, key);
+
+        let record = match self.redis().await {
+            Some(cache) => {
+                let ttl = self.pending_ttl.as_secs() as usize;
+                match cache
+                    .set_nx(&storage_key, &IdempotencyRecord::Pending, ttl)
+                    .await?
+                {
+                    Some(true) => return Ok(IdempotencyState::New),
+                    Some(false) => cache
+                        .get::<IdempotencyRecord>(&storage_key)
+                        .await?
+                        .unwrap_or(IdempotencyRecord::Pending),
+                    None => return Ok(self.begin_in_memory(storage_key)),
+                }
+            }
+            None => return Ok(self.begin_in_memory(storage_key)),
+        };
+
+        Self::state_from(record)
+    }
+
+    fn begin_in_memory<T: DeserializeOwned>(&self, storage_key: String) -> IdempotencyState<T> {
+        let now = Instant::now();
+        match self.memory.entry(storage_key) {
+            Entry::Vacant(entry) => {
+                entry.insert((IdempotencyRecord::Pending, now));
+                IdempotencyState::New
+            }
+            Entry::Occupied(mut entry) if self.is_expired(entry.get(), now) => {
+                entry.insert((IdempotencyRecord::Pending, now));
+                IdempotencyState::New
+            }
+            Entry::Occupied(entry) => {
+                Self::state_from(entry.get().0.clone()).unwrap_or(IdempotencyState::InProgress)
+            }
+        }
+    }
+
+    fn is_expired(&self, (record, stored_at): &(IdempotencyRecord, Instant), now: Instant) -> bool {
+        let ttl = match record {
+            IdempotencyRecord::Pending => self.pending_ttl,
+            IdempotencyRecord::Completed { .. } => self.ttl,
+        };
+        now.duration_since(*stored_at) >= ttl
+    }
+
+    fn state_from<T: DeserializeOwned>(
+        record: IdempotencyRecord,
+    ) -> anyhow::Result<IdempotencyState<T>> {
+        match record {
+            IdempotencyRecord::Pending => Ok(IdempotencyState::InProgress),
+            IdempotencyRecord::Completed { response } => Ok(IdempotencyState::Completed(
+                serde_json::from_value(response)?,
+            )),
+        }
+    }
+
+    /// Remember the response produced for a claimed key, for the full window
+    pub async fn complete<T: Serialize>(
+        &self,
+        scope: &str,
+        key: &str,
+        response: &T,
+    ) -> anyhow::Result<()> {
+        let storage_key = keys::idempotency(scope, key);
+        let record = IdempotencyRecord::Completed {
+            response: serde_json::to_value(response)?,
+        };
+
+        match self.redis().await {
+            Some(cache) => {
+                cache
+                    .set(&storage_key, &record, self.ttl.as_secs() as usize)
+                    .await
+            }
+            None => {
+                self.memory.insert(storage_key, (record, Instant::now()));
+                Ok(())
+            }
+        }
+    }
+
+    /// Release a claimed key after a failed request so it can be retried
+    pub async fn abandon(&self, scope: &str, key: &str) {
+        let storage_key = keys::idempotency(scope, key);
+        match self.redis().await {
+            Some(cache) => {
+                let _ = cache.delete(&storage_key).await;
+            }
+            None => {
+                self.memory.remove(&storage_key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_key_replays_completed_response() {
+        let store = IdempotencyStore::in_memory(Duration::from_secs(60));
+
+        assert_eq!(
+            store.begin::<String>("create_anchor", "k1").await.unwrap(),
+            IdempotencyState::New
+        );
+        assert_eq!(
+            store.begin::<String>("create_anchor", "k1").await.unwrap(),
+            IdempotencyState::InProgress
+        );
+
+        store
+            .complete("create_anchor", "k1", &"anchor-1".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            store.begin::<String>("create_anchor", "k1").await.unwrap(),
+            IdempotencyState::Completed("anchor-1".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_keys_are_scoped_per_endpoint() {
+        let store = IdempotencyStore::in_memory(Duration::from_secs(60));
+        store.begin::<String>("create_anchor", "k1").await.unwrap();
+        store
+            .complete("create_anchor", "k1", &"anchor-1".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store
+                .begin::<String>("create_corridor", "k1")
+                .await
+                .unwrap(),
+            IdempotencyState::New
+        );
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_and_expired_keys_can_be_reused() {
+        let store = IdempotencyStore::in_memory(Duration::from_secs(60));
+        store.begin::<String>("create_anchor", "k1").await.unwrap();
+        store.abandon("create_anchor", "k1").await;
+        assert_eq!(
+            store.begin::<String>("create_anchor", "k1").await.unwrap(),
+            IdempotencyState::New
+        );
+
+        let expiring = IdempotencyStore::in_memory(Duration::ZERO);
+        expiring
+            .begin::<String>("create_anchor", "k2")
+            .await
+            .unwrap();
+        assert_eq!(
+            expiring
+                .begin::<String>("create_anchor", "k2")
+                .await
+                .unwrap(),
+            IdempotencyState::New
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pending_claims_expire_before_completed_responses() {
+        let store = IdempotencyStore {
+            pending_ttl: Duration::ZERO,
+            ..IdempotencyStore::in_memory(Duration::from_secs(60))
+        };
+
+        store.begin::<String>("create_anchor", "k1").await.unwrap();
+        // The first claim never completed, so a retry may take the key over
+        assert_eq!(
+            store.begin::<String>("create_anchor", "k1").await.unwrap(),
+            IdempotencyState::New
+        );
+
+        store
+            .complete("create_anchor", "k1", &"anchor-1".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            store.begin::<String>("create_anchor", "k1").await.unwrap(),
+            IdempotencyState::Completed("anchor-1".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redis_pending_marker_is_short_lived() {
+        use crate::cache::{fake_redis::FakeRedis, CacheConfig};
+
+        let redis = FakeRedis::start().await;
+        let cache = Arc::new(redis.connect(CacheConfig::default()).await);
+        let store = IdempotencyStore::new(cache, Duration::from_secs(DEFAULT_TTL_SECS));
+        let storage_key = keys::idempotency("create_anchor", "k1");
+
+        assert_eq!(
+            store.begin::<String>("create_anchor", "k1").await.unwrap(),
+            IdempotencyState::New
+        );
+        let pending_ttl = redis.ttl(&storage_key).unwrap();
+        assert!(pending_ttl <= Duration::from_secs(PENDING_TTL_SECS));
+        assert_eq!(
+            store.begin::<String>("create_anchor", "k1").await.unwrap(),
+            IdempotencyState::InProgress
+        );
+
+        store
+            .complete("create_anchor", "k1", &"anchor-1".to_string())
+            .await
+            .unwrap();
+        // Completion keeps the response for the whole window
+        assert!(redis.ttl(&storage_key).unwrap() > Duration::from_secs(PENDING_TTL_SECS));
+        assert_eq!(
+            store.begin::<String>("create_anchor", "k1").await.unwrap(),
+            IdempotencyState::Completed("anchor-1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_idempotency_key_validation() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(idempotency_key(&headers).unwrap(), None);
+
+        headers.insert(IDEMPOTENCY_KEY_HEADER, " abc-123 ".parse().unwrap());
+        assert_eq!(
+            idempotency_key(&headers).unwrap(),
+            Some("abc-123".to_string())
+        );
+
+        headers.insert(IDEMPOTENCY_KEY_HEADER, "".parse().unwrap());
+        assert!(idempotency_key(&headers).is_err());
+
+        headers.insert(IDEMPOTENCY_KEY_HEADER, "x".repeat(256).parse().unwrap());
+        assert!(idempotency_key(&headers).is_err());
+    }
+}
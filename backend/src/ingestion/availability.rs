@@ -0,0 +1,96 @@
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// Default length of one availability window
+pub const DEFAULT_AVAILABILITY_WINDOW: Duration = Duration::from_secs(3600);
+
+/// Default number of recent windows availability is measured over
+pub const DEFAULT_AVAILABILITY_WINDOWS: u32 = 24;
+
+/// Percentage of the last `windows` windows, each `window` long and ending at
+/// `now`, in which the anchor had at least one transaction
+///
+/// `observed_since` is the oldest transaction seen when the fetch was cut off
+/// at its page limit. Windows ending before it were never observed, so they
+/// are left out rather than counted as silent. Returns `None` when no window
+/// was observed.
+pub fn availability(
+    activity: &[DateTime<Utc>],
+    now: DateTime<Utc>,
+    window: Duration,
+    windows: u32,
+    observed_since: Option<DateTime<Utc>>,
+) -> Option<f64> {
+    let window = chrono::Duration::from_std(window).ok()?;
+    if window <= chrono::Duration::zero() {
+        return None;
+    }
+
+    let mut observed = 0u32;
+    let mut active = 0u32;
+    for i in 0..windows {
+        let end = now - window * i as i32;
+        let start = end - window;
+        if observed_since.is_some_and(|since| end <= since) {
+            break;
+        }
+        observed += 1;
+        if activity.iter().any(|&t| t > start && t <= end) {
+            active += 1;
+        }
+    }
+
+    (observed > 0).then(|| active as f64 / observed as f64 * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    fn hours_ago(now: DateTime<Utc>, hours: &[i64]) -> Vec<DateTime<Utc>> {
+        hours
+            .iter()
+            .map(|&h| now - chrono::Duration::minutes(h * 60 + 30))
+            .collect()
+    }
+
+    #[test]
+    fn test_always_active_anchor_is_fully_available() {
+        let now = Utc::now();
+        let activity = hours_ago(now, &(0..24).collect::<Vec<_>>());
+
+        assert_eq!(availability(&activity, now, HOUR, 24, None), Some(100.0));
+    }
+
+    #[test]
+    fn test_intermittent_anchor_loses_availability() {
+        let now = Utc::now();
+        // Active in every other hour
+        let activity = hours_ago(now, &(0..24).step_by(2).collect::<Vec<_>>());
+        assert_eq!(availability(&activity, now, HOUR, 24, None), Some(50.0));
+
+        // Silent for the last 6 hours: each silent window costs availability
+        let went_quiet = hours_ago(now, &(6..24).collect::<Vec<_>>());
+        assert_eq!(availability(&went_quiet, now, HOUR, 24, None), Some(75.0));
+        let quieter = hours_ago(now, &(12..24).collect::<Vec<_>>());
+        assert_eq!(availability(&quieter, now, HOUR, 24, None), Some(50.0));
+
+        assert_eq!(availability(&[], now, HOUR, 24, None), Some(0.0));
+    }
+
+    #[test]
+    fn test_skips_windows_older_than_the_fetch() {
+        let now = Utc::now();
+        let activity = hours_ago(now, &[0, 1, 2, 3]);
+        let oldest = *activity.last().unwrap();
+
+        // Only the four hours the truncated fetch reached are counted
+        assert_eq!(
+            availability(&activity, now, HOUR, 24, Some(oldest)),
+            Some(100.0)
+        );
+        assert_eq!(availability(&activity, now, HOUR, 0, None), None);
+    }
+}
@@ -0,0 +1,489 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use super::availability::{DEFAULT_AVAILABILITY_WINDOW, DEFAULT_AVAILABILITY_WINDOWS};
+use super::ledger::DEFAULT_MAX_BATCH_ATTEMPTS;
+use super::retry_budget::{DEFAULT_RETRY_BUDGET, DEFAULT_RETRY_REFILL_INTERVAL};
+
+const DEFAULT_BATCH_SIZE: u32 = 5;
+const DEFAULT_IDLE_SLEEP_SECS: u64 = 5;
+const DEFAULT_ERROR_SLEEP_SECS: u64 = 10;
+const DEFAULT_DEGRADED_SLEEP_SECS: u64 = 300;
+const DEFAULT_METRICS_SYNC_INTERVAL_SECS: u64 = 300;
+const DEFAULT_RELIABILITY_HALF_LIFE_DAYS: u64 = 30;
+const SECS_PER_DAY: u64 = 24 * 3600;
+
+/// Largest ledger batch the ingestion loop may request in one pass
+pub const MAX_BATCH_SIZE: u32 = 100;
+
+/// Ingestion loop settings, read once at startup
+#[derive(Debug, Clone, PartialEq)]
+pub struct IngestionConfig {
+    /// Number of ledgers fetched per ingestion pass
+    pub batch_size: u32,
+    /// Sleep after a pass that found no new ledgers
+    pub idle_sleep: Duration,
+    /// Sleep after a failed pass
+    pub error_sleep: Duration,
+    /// Failed passes allowed, across all ingestion tasks, before backing off
+    pub retry_budget: u32,
+    /// Time for one spent retry to be refunded to the budget
+    pub retry_refill_interval: Duration,
+    /// Sleep after a failed pass once the retry budget is spent
+    pub degraded_sleep: Duration,
+    /// Interval between anchor/corridor metric syncs
+    pub metrics_sync_interval: Duration,
+    /// Age at which a transaction counts half as much in the reliability score
+    pub reliability_half_life: Duration,
+    /// Attempts at a failing ledger batch before it is dead-lettered and skipped
+    pub max_batch_attempts: u32,
+    /// Corridors whose metrics are persisted
+    pub corridor_filter: CorridorFilter,
+    /// Length of one window in the anchor availability metric
+    pub availability_window: Duration,
+    /// Number of recent windows anchor availability is measured over
+    pub availability_windows: u32,
+    /// Width of the buckets corridor metrics are aggregated into
+    pub bucket: BucketGranularity,
+    /// Payments too small to count towards corridor success rates
+    pub dust: DustThreshold,
+}
+
+impl Default for IngestionConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: DEFAULT_BATCH_SIZE,
+            idle_sleep: Duration::from_secs(DEFAULT_IDLE_SLEEP_SECS),
+            error_sleep: Duration::from_secs(DEFAULT_ERROR_SLEEP_SECS),
+            retry_budget: DEFAULT_RETRY_BUDGET,
+            retry_refill_interval: DEFAULT_RETRY_REFILL_INTERVAL,
+            degraded_sleep: Duration::from_secs(DEFAULT_DEGRADED_SLEEP_SECS),
+            metrics_sync_interval: Duration::from_secs(DEFAULT_METRICS_SYNC_INTERVAL_SECS),
+            reliability_half_life: Duration::from_secs(
+                DEFAULT_RELIABILITY_HALF_LIFE_DAYS * SECS_PER_DAY,
+            ),
+            max_batch_attempts: DEFAULT_MAX_BATCH_ATTEMPTS,
+            corridor_filter: CorridorFilter::default(),
+            availability_window: DEFAULT_AVAILABILITY_WINDOW,
+            availability_windows: DEFAULT_AVAILABILITY_WINDOWS,
+            bucket: BucketGranularity::default(),
+            dust: DustThreshold::default(),
+        }
+    }
+}
+
+impl IngestionConfig {
+    /// Create from environment variables
+    pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let batch_size = parse_var(&lookup, "INGESTION_BATCH_SIZE", DEFAULT_BATCH_SIZE)?;
+        if batch_size == 0 || batch_size > MAX_BATCH_SIZE {
+            bail!(
+                "INGESTION_BATCH_SIZE must be between 1 and {}, got {}",
+                MAX_BATCH_SIZE,
+                batch_size
+            );
+        }
+
+        let half_life_days = parse_var(
+            &lookup,
+            "RELIABILITY_HALF_LIFE_DAYS",
+            DEFAULT_RELIABILITY_HALF_LIFE_DAYS,
+        )?;
+        if half_life_days == 0 {
+            bail!("RELIABILITY_HALF_LIFE_DAYS must be at least 1");
+        }
+
+        let max_batch_attempts = parse_var(
+            &lookup,
+            "INGESTION_MAX_BATCH_ATTEMPTS",
+            DEFAULT_MAX_BATCH_ATTEMPTS,
+        )?;
+        if max_batch_attempts == 0 {
+            bail!("INGESTION_MAX_BATCH_ATTEMPTS must be at least 1");
+        }
+
+        let retry_budget = parse_var(&lookup, "INGESTION_RETRY_BUDGET", DEFAULT_RETRY_BUDGET)?;
+        if retry_budget == 0 {
+            bail!("INGESTION_RETRY_BUDGET must be at least 1");
+        }
+
+        let retry_refill_secs = parse_var(
+            &lookup,
+            "INGESTION_RETRY_REFILL_SECS",
+            DEFAULT_RETRY_REFILL_INTERVAL.as_secs(),
+        )?;
+        if retry_refill_secs == 0 {
+            bail!("INGESTION_RETRY_REFILL_SECS must be at least 1");
+        }
+
+        let availability_window_secs = parse_var(
+            &lookup,
+            "INGESTION_AVAILABILITY_WINDOW_SECS",
+            DEFAULT_AVAILABILITY_WINDOW.as_secs(),
+        )?;
+        if availability_window_secs == 0 {
+            bail!("INGESTION_AVAILABILITY_WINDOW_SECS must be at least 1");
+        }
+
+        let availability_windows = parse_var(
+            &lookup,
+            "INGESTION_AVAILABILITY_WINDOWS",
+            DEFAULT_AVAILABILITY_WINDOWS,
+        )?;
+        if availability_windows == 0 {
+            bail!("INGESTION_AVAILABILITY_WINDOWS must be at least 1");
+        }
+
+        let bucket = match lookup("INGESTION_BUCKET") {
+            Some(value) if !value.trim().is_empty() => value
+                .trim()
+                .parse()
+                .map_err(anyhow::Error::msg)
+                .context("Invalid value for INGESTION_BUCKET")?,
+            _ => BucketGranularity::default(),
+        };
+
+        let min_amount_usd: f64 = parse_var(&lookup, "INGESTION_DUST_MIN_USD", 0.0)?;
+        if !(min_amount_usd >= 0.0 && min_amount_usd.is_finite()) {
+            bail!("INGESTION_DUST_MIN_USD must be a non-negative amount");
+        }
+        let mut min_amounts = BTreeMap::new();
+        for entry in parse_list(&lookup, "INGESTION_DUST_MIN_AMOUNTS") {
+            let (code, amount) = entry.split_once('=').with_context(|| {
+                format!(
+                    "INGESTION_DUST_MIN_AMOUNTS entry '{}' is not CODE=amount",
+                    entry
+                )
+            })?;
+            let amount: f64 = amount.trim().parse().with_context(|| {
+                format!(
+                    "Invalid amount in INGESTION_DUST_MIN_AMOUNTS entry '{}'",
+                    entry
+                )
+            })?;
+            if !(amount >= 0.0 && amount.is_finite()) {
+                bail!(
+                    "INGESTION_DUST_MIN_AMOUNTS entry '{}' must be non-negative",
+                    entry
+                );
+            }
+            min_amounts.insert(code.trim().to_string(), amount);
+        }
+
+        Ok(Self {
+            batch_size,
+            idle_sleep: Duration::from_secs(parse_var(
+                &lookup,
+                "INGESTION_IDLE_SLEEP_SECS",
+                DEFAULT_IDLE_SLEEP_SECS,
+            )?),
+            error_sleep: Duration::from_secs(parse_var(
+                &lookup,
+                "INGESTION_ERROR_SLEEP_SECS",
+                DEFAULT_ERROR_SLEEP_SECS,
+            )?),
+            retry_budget,
+            retry_refill_interval: Duration::from_secs(retry_refill_secs),
+            degraded_sleep: Duration::from_secs(parse_var(
+                &lookup,
+                "INGESTION_DEGRADED_SLEEP_SECS",
+                DEFAULT_DEGRADED_SLEEP_SECS,
+            )?),
+            metrics_sync_interval: Duration::from_secs(parse_var(
+                &lookup,
+                "METRICS_SYNC_INTERVAL_SECS",
+                DEFAULT_METRICS_SYNC_INTERVAL_SECS,
+            )?),
+            reliability_half_life: Duration::from_secs(half_life_days * SECS_PER_DAY),
+            max_batch_attempts,
+            corridor_filter: CorridorFilter {
+                allow: parse_list(&lookup, "INGESTION_CORRIDOR_ALLOWLIST"),
+                deny: parse_list(&lookup, "INGESTION_CORRIDOR_DENYLIST"),
+            },
+            availability_window: Duration::from_secs(availability_window_secs),
+            availability_windows,
+            bucket,
+            dust: DustThreshold {
+                min_amount_usd,
+                min_amounts,
+            },
+        })
+    }
+}
+
+/// Width of the corridor metric buckets ingestion aggregates into
+///
+/// Every row in `corridor_metrics_hourly` records the granularity it was
+/// written with, and aggregation refuses to add rows of another one, so
+/// changing `INGESTION_BUCKET` requires clearing the stored buckets first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BucketGranularity {
+    #[default]
+    Hour,
+    Day,
+}
+
+impl BucketGranularity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BucketGranularity::Hour => "hour",
+            BucketGranularity::Day => "day",
+        }
+    }
+
+    /// Start of the bucket `dt` falls in, in UTC
+    pub fn truncate(self, dt: DateTime<Utc>) -> DateTime<Utc> {
+        let width = match self {
+            BucketGranularity::Hour => TimeDelta::hours(1),
+            BucketGranularity::Day => TimeDelta::days(1),
+        };
+        dt.duration_trunc(width).unwrap_or(dt)
+    }
+}
+
+impl fmt::Display for BucketGranularity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for BucketGranularity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "hour" => Ok(BucketGranularity::Hour),
+            "day" => Ok(BucketGranularity::Day),
+            _ => Err(format!("expected 'hour' or 'day', got '{}'", s)),
+        }
+    }
+}
+
+/// Corridors ingestion stores metrics for
+///
+/// Entries are full corridor keys (`USDC:GA...->XLM:native`) or bare asset
+/// codes matching either side of a corridor. The denylist wins over the
+/// allowlist, and an empty allowlist includes every corridor.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CorridorFilter {
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+}
+
+impl CorridorFilter {
+    pub fn includes(&self, corridor_key: &str, asset_a_code: &str, asset_b_code: &str) -> bool {
+        let matches = |entry: &String| {
+            if entry.contains("->") {
+                entry == corridor_key
+            } else {
+                entry == asset_a_code || entry == asset_b_code
+            }
+        };
+
+        if self.deny.iter().any(matches) {
+            return false;
+        }
+        self.allow.is_empty() || self.allow.iter().any(matches)
+    }
+}
+
+/// Smallest payments that count towards corridor success rates
+///
+/// A payment below the minimum for its source asset code, or below
+/// `min_amount_usd` when its asset has none, is left out of the success and
+/// failure counts. It still adds to corridor volume. All zero by default,
+/// counting everything.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DustThreshold {
+    /// Floor for payments whose amount is in USD or has no per-asset minimum
+    pub min_amount_usd: f64,
+    /// Minimum amount per asset code, in units of that asset
+    pub min_amounts: BTreeMap<String, f64>,
+}
+
+impl DustThreshold {
+    pub fn is_dust(&self, asset_code: &str, amount: f64) -> bool {
+        let min = self
+            .min_amounts
+            .get(asset_code)
+            .copied()
+            .unwrap_or(self.min_amount_usd);
+        amount < min
+    }
+}
+
+/// Comma-separated list, ignoring blank entries
+pub(crate) fn parse_list(lookup: &impl Fn(&str) -> Option<String>, name: &str) -> Vec<String> {
+    lookup(name)
+        .map(|value| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|entry| !entry.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub(crate) fn parse_var<T>(
+    lookup: &impl Fn(&str) -> Option<String>,
+    name: &str,
+    default: T,
+) -> Result<T>
+where
+    T: std::str::FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    match lookup(name) {
+        Some(value) if !value.trim().is_empty() => value
+            .trim()
+            .parse()
+            .with_context(|| format!("Invalid value for {}: {}", name, value)),
+        _ => Ok(default),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config_from(vars: &[(&str, &str)]) -> Result<IngestionConfig> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        IngestionConfig::from_lookup(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_defaults_when_unset() {
+        assert_eq!(config_from(&[]).unwrap(), IngestionConfig::default());
+        assert_eq!(IngestionConfig::default().batch_size, 5);
+        assert_eq!(
+            IngestionConfig::default().metrics_sync_interval,
+            Duration::from_secs(300)
+        );
+    }
+
+    #[test]
+    fn test_parses_overrides() {
+        let config = config_from(&[
+            ("INGESTION_BATCH_SIZE", "20"),
+            ("INGESTION_IDLE_SLEEP_SECS", "2"),
+            ("INGESTION_ERROR_SLEEP_SECS", "30"),
+            ("METRICS_SYNC_INTERVAL_SECS", "60"),
+            ("RELIABILITY_HALF_LIFE_DAYS", "7"),
+            ("INGESTION_MAX_BATCH_ATTEMPTS", "5"),
+            ("INGESTION_RETRY_BUDGET", "4"),
+            ("INGESTION_RETRY_REFILL_SECS", "30"),
+            ("INGESTION_DEGRADED_SLEEP_SECS", "900"),
+            ("INGESTION_AVAILABILITY_WINDOW_SECS", "600"),
+            ("INGESTION_AVAILABILITY_WINDOWS", "12"),
+            ("INGESTION_BUCKET", "day"),
+        ])
+        .unwrap();
+
+        assert_eq!(config.batch_size, 20);
+        assert_eq!(config.idle_sleep, Duration::from_secs(2));
+        assert_eq!(config.error_sleep, Duration::from_secs(30));
+        assert_eq!(config.metrics_sync_interval, Duration::from_secs(60));
+        assert_eq!(config.reliability_half_life, Duration::from_secs(7 * 24 * 3600));
+        assert_eq!(config.max_batch_attempts, 5);
+        assert_eq!(config.retry_budget, 4);
+        assert_eq!(config.retry_refill_interval, Duration::from_secs(30));
+        assert_eq!(config.degraded_sleep, Duration::from_secs(900));
+        assert_eq!(config.availability_window, Duration::from_secs(600));
+        assert_eq!(config.availability_windows, 12);
+        assert_eq!(config.bucket, BucketGranularity::Day);
+    }
+
+    #[test]
+    fn test_parses_dust_threshold() {
+        let config = config_from(&[
+            ("INGESTION_DUST_MIN_USD", "0.01"),
+            ("INGESTION_DUST_MIN_AMOUNTS", "XLM=1, BRL = 0.5"),
+        ])
+        .unwrap();
+
+        let dust = &config.dust;
+        assert_eq!(dust.min_amount_usd, 0.01);
+        assert_eq!(dust.min_amounts.get("XLM"), Some(&1.0));
+        assert!(dust.is_dust("XLM", 0.5));
+        assert!(!dust.is_dust("XLM", 1.0));
+        assert!(!dust.is_dust("BRL", 0.5));
+        // Assets without their own minimum fall back to the USD floor
+        assert!(dust.is_dust("USDC", 0.001));
+        assert!(!dust.is_dust("USDC", 0.01));
+        assert!(!DustThreshold::default().is_dust("USDC", 0.0));
+
+        assert!(config_from(&[("INGESTION_DUST_MIN_USD", "-1")]).is_err());
+        assert!(config_from(&[("INGESTION_DUST_MIN_AMOUNTS", "XLM")]).is_err());
+        assert!(config_from(&[("INGESTION_DUST_MIN_AMOUNTS", "XLM=lots")]).is_err());
+    }
+
+    #[test]
+    fn test_parses_corridor_lists() {
+        let config = config_from(&[
+            (
+                "INGESTION_CORRIDOR_ALLOWLIST",
+                " USDC, EURC:GISSUER->XLM:native ,",
+            ),
+            ("INGESTION_CORRIDOR_DENYLIST", ""),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            config.corridor_filter.allow,
+            vec!["USDC", "EURC:GISSUER->XLM:native"]
+        );
+        assert!(config.corridor_filter.deny.is_empty());
+    }
+
+    #[test]
+    fn test_corridor_filter_matching() {
+        let everything = CorridorFilter::default();
+        assert!(everything.includes("BRL:GB->XLM:native", "BRL", "XLM"));
+
+        let filter = CorridorFilter {
+            allow: vec!["USDC".to_string(), "EURC:GE->XLM:native".to_string()],
+            deny: vec!["USDC:GSPAM->XLM:native".to_string()],
+        };
+        assert!(filter.includes("USDC:GA->XLM:native", "USDC", "XLM"));
+        assert!(filter.includes("XLM:native->USDC:GA", "XLM", "USDC"));
+        assert!(filter.includes("EURC:GE->XLM:native", "EURC", "XLM"));
+        // Only the listed EURC issuer is allowed
+        assert!(!filter.includes("EURC:GOTHER->XLM:native", "EURC", "XLM"));
+        assert!(!filter.includes("BRL:GB->XLM:native", "BRL", "XLM"));
+        // Deny beats allow
+        assert!(!filter.includes("USDC:GSPAM->XLM:native", "USDC", "XLM"));
+    }
+
+    #[test]
+    fn test_rejects_out_of_range_batch_size() {
+        assert!(config_from(&[("INGESTION_BATCH_SIZE", "0")]).is_err());
+        assert!(config_from(&[("INGESTION_BATCH_SIZE", "101")]).is_err());
+        assert!(config_from(&[("INGESTION_BATCH_SIZE", "100")]).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_unparseable_values() {
+        assert!(config_from(&[("INGESTION_IDLE_SLEEP_SECS", "soon")]).is_err());
+        assert!(config_from(&[("RELIABILITY_HALF_LIFE_DAYS", "0")]).is_err());
+        assert!(config_from(&[("INGESTION_MAX_BATCH_ATTEMPTS", "0")]).is_err());
+        assert!(config_from(&[("INGESTION_RETRY_BUDGET", "0")]).is_err());
+        assert!(config_from(&[("INGESTION_RETRY_REFILL_SECS", "0")]).is_err());
+        assert!(config_from(&[("INGESTION_AVAILABILITY_WINDOW_SECS", "0")]).is_err());
+        assert!(config_from(&[("INGESTION_AVAILABILITY_WINDOWS", "0")]).is_err());
+        assert!(config_from(&[("INGESTION_BUCKET", "week")]).is_err());
+    }
+}
@@ -1,15 +1,33 @@
 use anyhow::{Context, Result};
+use base64::Engine;
 use chrono::{DateTime, TimeZone, Utc};
+use sha2::{Digest, Sha256};
 use sqlx::SqlitePool;
-use std::sync::Arc;
-use tracing::{info, warn};
+use std::sync::{Arc, Mutex};
+use stellar_xdr::curr::{LedgerHeaderHistoryEntry, Limits, ReadXdr};
+use tracing::{debug, error, info, warn};
 
 use crate::rpc::{GetLedgersResult, RpcLedger, StellarRpcClient};
 
+/// Attempts at a ledger batch before its range is dead-lettered
+pub const DEFAULT_MAX_BATCH_ATTEMPTS: u32 = 3;
+
+/// Upper bound on a decoded ledger header; real ones are a few hundred bytes
+const MAX_HEADER_XDR_LEN: usize = 4096;
+
 /// Ledger ingestion service that fetches and persists ledgers sequentially
 pub struct LedgerIngestionService {
     rpc_client: Arc<StellarRpcClient>,
     pool: SqlitePool,
+    max_batch_attempts: u32,
+    failed_batch: Mutex<Option<FailedBatch>>,
+}
+
+/// Consecutive failures of the batch starting at `start_ledger`
+#[derive(Debug, Clone, Copy)]
+struct FailedBatch {
+    start_ledger: u64,
+    attempts: u32,
 }
 
 /// Represents a payment operation extracted from a ledger
@@ -27,32 +45,105 @@ pub struct ExtractedPayment {
 
 impl LedgerIngestionService {
     pub fn new(rpc_client: Arc<StellarRpcClient>, pool: SqlitePool) -> Self {
-        Self { rpc_client, pool }
+        Self {
+            rpc_client,
+            pool,
+            max_batch_attempts: DEFAULT_MAX_BATCH_ATTEMPTS,
+            failed_batch: Mutex::new(None),
+        }
+    }
+
+    /// Set how many times a batch is attempted before it is dead-lettered
+    pub fn with_max_batch_attempts(mut self, max_batch_attempts: u32) -> Self {
+        self.max_batch_attempts = max_batch_attempts.max(1);
+        self
     }
 
     /// I'm running the main ingestion loop - fetches ledgers and persists them
+    ///
+    /// A requeued dead-letter range is retried before new ledgers. A fetched
+    /// batch that keeps failing to process is recorded in `ingestion_failures`
+    /// and skipped once it has been attempted `max_batch_attempts` times, so
+    /// ingestion keeps moving. RPC failures are returned as errors and leave the
+    /// resume point where it is, and an empty batch (caught up with the tip)
+    /// returns 0.
     pub async fn run_ingestion(&self, batch_size: u32) -> Result<u64> {
-        let cursor = self.get_cursor().await?;
+        if let Some(count) = self.retry_requeued_failure().await? {
+            return Ok(count);
+        }
+
         let start_ledger = match self.get_last_ledger().await? {
-            Some(l) => Some(l + 1),
+            Some(l) => l + 1,
             None => {
                 let health = self.rpc_client.check_health().await.context("Failed to check health")?;
-                Some(health.oldest_ledger)
+                health.oldest_ledger
             },
         };
 
+        let result = self.fetch_batch(start_ledger, batch_size).await?;
+        self.apply_or_dead_letter(start_ledger, &result).await
+    }
+
+    /// Fetch one batch starting at `start_ledger`
+    async fn fetch_batch(&self, start_ledger: u64, batch_size: u32) -> Result<GetLedgersResult> {
+        let cursor = self.get_cursor().await?;
+
         info!(
-            "Starting ingestion from ledger {:?}, cursor: {:?}",
+            "Starting ingestion from ledger {}, cursor: {:?}",
             start_ledger, cursor
         );
 
-        let result = self
-            .rpc_client
-            .fetch_ledgers(start_ledger, batch_size, cursor.as_deref())
+        self.rpc_client
+            .fetch_ledgers(Some(start_ledger), batch_size, cursor.as_deref())
             .await
-            .context("Failed to fetch ledgers")?;
+            .context("Failed to fetch ledgers")
+    }
+
+    /// Apply a fetched batch, dead-lettering its range once it has failed
+    /// `max_batch_attempts` times
+    async fn apply_or_dead_letter(
+        &self,
+        start_ledger: u64,
+        result: &GetLedgersResult,
+    ) -> Result<u64> {
+        match self.apply_batch(result).await {
+            Ok(count) => {
+                *self.failed_batch.lock().unwrap() = None;
+                Ok(count)
+            }
+            Err(e) => {
+                let attempts = self.record_failed_attempt(start_ledger);
+                if attempts < self.max_batch_attempts {
+                    warn!(
+                        "Ingestion of batch at ledger {} failed (attempt {}/{}): {:#}",
+                        start_ledger, attempts, self.max_batch_attempts, e
+                    );
+                    return Err(e);
+                }
+
+                let end_ledger = result.ledgers.last().map_or(start_ledger, |l| l.sequence);
+                error!(
+                    "DEAD-LETTERING ledgers {}-{} after {} failed attempts, skipping ahead: {:#}",
+                    start_ledger, end_ledger, attempts, e
+                );
+                self.dead_letter(start_ledger, end_ledger, attempts, &e).await?;
+                self.skip_to(end_ledger).await?;
+                *self.failed_batch.lock().unwrap() = None;
+                Ok(0)
+            }
+        }
+    }
 
-        let count = self.process_ledgers(&result).await?;
+    /// Persist a fetched batch and move the checkpoint past it
+    ///
+    /// An empty batch leaves the checkpoint untouched.
+    async fn apply_batch(&self, result: &GetLedgersResult) -> Result<u64> {
+        if result.ledgers.is_empty() {
+            debug!("No new ledgers, caught up with the tip");
+            return Ok(0);
+        }
+
+        let count = self.process_ledgers(result).await?;
 
         // I'm saving cursor for restart safety
         if let Some(new_cursor) = &result.cursor {
@@ -63,16 +154,134 @@ impl LedgerIngestionService {
         Ok(count)
     }
 
+    /// Count a failed attempt at the batch starting at `start_ledger`
+    fn record_failed_attempt(&self, start_ledger: u64) -> u32 {
+        let mut failed = self.failed_batch.lock().unwrap();
+        let attempts = match *failed {
+            Some(batch) if batch.start_ledger == start_ledger => batch.attempts + 1,
+            _ => 1,
+        };
+        *failed = Some(FailedBatch {
+            start_ledger,
+            attempts,
+        });
+        attempts
+    }
+
+    /// Record a ledger range that exhausted its retries
+    async fn dead_letter(
+        &self,
+        start_ledger: u64,
+        end_ledger: u64,
+        attempts: u32,
+        error: &anyhow::Error,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO ingestion_failures (start_ledger, end_ledger, attempts, last_error)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(start_ledger as i64)
+        .bind(end_ledger as i64)
+        .bind(attempts as i64)
+        .bind(format!("{:#}", error))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Retry the oldest requeued dead-letter range, if any
+    ///
+    /// Returns `None` when nothing is requeued. The range is marked `resolved`
+    /// on success and dead-lettered again if it fails to process; an RPC
+    /// failure is returned and leaves it requeued. The cursor is untouched.
+    async fn retry_requeued_failure(&self) -> Result<Option<u64>> {
+        let row: Option<(i64, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT id, start_ledger, end_ledger FROM ingestion_failures
+            WHERE status = 'requeued'
+            ORDER BY updated_at ASC, id ASC
+            LIMIT 1
+            "#,
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some((id, start_ledger, end_ledger)) = row else {
+            return Ok(None);
+        };
+
+        info!("Retrying dead-lettered ledgers {}-{}", start_ledger, end_ledger);
+        let limit = (end_ledger - start_ledger + 1).max(1) as u32;
+        let result = self
+            .rpc_client
+            .fetch_ledgers(Some(start_ledger as u64), limit, None)
+            .await
+            .context("Failed to fetch ledgers")?;
+
+        match self.process_ledgers(&result).await {
+            Ok(count) => {
+                sqlx::query(
+                    "UPDATE ingestion_failures SET status = 'resolved', updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+                )
+                .bind(id)
+                .execute(&self.pool)
+                .await?;
+                Ok(Some(count))
+            }
+            Err(e) => {
+                error!(
+                    "Requeued ledgers {}-{} failed again: {:#}",
+                    start_ledger, end_ledger, e
+                );
+                sqlx::query(
+                    r#"
+                    UPDATE ingestion_failures
+                    SET status = 'dead_lettered', attempts = attempts + 1, last_error = $1,
+                        updated_at = CURRENT_TIMESTAMP
+                    WHERE id = $2
+                    "#,
+                )
+                .bind(format!("{:#}", e))
+                .bind(id)
+                .execute(&self.pool)
+                .await?;
+                Ok(Some(0))
+            }
+        }
+    }
+
     /// I'm processing and persisting fetched ledgers
+    ///
+    /// Ledgers whose header shows no transactions are counted without
+    /// being persisted or fetching their payments. Every close time is decoded
+    /// before anything is written, so a malformed batch persists nothing.
     async fn process_ledgers(&self, result: &GetLedgersResult) -> Result<u64> {
+        let close_times = result
+            .ledgers
+            .iter()
+            .map(|ledger| {
+                self.parse_ledger_time(&ledger.ledger_close_time)
+                    .with_context(|| format!("Invalid close time for ledger {}", ledger.sequence))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
         let mut count = 0u64;
+        let mut skipped = 0u64;
 
-        for ledger in &result.ledgers {
-            if let Err(e) = self.persist_ledger(ledger).await {
-                warn!("Failed to persist ledger {}: {}", ledger.sequence, e);
+        for (ledger, close_time) in result.ledgers.iter().zip(close_times) {
+            if is_empty_ledger(ledger) {
+                debug!("Skipping empty ledger {}", ledger.sequence);
+                skipped += 1;
+                count += 1;
                 continue;
             }
 
+            self.persist_ledger(ledger, close_time)
+                .await
+                .with_context(|| format!("Failed to persist ledger {}", ledger.sequence))?;
+
             // Fetch real payments from Horizon
             match self.rpc_client.fetch_payments_for_ledger(ledger.sequence).await {
                 Ok(payments) => {
@@ -103,14 +312,12 @@ impl LedgerIngestionService {
             count += 1;
         }
 
-        info!("Processed {} ledgers", count);
+        info!("Processed {} ledgers ({} empty)", count, skipped);
         Ok(count)
     }
 
     /// I'm persisting a single ledger to the database
-    async fn persist_ledger(&self, ledger: &RpcLedger) -> Result<()> {
-        let close_time = self.parse_ledger_time(&ledger.ledger_close_time)?;
-
+    async fn persist_ledger(&self, ledger: &RpcLedger, close_time: DateTime<Utc>) -> Result<()> {
         sqlx::query(
             r#"
             INSERT INTO ledgers (sequence, hash, close_time, transaction_count, operation_count)
@@ -209,9 +416,283 @@ impl LedgerIngestionService {
         Ok(())
     }
 
+    /// Move the resume point past `last_ledger`, dropping the RPC cursor
+    async fn skip_to(&self, last_ledger: u64) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO ingestion_cursor (id, last_ledger_sequence, cursor, updated_at)
+            VALUES (1, $1, NULL, CURRENT_TIMESTAMP)
+            ON CONFLICT (id) DO UPDATE SET
+                last_ledger_sequence = EXCLUDED.last_ledger_sequence,
+                cursor = NULL,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(last_ledger as i64)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
     fn parse_ledger_time(&self, timestamp_str: &str) -> Result<DateTime<Utc>> {
         // I'm parsing unix timestamp string to DateTime
-        let ts: i64 = timestamp_str.parse().unwrap_or(0);
-        Ok(Utc.timestamp_opt(ts, 0).single().unwrap_or_else(Utc::now))
+        let ts: i64 = timestamp_str
+            .parse()
+            .with_context(|| format!("Unparseable timestamp {:?}", timestamp_str))?;
+        Utc.timestamp_opt(ts, 0)
+            .single()
+            .with_context(|| format!("Timestamp {} is out of range", ts))
+    }
+}
+
+/// Whether `ledger` applied no transactions
+///
+/// Only the header is decoded: its `tx_set_result_hash` is the hash of an
+/// empty `TransactionResultSet` exactly when the ledger has no transactions.
+/// A missing or undecodable header returns `false`, so the ledger goes
+/// through full processing.
+pub fn is_empty_ledger(ledger: &RpcLedger) -> bool {
+    let Some(header_xdr) = ledger.header_xdr.as_deref() else {
+        return false;
+    };
+    let Ok(bytes) = base64::engine::general_purpose::STANDARD.decode(header_xdr) else {
+        return false;
+    };
+    let limits = Limits {
+        depth: 16,
+        len: MAX_HEADER_XDR_LEN,
+    };
+    match LedgerHeaderHistoryEntry::from_xdr(bytes, limits) {
+        Ok(entry) => entry.header.tx_set_result_hash.0 == empty_result_set_hash(),
+        Err(_) => false,
+    }
+}
+
+/// SHA-256 of an XDR `TransactionResultSet` with no results
+fn empty_result_set_hash() -> [u8; 32] {
+    Sha256::digest([0u8; 4]).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::stellar::MOCK_LATEST_LEDGER;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        pool
+    }
+
+    fn unreachable_rpc() -> Arc<StellarRpcClient> {
+        Arc::new(StellarRpcClient::new(
+            "http://127.0.0.1:1".to_string(),
+            "http://127.0.0.1:1".to_string(),
+            false,
+        ))
+    }
+
+    /// Base64 header of a ledger whose transaction results hash to `tx_set_result_hash`
+    fn ledger_header(sequence: u32, tx_set_result_hash: [u8; 32]) -> String {
+        use stellar_xdr::curr::{
+            Hash, LedgerHeader, LedgerHeaderExt, LedgerHeaderHistoryEntryExt, StellarValue,
+            StellarValueExt, TimePoint, WriteXdr,
+        };
+
+        let header = LedgerHeader {
+            ledger_version: 21,
+            previous_ledger_hash: Hash([0; 32]),
+            scp_value: StellarValue {
+                tx_set_hash: Hash([0; 32]),
+                close_time: TimePoint(0),
+                upgrades: Default::default(),
+                ext: StellarValueExt::Basic,
+            },
+            tx_set_result_hash: Hash(tx_set_result_hash),
+            bucket_list_hash: Hash([0; 32]),
+            ledger_seq: sequence,
+            total_coins: 0,
+            fee_pool: 0,
+            inflation_seq: 0,
+            id_pool: 0,
+            base_fee: 100,
+            base_reserve: 5_000_000,
+            max_tx_set_size: 100,
+            skip_list: [Hash([0; 32]), Hash([0; 32]), Hash([0; 32]), Hash([0; 32])],
+            ext: LedgerHeaderExt::V0,
+        };
+        let entry = LedgerHeaderHistoryEntry {
+            hash: Hash([0; 32]),
+            header,
+            ext: LedgerHeaderHistoryEntryExt::V0,
+        };
+        base64::engine::general_purpose::STANDARD.encode(entry.to_xdr(Limits::none()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_empty_ledger_advances_checkpoint_without_processing() {
+        let pool = setup_pool().await;
+        // Mock RPC has payments for every ledger, so any payment fetch would show
+        let service = LedgerIngestionService::new(
+            Arc::new(StellarRpcClient::new_with_defaults(true)),
+            pool.clone(),
+        );
+        let ledger = |sequence: u64, header_xdr: Option<String>| RpcLedger {
+            hash: format!("hash_{}", sequence),
+            sequence,
+            ledger_close_time: "1700000000".to_string(),
+            header_xdr,
+            metadata_xdr: None,
+        };
+        let empty = empty_result_set_hash();
+        let batch = GetLedgersResult {
+            ledgers: vec![
+                ledger(500, Some(ledger_header(500, empty))),
+                ledger(501, Some(ledger_header(501, empty))),
+            ],
+            latest_ledger: 501,
+            oldest_ledger: 1,
+            cursor: Some("501".to_string()),
+        };
+        assert!(is_empty_ledger(&batch.ledgers[0]));
+        let busy = ledger(502, Some(ledger_header(502, [7; 32])));
+        assert!(!is_empty_ledger(&busy));
+        assert!(!is_empty_ledger(&ledger(503, Some("mock".to_string()))));
+        assert!(!is_empty_ledger(&ledger(504, None)));
+
+        assert_eq!(service.apply_batch(&batch).await.unwrap(), 2);
+
+        assert_eq!(service.get_last_ledger().await.unwrap(), Some(501));
+        for table in ["ledgers", "transactions", "ledger_payments"] {
+            let (rows,): (i64,) = sqlx::query_as(&format!("SELECT COUNT(*) FROM {}", table))
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(rows, 0, "{} should be untouched", table);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rpc_failures_are_not_dead_lettered() {
+        let pool = setup_pool().await;
+        let service =
+            LedgerIngestionService::new(unreachable_rpc(), pool.clone()).with_max_batch_attempts(2);
+        service.skip_to(99).await.unwrap();
+
+        for _ in 0..3 {
+            assert!(service.run_ingestion(5).await.is_err());
+        }
+        let (dead_lettered,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM ingestion_failures")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(dead_lettered, 0);
+        assert_eq!(service.get_last_ledger().await.unwrap(), Some(99));
+    }
+
+    #[tokio::test]
+    async fn test_caught_up_batch_keeps_resume_point() {
+        let pool = setup_pool().await;
+        let service = LedgerIngestionService::new(
+            Arc::new(StellarRpcClient::new_with_defaults(true)),
+            pool.clone(),
+        )
+        .with_max_batch_attempts(1);
+        service.skip_to(MOCK_LATEST_LEDGER).await.unwrap();
+
+        assert_eq!(service.run_ingestion(5).await.unwrap(), 0);
+        assert_eq!(
+            service.get_last_ledger().await.unwrap(),
+            Some(MOCK_LATEST_LEDGER)
+        );
+        let (dead_lettered,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM ingestion_failures")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(dead_lettered, 0);
+    }
+
+    #[tokio::test]
+    async fn test_failing_batch_is_dead_lettered_after_max_attempts() {
+        let pool = setup_pool().await;
+        let service =
+            LedgerIngestionService::new(unreachable_rpc(), pool.clone()).with_max_batch_attempts(2);
+        service.skip_to(99).await.unwrap();
+        // Ledger 102 has a close time that can't be decoded
+        let batch = GetLedgersResult {
+            ledgers: (100..=104)
+                .map(|sequence| RpcLedger {
+                    hash: format!("hash_{}", sequence),
+                    sequence,
+                    ledger_close_time: if sequence == 102 {
+                        "not-a-timestamp".to_string()
+                    } else {
+                        "1700000000".to_string()
+                    },
+                    header_xdr: None,
+                    metadata_xdr: None,
+                })
+                .collect(),
+            latest_ledger: 104,
+            oldest_ledger: 1,
+            cursor: Some("104".to_string()),
+        };
+
+        // First attempt fails and is retried on the next pass
+        assert!(service.apply_or_dead_letter(100, &batch).await.is_err());
+        let (dead_lettered,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM ingestion_failures")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(dead_lettered, 0);
+        assert_eq!(service.get_last_ledger().await.unwrap(), Some(99));
+
+        // Second attempt exhausts the retries: the range is recorded and skipped
+        assert_eq!(service.apply_or_dead_letter(100, &batch).await.unwrap(), 0);
+        let row: (i64, i64, i64, String) = sqlx::query_as(
+            "SELECT start_ledger, end_ledger, attempts, status FROM ingestion_failures",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(row, (100, 104, 2, "dead_lettered".to_string()));
+        assert_eq!(service.get_last_ledger().await.unwrap(), Some(104));
+
+        // Nothing from the malformed batch was written
+        let (ledgers,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM ledgers")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(ledgers, 0);
+    }
+
+    #[tokio::test]
+    async fn test_requeued_range_is_retried_before_new_ledgers() {
+        let pool = setup_pool().await;
+        sqlx::query(
+            "INSERT INTO ingestion_failures (start_ledger, end_ledger, attempts, last_error, status) VALUES (10, 12, 3, 'boom', 'requeued')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let mock = LedgerIngestionService::new(
+            Arc::new(StellarRpcClient::new_with_defaults(true)),
+            pool.clone(),
+        );
+        assert_eq!(mock.run_ingestion(5).await.unwrap(), 3);
+
+        let (status,): (String,) = sqlx::query_as("SELECT status FROM ingestion_failures")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(status, "resolved");
+        // The resume point is not moved by a requeued retry
+        assert_eq!(mock.get_last_ledger().await.unwrap(), None);
     }
 }
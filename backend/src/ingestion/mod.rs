@@ -1,22 +1,86 @@
 // I'm exporting the ledger ingestion module as required by issue #2
+pub mod availability;
+pub mod config;
 pub mod ledger;
+pub mod reliability;
+pub mod retry_budget;
 
 use anyhow::{Context, Result};
+use chrono::Utc;
+use dashmap::DashMap;
 use serde::Serialize;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::{info, warn};
 
-use crate::database::Database;
+use crate::analytics::health::{PendingStatus, StatusHysteresis, StatusThresholds};
+use crate::database::{AnchorRpcUpdate, Database};
+use self::availability::{availability, DEFAULT_AVAILABILITY_WINDOW, DEFAULT_AVAILABILITY_WINDOWS};
+use self::reliability::{
+    decayed_reliability_score, TransactionOutcome, DEFAULT_RELIABILITY_HALF_LIFE,
+};
+use crate::models::Anchor;
 use crate::rpc::StellarRpcClient;
+use crate::services::webhook::{detect_status_transition, WebhookService};
 
 pub struct DataIngestionService {
     rpc_client: Arc<StellarRpcClient>,
     db: Arc<Database>,
+    webhooks: Arc<WebhookService>,
+    reliability_half_life: Duration,
+    status_thresholds: StatusThresholds,
+    status_hysteresis: StatusHysteresis,
+    /// Status changes waiting on more syncs, keyed by anchor id
+    pending_statuses: DashMap<String, PendingStatus>,
+    availability_window: Duration,
+    availability_windows: u32,
 }
 
+/// Payments fetched per anchor on each sync
+const ANCHOR_PAYMENTS_LIMIT: u32 = 100;
+
 impl DataIngestionService {
-    pub fn new(rpc_client: Arc<StellarRpcClient>, db: Arc<Database>) -> Self {
-        Self { rpc_client, db }
+    pub fn new(
+        rpc_client: Arc<StellarRpcClient>,
+        db: Arc<Database>,
+        webhooks: Arc<WebhookService>,
+    ) -> Self {
+        Self {
+            rpc_client,
+            db,
+            webhooks,
+            reliability_half_life: DEFAULT_RELIABILITY_HALF_LIFE,
+            status_thresholds: StatusThresholds::default(),
+            status_hysteresis: StatusHysteresis::default(),
+            pending_statuses: DashMap::new(),
+            availability_window: DEFAULT_AVAILABILITY_WINDOW,
+            availability_windows: DEFAULT_AVAILABILITY_WINDOWS,
+        }
+    }
+
+    /// Set the reliability cutoffs used to derive anchor status
+    pub fn with_status_thresholds(mut self, thresholds: StatusThresholds) -> Self {
+        self.status_thresholds = thresholds;
+        self
+    }
+
+    /// Set how far past a threshold, and for how many syncs, before status changes
+    pub fn with_status_hysteresis(mut self, hysteresis: StatusHysteresis) -> Self {
+        self.status_hysteresis = hysteresis;
+        self
+    }
+
+    /// Set the half-life used to weight transactions in the reliability score
+    pub fn with_reliability_half_life(mut self, half_life: Duration) -> Self {
+        self.reliability_half_life = half_life;
+        self
+    }
+
+    /// Measure availability over the last `windows` windows of `window` each
+    pub fn with_availability_windows(mut self, window: Duration, windows: u32) -> Self {
+        self.availability_window = window;
+        self.availability_windows = windows;
+        self
     }
 
     /// Sync all metrics from Stellar network
@@ -33,28 +97,81 @@ impl DataIngestionService {
     pub async fn sync_anchor_metrics(&self) -> Result<()> {
         info!("Syncing anchor metrics from Stellar network");
 
-        let anchors = self.db.list_anchors(0, 100).await?;
+        self.backfill(false).await?;
+
+        Ok(())
+    }
+
+    /// Recompute anchor metrics from RPC.
+    ///
+    /// With `dry_run` set, every delta is computed and returned but nothing is
+    /// written to the database and no webhooks are sent.
+    pub async fn backfill(&self, dry_run: bool) -> Result<IngestionSummary> {
+        let anchors = self.db.list_anchors(100, 0).await?;
+        let mut summary = IngestionSummary {
+            dry_run,
+            ..Default::default()
+        };
 
         for anchor in anchors {
-            match self.process_anchor_metrics(&anchor.stellar_account).await {
+            let (update, pending) = match self.compute_anchor_update(&anchor).await {
+                Ok(Some(computed)) => computed,
+                Ok(None) => continue,
+                Err(e) => {
+                    warn!("Failed to compute metrics for anchor {}: {}", anchor.name, e);
+                    summary.anchors_failed += 1;
+                    continue;
+                }
+            };
+
+            summary.anchors_processed += 1;
+            let delta = AnchorDelta::new(&anchor, &update);
+            if delta.has_changes() {
+                if delta.old_status != delta.new_status {
+                    summary.status_changes += 1;
+                }
+                summary.deltas.push(delta);
+            }
+
+            if dry_run {
+                continue;
+            }
+
+            if let Some(pending) = pending {
+                self.pending_statuses.insert(anchor.id.clone(), pending);
+            } else {
+                self.pending_statuses.remove(&anchor.id);
+            }
+
+            match self.apply_anchor_update(&anchor, update).await {
                 Ok(_) => info!("Updated metrics for anchor: {}", anchor.name),
-                Err(e) => warn!("Failed to update anchor {}: {}", anchor.name, e),
+                Err(e) => {
+                    warn!("Failed to update anchor {}: {}", anchor.name, e);
+                    summary.anchors_failed += 1;
+                }
             }
         }
 
-        Ok(())
+        summary.anchors_changed = summary.deltas.len();
+        Ok(summary)
     }
 
-    /// Process metrics for a single anchor
-    async fn process_anchor_metrics(&self, account_id: &str) -> Result<()> {
+    /// Compute fresh metrics for a single anchor, or `None` if it has no payments
+    ///
+    /// Also returns the status change still waiting on later syncs, if any.
+    async fn compute_anchor_update(
+        &self,
+        anchor: &Anchor,
+    ) -> Result<Option<(AnchorRpcUpdate, Option<PendingStatus>)>> {
+        let account_id = anchor.stellar_account.as_str();
         let payments = self
             .rpc_client
-            .fetch_account_payments(account_id, 100)
+            .fetch_account_payments(account_id, ANCHOR_PAYMENTS_LIMIT)
             .await
             .context("Failed to fetch payments")?;
 
         if payments.is_empty() {
-            return Ok(());
+            return Ok(None);
         }
 
         let mut successful = 0;
@@ -62,11 +179,20 @@ impl DataIngestionService {
         let mut total_volume = 0.0;
         let settlement_times = Vec::new(); // Removed mut as it's never pushed to
 
+        let now = Utc::now();
+        let mut outcomes = Vec::with_capacity(payments.len());
+
         for payment in &payments {
             let amount: f64 = payment.amount.parse().unwrap_or(0.0);
             total_volume += amount;
 
             successful += 1;
+            outcomes.push(TransactionOutcome {
+                timestamp: chrono::DateTime::parse_from_rfc3339(&payment.created_at)
+                    .map(|t| t.with_timezone(&Utc))
+                    .unwrap_or(now),
+                successful: true,
+            });
         }
 
         let total_transactions = (successful + failed) as i64;
@@ -76,7 +202,8 @@ impl DataIngestionService {
             0.0
         };
 
-        let reliability_score = self.calculate_reliability_score(success_rate, failed as i64);
+        let reliability_score =
+            decayed_reliability_score(&outcomes, now, self.reliability_half_life);
 
         let avg_settlement_time = if !settlement_times.is_empty() {
             settlement_times.iter().sum::<i32>() / settlement_times.len() as i32
@@ -84,34 +211,56 @@ impl DataIngestionService {
             1000
         };
 
-        let status = if success_rate >= 98.0 {
-            "green"
-        } else if success_rate >= 95.0 {
-            "yellow"
-        } else {
-            "red"
-        };
+        let pending = self
+            .pending_statuses
+            .get(&anchor.id)
+            .map(|entry| entry.value().clone());
+        let decision = self.status_hysteresis.next_status(
+            &self.status_thresholds,
+            &anchor.status,
+            success_rate,
+            pending,
+        );
 
-        self.db
-            .update_anchor_from_rpc(crate::database::AnchorRpcUpdate {
-                stellar_account: account_id.to_string(),
-                total_transactions,
-                successful_transactions: successful as i64,
-                failed_transactions: failed as i64,
-                total_volume_usd: total_volume,
-                avg_settlement_time_ms: avg_settlement_time,
-                reliability_score,
-                status: status.to_string(),
-            })
-            .await?;
+        // A full page may not reach back over every window
+        let activity: Vec<_> = outcomes.iter().map(|o| o.timestamp).collect();
+        let observed_since = (payments.len() >= ANCHOR_PAYMENTS_LIMIT as usize)
+            .then(|| activity.iter().min().copied())
+            .flatten();
+        let availability = availability(
+            &activity,
+            now,
+            self.availability_window,
+            self.availability_windows,
+            observed_since,
+        );
 
-        Ok(())
+        let update = AnchorRpcUpdate {
+            stellar_account: account_id.to_string(),
+            total_transactions,
+            successful_transactions: successful as i64,
+            failed_transactions: failed as i64,
+            total_volume_usd: total_volume,
+            avg_settlement_time_ms: avg_settlement_time,
+            reliability_score,
+            status: decision.status.to_string(),
+            availability,
+        };
+        Ok(Some((update, decision.pending)))
     }
 
-    fn calculate_reliability_score(&self, success_rate: f64, failed_count: i64) -> f64 {
-        let base_score = success_rate / 100.0;
-        let penalty = (failed_count as f64 * 0.01).min(0.2);
-        (base_score - penalty).clamp(0.0, 1.0)
+    /// Persist computed metrics for an anchor and notify on status changes
+    async fn apply_anchor_update(&self, anchor: &Anchor, update: AnchorRpcUpdate) -> Result<()> {
+        let new_status = update.status.clone();
+        self.db.update_anchor_from_rpc(update).await?;
+
+        if let Some(change) = detect_status_transition(&anchor.id, &anchor.status, &new_status) {
+            if let Err(e) = self.webhooks.notify_anchor_status_change(&change).await {
+                warn!("Failed to dispatch status webhooks for {}: {}", anchor.name, e);
+            }
+        }
+
+        Ok(())
     }
 
     /// Get current network health status
@@ -133,6 +282,56 @@ pub struct NetworkHealth {
     pub ledger_retention: u64,
 }
 
+/// Metric change an ingestion pass makes (or would make) to an anchor
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AnchorDelta {
+    pub anchor_id: String,
+    pub stellar_account: String,
+    pub old_status: String,
+    pub new_status: String,
+    pub old_total_transactions: i64,
+    pub new_total_transactions: i64,
+    pub old_total_volume_usd: f64,
+    pub new_total_volume_usd: f64,
+    pub old_reliability_score: f64,
+    pub new_reliability_score: f64,
+}
+
+impl AnchorDelta {
+    fn new(anchor: &Anchor, update: &AnchorRpcUpdate) -> Self {
+        Self {
+            anchor_id: anchor.id.clone(),
+            stellar_account: anchor.stellar_account.clone(),
+            old_status: anchor.status.clone(),
+            new_status: update.status.clone(),
+            old_total_transactions: anchor.total_transactions,
+            new_total_transactions: update.total_transactions,
+            old_total_volume_usd: anchor.total_volume_usd,
+            new_total_volume_usd: update.total_volume_usd,
+            old_reliability_score: anchor.reliability_score,
+            new_reliability_score: update.reliability_score,
+        }
+    }
+
+    fn has_changes(&self) -> bool {
+        self.old_status != self.new_status
+            || self.old_total_transactions != self.new_total_transactions
+            || self.old_total_volume_usd != self.new_total_volume_usd
+            || self.old_reliability_score != self.new_reliability_score
+    }
+}
+
+/// Result of an ingestion pass
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IngestionSummary {
+    pub dry_run: bool,
+    pub anchors_processed: usize,
+    pub anchors_changed: usize,
+    pub anchors_failed: usize,
+    pub status_changes: usize,
+    pub deltas: Vec<AnchorDelta>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct IngestionStatus {
     pub last_ingested_ledger: u64,
@@ -153,11 +352,78 @@ impl DataIngestionService {
         let last_ingested = cursor_row.map(|r| r.0 as u64).unwrap_or(0);
 
         // We get network state
-        let health = self.rpc_client.check_health().await?;
+        let network_latest_ledger = self.rpc_client.latest_ledger_cached().await?;
         
         Ok(IngestionStatus {
             last_ingested_ledger: last_ingested,
-            network_latest_ledger: health.latest_ledger,
+            network_latest_ledger,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn seeded_service() -> (DataIngestionService, Arc<Database>) {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        let db = Arc::new(Database::new(pool));
+        let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+        let webhooks = Arc::new(WebhookService::new(Arc::clone(&db)));
+
+        (
+            DataIngestionService::new(rpc_client, Arc::clone(&db), webhooks),
+            db,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_backfill_leaves_db_unchanged() {
+        let (service, db) = seeded_service().await;
+        let before = db.list_anchors(100, 0).await.unwrap();
+
+        let summary = service.backfill(true).await.unwrap();
+
+        let after = db.list_anchors(100, 0).await.unwrap();
+        assert_eq!(
+            serde_json::to_value(&before).unwrap(),
+            serde_json::to_value(&after).unwrap()
+        );
+
+        // Mock RPC returns 100 successful payments for every seeded anchor
+        assert!(summary.dry_run);
+        assert_eq!(summary.anchors_processed, before.len());
+        assert_eq!(summary.anchors_changed, before.len());
+        assert_eq!(summary.status_changes, 1);
+
+        let anchor_usd = summary
+            .deltas
+            .iter()
+            .find(|d| d.anchor_id == "c2b2f2a2-2222-4222-a222-222222222222")
+            .unwrap();
+        assert_eq!(anchor_usd.old_status, "yellow");
+        assert_eq!(anchor_usd.new_status, "green");
+        assert_eq!(anchor_usd.old_total_transactions, 5000);
+        assert_eq!(anchor_usd.new_total_transactions, 100);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_applies_deltas() {
+        let (service, db) = seeded_service().await;
+
+        let summary = service.backfill(false).await.unwrap();
+        assert!(!summary.dry_run);
+
+        let anchors = db.list_anchors(100, 0).await.unwrap();
+        assert!(anchors.iter().all(|a| a.total_transactions == 100));
+        assert!(anchors.iter().all(|a| a.status == "green"));
+        assert!(anchors.iter().all(|a| a.availability.is_some()));
+    }
+}
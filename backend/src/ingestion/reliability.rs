@@ -0,0 +1,149 @@
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// Default half-life for transaction weights in the reliability score
+pub const DEFAULT_RELIABILITY_HALF_LIFE: Duration = Duration::from_secs(30 * 24 * 3600);
+
+/// Maximum penalty subtracted for (weighted) failed transactions
+const MAX_FAILURE_PENALTY: f64 = 0.2;
+
+/// Penalty per (weighted) failed transaction
+const FAILURE_PENALTY: f64 = 0.01;
+
+/// Outcome of a single transaction used for scoring
+#[derive(Debug, Clone, Copy)]
+pub struct TransactionOutcome {
+    pub timestamp: DateTime<Utc>,
+    pub successful: bool,
+}
+
+/// Weight of a transaction `age` old: halves every `half_life`
+pub fn decay_weight(age: chrono::Duration, half_life: Duration) -> f64 {
+    let half_life_secs = half_life.as_secs_f64();
+    if half_life_secs <= 0.0 {
+        return 1.0;
+    }
+    // Transactions timestamped in the future count as brand new
+    let age_secs = age.num_milliseconds().max(0) as f64 / 1000.0;
+    0.5_f64.powf(age_secs / half_life_secs)
+}
+
+/// Reliability score in `[0, 1]` with recent transactions weighted more heavily
+///
+/// Each transaction's weight halves every `half_life`. The score is the
+/// weighted success rate minus a penalty for weighted failures, so an old
+/// failure costs less than a recent one. With all transactions the same age
+/// this equals the unweighted score.
+pub fn decayed_reliability_score(
+    outcomes: &[TransactionOutcome],
+    now: DateTime<Utc>,
+    half_life: Duration,
+) -> f64 {
+    let mut total_weight = 0.0;
+    let mut success_weight = 0.0;
+    let mut failed_weight = 0.0;
+
+    for outcome in outcomes {
+        let weight = decay_weight(now - outcome.timestamp, half_life);
+        total_weight += weight;
+        if outcome.successful {
+            success_weight += weight;
+        } else {
+            failed_weight += weight;
+        }
+    }
+
+    if total_weight <= 0.0 {
+        return 0.0;
+    }
+
+    let base_score = success_weight / total_weight;
+    let penalty = (failed_weight * FAILURE_PENALTY).min(MAX_FAILURE_PENALTY);
+    (base_score - penalty).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcomes(now: DateTime<Utc>, profile: &[(i64, bool)]) -> Vec<TransactionOutcome> {
+        profile
+            .iter()
+            .map(|&(days_ago, successful)| TransactionOutcome {
+                timestamp: now - chrono::Duration::days(days_ago),
+                successful,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_decay_weight_halves_each_half_life() {
+        let half_life = Duration::from_secs(30 * 24 * 3600);
+        assert_eq!(decay_weight(chrono::Duration::zero(), half_life), 1.0);
+        assert!((decay_weight(chrono::Duration::days(30), half_life) - 0.5).abs() < 1e-9);
+        assert!((decay_weight(chrono::Duration::days(60), half_life) - 0.25).abs() < 1e-9);
+        assert_eq!(decay_weight(chrono::Duration::days(-1), half_life), 1.0);
+    }
+
+    #[test]
+    fn test_recent_failures_score_lower_than_old_failures() {
+        let now = Utc::now();
+        // Same totals: 8 successes and 2 failures each
+        let recovered = outcomes(
+            now,
+            &[
+                (365, false),
+                (360, false),
+                (5, true),
+                (4, true),
+                (3, true),
+                (3, true),
+                (2, true),
+                (2, true),
+                (1, true),
+                (0, true),
+            ],
+        );
+        let degrading = outcomes(
+            now,
+            &[
+                (365, true),
+                (360, true),
+                (300, true),
+                (250, true),
+                (200, true),
+                (150, true),
+                (100, true),
+                (60, true),
+                (1, false),
+                (0, false),
+            ],
+        );
+
+        let recovered_score =
+            decayed_reliability_score(&recovered, now, DEFAULT_RELIABILITY_HALF_LIFE);
+        let degrading_score =
+            decayed_reliability_score(&degrading, now, DEFAULT_RELIABILITY_HALF_LIFE);
+
+        assert!(recovered_score > 0.99);
+        assert!(degrading_score < 0.5);
+        assert!(recovered_score > degrading_score);
+    }
+
+    #[test]
+    fn test_uniform_age_matches_unweighted_score() {
+        let now = Utc::now();
+        let mut profile = vec![(0, true); 95];
+        profile.extend(vec![(0, false); 5]);
+
+        let score =
+            decayed_reliability_score(&outcomes(now, &profile), now, DEFAULT_RELIABILITY_HALF_LIFE);
+
+        // 0.95 success rate minus 5 * 0.01 failure penalty
+        assert!((score - 0.90).abs() < 1e-9);
+        assert_eq!(
+            decayed_reliability_score(&[], now, DEFAULT_RELIABILITY_HALF_LIFE),
+            0.0
+        );
+    }
+}
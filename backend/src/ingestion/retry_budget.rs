@@ -0,0 +1,153 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Default number of failed passes tolerated before ingestion is degraded
+pub const DEFAULT_RETRY_BUDGET: u32 = 10;
+
+/// Default time for one spent retry token to come back
+pub const DEFAULT_RETRY_REFILL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// What the ingestion loop should do after a failed pass
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// A token was spent; log the failure and retry after the normal error sleep
+    Retry,
+    /// The budget is spent; stay quiet and back off to the degraded sleep
+    Degraded,
+}
+
+/// Token bucket of retries shared by every ingestion task
+///
+/// Each failed pass spends one token and tokens trickle back at one per
+/// refill interval. Once the bucket is empty ingestion is degraded: a single
+/// warning is logged and callers back off until a pass succeeds again.
+pub struct RetryBudget {
+    capacity: u32,
+    refill_interval: Duration,
+    state: Mutex<BudgetState>,
+}
+
+#[derive(Debug)]
+struct BudgetState {
+    tokens: u32,
+    last_refill: Instant,
+    degraded: bool,
+}
+
+impl RetryBudget {
+    pub fn new(capacity: u32, refill_interval: Duration) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            refill_interval: refill_interval.max(Duration::from_millis(1)),
+            state: Mutex::new(BudgetState {
+                tokens: capacity.max(1),
+                last_refill: Instant::now(),
+                degraded: false,
+            }),
+        }
+    }
+
+    /// Spend a token for a failed pass
+    pub fn record_failure(&self) -> RetryDecision {
+        self.record_failure_at(Instant::now())
+    }
+
+    /// Clear the degraded state after a pass succeeds
+    pub fn record_success(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state.degraded {
+            state.degraded = false;
+            info!("Ingestion recovered, resuming normal retries");
+        }
+    }
+
+    pub fn is_degraded(&self) -> bool {
+        self.state.lock().unwrap().degraded
+    }
+
+    fn record_failure_at(&self, now: Instant) -> RetryDecision {
+        let mut state = self.state.lock().unwrap();
+        self.refill(&mut state, now);
+
+        if state.degraded {
+            return RetryDecision::Degraded;
+        }
+        if state.tokens > 0 {
+            state.tokens -= 1;
+            return RetryDecision::Retry;
+        }
+
+        state.degraded = true;
+        warn!(
+            "Ingestion degraded: retry budget of {} exhausted, backing off until a pass succeeds",
+            self.capacity
+        );
+        RetryDecision::Degraded
+    }
+
+    fn refill(&self, state: &mut BudgetState, now: Instant) {
+        let elapsed = now.saturating_duration_since(state.last_refill);
+        let earned = (elapsed.as_nanos() / self.refill_interval.as_nanos()) as u32;
+        if earned == 0 {
+            return;
+        }
+        state.tokens = state.tokens.saturating_add(earned).min(self.capacity);
+        state.last_refill += self.refill_interval * earned;
+    }
+}
+
+impl Default for RetryBudget {
+    fn default() -> Self {
+        Self::new(DEFAULT_RETRY_BUDGET, DEFAULT_RETRY_REFILL_INTERVAL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeated_failures_trip_degraded_state() {
+        let budget = RetryBudget::new(3, Duration::from_secs(60));
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert_eq!(budget.record_failure_at(now), RetryDecision::Retry);
+        }
+        assert!(!budget.is_degraded());
+
+        assert_eq!(budget.record_failure_at(now), RetryDecision::Degraded);
+        assert!(budget.is_degraded());
+        // Stays degraded rather than retrying as tokens trickle back
+        let later = now + Duration::from_secs(120);
+        assert_eq!(budget.record_failure_at(later), RetryDecision::Degraded);
+
+        budget.record_success();
+        assert!(!budget.is_degraded());
+        assert_eq!(budget.record_failure_at(later), RetryDecision::Retry);
+    }
+
+    #[test]
+    fn test_budget_refills_over_time() {
+        let budget = RetryBudget::new(2, Duration::from_secs(10));
+        let now = Instant::now();
+
+        budget.record_failure_at(now);
+        budget.record_failure_at(now);
+        assert_eq!(budget.state.lock().unwrap().tokens, 0);
+
+        // One token per interval, capped at the capacity
+        budget.record_failure_at(now + Duration::from_secs(15));
+        assert_eq!(budget.state.lock().unwrap().tokens, 0);
+        assert_eq!(
+            budget.record_failure_at(now + Duration::from_secs(25)),
+            RetryDecision::Retry
+        );
+        budget.refill(
+            &mut budget.state.lock().unwrap(),
+            now + Duration::from_secs(3600),
+        );
+        assert_eq!(budget.state.lock().unwrap().tokens, 2);
+    }
+}
@@ -0,0 +1,139 @@
+//! Lock-free latency histograms with fixed buckets
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use utoipa::ToSchema;
+
+/// Bucket upper bounds in microseconds; one extra bucket catches the rest
+const BUCKET_BOUNDS_US: [u64; 15] = [
+    50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 25_000, 50_000, 100_000, 250_000, 500_000,
+    1_000_000, 5_000_000,
+];
+
+/// Counts samples into fixed latency buckets
+///
+/// Recording is a couple of relaxed atomic adds, so it is safe on hot paths.
+/// Percentiles report the upper bound of the bucket holding the sample,
+/// which overstates latency by at most one bucket width.
+pub struct LatencyHistogram {
+    buckets: [AtomicU64; BUCKET_BOUNDS_US.len() + 1],
+    max_us: AtomicU64,
+}
+
+/// Percentiles of a histogram, in milliseconds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct LatencySnapshot {
+    pub count: u64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            max_us: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record(&self, elapsed: Duration) {
+        let us = elapsed.as_micros().min(u64::MAX as u128) as u64;
+        let bucket = BUCKET_BOUNDS_US
+            .iter()
+            .position(|bound| us <= *bound)
+            .unwrap_or(BUCKET_BOUNDS_US.len());
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.max_us.fetch_max(us, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> LatencySnapshot {
+        let counts: Vec<u64> = self
+            .buckets
+            .iter()
+            .map(|b| b.load(Ordering::Relaxed))
+            .collect();
+        let max_us = self.max_us.load(Ordering::Relaxed);
+        let count = counts.iter().sum();
+
+        LatencySnapshot {
+            count,
+            p50_ms: percentile_ms(&counts, count, max_us, 0.50),
+            p95_ms: percentile_ms(&counts, count, max_us, 0.95),
+            p99_ms: percentile_ms(&counts, count, max_us, 0.99),
+        }
+    }
+
+    pub fn reset(&self) {
+        for bucket in &self.buckets {
+            bucket.store(0, Ordering::Relaxed);
+        }
+        self.max_us.store(0, Ordering::Relaxed);
+    }
+}
+
+fn percentile_ms(counts: &[u64], total: u64, max_us: u64, quantile: f64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+
+    let rank = ((quantile * total as f64).ceil() as u64).max(1);
+    let mut seen = 0;
+    for (i, count) in counts.iter().enumerate() {
+        seen += count;
+        if seen >= rank {
+            // Never report more than the slowest sample actually seen
+            let bound_us = BUCKET_BOUNDS_US.get(i).copied().unwrap_or(max_us);
+            return bound_us.min(max_us) as f64 / 1000.0;
+        }
+    }
+    max_us as f64 / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles_from_recorded_samples() {
+        let histogram = LatencyHistogram::new();
+        // 90 fast reads, 9 slower ones and a single outlier
+        for _ in 0..90 {
+            histogram.record(Duration::from_micros(400));
+        }
+        for _ in 0..9 {
+            histogram.record(Duration::from_millis(8));
+        }
+        histogram.record(Duration::from_millis(300));
+
+        let snapshot = histogram.snapshot();
+        assert_eq!(snapshot.count, 100);
+        assert_eq!(snapshot.p50_ms, 0.5);
+        assert_eq!(snapshot.p95_ms, 10.0);
+        assert_eq!(snapshot.p99_ms, 10.0);
+        assert!(snapshot.p50_ms <= snapshot.p95_ms && snapshot.p95_ms <= snapshot.p99_ms);
+
+        histogram.record(Duration::from_millis(300));
+        histogram.record(Duration::from_millis(300));
+        assert_eq!(histogram.snapshot().p99_ms, 300.0);
+    }
+
+    #[test]
+    fn test_empty_and_reset() {
+        let histogram = LatencyHistogram::new();
+        assert_eq!(histogram.snapshot(), LatencySnapshot::default());
+
+        histogram.record(Duration::from_secs(30));
+        assert_eq!(histogram.snapshot().p50_ms, 30_000.0);
+
+        histogram.reset();
+        assert_eq!(histogram.snapshot(), LatencySnapshot::default());
+    }
+}
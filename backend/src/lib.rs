@@ -1,23 +1,36 @@
 pub mod analytics;
 pub mod api;
+pub mod audit;
 pub mod auth;
 pub mod auth_middleware;
 pub mod broadcast;
 pub mod cache;
 pub mod cache_invalidation;
 pub mod cache_middleware;
+pub mod corridor_feed;
+pub mod cursor;
 pub mod database;
 pub mod db;
+pub mod error;
 pub mod handlers;
+pub mod idempotency;
 pub mod ingestion;
+pub mod latency;
+pub mod log_redact;
+pub mod maintenance;
 pub mod ml;
 pub mod ml_handlers;
 pub mod models;
+pub mod pretty_json;
+pub mod readiness;
+pub mod retry;
+pub mod server_timing;
 pub mod services;
 pub mod snapshot;
 pub mod rate_limit;
 pub mod snapshot_handlers;
 pub mod state;
+pub mod trace_sampling;
 pub mod websocket;
 
 pub mod rpc;
@@ -0,0 +1,217 @@
+//! Masking of sensitive values in log output
+//!
+//! With `LOG_REDACT=true` every formatted log line passes through [`redact`]
+//! before it is written: Stellar account ids keep only their first and last
+//! four characters, and URL query strings are dropped.
+
+use std::io::{self, Write};
+
+use tracing_subscriber::fmt::MakeWriter;
+
+use crate::ingestion::config::parse_var;
+
+/// Length of a `G...` account id
+const ACCOUNT_ID_LEN: usize = 56;
+
+/// Characters kept at each end of a masked account id
+const ACCOUNT_ID_VISIBLE: usize = 4;
+
+const REDACTED_QUERY: &str = "?[REDACTED]";
+
+/// Mask account ids and strip URL query strings from `text`
+pub fn redact(text: &str) -> String {
+    mask_account_ids(&strip_query_strings(text))
+}
+
+fn strip_query_strings(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = find_url(rest) {
+        let end = rest[start..]
+            .find(is_url_end)
+            .map_or(rest.len(), |len| start + len);
+        match rest[start..end].find('?') {
+            Some(query) => {
+                out.push_str(&rest[..start + query]);
+                out.push_str(REDACTED_QUERY);
+            }
+            None => out.push_str(&rest[..end]),
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn find_url(text: &str) -> Option<usize> {
+    ["http://", "https://"]
+        .iter()
+        .filter_map(|scheme| text.find(scheme))
+        .min()
+}
+
+fn is_url_end(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '"' | '\'' | '<' | '>' | '`')
+}
+
+fn mask_account_ids(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = String::with_capacity(text.len());
+    let mut copied = 0;
+    let mut i = 0;
+    while i + ACCOUNT_ID_LEN <= bytes.len() {
+        if !is_account_id_at(bytes, i) {
+            i += 1;
+            continue;
+        }
+        let end = i + ACCOUNT_ID_LEN;
+        out.push_str(&text[copied..i + ACCOUNT_ID_VISIBLE]);
+        out.push_str("...");
+        out.push_str(&text[end - ACCOUNT_ID_VISIBLE..end]);
+        i = end;
+        copied = end;
+    }
+    out.push_str(&text[copied..]);
+    out
+}
+
+/// A whole base32 `G` address starts at `i`, not embedded in a longer word
+fn is_account_id_at(bytes: &[u8], i: usize) -> bool {
+    let end = i + ACCOUNT_ID_LEN;
+    bytes[i] == b'G'
+        && (i == 0 || !bytes[i - 1].is_ascii_alphanumeric())
+        && bytes[i + 1..end]
+            .iter()
+            .all(|b| b.is_ascii_uppercase() || (b'2'..=b'7').contains(b))
+        && !bytes.get(end).is_some_and(|b| b.is_ascii_alphanumeric())
+}
+
+/// Log writer factory that redacts lines from `inner` when enabled
+///
+/// The fmt layer hands each event to the writer in one `write` call, so
+/// values are never split across calls.
+pub struct RedactingMakeWriter<M> {
+    inner: M,
+    enabled: bool,
+}
+
+impl<M> RedactingMakeWriter<M> {
+    pub fn new(inner: M, enabled: bool) -> Self {
+        Self { inner, enabled }
+    }
+
+    /// Redact when `LOG_REDACT` is `true` (default `false`)
+    pub fn from_env(inner: M) -> anyhow::Result<Self> {
+        Self::from_lookup(inner, |name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(inner: M, lookup: impl Fn(&str) -> Option<String>) -> anyhow::Result<Self> {
+        let enabled = parse_var(&lookup, "LOG_REDACT", false)?;
+        Ok(Self::new(inner, enabled))
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for RedactingMakeWriter<M> {
+    type Writer = RedactingWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter {
+            inner: self.inner.make_writer(),
+            enabled: self.enabled,
+        }
+    }
+}
+
+pub struct RedactingWriter<W> {
+    inner: W,
+    enabled: bool,
+}
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !self.enabled {
+            return self.inner.write(buf);
+        }
+        let line = redact(&String::from_utf8_lossy(buf));
+        self.inner.write_all(line.as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    const ACCOUNT: &str = "GAHK7EEG2WWHVKDNT4CEQFZGKF2LGDSW2IVM4S5DP42RBW3K6BTODB4A";
+
+    #[test]
+    fn test_redacts_account_id_and_query_string() {
+        let line = format!(
+            "Request failed for {}: https://horizon.stellar.org/accounts/{}/payments?cursor=now&limit=200 (status 500)",
+            ACCOUNT, ACCOUNT
+        );
+        assert_eq!(
+            redact(&line),
+            "Request failed for GAHK...DB4A: \
+             https://horizon.stellar.org/accounts/GAHK...DB4A/payments?[REDACTED] (status 500)"
+        );
+    }
+
+    #[test]
+    fn test_leaves_other_text_alone() {
+        let text = format!(
+            "ledger 1234 at https://horizon.stellar.org/ledgers, key X{} and {}Z",
+            ACCOUNT, ACCOUNT
+        );
+        assert_eq!(redact(&text), text);
+        // One character short of an account id
+        assert_eq!(redact(&ACCOUNT[..55]), &ACCOUNT[..55]);
+    }
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_writer_redacts_only_when_enabled() {
+        for (value, expected) in [
+            (None, ACCOUNT.to_string()),
+            (Some("true"), "GAHK...DB4A".to_string()),
+        ] {
+            let captured = Captured::default();
+            let sink = captured.clone();
+            let make_writer =
+                RedactingMakeWriter::from_lookup(move || sink.clone(), |_| value.map(String::from))
+                    .unwrap();
+            make_writer
+                .make_writer()
+                .write_all(ACCOUNT.as_bytes())
+                .unwrap();
+            assert_eq!(
+                String::from_utf8(captured.0.lock().unwrap().clone()).unwrap(),
+                expected
+            );
+        }
+
+        assert!(RedactingMakeWriter::from_lookup(std::io::stdout, |_| Some("yes".into())).is_err());
+    }
+}
@@ -1,29 +1,68 @@
 use anyhow::Result;
 use axum::{
+    extract::DefaultBodyLimit,
     routing::{get, put},
-    Router,
+    Extension, Router,
 };
 use dotenv::dotenv;
 use std::sync::Arc;
 use tower_http::cors::{Any, CorsLayer};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+use stellar_insights_backend::analytics::health::{StatusHysteresis, StatusThresholds};
 use stellar_insights_backend::api::anchors_cached::get_anchors;
-use stellar_insights_backend::api::corridors_cached::{get_corridor_detail, list_corridors};
+use stellar_insights_backend::api::pagination::{PageLimits, PublicBasePath};
+use stellar_insights_backend::api::precision::ResponsePrecision;
+use stellar_insights_backend::api::corridors_cached::{
+    export_corridors, get_corridor_detail, get_corridor_history, get_corridor_peers,
+    get_corridor_rollup, get_corridor_summary, get_corridors_batch, list_corridors,
+};
+use stellar_insights_backend::api::corridor_alerts;
+use stellar_insights_backend::api::corridor_import;
 use stellar_insights_backend::api::cache_stats;
 use stellar_insights_backend::api::metrics_cached;
+use stellar_insights_backend::api::webhooks;
+use stellar_insights_backend::audit::audit_middleware;
 use stellar_insights_backend::auth::AuthService;
+use stellar_insights_backend::broadcast::{spawn_cache_stats_stream, DEFAULT_CACHE_STATS_INTERVAL};
 use stellar_insights_backend::auth_middleware::auth_middleware;
-use stellar_insights_backend::cache::{CacheConfig, CacheManager};
+use stellar_insights_backend::cache::{
+    connect_redis, is_redis_config_error, CacheConfig, CacheManager, REDIS_RECONNECT_INTERVAL,
+};
 use stellar_insights_backend::cache_invalidation::CacheInvalidationService;
+use stellar_insights_backend::cache_middleware::{
+    cache_bypass_middleware, cache_control_middleware, CacheBypass, CacheControl,
+};
 use stellar_insights_backend::database::Database;
+use stellar_insights_backend::db::backend::DatabaseBackend;
+use stellar_insights_backend::db::slow_query::SlowQueryLog;
 use stellar_insights_backend::handlers::*;
+use stellar_insights_backend::ingestion::config::IngestionConfig;
+use stellar_insights_backend::ingestion::retry_budget::{RetryBudget, RetryDecision};
 use stellar_insights_backend::ingestion::DataIngestionService;
-use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::idempotency::IdempotencyStore;
+use stellar_insights_backend::log_redact::RedactingMakeWriter;
+use stellar_insights_backend::maintenance::{maintenance_middleware, MaintenanceMode};
+use stellar_insights_backend::ml::{FallbackStrategy, MLService};
+use stellar_insights_backend::ml_handlers;
+use stellar_insights_backend::pretty_json::pretty_json_middleware;
+use stellar_insights_backend::readiness::{self, Readiness, WARM_RETRY_DELAY};
+use stellar_insights_backend::server_timing::server_timing_middleware;
+use stellar_insights_backend::trace_sampling::{trace_sampling_middleware, TraceSampler};
+use stellar_insights_backend::rpc::stellar::{DEFAULT_MAX_IN_FLIGHT_REQUESTS, DEFAULT_REQUEST_TIMEOUT};
+use stellar_insights_backend::rpc::network::{DEFAULT_TESTNET_HORIZON_URL, DEFAULT_TESTNET_RPC_URL};
+use stellar_insights_backend::rpc::{Network, StellarRpcClient};
 use stellar_insights_backend::rpc_handlers;
+use stellar_insights_backend::rpc_handlers::{rpc_method_filter_middleware, RpcMethodFilter};
 use stellar_insights_backend::rate_limit::{RateLimiter, RateLimitConfig, rate_limit_middleware};
+use stellar_insights_backend::services::aggregation::AggregationService;
+use stellar_insights_backend::services::corridor_alerts::CorridorAlertService;
+use stellar_insights_backend::services::fx::FxService;
+use stellar_insights_backend::services::indexing::IndexingService;
+use stellar_insights_backend::services::retention::{MetricsRetentionConfig, MetricsRetentionService};
+use stellar_insights_backend::services::webhook::WebhookService;
 use stellar_insights_backend::state::AppState;
-use stellar_insights_backend::websocket::WsState;
+use stellar_insights_backend::websocket::{ws_handler, WsState};
 
 
 #[tokio::main]
@@ -32,13 +71,18 @@ async fn main() -> Result<()> {
     dotenv().ok();
 
     // Initialize tracing
+    let log_writer = RedactingMakeWriter::from_env(std::io::stdout)?;
+    let log_redacted = log_writer.enabled();
     tracing_subscriber::registry()
         .with(
             tracing_subscriber::EnvFilter::try_from_default_env()
                 .unwrap_or_else(|_| "backend=info,tower_http=debug".into()),
         )
-        .with(tracing_subscriber::fmt::layer())
+        .with(tracing_subscriber::fmt::layer().with_writer(log_writer))
         .init();
+    if log_redacted {
+        tracing::info!("Redacting account ids and query strings in logs");
+    }
 
     // Database connection
     let database_url =
@@ -50,7 +94,24 @@ async fn main() -> Result<()> {
     tracing::info!("Running database migrations...");
     sqlx::migrate!("./migrations").run(&pool).await?;
 
-    let db = Arc::new(Database::new(pool.clone()));
+    let slow_query_log = Arc::new(SlowQueryLog::from_env()?);
+    let db = Arc::new(Database::new(pool.clone()).with_slow_query_log(Arc::clone(&slow_query_log)));
+
+    let ingestion_config = IngestionConfig::from_env()?;
+    tracing::info!("Ingestion config: {:?}", ingestion_config);
+
+    let page_limits = Arc::new(PageLimits::from_env()?);
+    tracing::info!("Page limits: {:?}", page_limits);
+    let public_base_path = Arc::new(PublicBasePath::from_env()?);
+    let response_precision = Arc::new(ResponsePrecision::from_env()?);
+
+    let retention_config = MetricsRetentionConfig::from_env()?;
+    tracing::info!("Metrics retention config: {:?}", retention_config);
+
+    let status_thresholds = Arc::new(StatusThresholds::from_env()?);
+    tracing::info!("Status thresholds: {:?}", status_thresholds);
+    let status_hysteresis = StatusHysteresis::from_env()?;
+    tracing::info!("Status hysteresis: {:?}", status_hysteresis);
 
     // Initialize Stellar RPC Client
     let mock_mode = std::env::var("RPC_MOCK_MODE")
@@ -64,6 +125,12 @@ async fn main() -> Result<()> {
     let horizon_url = std::env::var("STELLAR_HORIZON_URL")
         .unwrap_or_else(|_| "https://horizon.stellar.org".to_string());
 
+    let testnet_rpc_url = std::env::var("STELLAR_TESTNET_RPC_URL")
+        .unwrap_or_else(|_| DEFAULT_TESTNET_RPC_URL.to_string());
+
+    let testnet_horizon_url = std::env::var("STELLAR_TESTNET_HORIZON_URL")
+        .unwrap_or_else(|_| DEFAULT_TESTNET_HORIZON_URL.to_string());
+
     tracing::info!(
         "Initializing Stellar RPC client (mock_mode: {}, rpc: {}, horizon: {})",
         mock_mode,
@@ -71,24 +138,70 @@ async fn main() -> Result<()> {
         horizon_url
     );
 
-    let rpc_client = Arc::new(StellarRpcClient::new(rpc_url, horizon_url, mock_mode));
+    let max_in_flight = std::env::var("RPC_MAX_IN_FLIGHT_REQUESTS")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_MAX_IN_FLIGHT_REQUESTS);
+
+    let request_timeout = std::env::var("RPC_REQUEST_TIMEOUT_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .map(std::time::Duration::from_secs)
+        .unwrap_or(DEFAULT_REQUEST_TIMEOUT);
+
+    let rpc_client = Arc::new(
+        StellarRpcClient::new(rpc_url, horizon_url, mock_mode)
+            .with_network_urls(Network::Testnet, testnet_rpc_url, testnet_horizon_url)
+            .with_max_in_flight_requests(max_in_flight)
+            .with_request_timeout(request_timeout),
+    );
 
     // Initialize WebSocket state
-    let ws_state = Arc::new(WsState::new());
+    let ws_state = Arc::new(WsState::from_env()?);
     tracing::info!("WebSocket state initialized");
 
+    // Initialize Webhook Service
+    let webhook_service = Arc::new(WebhookService::new(Arc::clone(&db)));
+
+    // Initialize Corridor Alert Service
+    let corridor_alert_service = Arc::new(CorridorAlertService::new(Arc::clone(&db)));
+
     // Initialize Data Ingestion Service
-    let ingestion_service = Arc::new(DataIngestionService::new(
-        Arc::clone(&rpc_client),
-        Arc::clone(&db),
-    ));
+    let ingestion_service = Arc::new(
+        DataIngestionService::new(
+            Arc::clone(&rpc_client),
+            Arc::clone(&db),
+            Arc::clone(&webhook_service),
+        )
+        .with_reliability_half_life(ingestion_config.reliability_half_life)
+        .with_status_thresholds(status_thresholds.as_ref().clone())
+        .with_status_hysteresis(status_hysteresis)
+        .with_availability_windows(
+            ingestion_config.availability_window,
+            ingestion_config.availability_windows,
+        ),
+    );
 
 
     // Initialize Redis cache
     let cache_config = CacheConfig::default();
     let cache = Arc::new(CacheManager::new(cache_config).await?);
+    cache.spawn_reconnect_task(REDIS_RECONNECT_INTERVAL);
     tracing::info!("Cache manager initialized");
 
+    // Stream cache statistics to subscribed WebSocket clients
+    let cache_stats_interval = std::env::var("CACHE_STATS_STREAM_INTERVAL_MS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|ms| *ms > 0)
+        .map(std::time::Duration::from_millis)
+        .unwrap_or(DEFAULT_CACHE_STATS_INTERVAL);
+    spawn_cache_stats_stream(Arc::clone(&ws_state), Arc::clone(&cache), cache_stats_interval);
+
+    // Initialize FX rates for quoting corridor volumes
+    let fx_service = Arc::new(FxService::from_env(Arc::clone(&cache)));
+
     // Initialize cache invalidation service
     let cache_invalidation = Arc::new(CacheInvalidationService::new(Arc::clone(&cache)));
 
@@ -97,54 +210,126 @@ async fn main() -> Result<()> {
         Arc::clone(&db),
         Arc::clone(&ws_state),
         Arc::clone(&ingestion_service),
+        Arc::clone(&webhook_service),
+        Arc::clone(&corridor_alert_service),
+        Arc::clone(&cache_invalidation),
     );
 
     // Create cached state tuple for cached API handlers
-    let cached_state = (Arc::clone(&db), Arc::clone(&cache), Arc::clone(&rpc_client));
+    let cached_state = (
+        Arc::clone(&db) as Arc<dyn DatabaseBackend>,
+        Arc::clone(&cache),
+        Arc::clone(&rpc_client),
+    );
+
+    // Retries shared by the ingestion tasks so an outage degrades them once
+    let retry_budget = Arc::new(RetryBudget::new(
+        ingestion_config.retry_budget,
+        ingestion_config.retry_refill_interval,
+    ));
+
+    // Payments feeding the corridor metrics, and the job that buckets them
+    let indexing_service = IndexingService::new(Arc::clone(&rpc_client), Arc::clone(&db));
+    let aggregation_service = AggregationService::new(Arc::clone(&db), Default::default())
+        .with_alerts(Arc::clone(&corridor_alert_service))
+        .with_corridor_filter(ingestion_config.corridor_filter.clone())
+        .with_dust_threshold(ingestion_config.dust.clone())
+        .with_bucket(ingestion_config.bucket)
+        .with_ws_state(Arc::clone(&ws_state))
+        .with_cache_invalidation(Arc::clone(&cache_invalidation));
 
     let ingestion_clone = Arc::clone(&ingestion_service);
     let cache_invalidation_clone = Arc::clone(&cache_invalidation);
+    let metrics_retry_budget = Arc::clone(&retry_budget);
+    let metrics_sync_interval = ingestion_config.metrics_sync_interval;
+    let metrics_degraded_sleep = ingestion_config.degraded_sleep;
     tokio::spawn(async move {
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(300)); // 5 minutes
+        let mut interval = tokio::time::interval(metrics_sync_interval);
+        let mut degraded = false;
         loop {
             interval.tick().await;
             if let Err(e) = ingestion_clone.sync_all_metrics().await {
-                tracing::error!("Metrics synchronization failed: {}", e);
+                match metrics_retry_budget.record_failure() {
+                    RetryDecision::Retry => {
+                        degraded = false;
+                        tracing::error!("Metrics synchronization failed: {}", e)
+                    }
+                    RetryDecision::Degraded => {
+                        if degraded {
+                            tracing::debug!("Metrics synchronization failed: {}", e);
+                        } else {
+                            degraded = true;
+                            tracing::warn!(
+                                "Metrics synchronization degraded, backing off for {:?}: {}",
+                                metrics_degraded_sleep,
+                                e
+                            );
+                        }
+                        tokio::time::sleep(metrics_degraded_sleep).await;
+                        interval.reset();
+                    }
+                }
             } else {
-                // Invalidate caches after successful sync
+                degraded = false;
+                metrics_retry_budget.record_success();
+                // Invalidate caches after successful sync. The sync only
+                // writes anchors; corridor caches are dropped per corridor
+                // by the aggregation job below.
                 if let Err(e) = cache_invalidation_clone.invalidate_anchors().await {
                     tracing::warn!("Failed to invalidate anchor caches: {}", e);
                 }
-                if let Err(e) = cache_invalidation_clone.invalidate_corridors().await {
-                    tracing::warn!("Failed to invalidate corridor caches: {}", e);
-                }
                 if let Err(e) = cache_invalidation_clone.invalidate_metrics().await {
                     tracing::warn!("Failed to invalidate metrics caches: {}", e);
                 }
             }
+
+            if let Err(e) = indexing_service.run_payment_ingestion().await {
+                tracing::warn!("Payment ingestion failed: {}", e);
+            }
+            if let Err(e) = aggregation_service.run_hourly_aggregation().await {
+                tracing::warn!("Corridor aggregation failed: {}", e);
+            }
+        }
+    });
+
+    // Metrics history retention task
+    let retention_service = MetricsRetentionService::new(Arc::clone(&db), retention_config);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(retention_service.config().prune_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = retention_service.prune().await {
+                tracing::error!("Metrics history pruning failed: {}", e);
+            }
         }
     });
 
     // Initialize Auth Service with its own Redis connection
     let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
-    let auth_redis_connection = if let Ok(client) = redis::Client::open(redis_url.as_str()) {
-        match client.get_multiplexed_tokio_connection().await {
-            Ok(conn) => {
-                tracing::info!("Auth service connected to Redis");
-                Some(conn)
-            }
-            Err(e) => {
-                tracing::warn!("Auth service failed to connect to Redis ({}), refresh tokens will not persist", e);
-                None
-            }
+    let auth_redis_connection = match connect_redis(&redis_url).await {
+        Ok(conn) => {
+            tracing::info!("Auth service connected to Redis");
+            Some(conn)
+        }
+        Err(e) if is_redis_config_error(&e) => {
+            tracing::error!("Redis configuration error for auth service ({}), refresh tokens will not persist", e);
+            None
+        }
+        Err(e) => {
+            tracing::warn!("Auth service failed to connect to Redis ({}), refresh tokens will not persist", e);
+            None
         }
-    } else {
-        tracing::warn!("Invalid Redis URL for auth service");
-        None
     };
     let auth_service = Arc::new(AuthService::new(Arc::new(tokio::sync::RwLock::new(auth_redis_connection))));
     tracing::info!("Auth service initialized");
 
+    // ML prediction service, scoring with the offline-trained weights
+    let ml_service = Arc::new(tokio::sync::RwLock::new(
+        MLService::new(Database::new(pool.clone()))?
+            .with_fallback(FallbackStrategy::from_env())
+            .with_cache(Arc::clone(&cache), ingestion_config.metrics_sync_interval),
+    ));
+
     // ML Retraining task (commented out)
     /*
     let ml_service_clone = ml_service.clone();
@@ -163,22 +348,38 @@ async fn main() -> Result<()> {
 
     // Ledger ingestion task (commented out)
     /*
+    let ledger_ingestion_service = Arc::new(
+        stellar_insights_backend::ingestion::ledger::LedgerIngestionService::new(
+            Arc::clone(&rpc_client),
+            pool.clone(),
+        )
+        .with_max_batch_attempts(ingestion_config.max_batch_attempts),
+    );
     let ledger_ingestion_clone = Arc::clone(&ledger_ingestion_service);
+    let ledger_ingestion_config = ingestion_config.clone();
+    let ledger_retry_budget = Arc::clone(&retry_budget);
     tokio::spawn(async move {
         tracing::info!("Starting ledger ingestion background task");
         loop {
-            match ledger_ingestion_clone.run_ingestion(5).await {
+            match ledger_ingestion_clone.run_ingestion(ledger_ingestion_config.batch_size).await {
                 Ok(count) => {
+                    ledger_retry_budget.record_success();
                     if count == 0 {
-                        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                        tokio::time::sleep(ledger_ingestion_config.idle_sleep).await;
                     } else {
                         tokio::task::yield_now().await;
                     }
                 }
-                Err(e) => {
-                    tracing::error!("Ledger ingestion failed: {}", e);
-                    tokio::time::sleep(std::time::Duration::from_secs(10)).await;
-                }
+                Err(e) => match ledger_retry_budget.record_failure() {
+                    RetryDecision::Retry => {
+                        tracing::error!("Ledger ingestion failed: {}", e);
+                        tokio::time::sleep(ledger_ingestion_config.error_sleep).await;
+                    }
+                    RetryDecision::Degraded => {
+                        tracing::debug!("Ledger ingestion failed: {}", e);
+                        tokio::time::sleep(ledger_ingestion_config.degraded_sleep).await;
+                    }
+                },
             }
         }
     });
@@ -189,19 +390,18 @@ async fn main() -> Result<()> {
     let _ = ingestion_service.sync_all_metrics().await;
 
     // Initialize rate limiter
-    let rate_limiter_result = RateLimiter::new().await;
-    let rate_limiter = match rate_limiter_result {
-        Ok(limiter) => {
-            tracing::info!("Rate limiter initialized successfully");
-            Arc::new(limiter)
-        },
-        Err(e) => {
-            tracing::warn!("Failed to initialize Redis rate limiter, creating with memory fallback: {}", e);
-            Arc::new(RateLimiter::new().await.unwrap_or_else(|_| {
-                panic!("Failed to create rate limiter: critical error")
-            }))
+    // Falls back to per-instance memory limits whenever Redis is unavailable
+    let rate_limiter = Arc::new(RateLimiter::new().await?);
+    tracing::info!("Rate limiter initialized");
+
+    let rate_limiter_clone = rate_limiter.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            rate_limiter_clone.prune_memory();
         }
-    };
+    });
 
     // Configure rate limits for endpoints
     rate_limiter.register_endpoint("/health".to_string(), RateLimitConfig {
@@ -229,6 +429,35 @@ async fn main() -> Result<()> {
         whitelist_ips: vec![],
     }).await;
 
+    // Hold /health/ready at 503 until the database and caches are warm
+    let readiness = Arc::new(Readiness::from_env());
+    if !readiness.is_ready() {
+        let readiness = Arc::clone(&readiness);
+        let db = Arc::clone(&db);
+        let cache = Arc::clone(&cache);
+        let status_thresholds = Arc::clone(&status_thresholds);
+        tokio::spawn(async move {
+            readiness::warm_up(&readiness, WARM_RETRY_DELAY, || async {
+                db.ping().await?;
+                readiness::warm_cache(db.as_ref(), &cache, &status_thresholds).await
+            })
+            .await;
+        });
+    }
+
+    // Initialize maintenance mode
+    let maintenance = Arc::new(MaintenanceMode::from_env());
+    if maintenance.is_enabled() {
+        tracing::warn!("Starting in maintenance mode; write operations are disabled");
+    }
+
+    // Initialize idempotency key storage for create endpoints
+    let idempotency = Arc::new(IdempotencyStore::from_env(Arc::clone(&cache)));
+
+    // Initialize request span sampling for high-volume routes
+    let trace_sampler = Arc::new(TraceSampler::from_env());
+    tracing::info!("Trace sample rate: {}", trace_sampler.sample_rate());
+
     // CORS configuration
     let cors = CorsLayer::new()
         .allow_origin(Any)
@@ -239,15 +468,58 @@ async fn main() -> Result<()> {
     use tower::ServiceBuilder;
     use axum::middleware;
 
+    // Cache-Control policies; public max-age follows the server-side cache TTLs
+    let anchor_cache_control = middleware::from_fn_with_state(
+        CacheControl::for_cache_type(&cache.config, "anchor"),
+        cache_control_middleware,
+    );
+    let corridor_cache_control = middleware::from_fn_with_state(
+        CacheControl::for_cache_type(&cache.config, "corridor"),
+        cache_control_middleware,
+    );
+    let dashboard_cache_control = middleware::from_fn_with_state(
+        CacheControl::for_cache_type(&cache.config, "dashboard"),
+        cache_control_middleware,
+    );
+    let no_store = middleware::from_fn_with_state(CacheControl::NoStore, cache_control_middleware);
+
     // Build auth router
-    let auth_routes = stellar_insights_backend::api::auth::routes(auth_service.clone());
+    let auth_routes = stellar_insights_backend::api::auth::routes(auth_service.clone())
+        .layer(no_store.clone());
 
-    // Build cached routes (anchors list, corridors list/detail) with cache state
+    // Build cached routes (anchors list, corridors list/detail) with cache state.
+    // `get` routes also answer HEAD with the GET headers and an empty body.
     let cached_routes = Router::new()
-        .route("/api/anchors", get(get_anchors))
-        .route("/api/corridors", get(list_corridors))
-        .route("/api/corridors/:corridor_key", get(get_corridor_detail))
+        .route("/api/anchors", get(get_anchors).layer(anchor_cache_control.clone()))
+        .route("/api/corridors", get(list_corridors).layer(corridor_cache_control.clone()))
+        .route("/api/corridors/batch", axum::routing::post(get_corridors_batch))
+        .route("/api/corridors/export", get(export_corridors).layer(no_store.clone()))
+        .route(
+            "/api/corridors/summary",
+            get(get_corridor_summary).layer(dashboard_cache_control.clone()),
+        )
+        .route(
+            "/api/corridors/:corridor_key",
+            get(get_corridor_detail).layer(corridor_cache_control.clone()),
+        )
+        .route(
+            "/api/corridors/:corridor_key/rollup",
+            get(get_corridor_rollup).layer(corridor_cache_control.clone()),
+        )
+        .route(
+            "/api/corridors/:corridor_key/history",
+            get(get_corridor_history).layer(no_store.clone()),
+        )
+        .route(
+            "/api/corridors/:corridor_key/peers",
+            get(get_corridor_peers).layer(corridor_cache_control),
+        )
         .with_state(cached_state.clone())
+        .layer(Extension(Arc::clone(&status_thresholds)))
+        .layer(Extension(Arc::clone(&fx_service)))
+        .layer(Extension(Arc::clone(&page_limits)))
+        .layer(Extension(Arc::clone(&public_base_path)))
+        .layer(Extension(Arc::clone(&response_precision)))
         .layer(
             ServiceBuilder::new()
                 .layer(middleware::from_fn_with_state(
@@ -260,13 +532,21 @@ async fn main() -> Result<()> {
     // Build non-cached anchor routes with app state
     let anchor_routes = Router::new()
         .route("/health", get(health_check))
-        .route("/api/anchors/:id", get(get_anchor))
+        .route("/api/version", get(version))
+        .route("/api/anchors/:id", get(get_anchor).layer(anchor_cache_control))
         .route(
             "/api/anchors/account/:stellar_account",
             get(get_anchor_by_account),
         )
         .route("/api/anchors/:id/assets", get(get_anchor_assets))
+        .route("/api/anchors/:id/assets/volume", get(get_anchor_asset_volumes))
+        .route(
+            "/api/corridors/:corridor_key/transactions",
+            get(get_corridor_transactions),
+        )
+        .route("/api/corridors/:corridor_key/at", get(get_corridor_metrics_at))
         .with_state(app_state.clone())
+        .layer(Extension(Arc::clone(&status_thresholds)))
         .layer(
             ServiceBuilder::new()
                 .layer(middleware::from_fn_with_state(
@@ -279,14 +559,47 @@ async fn main() -> Result<()> {
     // Build protected anchor routes (require authentication)
     let protected_anchor_routes = Router::new()
         .route("/api/anchors", axum::routing::post(create_anchor))
-        .route("/api/anchors/:id/metrics", put(update_anchor_metrics))
+        .route(
+            "/api/anchors/:id/metrics",
+            put(update_anchor_metrics).patch(patch_anchor_metrics),
+        )
         .route("/api/anchors/:id/assets", axum::routing::post(create_anchor_asset))
+        .route(
+            "/api/anchors/:id/recompute-reliability",
+            axum::routing::post(recompute_anchor_reliability),
+        )
         .route("/api/corridors", axum::routing::post(create_corridor))
+        .route(
+            "/api/corridors/import",
+            axum::routing::post(corridor_import::import_corridors)
+                .layer(DefaultBodyLimit::max(corridor_import::MAX_IMPORT_BYTES)),
+        )
         .route(
             "/api/corridors/:id/metrics-from-transactions",
             put(update_corridor_metrics_from_transactions),
         )
+        .route(
+            "/api/webhooks",
+            get(webhooks::list_webhooks).post(webhooks::create_webhook),
+        )
+        .route(
+            "/api/corridor-alerts",
+            get(corridor_alerts::list_corridor_alerts).post(corridor_alerts::create_corridor_alert),
+        )
+        .route(
+            "/api/corridor-alerts/:id",
+            get(corridor_alerts::get_corridor_alert)
+                .put(corridor_alerts::update_corridor_alert)
+                .delete(corridor_alerts::delete_corridor_alert),
+        )
         .with_state(app_state.clone())
+        .layer(Extension(Arc::clone(&idempotency)))
+        .layer(Extension(Arc::clone(&status_thresholds)))
+        .layer(no_store.clone())
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&db),
+            audit_middleware,
+        ))
         .layer(
             ServiceBuilder::new()
                 .layer(middleware::from_fn(auth_middleware))
@@ -297,11 +610,59 @@ async fn main() -> Result<()> {
         )
         .layer(cors.clone());
 
+    // Build protected ingestion routes (require authentication)
+    let ingestion_routes = stellar_insights_backend::api::ingestion::routes(
+        Arc::clone(&ingestion_service),
+        Arc::clone(&cache_invalidation),
+        Arc::clone(&db),
+    )
+    .layer(no_store.clone())
+    .layer(middleware::from_fn_with_state(
+        Arc::clone(&db),
+        audit_middleware,
+    ))
+    .layer(
+        ServiceBuilder::new()
+            .layer(middleware::from_fn(auth_middleware))
+            .layer(middleware::from_fn_with_state(
+                rate_limiter.clone(),
+                rate_limit_middleware,
+            ))
+    )
+    .layer(cors.clone());
+
     // Build cache stats and metrics routes
     let cache_routes = cache_stats::routes(Arc::clone(&cache));
-    let metrics_routes = metrics_cached::routes(Arc::clone(&cache));
+    let ws_routes = Router::new()
+        .route("/ws", get(ws_handler))
+        .with_state(Arc::clone(&ws_state));
+    let cache_flush_routes = cache_stats::flush_routes(Arc::clone(&cache_invalidation))
+        .layer(no_store.clone())
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&db),
+            audit_middleware,
+        ))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn(auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                ))
+        )
+        .layer(cors.clone());
+    let metrics_routes = metrics_cached::routes(Arc::clone(&cache)).layer(dashboard_cache_control);
+    let database_metrics_routes =
+        metrics_cached::database_routes(Arc::clone(&slow_query_log)).layer(cors.clone());
+    let websocket_metrics_routes =
+        metrics_cached::websocket_routes(Arc::clone(&ws_state)).layer(cors.clone());
+    let rate_limit_routes = stellar_insights_backend::api::rate_limit::routes(rate_limiter.clone())
+        .layer(cors.clone());
 
-    // Build RPC router
+    // Build RPC router; methods left out of RPC_ENABLED_METHODS answer 404
+    let rpc_method_filter = Arc::new(RpcMethodFilter::from_env()?);
+    let rpc_method_filter =
+        middleware::from_fn_with_state(rpc_method_filter, rpc_method_filter_middleware);
     let rpc_routes = Router::new()
         .route("/api/rpc/health", get(rpc_handlers::rpc_health_check))
         .route(
@@ -315,7 +676,35 @@ async fn main() -> Result<()> {
         )
         .route("/api/rpc/trades", get(rpc_handlers::get_trades))
         .route("/api/rpc/orderbook", get(rpc_handlers::get_order_book))
-        .with_state(rpc_client)
+        .route("/api/rpc/events", get(rpc_handlers::get_events))
+        .route(
+            "/api/rpc/transaction/:hash/effects",
+            get(rpc_handlers::get_transaction_effects),
+        )
+        .with_state(Arc::clone(&rpc_client))
+        .layer(rpc_method_filter.clone())
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                ))
+        )
+        .layer(cors.clone());
+
+    // Build cached RPC passthrough routes
+    let rpc_cached_routes = Router::new()
+        .route("/api/rpc/fee-stats", get(rpc_handlers::get_fee_stats))
+        .route(
+            "/api/rpc/trades/volume",
+            get(rpc_handlers::get_trade_volume),
+        )
+        .route(
+            "/api/rpc/account/:account_id/balances",
+            get(rpc_handlers::get_account_balances),
+        )
+        .with_state((Arc::clone(&rpc_client), Arc::clone(&cache)))
+        .layer(rpc_method_filter)
         .layer(
             ServiceBuilder::new()
                 .layer(middleware::from_fn_with_state(
@@ -325,15 +714,103 @@ async fn main() -> Result<()> {
         )
         .layer(cors.clone());
 
+    // Build ML prediction routes
+    let ml_routes = ml_handlers::routes(Arc::clone(&ml_service))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                ))
+        )
+        .layer(cors.clone());
+
+    // Build GraphQL routes (read-only, alongside the REST endpoints)
+    let graphql_routes =
+        stellar_insights_backend::api::graphql::routes(Arc::clone(&db), *page_limits)
+            .layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn_with_state(
+                        rate_limiter.clone(),
+                        rate_limit_middleware,
+                    ))
+            )
+            .layer(cors.clone());
+
+    // Build OpenAPI spec and Swagger UI routes
+    let openapi_routes = stellar_insights_backend::api::openapi::routes().layer(cors.clone());
+
+    // Build protected admin routes (require authentication)
+    let admin_routes = stellar_insights_backend::api::admin::routes(Arc::clone(&maintenance), Arc::clone(&db))
+        .layer(no_store.clone())
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&db),
+            audit_middleware,
+        ))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn(auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                ))
+        )
+        .layer(cors.clone());
+
+    // Dev-only routes (e.g. POST /api/dev/seed) are mounted only when ENABLE_DEV_ENDPOINTS=true
+    let dev_config = stellar_insights_backend::api::dev::DevConfig::from_env()
+        .with_bucket(ingestion_config.bucket);
+    let dev_routes = if dev_config.enabled {
+        if dev_config.production {
+            tracing::warn!("ENABLE_DEV_ENDPOINTS is set with APP_ENV=production; dev endpoints will refuse requests");
+        } else {
+            tracing::warn!("Dev endpoints enabled; do not use this configuration in production");
+        }
+        stellar_insights_backend::api::dev::routes(Arc::clone(&db), dev_config)
+            .layer(no_store)
+            .layer(cors.clone())
+    } else {
+        Router::new()
+    };
+
+    let readiness_routes = readiness::routes(readiness).layer(cors.clone());
+
     // Merge routers
     let app = Router::new()
         .merge(auth_routes)
         .merge(cached_routes)
         .merge(anchor_routes)
         .merge(protected_anchor_routes)
+        .merge(ingestion_routes)
         .merge(rpc_routes)
+        .merge(rpc_cached_routes)
         .merge(cache_routes)
-        .merge(metrics_routes);
+        .merge(ws_routes)
+        .merge(cache_flush_routes)
+        .merge(metrics_routes)
+        .merge(database_metrics_routes)
+        .merge(websocket_metrics_routes)
+        .merge(rate_limit_routes)
+        .merge(ml_routes)
+        .merge(admin_routes)
+        .merge(dev_routes)
+        .merge(graphql_routes)
+        .merge(openapi_routes)
+        .merge(readiness_routes)
+        .layer(middleware::from_fn_with_state(
+            CacheBypass::from_env(),
+            cache_bypass_middleware,
+        ))
+        .layer(middleware::from_fn(pretty_json_middleware))
+        .layer(middleware::from_fn(server_timing_middleware))
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&maintenance),
+            maintenance_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            trace_sampler,
+            trace_sampling_middleware,
+        ));
 
     // Start server
     let host = std::env::var("SERVER_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
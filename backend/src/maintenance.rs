@@ -0,0 +1,180 @@
+use axum::{
+    extract::{Request, State},
+    http::{header, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Default Retry-After sent with maintenance responses
+const DEFAULT_RETRY_AFTER_SECS: u64 = 300;
+
+/// Paths that accept writes even during maintenance: auth and the toggle, so
+/// operators can still log in and turn maintenance off again, and the
+/// read-only endpoints that take their query in a POST body
+const EXEMPT_PATH_PREFIXES: &[&str] = &[
+    "/api/auth/",
+    "/api/admin/maintenance",
+    "/graphql",
+    "/api/corridors/batch",
+    "/api/ml/predict/batch",
+];
+
+/// Runtime maintenance-mode flag
+///
+/// While enabled, mutating requests are rejected with 503 and reads keep
+/// being served.
+pub struct MaintenanceMode {
+    enabled: AtomicBool,
+    retry_after_secs: u64,
+}
+
+impl MaintenanceMode {
+    pub fn new(enabled: bool, retry_after_secs: u64) -> Self {
+        Self {
+            enabled: AtomicBool::new(enabled),
+            retry_after_secs,
+        }
+    }
+
+    /// Load the initial state from `MAINTENANCE_MODE` and
+    /// `MAINTENANCE_RETRY_AFTER_SECS`
+    pub fn from_env() -> Self {
+        let enabled = std::env::var("MAINTENANCE_MODE")
+            .ok()
+            .and_then(|v| v.parse::<bool>().ok())
+            .unwrap_or(false);
+        let retry_after_secs = std::env::var("MAINTENANCE_RETRY_AFTER_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(DEFAULT_RETRY_AFTER_SECS);
+
+        Self::new(enabled, retry_after_secs)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn retry_after_secs(&self) -> u64 {
+        self.retry_after_secs
+    }
+
+    /// Whether a request is blocked while maintenance is enabled
+    fn blocks(method: &Method, path: &str) -> bool {
+        let is_write = matches!(
+            *method,
+            Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+        );
+        is_write
+            && !EXEMPT_PATH_PREFIXES
+                .iter()
+                .any(|prefix| path.starts_with(prefix))
+    }
+}
+
+/// Error returned for writes rejected during maintenance
+#[derive(Debug)]
+pub struct MaintenanceError {
+    pub retry_after_secs: u64,
+}
+
+impl IntoResponse for MaintenanceError {
+    fn into_response(self) -> Response {
+        let body = serde_json::json!({
+            "error": "Service is in maintenance mode; write operations are temporarily disabled",
+            "retry_after": self.retry_after_secs,
+        });
+
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::RETRY_AFTER, self.retry_after_secs.to_string())],
+            axum::Json(body),
+        )
+            .into_response()
+    }
+}
+
+/// Middleware rejecting writes while maintenance mode is enabled
+pub async fn maintenance_middleware(
+    State(maintenance): State<Arc<MaintenanceMode>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if maintenance.is_enabled() && MaintenanceMode::blocks(req.method(), req.uri().path()) {
+        return MaintenanceError {
+            retry_after_secs: maintenance.retry_after_secs(),
+        }
+        .into_response();
+    }
+
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn app(maintenance: Arc<MaintenanceMode>) -> Router {
+        Router::new()
+            .route(
+                "/api/anchors",
+                get(|| async { "list" }).post(|| async { "created" }),
+            )
+            .route("/api/auth/login", axum::routing::post(|| async { "token" }))
+            .layer(middleware::from_fn_with_state(
+                maintenance,
+                maintenance_middleware,
+            ))
+    }
+
+    async fn send(app: Router, method: Method, uri: &str) -> Response {
+        let req = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+        app.oneshot(req).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_write_blocked_during_maintenance() {
+        let maintenance = Arc::new(MaintenanceMode::new(true, 120));
+
+        let response = send(app(maintenance), Method::POST, "/api/anchors").await;
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "120");
+    }
+
+    #[tokio::test]
+    async fn test_read_allowed_during_maintenance() {
+        let maintenance = Arc::new(MaintenanceMode::new(true, 120));
+
+        let response = send(app(maintenance), Method::GET, "/api/anchors").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_exempt_paths_and_toggle() {
+        let maintenance = Arc::new(MaintenanceMode::new(true, 120));
+
+        let response = send(app(maintenance.clone()), Method::POST, "/api/auth/login").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        for path in ["/graphql", "/api/corridors/batch", "/api/ml/predict/batch"] {
+            assert!(!MaintenanceMode::blocks(&Method::POST, path), "{}", path);
+        }
+
+        maintenance.set_enabled(false);
+        let response = send(app(maintenance), Method::POST, "/api/anchors").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -1,6 +1,11 @@
 use chrono::{DateTime, Utc, Datelike, Timelike};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::Duration;
+use crate::cache::{keys, CacheManager, CacheSchema};
 use crate::database::Database;
+use crate::models::corridor::parse_corridor_key;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PredictionFeatures {
@@ -12,11 +17,96 @@ pub struct PredictionFeatures {
     pub recent_success_rate: f32,
 }
 
+impl PredictionFeatures {
+    /// Stable hash of the feature vector and the model that scores it
+    pub fn cache_hash(&self, model_version: &str) -> String {
+        let mut hasher = Sha256::new();
+        for value in [
+            self.corridor_hash,
+            self.amount_usd,
+            self.hour_of_day,
+            self.day_of_week,
+            self.liquidity_depth,
+            self.recent_success_rate,
+        ] {
+            hasher.update(value.to_bits().to_le_bytes());
+        }
+        hasher.update(model_version.as_bytes());
+        hex::encode(&hasher.finalize()[..8])
+    }
+}
+
+/// Which predictor produced a result
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelKind {
+    Trained,
+    /// Heuristic used until the first training run completes
+    Fallback,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PredictionResult {
     pub success_probability: f32,
     pub confidence: f32,
     pub model_version: String,
+    pub model: ModelKind,
+}
+
+impl CacheSchema for PredictionResult {
+    const TAG: &'static str = "ml_prediction.v1";
+}
+
+/// Confidence reported for heuristic predictions
+const FALLBACK_CONFIDENCE: f32 = 0.5;
+
+/// Deterministic predictor used while no trained model exists
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FallbackStrategy {
+    /// The corridor's recent success rate, i.e. its last observed value
+    LastValue,
+    /// The same probability for every payment
+    Constant(f32),
+}
+
+impl FallbackStrategy {
+    /// Load from `ML_FALLBACK_STRATEGY`: `last_value` (default) or a fixed
+    /// probability such as `0.8`
+    pub fn from_env() -> Self {
+        match std::env::var("ML_FALLBACK_STRATEGY") {
+            Ok(value) => Self::parse(&value).unwrap_or_else(|| {
+                tracing::warn!("Invalid ML_FALLBACK_STRATEGY '{}', using last_value", value);
+                Self::LastValue
+            }),
+            Err(_) => Self::LastValue,
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.eq_ignore_ascii_case("last_value") {
+            return Some(Self::LastValue);
+        }
+        value
+            .parse::<f32>()
+            .ok()
+            .filter(|p| (0.0..=1.0).contains(p))
+            .map(Self::Constant)
+    }
+
+    pub fn predict(&self, features: &PredictionFeatures) -> PredictionResult {
+        let probability = match self {
+            Self::LastValue => features.recent_success_rate,
+            Self::Constant(p) => *p,
+        };
+
+        PredictionResult {
+            success_probability: probability.clamp(0.0, 1.0),
+            confidence: FALLBACK_CONFIDENCE,
+            model_version: "fallback".to_string(),
+            model: ModelKind::Fallback,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -58,9 +148,15 @@ impl SimpleMLModel {
             success_probability: prob,
             confidence: if prob > 0.7 || prob < 0.3 { 0.9 } else { 0.7 },
             model_version: self.version.clone(),
+            model: ModelKind::Trained,
         }
     }
 
+    /// Score a batch of feature vectors in one pass
+    pub fn predict_batch(&self, features: Vec<PredictionFeatures>) -> Vec<PredictionResult> {
+        features.into_iter().map(|f| self.predict(f)).collect()
+    }
+
     pub fn train(&mut self, _training_data: &[(Vec<f32>, f32)]) {
         // Simple gradient descent (placeholder)
         // In production, this would implement actual training
@@ -71,21 +167,77 @@ impl SimpleMLModel {
     }
 }
 
+/// Most entities scored by one batch request
+pub const MAX_BATCH_PREDICTIONS: usize = 100;
+
+/// Cache tags for a prediction, so corridor invalidation drops it
+///
+/// Corridor ids are tagged under their canonical key, the one invalidation
+/// uses; anything else (anchor ids) is tagged as given.
+fn prediction_tags(entity_id: &str) -> [String; 2] {
+    let tag_key = parse_corridor_key(entity_id)
+        .map(|corridor| corridor.to_string_key())
+        .unwrap_or_else(|_| entity_id.to_string());
+    [keys::corridors_tag(), keys::corridor_tag(&tag_key)]
+}
+
 pub struct MLService {
-    model: SimpleMLModel,
+    /// `None` when no trained weights are available
+    model: Option<SimpleMLModel>,
+    fallback: FallbackStrategy,
+    /// Prediction cache and how long entries live
+    cache: Option<(Arc<CacheManager>, usize)>,
     #[allow(dead_code)]
     db: Database,
 }
 
 impl MLService {
+    /// Service scoring with the offline-trained weights
     pub fn new(db: Database) -> anyhow::Result<Self> {
-        let model = SimpleMLModel::new();
-        Ok(Self { model, db })
+        Ok(Self {
+            model: Some(SimpleMLModel::new()),
+            ..Self::untrained(db)
+        })
+    }
+
+    /// Service without trained weights, answering with the fallback until
+    /// [`MLService::train_model`] runs
+    pub fn untrained(db: Database) -> Self {
+        Self {
+            model: None,
+            fallback: FallbackStrategy::LastValue,
+            cache: None,
+            db,
+        }
+    }
+
+    pub fn with_fallback(mut self, fallback: FallbackStrategy) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Cache predictions for `ttl`, normally the metrics sync interval
+    ///
+    /// Entries are tagged with their corridor so corridor invalidation drops
+    /// them too.
+    pub fn with_cache(mut self, cache: Arc<CacheManager>, ttl: Duration) -> Self {
+        self.cache = Some((cache, ttl.as_secs().max(1) as usize));
+        self
+    }
+
+    /// Predictor currently answering requests
+    pub fn model_kind(&self) -> ModelKind {
+        match self.model {
+            Some(_) => ModelKind::Trained,
+            None => ModelKind::Fallback,
+        }
     }
 
     pub async fn train_model(&mut self) -> anyhow::Result<()> {
         let training_data = self.prepare_training_data().await?;
-        self.model.train(&training_data);
+        self.model
+            .get_or_insert_with(SimpleMLModel::new)
+            .train(&training_data);
         Ok(())
     }
 
@@ -113,13 +265,15 @@ impl MLService {
     }
 
     fn hash_corridor(&self, asset_code: &Option<String>, asset_issuer: &Option<String>) -> f32 {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        
-        let mut hasher = DefaultHasher::new();
-        asset_code.hash(&mut hasher);
-        asset_issuer.hash(&mut hasher);
-        (hasher.finish() % 1000) as f32 / 1000.0
+        let mut hasher = Sha256::new();
+        for part in [asset_code, asset_issuer] {
+            let part = part.as_deref().unwrap_or_default();
+            hasher.update((part.len() as u64).to_le_bytes());
+            hasher.update(part.as_bytes());
+        }
+        let digest = hasher.finalize();
+        let value = u64::from_le_bytes(digest[..8].try_into().unwrap());
+        (value % 1000) as f32 / 1000.0
     }
 
     pub async fn predict_payment_success(
@@ -128,6 +282,74 @@ impl MLService {
         amount_usd: f64,
         timestamp: DateTime<Utc>,
     ) -> anyhow::Result<PredictionResult> {
+        let features = self.features_for(corridor, amount_usd, timestamp).await;
+
+        let Some((cache, ttl)) = &self.cache else {
+            return Ok(self.predict(features));
+        };
+
+        let key = keys::ml_prediction(corridor, &features.cache_hash(self.model_version()));
+        if let Some(cached) = cache.get::<PredictionResult>(&key).await? {
+            return Ok(cached);
+        }
+
+        let result = self.predict(features);
+        cache
+            .set_tagged(&key, &result, *ttl, &prediction_tags(corridor))
+            .await?;
+        Ok(result)
+    }
+
+    /// Predictions for several corridors or anchors, in request order
+    ///
+    /// Cached entries are reused; the rest are scored by the model in one pass.
+    pub async fn predict_batch(
+        &self,
+        entity_ids: &[String],
+        amount_usd: f64,
+        timestamp: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<PredictionResult>> {
+        let mut features = Vec::with_capacity(entity_ids.len());
+        for entity_id in entity_ids {
+            features.push(self.features_for(entity_id, amount_usd, timestamp).await);
+        }
+
+        let Some((cache, ttl)) = &self.cache else {
+            return Ok(self.predict_many(features));
+        };
+
+        let cache_keys: Vec<String> = entity_ids
+            .iter()
+            .zip(&features)
+            .map(|(id, f)| keys::ml_prediction(id, &f.cache_hash(self.model_version())))
+            .collect();
+        let mut results = cache.get_many::<PredictionResult>(&cache_keys).await?;
+
+        let misses: Vec<usize> = (0..results.len())
+            .filter(|&i| results[i].is_none())
+            .collect();
+        let scored = self.predict_many(misses.iter().map(|&i| features[i].clone()).collect());
+        for (i, result) in misses.into_iter().zip(scored) {
+            cache
+                .set_tagged(
+                    &cache_keys[i],
+                    &result,
+                    *ttl,
+                    &prediction_tags(&entity_ids[i]),
+                )
+                .await?;
+            results[i] = Some(result);
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    async fn features_for(
+        &self,
+        corridor: &str,
+        amount_usd: f64,
+        timestamp: DateTime<Utc>,
+    ) -> PredictionFeatures {
         let parts: Vec<&str> = corridor.split('-').collect();
         let corridor_hash = self.hash_corridor(
             &Some(parts.get(0).unwrap_or(&"").to_string()),
@@ -137,16 +359,35 @@ impl MLService {
         let liquidity = self.get_corridor_liquidity(corridor).await.unwrap_or(1000.0);
         let recent_success = self.get_recent_success_rate(corridor).await.unwrap_or(0.8);
 
-        let features = PredictionFeatures {
+        PredictionFeatures {
             corridor_hash,
             amount_usd: amount_usd.log10().max(0.0) as f32,
             hour_of_day: timestamp.hour() as f32 / 24.0,
             day_of_week: timestamp.weekday().num_days_from_monday() as f32 / 7.0,
             liquidity_depth: liquidity.log10() as f32,
             recent_success_rate: recent_success,
-        };
+        }
+    }
 
-        Ok(self.model.predict(features))
+    fn model_version(&self) -> &str {
+        match &self.model {
+            Some(model) => model.version.as_str(),
+            None => "fallback",
+        }
+    }
+
+    fn predict(&self, features: PredictionFeatures) -> PredictionResult {
+        match &self.model {
+            Some(model) => model.predict(features),
+            None => self.fallback.predict(&features),
+        }
+    }
+
+    fn predict_many(&self, features: Vec<PredictionFeatures>) -> Vec<PredictionResult> {
+        match &self.model {
+            Some(model) => model.predict_batch(features),
+            None => features.iter().map(|f| self.fallback.predict(f)).collect(),
+        }
     }
 
     async fn get_corridor_liquidity(&self, corridor: &str) -> Option<f64> {
@@ -163,7 +404,9 @@ impl MLService {
         println!("Starting weekly model retraining...");
         self.train_model().await?;
         
-        println!("Model retrained successfully. Version: {}", self.model.version);
+        if let Some(model) = &self.model {
+            println!("Model retrained successfully. Version: {}", model.version);
+        }
         Ok(())
     }
 }
@@ -1,9 +1,27 @@
-use axum::{extract::Query, http::StatusCode, response::Json, Extension};
+use axum::{
+    extract::Query,
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Extension, Router,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use crate::ml::{MLService, PredictionResult};
+use crate::error::{ApiError, ApiResult};
+use crate::ml::{MLService, ModelKind, PredictionResult, MAX_BATCH_PREDICTIONS};
+
+/// Longest corridor or anchor id accepted in a batch
+const MAX_ENTITY_ID_LEN: usize = 256;
+
+pub fn routes(ml_service: Arc<RwLock<MLService>>) -> Router {
+    Router::new()
+        .route("/api/ml/predict", get(predict_payment_success))
+        .route("/api/ml/predict/batch", post(predict_batch))
+        .route("/api/ml/status", get(get_model_status))
+        .layer(Extension(ml_service))
+}
 
 #[derive(Debug, Deserialize)]
 pub struct PredictionQuery {
@@ -22,6 +40,8 @@ pub struct PredictionResponse {
     pub success_probability: f32,
     pub confidence: f32,
     pub model_version: String,
+    /// `trained`, or `fallback` when no trained weights are loaded
+    pub model: ModelKind,
     pub risk_level: String,
     pub recommendation: String,
 }
@@ -44,6 +64,7 @@ impl From<PredictionResult> for PredictionResponse {
             success_probability: result.success_probability,
             confidence: result.confidence,
             model_version: result.model_version,
+            model: result.model,
             risk_level: risk_level.to_string(),
             recommendation: recommendation.to_string(),
         }
@@ -65,19 +86,102 @@ pub async fn predict_payment_success(
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct BatchPredictionRequest {
+    /// Corridor or anchor ids to score
+    pub entity_ids: Vec<String>,
+    pub amount_usd: f64,
+    #[serde(default = "default_timestamp")]
+    pub timestamp: DateTime<Utc>,
+}
+
+impl BatchPredictionRequest {
+    fn validate(&self) -> Result<(), ApiError> {
+        if self.entity_ids.is_empty() {
+            return Err(ApiError::BadRequest(
+                "entity_ids must not be empty".to_string(),
+            ));
+        }
+        if self.entity_ids.len() > MAX_BATCH_PREDICTIONS {
+            return Err(ApiError::BadRequest(format!(
+                "At most {} entity ids per batch, got {}",
+                MAX_BATCH_PREDICTIONS,
+                self.entity_ids.len()
+            )));
+        }
+        if let Some(id) = self.entity_ids.iter().find(|id| !is_valid_entity_id(id)) {
+            return Err(ApiError::BadRequest(format!("Invalid entity id '{}'", id)));
+        }
+        if !self.amount_usd.is_finite() || self.amount_usd < 0.0 {
+            return Err(ApiError::BadRequest(
+                "amount_usd must be a non-negative number".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn is_valid_entity_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_ENTITY_ID_LEN
+        && !id.chars().any(|c| c.is_whitespace() || c.is_control())
+}
+
+#[derive(Debug, Serialize)]
+pub struct EntityPrediction {
+    pub entity_id: String,
+    #[serde(flatten)]
+    pub prediction: PredictionResponse,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchPredictionResponse {
+    pub predictions: Vec<EntityPrediction>,
+}
+
+/// POST /api/ml/predict/batch - Score up to `MAX_BATCH_PREDICTIONS` entities at once
+pub async fn predict_batch(
+    Extension(ml_service): Extension<Arc<RwLock<MLService>>>,
+    Json(request): Json<BatchPredictionRequest>,
+) -> ApiResult<Json<BatchPredictionResponse>> {
+    request.validate()?;
+
+    let results = ml_service
+        .read()
+        .await
+        .predict_batch(&request.entity_ids, request.amount_usd, request.timestamp)
+        .await?;
+
+    Ok(Json(BatchPredictionResponse {
+        predictions: request
+            .entity_ids
+            .into_iter()
+            .zip(results)
+            .map(|(entity_id, result)| EntityPrediction {
+                entity_id,
+                prediction: result.into(),
+            })
+            .collect(),
+    }))
+}
+
 #[derive(Debug, Serialize)]
 pub struct ModelStatusResponse {
     pub version: String,
+    pub model: ModelKind,
     pub last_trained: String,
     pub accuracy: f32,
     pub total_predictions: u64,
 }
 
 pub async fn get_model_status(
-    Extension(_ml_service): Extension<Arc<RwLock<MLService>>>,
+    Extension(ml_service): Extension<Arc<RwLock<MLService>>>,
 ) -> Json<ModelStatusResponse> {
+    let model = ml_service.read().await.model_kind();
+
     Json(ModelStatusResponse {
         version: "1.0.0".to_string(),
+        model,
         last_trained: Utc::now().format("%Y-%m-%d").to_string(),
         accuracy: 0.87,
         total_predictions: 1000,
@@ -1,4 +1,4 @@
-use crate::ml::PredictionFeatures;
+use crate::ml::{ModelKind, PredictionFeatures};
 
 #[tokio::test]
 async fn test_ml_prediction() {
@@ -27,6 +27,7 @@ fn test_prediction_result_risk_levels() {
         success_probability: 0.9,
         confidence: 0.8,
         model_version: "1.0.0".to_string(),
+        model: ModelKind::Trained,
     };
     
     let response: PredictionResponse = high_prob.into();
@@ -37,6 +38,7 @@ fn test_prediction_result_risk_levels() {
         success_probability: 0.3,
         confidence: 0.8,
         model_version: "1.0.0".to_string(),
+        model: ModelKind::Trained,
     };
     
     let response: PredictionResponse = low_prob.into();
@@ -62,4 +64,269 @@ fn test_simple_model_prediction() {
     assert!(result.success_probability >= 0.0 && result.success_probability <= 1.0);
     assert!(result.confidence >= 0.0 && result.confidence <= 1.0);
     assert_eq!(result.model_version, "1.0.0");
+    assert_eq!(result.model, ModelKind::Trained);
+}
+
+#[test]
+fn test_fallback_strategy_prediction() {
+    use crate::ml::FallbackStrategy;
+
+    let features = PredictionFeatures {
+        corridor_hash: 0.5,
+        amount_usd: 2.0,
+        hour_of_day: 0.5,
+        day_of_week: 0.3,
+        liquidity_depth: 3.0,
+        recent_success_rate: 0.85,
+    };
+
+    let last_value = FallbackStrategy::LastValue.predict(&features);
+    assert_eq!(last_value.success_probability, 0.85);
+    assert_eq!(last_value.model, ModelKind::Fallback);
+
+    let constant = FallbackStrategy::Constant(0.6).predict(&features);
+    assert_eq!(constant.success_probability, 0.6);
+    assert_eq!(constant.model, ModelKind::Fallback);
+
+    assert_eq!(
+        FallbackStrategy::parse("last_value"),
+        Some(FallbackStrategy::LastValue)
+    );
+    assert_eq!(
+        FallbackStrategy::parse("0.75"),
+        Some(FallbackStrategy::Constant(0.75))
+    );
+    assert_eq!(FallbackStrategy::parse("1.5"), None);
+    assert_eq!(FallbackStrategy::parse("average"), None);
+}
+
+#[tokio::test]
+async fn test_service_switches_from_fallback_to_trained() {
+    use crate::database::Database;
+    use crate::ml::MLService;
+    use chrono::Utc;
+
+    let pool = sqlx::SqlitePool::connect_lazy("sqlite::memory:").unwrap();
+    let mut service = MLService::untrained(Database::new(pool));
+    assert_eq!(service.model_kind(), ModelKind::Fallback);
+
+    let now = Utc::now();
+    let fallback = service
+        .predict_payment_success("USDC-XLM", 100.0, now)
+        .await
+        .unwrap();
+    assert_eq!(fallback.model, ModelKind::Fallback);
+    assert!((0.0..=1.0).contains(&fallback.success_probability));
+    // Deterministic until trained
+    let again = service
+        .predict_payment_success("USDC-XLM", 100.0, now)
+        .await
+        .unwrap();
+    assert_eq!(again.success_probability, fallback.success_probability);
+
+    service.train_model().await.unwrap();
+    assert_eq!(service.model_kind(), ModelKind::Trained);
+    let trained = service
+        .predict_payment_success("USDC-XLM", 100.0, now)
+        .await
+        .unwrap();
+    assert_eq!(trained.model, ModelKind::Trained);
+}
+
+#[tokio::test]
+async fn test_new_service_loads_trained_weights() {
+    use crate::database::Database;
+    use crate::ml::MLService;
+    use chrono::Utc;
+
+    let pool = sqlx::SqlitePool::connect_lazy("sqlite::memory:").unwrap();
+    let service = MLService::new(Database::new(pool)).unwrap();
+    assert_eq!(service.model_kind(), ModelKind::Trained);
+
+    let result = service
+        .predict_payment_success("USDC-XLM", 100.0, Utc::now())
+        .await
+        .unwrap();
+    assert_eq!(result.model, ModelKind::Trained);
+    assert_eq!(result.model_version, "1.0.0");
+}
+
+#[test]
+fn test_feature_cache_hash() {
+    let features = PredictionFeatures {
+        corridor_hash: 0.5,
+        amount_usd: 2.0,
+        hour_of_day: 0.5,
+        day_of_week: 0.3,
+        liquidity_depth: 3.0,
+        recent_success_rate: 0.85,
+    };
+    let hash = features.cache_hash("1.0.0");
+
+    assert_eq!(hash, features.clone().cache_hash("1.0.0"));
+    assert_ne!(hash, features.cache_hash("fallback"));
+    let larger = PredictionFeatures {
+        amount_usd: 3.0,
+        ..features
+    };
+    assert_ne!(hash, larger.cache_hash("1.0.0"));
+}
+
+#[tokio::test]
+async fn test_repeated_prediction_hits_cache() {
+    use crate::cache::{keys, CacheManager};
+    use crate::database::Database;
+    use crate::ml::MLService;
+    use chrono::Utc;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let cache = Arc::new(CacheManager::in_memory(Default::default()));
+    let pool = sqlx::SqlitePool::connect_lazy("sqlite::memory:").unwrap();
+    let service = MLService::new(Database::new(pool))
+        .unwrap()
+        .with_cache(Arc::clone(&cache), Duration::from_secs(60));
+    let corridor = "TESTML-XLM";
+    let now = Utc::now();
+
+    let first = service
+        .predict_payment_success(corridor, 100.0, now)
+        .await
+        .unwrap();
+    let hits = cache.get_stats().hits;
+    let second = service
+        .predict_payment_success(corridor, 100.0, now)
+        .await
+        .unwrap();
+
+    assert_eq!(cache.get_stats().hits, hits + 1);
+    assert_eq!(second.success_probability, first.success_probability);
+    assert_eq!(second.model, first.model);
+
+    cache
+        .invalidate_tag(&keys::corridor_tag(corridor))
+        .await
+        .unwrap();
+    let hits = cache.get_stats().hits;
+    service
+        .predict_payment_success(corridor, 100.0, now)
+        .await
+        .unwrap();
+    assert_eq!(cache.get_stats().hits, hits);
+}
+
+#[tokio::test]
+async fn test_corridor_invalidation_by_canonical_key_drops_prediction() {
+    use crate::cache::{keys, CacheManager};
+    use crate::database::Database;
+    use crate::ml::MLService;
+    use chrono::Utc;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let cache = Arc::new(CacheManager::in_memory(Default::default()));
+    let pool = sqlx::SqlitePool::connect_lazy("sqlite::memory:").unwrap();
+    let service = MLService::new(Database::new(pool))
+        .unwrap()
+        .with_cache(Arc::clone(&cache), Duration::from_secs(60));
+    let now = Utc::now();
+
+    // Non-canonical order and lower-case code, as a client might send it
+    service
+        .predict_payment_success("usdc:GISSUER->EURC:GISSUER", 100.0, now)
+        .await
+        .unwrap();
+    cache
+        .invalidate_tag(&keys::corridor_tag("EURC:GISSUER->USDC:GISSUER"))
+        .await
+        .unwrap();
+
+    let hits = cache.get_stats().hits;
+    service
+        .predict_payment_success("usdc:GISSUER->EURC:GISSUER", 100.0, now)
+        .await
+        .unwrap();
+    assert_eq!(cache.get_stats().hits, hits);
+}
+
+async fn batch_request(entity_ids: Vec<String>) -> axum::response::Response {
+    use crate::database::Database;
+    use crate::ml::MLService;
+    use crate::ml_handlers::routes;
+    use axum::body::Body;
+    use axum::http::Request;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+    use tower::ServiceExt;
+
+    let pool = sqlx::SqlitePool::connect_lazy("sqlite::memory:").unwrap();
+    let service = MLService::new(Database::new(pool)).unwrap();
+    let body = serde_json::json!({ "entity_ids": entity_ids, "amount_usd": 250.0 });
+
+    routes(Arc::new(RwLock::new(service)))
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/ml/predict/batch")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_batch_prediction_response_shape() {
+    use axum::http::StatusCode;
+
+    let ids = vec!["USDC-XLM".to_string(), "anchor-123".to_string()];
+    let response = batch_request(ids).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let predictions = json["predictions"].as_array().unwrap();
+
+    assert_eq!(predictions.len(), 2);
+    assert_eq!(predictions[0]["entity_id"], "USDC-XLM");
+    assert_eq!(predictions[1]["entity_id"], "anchor-123");
+    for prediction in predictions {
+        assert!(prediction["success_probability"].is_number());
+        assert!(prediction["confidence"].is_number());
+        assert!(prediction["risk_level"].is_string());
+        assert_eq!(prediction["model"], "trained");
+    }
+}
+
+#[tokio::test]
+async fn test_batch_prediction_size_cap_and_id_validation() {
+    use crate::ml::MAX_BATCH_PREDICTIONS;
+    use axum::http::StatusCode;
+
+    let at_cap = (0..MAX_BATCH_PREDICTIONS).map(|i| format!("C{}-XLM", i));
+    assert_eq!(
+        batch_request(at_cap.collect()).await.status(),
+        StatusCode::OK
+    );
+
+    let over_cap = (0..=MAX_BATCH_PREDICTIONS).map(|i| format!("C{}-XLM", i));
+    assert_eq!(
+        batch_request(over_cap.collect()).await.status(),
+        StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+        batch_request(vec![]).await.status(),
+        StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+        batch_request(vec!["USDC XLM".to_string()]).await.status(),
+        StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+        batch_request(vec!["x".repeat(257)]).await.status(),
+        StatusCode::BAD_REQUEST
+    );
 }
@@ -1,16 +1,77 @@
+use async_graphql::SimpleObject;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::cache::CacheSchema;
 
 pub mod corridor;
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum SortBy {
     #[default]
     SuccessRate,
     Volume,
+    /// Most recently written metrics first
+    LastUpdated,
+    /// Anchor name or corridor key, alphabetically
+    Name,
 }
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+
+impl SortBy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SortBy::SuccessRate => "success_rate",
+            SortBy::Volume => "volume",
+            SortBy::LastUpdated => "last_updated",
+            SortBy::Name => "name",
+        }
+    }
+
+    /// Direction used when the request does not give an `order`
+    pub fn default_order(self) -> SortOrder {
+        match self {
+            SortBy::Name => SortOrder::Asc,
+            SortBy::SuccessRate | SortBy::Volume | SortBy::LastUpdated => SortOrder::Desc,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SortOrder::Asc => "asc",
+            SortOrder::Desc => "desc",
+        }
+    }
+
+    /// SQL keyword for an `ORDER BY` clause
+    pub fn sql(self) -> &'static str {
+        match self {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        }
+    }
+
+    /// Apply this direction to an ascending comparison
+    pub fn apply(self, ordering: std::cmp::Ordering) -> std::cmp::Ordering {
+        match self {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema, SimpleObject)]
+#[graphql(complex)]
 pub struct Anchor {
     pub id: String,
     pub name: String,
@@ -23,11 +84,13 @@ pub struct Anchor {
     pub avg_settlement_time_ms: i32,
     pub reliability_score: f64,
     pub status: String,
+    /// Percentage of recent ingestion windows with transactions, unset until synced
+    pub availability: Option<f64>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema, SimpleObject)]
 pub struct Asset {
     pub id: String,
     pub anchor_id: String,
@@ -39,7 +102,21 @@ pub struct Asset {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+/// Payment activity in one of an anchor's assets
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct AnchorAssetVolume {
+    pub asset_code: String,
+    pub asset_issuer: String,
+    pub transaction_count: i64,
+    /// Sum of payment amounts, in units of the asset
+    pub volume: f64,
+}
+
+impl CacheSchema for Vec<AnchorAssetVolume> {
+    const TAG: &'static str = "anchor_asset_volumes.v1";
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct AnchorMetricsHistory {
     pub id: String,
     pub anchor_id: String,
@@ -67,11 +144,13 @@ pub struct AnchorMetrics {
     pub status: AnchorStatus,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// Anchor health by success rate; the cutoffs are [`crate::analytics::health::StatusThresholds`]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
 pub enum AnchorStatus {
-    Green,  // >98% success, <1% failures
-    Yellow, // 95-98% success, 1-5% failures
-    Red,    // <95% success, >5% failures
+    Green,
+    Yellow,
+    Red,
 }
 
 impl AnchorStatus {
@@ -82,15 +161,24 @@ impl AnchorStatus {
             AnchorStatus::Red => "red",
         }
     }
+}
 
-    pub fn from_metrics(success_rate: f64, failure_rate: f64) -> Self {
-        if success_rate > 98.0 && failure_rate <= 1.0 {
-            AnchorStatus::Green
-        } else if success_rate >= 95.0 && failure_rate <= 5.0 {
-            AnchorStatus::Yellow
-        } else {
-            AnchorStatus::Red
-        }
+/// Reliability-score bounds for anchor list queries
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AnchorFilter {
+    pub min_reliability: Option<f64>,
+    /// Exclusive, so adjacent status bands don't overlap
+    pub max_reliability: Option<f64>,
+}
+
+impl AnchorFilter {
+    /// Whether a reliability score passes the bounds, mirroring the SQL predicates
+    pub fn matches(&self, reliability_score: f64) -> bool {
+        self.min_reliability
+            .is_none_or(|min| reliability_score >= min)
+            && self
+                .max_reliability
+                .is_none_or(|max| reliability_score < max)
     }
 }
 
@@ -101,11 +189,12 @@ pub struct AnchorWithAssets {
     pub assets: Vec<Asset>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct AnchorDetailResponse {
     pub anchor: Anchor,
     pub assets: Vec<Asset>,
     pub metrics_history: Vec<AnchorMetricsHistory>,
+    pub health: crate::analytics::health::AnchorHealthBreakdown,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -151,6 +240,29 @@ pub struct CreateAnchorRequest {
     pub home_domain: Option<String>,
 }
 
+/// Sparse anchor metrics update; omitted fields are left unchanged
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AnchorMetricsPatch {
+    pub total_transactions: Option<i64>,
+    pub successful_transactions: Option<i64>,
+    pub failed_transactions: Option<i64>,
+    pub avg_settlement_time_ms: Option<i32>,
+    pub total_volume_usd: Option<f64>,
+    pub reliability_score: Option<f64>,
+}
+
+impl AnchorMetricsPatch {
+    pub fn is_empty(&self) -> bool {
+        self.total_transactions.is_none()
+            && self.successful_transactions.is_none()
+            && self.failed_transactions.is_none()
+            && self.avg_settlement_time_ms.is_none()
+            && self.total_volume_usd.is_none()
+            && self.reliability_score.is_none()
+    }
+}
+
 // =========================
 // Corridor domain (new)
 // =========================
@@ -164,6 +276,83 @@ pub struct CreateCorridorRequest {
     pub dest_asset_issuer: String,
 }
 
+// =========================
+// Webhook domain
+// =========================
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct WebhookRecord {
+    pub id: String,
+    pub url: String,
+    #[serde(skip_serializing)]
+    pub secret: String,
+    pub event_type: String,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    pub secret: String,
+    pub event_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CorridorAlertRecord {
+    pub id: String,
+    pub corridor_key: String,
+    pub min_success_rate: f64,
+    pub notify_url: String,
+    pub is_active: bool,
+    pub last_triggered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Ledger range skipped by ingestion after exhausting its retries
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct IngestionFailureRecord {
+    pub id: i64,
+    pub start_ledger: i64,
+    pub end_ledger: i64,
+    pub attempts: i64,
+    pub last_error: String,
+    /// `dead_lettered`, `requeued` or `resolved`
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One successful write, as recorded in `audit_log`
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AuditLogRecord {
+    pub id: i64,
+    /// Username of the authenticated caller, `anonymous` without one
+    pub principal: String,
+    /// e.g. `create_anchor`, `flush_cache`
+    pub action: String,
+    /// Id of the written resource, when there is one
+    pub target_id: Option<String>,
+    pub request_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateCorridorAlertRequest {
+    pub corridor_key: String,
+    pub min_success_rate: f64,
+    pub notify_url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateCorridorAlertRequest {
+    pub min_success_rate: Option<f64>,
+    pub notify_url: Option<String>,
+    pub is_active: Option<bool>,
+}
+
 // =========================
 // Payment domain
 // =========================
@@ -177,10 +366,23 @@ pub struct PaymentRecord {
     pub asset_type: String,
     pub asset_code: Option<String>,
     pub asset_issuer: Option<String>,
+    /// Asset a path payment was sent in; `None` when it is the delivered asset
+    pub source_asset_code: Option<String>,
+    pub source_asset_issuer: Option<String>,
     pub amount: f64,
     pub created_at: DateTime<Utc>,
 }
 
+/// Raw payment within a corridor, joined with its transaction outcome
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CorridorTransactionRecord {
+    pub transaction_hash: String,
+    pub amount: f64,
+    pub successful: bool,
+    pub created_at: String,
+    pub ledger_sequence: Option<i64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct IngestionState {
     pub task_name: String,
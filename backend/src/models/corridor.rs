@@ -1,8 +1,14 @@
+use async_graphql::SimpleObject;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, sqlx::FromRow)]
+use crate::cache::CacheSchema;
+
+#[derive(
+    Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, sqlx::FromRow, SimpleObject,
+)]
+#[graphql(complex)]
 pub struct Corridor {
     pub asset_a_code: String,
     pub asset_a_issuer: String,
@@ -18,9 +24,9 @@ impl Corridor {
         asset_b_issuer: String,
     ) -> Self {
         let mut corridor = Corridor {
-            asset_a_code,
+            asset_a_code: canonical_asset_code(&asset_a_code),
             asset_a_issuer,
-            asset_b_code,
+            asset_b_code: canonical_asset_code(&asset_b_code),
             asset_b_issuer,
         };
         corridor.normalize_ordering();
@@ -28,15 +34,22 @@ impl Corridor {
     }
 
     fn normalize_ordering(&mut self) {
-        let asset_a_key = format!("{}:{}", self.asset_a_code, self.asset_a_issuer);
-        let asset_b_key = format!("{}:{}", self.asset_b_code, self.asset_b_issuer);
-
-        if asset_a_key > asset_b_key {
+        if !in_canonical_order(
+            &self.asset_a_code,
+            &self.asset_a_issuer,
+            &self.asset_b_code,
+            &self.asset_b_issuer,
+        ) {
             std::mem::swap(&mut self.asset_a_code, &mut self.asset_b_code);
             std::mem::swap(&mut self.asset_a_issuer, &mut self.asset_b_issuer);
         }
     }
 
+    /// Parse a `CODE:ISSUER->CODE:ISSUER` corridor key
+    pub fn from_key(corridor_key: &str) -> Option<Self> {
+        parse_corridor_key(corridor_key).ok()
+    }
+
     pub fn to_string_key(&self) -> String {
         format!(
             "{}:{}->{}:{}",
@@ -45,8 +58,108 @@ impl Corridor {
     }
 }
 
+/// Asset code as it appears in corridor keys
+///
+/// Stellar asset codes are case-sensitive on the ledger, but users type
+/// `usdc` and `USDC` for the same asset, so corridor keys upper-case every
+/// code. Issuers are kept exactly as given: account ids are already
+/// upper-case strkeys and `native` is lower-case by convention.
+pub fn canonical_asset_code(code: &str) -> String {
+    code.to_ascii_uppercase()
+}
+
+/// Whether asset A sorts before (or equal to) asset B, comparing `CODE:ISSUER`
+fn in_canonical_order(a_code: &str, a_issuer: &str, b_code: &str, b_issuer: &str) -> bool {
+    format!("{}:{}", a_code, a_issuer) <= format!("{}:{}", b_code, b_issuer)
+}
+
+/// Canonical key for the corridor between two assets, whichever order they come in
+pub fn normalize_corridor_key(
+    a_code: &str,
+    a_issuer: &str,
+    b_code: &str,
+    b_issuer: &str,
+) -> String {
+    let a_code = canonical_asset_code(a_code);
+    let b_code = canonical_asset_code(b_code);
+    if in_canonical_order(&a_code, a_issuer, &b_code, b_issuer) {
+        format!("{}:{}->{}:{}", a_code, a_issuer, b_code, b_issuer)
+    } else {
+        format!("{}:{}->{}:{}", b_code, b_issuer, a_code, a_issuer)
+    }
+}
+
+/// Why a corridor key could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CorridorKeyError {
+    /// No `->` between the two assets
+    MissingSeparator,
+    /// An asset is not a non-empty `CODE:ISSUER` pair
+    MalformedAsset(String),
+}
+
+impl std::fmt::Display for CorridorKeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CorridorKeyError::MissingSeparator => {
+                write!(f, "Invalid corridor key: expected CODE:ISSUER->CODE:ISSUER")
+            }
+            CorridorKeyError::MalformedAsset(asset) => {
+                write!(f, "Invalid corridor key: malformed asset '{}'", asset)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CorridorKeyError {}
+
+/// Parse a `CODE:ISSUER->CODE:ISSUER` key into a normalized [`Corridor`]
+///
+/// Either asset order is accepted and codes are upper-cased (see
+/// [`canonical_asset_code`]); the result always uses canonical order, so `parse_corridor_key(k)?.to_string_key()` is the canonical form of `k`.
+pub fn parse_corridor_key(corridor_key: &str) -> Result<Corridor, CorridorKeyError> {
+    let (asset_a, asset_b) = corridor_key
+        .split_once("->")
+        .ok_or(CorridorKeyError::MissingSeparator)?;
+    let (asset_a_code, asset_a_issuer) = parse_asset(asset_a)?;
+    let (asset_b_code, asset_b_issuer) = parse_asset(asset_b)?;
+
+    Ok(Corridor::new(
+        asset_a_code.to_string(),
+        asset_a_issuer.to_string(),
+        asset_b_code.to_string(),
+        asset_b_issuer.to_string(),
+    ))
+}
+
+/// Validate the two assets of a corridor into a normalized [`Corridor`]
+///
+/// Applies the same rules as [`parse_corridor_key`].
+pub fn parse_corridor_assets(
+    asset_a_code: &str,
+    asset_a_issuer: &str,
+    asset_b_code: &str,
+    asset_b_issuer: &str,
+) -> Result<Corridor, CorridorKeyError> {
+    parse_corridor_key(&format!(
+        "{}:{}->{}:{}",
+        asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer
+    ))
+}
+
+fn parse_asset(asset: &str) -> Result<(&str, &str), CorridorKeyError> {
+    let malformed = || CorridorKeyError::MalformedAsset(asset.to_string());
+    let (code, issuer) = asset.split_once(':').ok_or_else(malformed)?;
+    if code.is_empty() || issuer.is_empty() || issuer.contains(':') || issuer.contains("->") {
+        return Err(malformed());
+    }
+    Ok((code, issuer))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct CorridorMetrics {
+    /// Not a `corridor_metrics` column; rows are keyed by corridor and date
+    #[sqlx(default)]
     pub id: String,
     pub corridor_key: String,
     pub asset_a_code: String,
@@ -59,16 +172,38 @@ pub struct CorridorMetrics {
     pub failed_transactions: i64,
     pub success_rate: f64,
     pub volume_usd: f64,
+    #[sqlx(default)]
     pub avg_settlement_latency_ms: Option<i32>,
     /// Median settlement latency in milliseconds
     #[sqlx(default)]
     pub median_settlement_latency_ms: Option<i32>,
     #[serde(default)]
+    #[sqlx(default)]
     pub liquidity_depth_usd: f64,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Network-wide headline numbers across the latest corridor metrics
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct CorridorNetworkSummary {
+    pub total_corridors: i64,
+    /// Mean of the corridor success rates
+    pub avg_success_rate: f64,
+    /// Success rate weighted by each corridor's volume
+    pub volume_weighted_success_rate: f64,
+    pub total_volume_usd: f64,
+    /// Success rate below which a corridor counts as unhealthy
+    pub health_threshold: f64,
+    pub unhealthy_corridors: i64,
+    /// Corridor with the most transactions
+    pub most_active_corridor: Option<String>,
+}
+
+impl CacheSchema for CorridorNetworkSummary {
+    const TAG: &'static str = "corridor_network_summary.v1";
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct CorridorMetricsHistory {
     pub id: String,
@@ -202,6 +337,81 @@ mod tests {
         assert!(key.contains("->"));
     }
 
+    #[test]
+    fn test_normalize_corridor_key_ignores_input_order() {
+        let forward = normalize_corridor_key("USDC", "issuer1", "EURC", "issuer2");
+        let reverse = normalize_corridor_key("EURC", "issuer2", "USDC", "issuer1");
+
+        assert_eq!(forward, "EURC:issuer2->USDC:issuer1");
+        assert_eq!(forward, reverse);
+        assert_eq!(
+            forward,
+            Corridor::new(
+                "USDC".to_string(),
+                "issuer1".to_string(),
+                "EURC".to_string(),
+                "issuer2".to_string(),
+            )
+            .to_string_key()
+        );
+    }
+
+    #[test]
+    fn test_parse_corridor_key_canonicalizes_order() {
+        let forward = parse_corridor_key("EURC:issuer2->USDC:issuer1").unwrap();
+        let reverse = parse_corridor_key("USDC:issuer1->EURC:issuer2").unwrap();
+
+        assert_eq!(forward, reverse);
+        assert_eq!(reverse.to_string_key(), "EURC:issuer2->USDC:issuer1");
+        assert_eq!(
+            reverse.to_string_key(),
+            normalize_corridor_key("USDC", "issuer1", "EURC", "issuer2")
+        );
+    }
+
+    #[test]
+    fn test_mixed_case_codes_normalize_to_one_key() {
+        let canonical = "EURC:GISSUER2->USDC:GISSUER1";
+
+        for key in [
+            "usdc:GISSUER1->eurc:GISSUER2",
+            "Usdc:GISSUER1->EURC:GISSUER2",
+            "EURC:GISSUER2->usdc:GISSUER1",
+        ] {
+            assert_eq!(parse_corridor_key(key).unwrap().to_string_key(), canonical);
+        }
+        assert_eq!(
+            normalize_corridor_key("usdc", "GISSUER1", "Eurc", "GISSUER2"),
+            canonical
+        );
+        assert_eq!(
+            parse_corridor_assets("xlm", "native", "usdc", "GISSUER1")
+                .unwrap()
+                .to_string_key(),
+            "USDC:GISSUER1->XLM:native"
+        );
+        // Issuers are not case-folded
+        assert_ne!(
+            normalize_corridor_key("USDC", "gissuer1", "EURC", "GISSUER2"),
+            canonical
+        );
+    }
+
+    #[test]
+    fn test_parse_corridor_key_rejects_malformed_keys() {
+        assert_eq!(
+            parse_corridor_key("USDC:issuer1"),
+            Err(CorridorKeyError::MissingSeparator)
+        );
+        assert!(matches!(
+            parse_corridor_key("USDC->EURC:issuer2"),
+            Err(CorridorKeyError::MalformedAsset(_))
+        ));
+        assert!(parse_corridor_key(":issuer1->EURC:issuer2").is_err());
+        assert!(parse_corridor_key("USDC:a:b->EURC:issuer2").is_err());
+        assert!(parse_corridor_key("USDC:issuer1->EURC:").is_err());
+    }
+
     #[test]
     fn test_payment_record_get_corridor() {
         let payment = PaymentRecord {
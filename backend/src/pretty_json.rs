@@ -0,0 +1,125 @@
+//! Opt-in pretty-printed JSON responses for hand testing
+//!
+//! [`pretty_json_middleware`] re-serializes a JSON response body with
+//! `serde_json::to_string_pretty` when the request has `?pretty=true` or an
+//! `X-Pretty: true` header. It runs after handlers and caches, so cached
+//! values stay compact; CSV, Prometheus and other non-JSON bodies pass through.
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header, HeaderMap, HeaderName, Method},
+    middleware::Next,
+    response::Response,
+};
+
+pub const X_PRETTY: HeaderName = HeaderName::from_static("x-pretty");
+
+/// Whether the client asked for pretty output
+pub fn wants_pretty(query: Option<&str>, headers: &HeaderMap) -> bool {
+    let from_query = query
+        .unwrap_or_default()
+        .split('&')
+        .filter_map(|pair| pair.strip_prefix("pretty="))
+        .any(|value| value == "true");
+    let from_header = headers
+        .get(X_PRETTY)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.eq_ignore_ascii_case("true"));
+    from_query || from_header
+}
+
+fn is_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"))
+}
+
+/// Middleware pretty-printing JSON responses on request
+///
+/// Object keys come back in sorted order. Bodies that fail to parse are
+/// returned unchanged. HEAD responses have no body to reformat, so they keep
+/// the compact `Content-Length`.
+pub async fn pretty_json_middleware(req: Request, next: Next) -> Response {
+    let pretty = req.method() != Method::HEAD && wants_pretty(req.uri().query(), req.headers());
+    let response = next.run(req).await;
+    if !pretty || !is_json(response.headers()) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(_) => return Response::from_parts(parts, Body::empty()),
+    };
+    let body = match serde_json::from_slice::<serde_json::Value>(&bytes)
+        .ok()
+        .and_then(|value| serde_json::to_string_pretty(&value).ok())
+    {
+        Some(pretty) => {
+            parts.headers.remove(header::CONTENT_LENGTH);
+            Body::from(pretty)
+        }
+        None => Body::from(bytes),
+    };
+    Response::from_parts(parts, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware, routing::get, Json, Router};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/json",
+                get(|| async { Json(serde_json::json!({ "anchors": [1, 2], "total": 2 })) }),
+            )
+            .route(
+                "/csv",
+                get(|| async { ([(header::CONTENT_TYPE, "text/csv")], "a,b\n1,2") }),
+            )
+            .layer(middleware::from_fn(pretty_json_middleware))
+    }
+
+    async fn body(uri: &str, pretty_header: bool) -> String {
+        let mut request = Request::builder().uri(uri);
+        if pretty_header {
+            request = request.header(X_PRETTY, "true");
+        }
+        let response = app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_pretty_output_only_when_requested() {
+        let compact = body("/json", false).await;
+        assert!(!compact.contains('\n'));
+
+        for pretty in [
+            body("/json?pretty=true", false).await,
+            body("/json", true).await,
+        ] {
+            assert!(pretty.contains('\n'));
+            assert_eq!(
+                serde_json::from_str::<serde_json::Value>(&pretty).unwrap(),
+                serde_json::from_str::<serde_json::Value>(&compact).unwrap()
+            );
+        }
+        assert!(!body("/json?pretty=false", false).await.contains('\n'));
+    }
+
+    #[tokio::test]
+    async fn test_non_json_responses_are_untouched() {
+        assert_eq!(body("/csv?pretty=true", true).await, "a,b\n1,2");
+    }
+}
@@ -4,12 +4,22 @@ use axum::{
     middleware::Next,
     response::{IntoResponse, Response},
 };
+use dashmap::DashMap;
 use redis::aio::MultiplexedConnection;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
+use crate::rpc::Network;
+
+/// Length of a rate limit window
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Minimum time between Redis reconnection attempts while in memory mode
+const REDIS_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
 /// Rate limit configuration for an endpoint
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RateLimitConfig {
@@ -26,38 +36,166 @@ impl Default for RateLimitConfig {
     }
 }
 
+/// Fixed-window request counters kept in process memory.
+///
+/// Used automatically while Redis is unavailable. Counters are per instance
+/// only: with several replicas behind a load balancer each one enforces the
+/// limit independently, so the effective limit is multiplied by the replica
+/// count until Redis comes back.
+pub struct MemoryRateLimiter {
+    windows: DashMap<String, MemoryWindow>,
+    window: Duration,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct MemoryWindow {
+    count: u32,
+    expires_at: Instant,
+}
+
+impl MemoryRateLimiter {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            windows: DashMap::new(),
+            window,
+        }
+    }
+
+    /// Count a request against `key`, returning (allowed, remaining, reset_after_secs)
+    pub fn check(&self, key: &str, limit: u32) -> (bool, u32, u32) {
+        let now = Instant::now();
+        let mut entry = self
+            .windows
+            .entry(key.to_string())
+            .or_insert(MemoryWindow {
+                count: 0,
+                expires_at: now + self.window,
+            });
+
+        if now >= entry.expires_at {
+            entry.count = 0;
+            entry.expires_at = now + self.window;
+        }
+
+        let reset_after = Self::secs_until(entry.expires_at, now);
+
+        if entry.count >= limit {
+            return (false, 0, reset_after);
+        }
+
+        entry.count += 1;
+        (entry.count < limit, limit - entry.count, reset_after)
+    }
+
+    /// Current (used, reset_after_secs) for `key` without counting a request
+    pub fn usage(&self, key: &str) -> (u32, u32) {
+        let now = Instant::now();
+        match self.windows.get(key) {
+            Some(window) if now < window.expires_at => {
+                (window.count, Self::secs_until(window.expires_at, now))
+            }
+            _ => (0, self.window.as_secs() as u32),
+        }
+    }
+
+    /// Drop expired windows so idle clients don't accumulate
+    pub fn prune(&self) {
+        let now = Instant::now();
+        self.windows.retain(|_, window| now < window.expires_at);
+    }
+
+    fn secs_until(deadline: Instant, now: Instant) -> u32 {
+        deadline.saturating_duration_since(now).as_secs().max(1) as u32
+    }
+}
+
 /// Rate limiter state
+///
+/// Counters live in Redis when it is reachable and fall back to a
+/// [`MemoryRateLimiter`] otherwise, reconnecting in the background of normal
+/// requests once Redis recovers.
 pub struct RateLimiter {
+    redis_url: String,
     redis_connection: Arc<RwLock<Option<MultiplexedConnection>>>,
+    last_redis_attempt: Arc<RwLock<Instant>>,
     endpoint_configs: Arc<RwLock<HashMap<String, RateLimitConfig>>>,
-    fallback_memory_store: Arc<RwLock<HashMap<String, (u32, i64)>>>,
+    memory: MemoryRateLimiter,
 }
 
 impl RateLimiter {
     pub async fn new() -> anyhow::Result<Self> {
         let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
-        
-        let connection = if let Ok(client) = redis::Client::open(redis_url.as_str()) {
-            match client.get_multiplexed_tokio_connection().await {
-                Ok(conn) => {
-                    tracing::info!("Connected to Redis for rate limiting");
-                    Some(conn)
-                }
-                Err(e) => {
-                    tracing::warn!("Failed to connect to Redis ({}), using memory-only rate limiting", e);
-                    None
-                }
-            }
+        Ok(Self::with_redis_url(redis_url).await)
+    }
+
+    /// Create a limiter against a specific Redis URL, starting in memory mode
+    /// if it cannot be reached
+    pub async fn with_redis_url(redis_url: String) -> Self {
+        let connection = Self::connect(&redis_url).await;
+        if connection.is_some() {
+            tracing::info!("Connected to Redis for rate limiting");
         } else {
-            tracing::warn!("Invalid Redis URL, using memory-only rate limiting");
-            None
-        };
+            tracing::warn!("Redis unavailable, using per-instance memory rate limiting");
+        }
 
-        Ok(Self {
+        Self {
+            redis_url,
             redis_connection: Arc::new(RwLock::new(connection)),
+            last_redis_attempt: Arc::new(RwLock::new(Instant::now())),
             endpoint_configs: Arc::new(RwLock::new(HashMap::new())),
-            fallback_memory_store: Arc::new(RwLock::new(HashMap::new())),
-        })
+            memory: MemoryRateLimiter::new(WINDOW),
+        }
+    }
+
+    async fn connect(redis_url: &str) -> Option<MultiplexedConnection> {
+        match crate::cache::connect_redis(redis_url).await {
+            Ok(conn) => Some(conn),
+            Err(e) if crate::cache::is_redis_config_error(&e) => {
+                tracing::error!("Rate limiter Redis configuration error: {}", e);
+                None
+            }
+            Err(e) => {
+                tracing::debug!("Rate limiter Redis connection failed: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Whether counters are currently kept in Redis
+    pub async fn is_using_redis(&self) -> bool {
+        self.redis_connection.read().await.is_some()
+    }
+
+    /// Current Redis connection, reconnecting if memory mode has lasted long enough
+    async fn redis(&self) -> Option<MultiplexedConnection> {
+        if let Some(conn) = self.redis_connection.read().await.as_ref() {
+            return Some(conn.clone());
+        }
+
+        {
+            let mut last_attempt = self.last_redis_attempt.write().await;
+            if last_attempt.elapsed() < REDIS_RETRY_INTERVAL {
+                return None;
+            }
+            *last_attempt = Instant::now();
+        }
+
+        let conn = Self::connect(&self.redis_url).await?;
+        tracing::info!("Redis reachable again, switching rate limiting back to Redis");
+        *self.redis_connection.write().await = Some(conn.clone());
+        Some(conn)
+    }
+
+    /// Switch to memory mode after a Redis failure
+    async fn mark_redis_down(&self, error: &(dyn std::fmt::Display + Sync)) {
+        let mut connection = self.redis_connection.write().await;
+        if connection.take().is_some() {
+            tracing::warn!(
+                "Redis rate limiting failed ({}), falling back to per-instance memory limits",
+                error
+            );
+            *self.last_redis_attempt.write().await = Instant::now();
+        }
     }
 
     /// Register a rate limit config for an endpoint
@@ -72,15 +210,21 @@ impl RateLimiter {
         })
     }
 
-    /// Check rate limit for an IP/endpoint combination
+    /// Check rate limit for an IP/endpoint combination on `network`
+    ///
+    /// Each network has its own window, so testnet traffic never uses up
+    /// the mainnet allowance.
     pub async fn check_rate_limit(
         &self,
         ip: &str,
         endpoint: &str,
+        network: Network,
     ) -> (bool, RateLimitInfo) {
         // Get endpoint config
-        let configs = self.endpoint_configs.read().await;
-        let config = configs
+        let config = self
+            .endpoint_configs
+            .read()
+            .await
             .get(endpoint)
             .cloned()
             .unwrap_or_default();
@@ -95,12 +239,11 @@ impl RateLimiter {
             });
         }
 
-        let key = format!("ratelimit:{}:{}", endpoint, ip);
+        let key = limit_key(endpoint, ip, network);
         let limit = config.requests_per_minute;
 
         // Try Redis first
-        if let Some(conn) = self.redis_connection.read().await.as_ref() {
-            let mut conn = conn.clone();
+        if let Some(mut conn) = self.redis().await {
             match self.check_redis_limit(&mut conn, &key, limit).await {
                 Ok((allowed, remaining, reset)) => {
                     return (allowed, RateLimitInfo {
@@ -110,13 +253,12 @@ impl RateLimiter {
                         is_whitelisted: false,
                     });
                 }
-                Err(_) => {
-                }
+                Err(e) => self.mark_redis_down(&e).await,
             }
         }
 
         // Fall back to memory store
-        let (allowed, remaining, reset) = self.check_memory_limit(&key, limit).await;
+        let (allowed, remaining, reset) = self.memory.check(&key, limit);
         (
             allowed,
             RateLimitInfo {
@@ -128,6 +270,63 @@ impl RateLimiter {
         )
     }
 
+    /// Registered endpoint paths
+    pub async fn registered_endpoints(&self) -> Vec<String> {
+        let mut endpoints: Vec<String> =
+            self.endpoint_configs.read().await.keys().cloned().collect();
+        endpoints.sort();
+        endpoints
+    }
+
+    /// Report current usage for an IP/endpoint combination without consuming a request
+    pub async fn status(&self, ip: &str, endpoint: &str, network: Network) -> RateLimitInfo {
+        let config = self
+            .endpoint_configs
+            .read()
+            .await
+            .get(endpoint)
+            .cloned()
+            .unwrap_or_default();
+        let limit = config.requests_per_minute;
+
+        if self.is_whitelisted(ip, &config) {
+            return RateLimitInfo {
+                limit,
+                remaining: limit,
+                reset_after: 60,
+                is_whitelisted: true,
+            };
+        }
+
+        let key = limit_key(endpoint, ip, network);
+
+        if let Some(mut conn) = self.redis().await {
+            use redis::AsyncCommands;
+
+            match conn.get::<_, Option<u32>>(&key).await {
+                Ok(current) => {
+                    let ttl: i64 = conn.ttl(&key).await.unwrap_or(-1);
+                    return RateLimitInfo {
+                        limit,
+                        remaining: limit.saturating_sub(current.unwrap_or(0)),
+                        reset_after: if ttl > 0 { ttl as u32 } else { 60 },
+                        is_whitelisted: false,
+                    };
+                }
+                Err(e) => self.mark_redis_down(&e).await,
+            }
+        }
+
+        let (used, reset_after) = self.memory.usage(&key);
+
+        RateLimitInfo {
+            limit,
+            remaining: limit.saturating_sub(used),
+            reset_after,
+            is_whitelisted: false,
+        }
+    }
+
     /// Check rate limit in Redis
     async fn check_redis_limit(
         &self,
@@ -155,33 +354,9 @@ impl RateLimiter {
         Ok((new_count < limit, remaining, 60))
     }
 
-    /// Check rate limit in memory (fallback)
-    async fn check_memory_limit(
-        &self,
-        key: &str,
-        limit: u32,
-    ) -> (bool, u32, u32) {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64;
-
-        let mut store = self.fallback_memory_store.write().await;
-
-        let (count, expiry) = store.get(key).copied().unwrap_or((0, now + 60));
-
-        if now > expiry {
-            // Reset counter
-            store.insert(key.to_string(), (1, now + 60));
-            (true, limit - 1, 60)
-        } else if count >= limit {
-            (false, 0, (expiry - now) as u32)
-        } else {
-            let new_count = count + 1;
-            store.insert(key.to_string(), (new_count, expiry));
-            let remaining = if new_count >= limit { 0 } else { limit - new_count };
-            (new_count < limit, remaining, (expiry - now) as u32)
-        }
+    /// Drop expired in-memory windows
+    pub fn prune_memory(&self) {
+        self.memory.prune();
     }
 }
 
@@ -226,6 +401,11 @@ impl IntoResponse for RateLimitError {
     }
 }
 
+/// Counter key for one client on one endpoint of one network
+fn limit_key(endpoint: &str, ip: &str, network: Network) -> String {
+    format!("ratelimit:{}:{}:{}", network, endpoint, ip)
+}
+
 /// Middleware for rate limiting
 pub async fn rate_limit_middleware(
     State(limiter): State<Arc<RateLimiter>>,
@@ -235,8 +415,9 @@ pub async fn rate_limit_middleware(
 ) -> Response {
     let ip = addr.0.ip().to_string();
     let path = req.uri().path().to_string();
+    let network = Network::from_query(req.uri().query());
 
-    let (allowed, info) = limiter.check_rate_limit(&ip, &path).await;
+    let (allowed, info) = limiter.check_rate_limit(&ip, &path, network).await;
 
     if !allowed {
         return RateLimitError { info }.into_response();
@@ -258,3 +439,125 @@ pub async fn rate_limit_middleware(
 
     response
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Limiter pointed at a closed port so Redis is never reachable
+    async fn memory_only_limiter() -> RateLimiter {
+        RateLimiter::with_redis_url("redis://127.0.0.1:1".to_string()).await
+    }
+
+    #[tokio::test]
+    async fn test_memory_limiter_enforces_limit_without_redis() {
+        let limiter = memory_only_limiter().await;
+        assert!(!limiter.is_using_redis().await);
+        limiter
+            .register_endpoint(
+                "/api/test".to_string(),
+                RateLimitConfig {
+                    requests_per_minute: 3,
+                    whitelist_ips: vec![],
+                },
+            )
+            .await;
+
+        let results: Vec<bool> = collect_allowed(&limiter, "1.2.3.4", "/api/test", 4).await;
+        assert_eq!(results, vec![true, true, false, false]);
+
+        // Other clients have their own window
+        let (allowed, info) = limiter
+            .check_rate_limit("5.6.7.8", "/api/test", Network::Mainnet)
+            .await;
+        assert!(allowed);
+        assert_eq!(info.remaining, 2);
+    }
+
+    #[tokio::test]
+    async fn test_networks_have_separate_windows() {
+        let limiter = memory_only_limiter().await;
+        limiter
+            .register_endpoint(
+                "/api/rpc/test".to_string(),
+                RateLimitConfig {
+                    requests_per_minute: 2,
+                    whitelist_ips: vec![],
+                },
+            )
+            .await;
+
+        let check = |network| limiter.check_rate_limit("1.2.3.4", "/api/rpc/test", network);
+        assert!(check(Network::Testnet).await.0);
+        assert!(!check(Network::Testnet).await.0);
+        // Exhausting testnet leaves the mainnet allowance untouched
+        assert!(check(Network::Mainnet).await.0);
+    }
+
+    async fn collect_allowed(
+        limiter: &RateLimiter,
+        ip: &str,
+        endpoint: &str,
+        n: usize,
+    ) -> Vec<bool> {
+        let mut results = Vec::with_capacity(n);
+        for _ in 0..n {
+            let (allowed, _) = limiter
+                .check_rate_limit(ip, endpoint, Network::Mainnet)
+                .await;
+            results.push(allowed);
+        }
+        results
+    }
+
+    #[test]
+    fn test_memory_window_resets_after_expiry() {
+        let memory = MemoryRateLimiter::new(Duration::from_millis(20));
+
+        assert!(memory.check("key", 2).0);
+        assert!(!memory.check("key", 2).0);
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(memory.usage("key").0, 0);
+        assert!(memory.check("key", 2).0);
+
+        std::thread::sleep(Duration::from_millis(30));
+        memory.prune();
+        assert!(memory.windows.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_status_reports_remaining_after_requests() {
+        let limiter = RateLimiter::new().await.unwrap();
+        let endpoint = "/api/test/status";
+        let ip = format!("10.0.0.{}", std::process::id() % 250);
+        limiter
+            .register_endpoint(
+                endpoint.to_string(),
+                RateLimitConfig {
+                    requests_per_minute: 10,
+                    whitelist_ips: vec![],
+                },
+            )
+            .await;
+
+        for _ in 0..3 {
+            limiter
+                .check_rate_limit(&ip, endpoint, Network::Mainnet)
+                .await;
+        }
+
+        let status = limiter.status(&ip, endpoint, Network::Mainnet).await;
+        assert_eq!(status.limit, 10);
+        assert_eq!(status.remaining, 7);
+
+        // Inspecting status does not consume a request
+        assert_eq!(
+            limiter
+                .status(&ip, endpoint, Network::Mainnet)
+                .await
+                .remaining,
+            7
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Startup readiness gate
+//!
+//! With `STARTUP_WARM=true`, `GET /health/ready` answers 503 until the
+//! database answers a ping and the first caches are warm, so load balancers
+//! hold traffic back from a freshly started instance. Without it the server
+//! reports ready as soon as it binds.
+
+use anyhow::Result;
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::analytics::health::StatusThresholds;
+use crate::cache::{keys, CacheManager};
+use crate::cache_middleware::CacheAware;
+use crate::db::backend::DatabaseBackend;
+use crate::models::corridor::CorridorNetworkSummary;
+
+/// Wait between failed warm-up attempts
+pub const WARM_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Whether this instance has finished warming up
+pub struct Readiness {
+    ready: AtomicBool,
+}
+
+impl Readiness {
+    pub fn new(ready: bool) -> Self {
+        Self {
+            ready: AtomicBool::new(ready),
+        }
+    }
+
+    /// Start unready when `STARTUP_WARM` is `true`
+    pub fn from_env() -> Self {
+        let warm = std::env::var("STARTUP_WARM")
+            .ok()
+            .and_then(|v| v.parse::<bool>().ok())
+            .unwrap_or(false);
+
+        Self::new(!warm)
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Relaxed)
+    }
+
+    pub fn mark_ready(&self) {
+        self.ready.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Run `step` until it succeeds, then mark the instance ready
+///
+/// Failures are logged and retried after `retry_delay`; the instance stays
+/// unready until a whole attempt succeeds.
+pub async fn warm_up<F, Fut>(readiness: &Readiness, retry_delay: Duration, mut step: F)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    loop {
+        match step().await {
+            Ok(()) => break,
+            Err(e) => {
+                tracing::warn!("Startup warm-up failed, retrying: {:#}", e);
+                tokio::time::sleep(retry_delay).await;
+            }
+        }
+    }
+    readiness.mark_ready();
+    tracing::info!("Startup warm-up complete; serving as ready");
+}
+
+/// Load the corridor network summary into the cache the way the first
+/// `GET /api/corridors/summary` would
+pub async fn warm_cache(
+    db: &dyn DatabaseBackend,
+    cache: &Arc<CacheManager>,
+    thresholds: &StatusThresholds,
+) -> Result<()> {
+    let _: CorridorNetworkSummary = <()>::get_or_fetch_tagged(
+        cache,
+        &keys::corridor_network_summary(),
+        cache.config.get_ttl("dashboard"),
+        &[keys::corridors_tag(), keys::corridor_lists_tag()],
+        db.corridor_summary(thresholds.yellow_min_success_rate),
+    )
+    .await?;
+    Ok(())
+}
+
+/// GET /health/ready - 200 once warm-up is done, 503 before
+pub async fn ready_check(State(readiness): State<Arc<Readiness>>) -> Response {
+    if readiness.is_ready() {
+        Json(serde_json::json!({ "status": "ready" })).into_response()
+    } else {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({ "status": "warming_up" })),
+        )
+            .into_response()
+    }
+}
+
+pub fn routes(readiness: Arc<Readiness>) -> Router {
+    Router::new()
+        .route("/health/ready", get(ready_check))
+        .with_state(readiness)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use tower::ServiceExt;
+
+    async fn status(readiness: &Arc<Readiness>) -> StatusCode {
+        routes(Arc::clone(readiness))
+            .oneshot(
+                Request::builder()
+                    .uri("/health/ready")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_ready_after_warm_up_succeeds() {
+        let readiness = Arc::new(Readiness::new(false));
+        assert_eq!(status(&readiness).await, StatusCode::SERVICE_UNAVAILABLE);
+
+        let (release, released) = tokio::sync::oneshot::channel::<()>();
+        let warming = tokio::spawn({
+            let readiness = Arc::clone(&readiness);
+            async move {
+                let mut released = Some(released);
+                let mut attempts = 0;
+                warm_up(&readiness, Duration::from_millis(1), || {
+                    attempts += 1;
+                    let released = released.take();
+                    async move {
+                        match released {
+                            Some(released) => {
+                                // First attempt waits for the test, then fails
+                                released.await.ok();
+                                anyhow::bail!("database not reachable yet")
+                            }
+                            None => Ok(()),
+                        }
+                    }
+                })
+                .await;
+                attempts
+            }
+        });
+
+        tokio::task::yield_now().await;
+        assert_eq!(status(&readiness).await, StatusCode::SERVICE_UNAVAILABLE);
+
+        release.send(()).unwrap();
+        assert_eq!(warming.await.unwrap(), 2);
+        assert_eq!(status(&readiness).await, StatusCode::OK);
+    }
+}
@@ -0,0 +1,179 @@
+//! Exponential backoff with jitter for outbound calls
+//!
+//! [`retry_with_backoff`] reruns an operation until it succeeds, fails with an
+//! error the caller's predicate calls permanent, or runs out of attempts.
+//! Delays double from [`RetryPolicy::base_delay`] up to
+//! [`RetryPolicy::max_delay`], each shortened by a random share of up to
+//! [`RetryPolicy::jitter`] so clients that failed together don't retry in step.
+
+use rand::Rng;
+use std::fmt::Display;
+use std::future::Future;
+use std::time::Duration;
+use tracing::warn;
+
+/// How often and how patiently to retry
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Attempts in total, the first included; 0 is treated as 1
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub base_delay: Duration,
+    /// Upper bound on any single delay
+    pub max_delay: Duration,
+    /// Largest share (0.0-1.0) of a delay randomly taken off it
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `retry` (0 for the first retry), without jitter
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.min(31));
+        self.base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+
+    /// [`RetryPolicy::delay`] shortened by `jitter * unit`, `unit` in 0.0..=1.0
+    fn jittered_delay(&self, retry: u32, unit: f64) -> Duration {
+        let jitter = self.jitter.clamp(0.0, 1.0) * unit.clamp(0.0, 1.0);
+        self.delay(retry).mul_f64(1.0 - jitter)
+    }
+}
+
+/// Run `operation` until it succeeds or fails in a way not worth retrying
+///
+/// `is_retryable` decides whether an error is transient. The last error is
+/// returned once `policy.max_attempts` attempts have failed.
+pub async fn retry_with_backoff<T, E, F, Fut, P>(
+    policy: &RetryPolicy,
+    is_retryable: P,
+    mut operation: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    P: Fn(&E) -> bool,
+    E: Display,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 0;
+
+    loop {
+        let err = match operation().await {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+        attempt += 1;
+        if attempt >= max_attempts || !is_retryable(&err) {
+            return Err(err);
+        }
+
+        let delay = policy.jittered_delay(attempt - 1, rand::thread_rng().gen());
+        warn!(
+            "Attempt {}/{} failed, retrying in {} ms: {}",
+            attempt,
+            max_attempts,
+            delay.as_millis(),
+            err
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(4),
+            jitter: 0.5,
+        }
+    }
+
+    #[test]
+    fn test_backoff_schedule() {
+        let policy = RetryPolicy {
+            jitter: 0.0,
+            ..RetryPolicy::default()
+        };
+        let delays: Vec<u128> = (0..9)
+            .map(|retry| policy.delay(retry).as_millis())
+            .collect();
+        assert_eq!(
+            delays,
+            vec![100, 200, 400, 800, 1_600, 3_200, 6_400, 10_000, 10_000]
+        );
+        assert_eq!(policy.delay(u32::MAX), policy.max_delay);
+
+        // Jitter only ever shortens a delay, by at most its share
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.jittered_delay(2, 0.0), Duration::from_millis(400));
+        assert_eq!(policy.jittered_delay(2, 1.0), Duration::from_millis(320));
+        assert_eq!(policy.jittered_delay(2, 0.5), Duration::from_millis(360));
+    }
+
+    #[tokio::test]
+    async fn test_retries_until_success() {
+        let attempts = AtomicU32::new(0);
+        let result: Result<u32, String> = retry_with_backoff(
+            &policy(5),
+            |_| true,
+            || async {
+                match attempts.fetch_add(1, Ordering::SeqCst) {
+                    0 | 1 => Err("unavailable".to_string()),
+                    n => Ok(n),
+                }
+            },
+        )
+        .await;
+
+        assert_eq!(result, Ok(2));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_predicate_decides_what_is_retried() {
+        let attempts = AtomicU32::new(0);
+        let permanent: Result<(), String> = retry_with_backoff(
+            &policy(5),
+            |err: &String| err != "not found",
+            || async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err("not found".to_string())
+            },
+        )
+        .await;
+        assert_eq!(permanent, Err("not found".to_string()));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        let attempts = AtomicU32::new(0);
+        let exhausted: Result<(), String> = retry_with_backoff(
+            &policy(3),
+            |err: &String| err != "not found",
+            || async {
+                let n = attempts.fetch_add(1, Ordering::SeqCst);
+                Err(format!("timeout {}", n))
+            },
+        )
+        .await;
+        assert_eq!(exhausted, Err("timeout 2".to_string()));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+}
@@ -1,6 +1,11 @@
+pub mod network;
 pub mod stellar;
 
+pub use network::Network;
+
 pub use stellar::{
-    Asset, GetLedgersResult, HealthResponse, LedgerInfo, OrderBook, OrderBookEntry, Payment, Price,
-    RpcLedger, StellarRpcClient, Trade,
+    AccountBalance, AccountBalances, Asset, ContractEvent, FeeDistribution, FeeStats,
+    GetEventsResult, GetLedgersResult, HealthResponse, HorizonNotFound, LedgerInfo, OrderBook,
+    OrderBookEntry, Payment, Price, RpcLedger, StellarRpcClient, Trade, TradeVolume,
+    TransactionEffect,
 };
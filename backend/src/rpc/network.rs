@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Default SDF testnet endpoints, used unless overridden from the environment
+pub const DEFAULT_TESTNET_RPC_URL: &str = "https://soroban-testnet.stellar.org";
+pub const DEFAULT_TESTNET_HORIZON_URL: &str = "https://horizon-testnet.stellar.org";
+
+/// Stellar network an RPC request is served from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Network {
+    #[default]
+    Mainnet,
+    Testnet,
+}
+
+impl Network {
+    pub const ALL: [Network; 2] = [Network::Mainnet, Network::Testnet];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Network::Mainnet => "mainnet",
+            Network::Testnet => "testnet",
+        }
+    }
+
+    /// Network named by the `network` parameter of a raw query string
+    ///
+    /// Missing or unrecognised values fall back to mainnet; handlers reject
+    /// unrecognised values themselves, so this is only for bucketing.
+    pub fn from_query(query: Option<&str>) -> Self {
+        query
+            .unwrap_or_default()
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("network="))
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Network {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mainnet" => Ok(Network::Mainnet),
+            "testnet" => Ok(Network::Testnet),
+            other => anyhow::bail!("network must be mainnet or testnet, got '{}'", other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_network() {
+        assert_eq!("mainnet".parse::<Network>().unwrap(), Network::Mainnet);
+        assert_eq!("testnet".parse::<Network>().unwrap(), Network::Testnet);
+        assert!("futurenet".parse::<Network>().is_err());
+        assert!("Testnet".parse::<Network>().is_err());
+    }
+
+    #[test]
+    fn test_network_from_query() {
+        assert_eq!(Network::from_query(None), Network::Mainnet);
+        assert_eq!(
+            Network::from_query(Some("limit=5&network=testnet")),
+            Network::Testnet
+        );
+        assert_eq!(Network::from_query(Some("network=bogus")), Network::Mainnet);
+    }
+}
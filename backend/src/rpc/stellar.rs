@@ -1,21 +1,98 @@
 use anyhow::{Context, Result};
+use futures::future::{BoxFuture, FutureExt, Shared};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tracing::{debug, info, warn};
+use tokio::sync::{Mutex, Semaphore};
+use tracing::{debug, info};
+
+use super::network::{Network, DEFAULT_TESTNET_HORIZON_URL, DEFAULT_TESTNET_RPC_URL};
+use crate::cache::CacheSchema;
+use crate::retry::{retry_with_backoff, RetryPolicy};
 
 const MAX_RETRIES: u32 = 3;
-const INITIAL_BACKOFF_MS: u64 = 100;
-const BACKOFF_MULTIPLIER: u64 = 2;
+
+/// Backoff for RPC and Horizon requests: 100ms doubling, jittered
+const RPC_RETRY_POLICY: RetryPolicy = RetryPolicy {
+    max_attempts: MAX_RETRIES + 1,
+    base_delay: Duration::from_millis(100),
+    max_delay: Duration::from_secs(2),
+    jitter: 0.2,
+};
+
+/// Default cap on concurrent outbound RPC/Horizon requests
+pub const DEFAULT_MAX_IN_FLIGHT_REQUESTS: usize = 10;
+
+/// Default limit on a single request, from connect to the last byte read
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default time [`StellarRpcClient::latest_ledger_cached`] reuses a fetched sequence
+pub const DEFAULT_LATEST_LEDGER_TTL: Duration = Duration::from_secs(2);
+
+/// Largest page Horizon serves for collection endpoints
+const HORIZON_MAX_PAGE_SIZE: u32 = 200;
+
+/// Default cap on trades read by [`StellarRpcClient::fetch_trade_volume`]
+pub const DEFAULT_TRADE_VOLUME_MAX_RECORDS: usize = 10_000;
+
+/// Network tip reported in mock mode; every mock fixture is anchored to it
+pub const MOCK_LATEST_LEDGER: u64 = 51583040;
+const MOCK_OLDEST_LEDGER: u64 = 51565760;
+/// Close time of [`MOCK_LATEST_LEDGER`], 2026-01-22T10:30:00Z
+const MOCK_LATEST_CLOSE_TIME: i64 = 1769077800;
+const MOCK_LEDGER_CLOSE_SECS: i64 = 5;
+/// Payments (and trades) per mock ledger
+const MOCK_OPS_PER_LEDGER: u64 = 3;
+const MOCK_ISSUER: &str = "GBXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX";
+
+/// Result of an upstream call shared by everyone waiting on it
+type SharedCall =
+    Shared<BoxFuture<'static, Result<Arc<dyn Any + Send + Sync>, Arc<anyhow::Error>>>>;
+
+/// Last latest-ledger sequence and when it was fetched
+type LatestLedgerCache = Arc<Mutex<Option<(Instant, u64)>>>;
+
+/// RPC and Horizon endpoints for one network
+#[derive(Clone)]
+struct NetworkEndpoints {
+    rpc_url: String,
+    horizon_url: String,
+    latest_ledger: LatestLedgerCache,
+}
+
+impl NetworkEndpoints {
+    fn new(rpc_url: String, horizon_url: String) -> Self {
+        Self {
+            rpc_url,
+            horizon_url,
+            latest_ledger: Arc::new(Mutex::new(None)),
+        }
+    }
+}
 
 /// Stellar RPC Client for interacting with Stellar network via RPC and Horizon API
+///
+/// Holds endpoints for every [`Network`] and talks to one of them;
+/// [`Self::for_network`] switches to another.
 #[derive(Clone)]
 pub struct StellarRpcClient {
     client: Client,
+    network: Network,
     rpc_url: String,
     horizon_url: String,
+    networks: HashMap<Network, NetworkEndpoints>,
     mock_mode: bool,
+    /// Shared by all clones; requests beyond the limit wait for a permit
+    in_flight: Arc<Semaphore>,
+    /// Upstream calls in progress, keyed by request URL, shared by all clones
+    calls: Arc<std::sync::Mutex<HashMap<String, SharedCall>>>,
+    /// Last latest-ledger sequence of the current network, shared by all clones
+    latest_ledger: LatestLedgerCache,
+    latest_ledger_ttl: Duration,
 }
 
 // ============================================================================
@@ -73,6 +150,27 @@ pub struct Payment {
     pub asset_issuer: Option<String>,
     pub amount: String,
     pub created_at: String,
+    /// Asset a path payment was sent in; absent on plain payments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_asset_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_asset_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_asset_issuer: Option<String>,
+}
+
+impl Payment {
+    /// `(code, issuer)` of the asset a path payment was sent in, with the
+    /// native asset as `XLM`/`native`; `None` for plain payments
+    pub fn source_asset(&self) -> Option<(String, String)> {
+        match self.source_asset_type.as_deref()? {
+            "native" => Some(("XLM".to_string(), "native".to_string())),
+            _ => Some((
+                self.source_asset_code.clone()?,
+                self.source_asset_issuer.clone()?,
+            )),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -93,6 +191,24 @@ pub struct Trade {
     pub trade_type: String,
 }
 
+/// DEX volume for an asset pair over a trailing window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeVolume {
+    pub base: Asset,
+    pub counter: Asset,
+    /// Start of the window (RFC 3339); trades closed before it are excluded
+    pub since: String,
+    pub trade_count: u64,
+    pub base_volume: f64,
+    pub counter_volume: f64,
+    /// Whether the record cap was hit before the whole window was read
+    pub truncated: bool,
+}
+
+impl CacheSchema for TradeVolume {
+    const TAG: &'static str = "trade_volume.v1";
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Price {
     pub n: i64,
@@ -134,6 +250,102 @@ pub struct EmbeddedRecords<T> {
     pub records: Vec<T>,
 }
 
+/// Error returned when Horizon responds 404 for the requested resource
+#[derive(Debug, Clone)]
+pub struct HorizonNotFound {
+    pub url: String,
+}
+
+impl std::fmt::Display for HorizonNotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Horizon resource not found: {}", self.url)
+    }
+}
+
+impl std::error::Error for HorizonNotFound {}
+
+/// Whether a failed request may succeed if sent again
+///
+/// Horizon 404s are permanent; transport errors, timeouts and other error
+/// statuses are retried.
+fn is_retryable_error(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<HorizonNotFound>().is_none()
+}
+
+/// Balances and trustlines held by an account
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountBalances {
+    pub account_id: String,
+    pub balances: Vec<AccountBalance>,
+}
+
+impl CacheSchema for AccountBalances {
+    const TAG: &'static str = "account_balances.v1";
+}
+
+/// A single balance entry; every non-native balance is a trustline with a limit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountBalance {
+    pub asset_type: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    pub balance: String,
+    pub limit: Option<String>,
+}
+
+/// An operation effect of a transaction, normalized across effect types
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionEffect {
+    pub id: String,
+    pub account: String,
+    #[serde(rename = "type")]
+    pub effect_type: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub amount: Option<String>,
+    #[serde(default)]
+    pub asset_type: Option<String>,
+    #[serde(default)]
+    pub asset_code: Option<String>,
+    #[serde(default)]
+    pub asset_issuer: Option<String>,
+    /// Remaining type-specific fields as returned by Horizon
+    #[serde(flatten)]
+    pub details: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Horizon `/fee_stats` response; all values are stroop amounts encoded as strings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeStats {
+    pub last_ledger: String,
+    pub last_ledger_base_fee: String,
+    pub ledger_capacity_usage: String,
+    pub fee_charged: FeeDistribution,
+    pub max_fee: FeeDistribution,
+}
+
+impl CacheSchema for FeeStats {
+    const TAG: &'static str = "fee_stats.v1";
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeDistribution {
+    pub max: String,
+    pub min: String,
+    pub mode: String,
+    pub p10: String,
+    pub p20: String,
+    pub p30: String,
+    pub p40: String,
+    pub p50: String,
+    pub p60: String,
+    pub p70: String,
+    pub p80: String,
+    pub p90: String,
+    pub p95: String,
+    pub p99: String,
+}
+
 // I'm adding structs for getLedgers RPC method as required by issue #2
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RpcLedger {
@@ -157,10 +369,47 @@ pub struct GetLedgersResult {
     pub cursor: Option<String>,
 }
 
+/// A Soroban contract event returned by RPC `getEvents`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractEvent {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub ledger: u64,
+    pub ledger_closed_at: String,
+    pub contract_id: String,
+    /// Base64 XDR `ScVal`s
+    pub topic: Vec<String>,
+    /// Base64 XDR `ScVal`
+    pub value: String,
+    #[serde(default)]
+    pub in_successful_contract_call: bool,
+    #[serde(default)]
+    pub tx_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetEventsResult {
+    pub events: Vec<ContractEvent>,
+    pub latest_ledger: u64,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
 // ============================================================================
 // Implementation
 // ============================================================================
 
+fn http_client(timeout: Duration) -> Client {
+    Client::builder()
+        .connect_timeout(timeout)
+        .timeout(timeout)
+        .build()
+        .expect("Failed to build HTTP client")
+}
+
 impl StellarRpcClient {
     /// Create a new Stellar RPC client
     ///
@@ -168,18 +417,83 @@ impl StellarRpcClient {
     /// * `rpc_url` - The Stellar RPC endpoint URL (e.g., OnFinality)
     /// * `horizon_url` - The Horizon API endpoint URL
     /// * `mock_mode` - If true, returns mock data instead of making real API calls
+    ///
+    /// The URLs are used for mainnet; testnet uses the public SDF endpoints
+    /// unless set with [`Self::with_network_urls`].
     pub fn new(rpc_url: String, horizon_url: String, mock_mode: bool) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
-            .expect("Failed to build HTTP client");
-
+        let mainnet = NetworkEndpoints::new(rpc_url, horizon_url);
+        let testnet = NetworkEndpoints::new(
+            DEFAULT_TESTNET_RPC_URL.to_string(),
+            DEFAULT_TESTNET_HORIZON_URL.to_string(),
+        );
         Self {
-            client,
-            rpc_url,
-            horizon_url,
+            client: http_client(DEFAULT_REQUEST_TIMEOUT),
+            network: Network::Mainnet,
+            rpc_url: mainnet.rpc_url.clone(),
+            horizon_url: mainnet.horizon_url.clone(),
+            latest_ledger: Arc::clone(&mainnet.latest_ledger),
+            networks: HashMap::from([(Network::Mainnet, mainnet), (Network::Testnet, testnet)]),
             mock_mode,
+            in_flight: Arc::new(Semaphore::new(DEFAULT_MAX_IN_FLIGHT_REQUESTS)),
+            calls: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            latest_ledger_ttl: DEFAULT_LATEST_LEDGER_TTL,
+        }
+    }
+
+    /// Set the RPC and Horizon URLs used for `network`
+    pub fn with_network_urls(
+        mut self,
+        network: Network,
+        rpc_url: String,
+        horizon_url: String,
+    ) -> Self {
+        self.networks
+            .insert(network, NetworkEndpoints::new(rpc_url, horizon_url));
+        if network == self.network {
+            self = self.for_network(network);
         }
+        self
+    }
+
+    /// A client for `network` sharing this client's connection pool, request
+    /// limit and in-flight calls
+    ///
+    /// Mock mode serves the same fixtures for every network.
+    pub fn for_network(&self, network: Network) -> Self {
+        let endpoints = &self.networks[&network];
+        Self {
+            network,
+            rpc_url: endpoints.rpc_url.clone(),
+            horizon_url: endpoints.horizon_url.clone(),
+            latest_ledger: Arc::clone(&endpoints.latest_ledger),
+            ..self.clone()
+        }
+    }
+
+    /// Network this client talks to
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    /// Limit how many requests this client (and its clones) run at once
+    pub fn with_max_in_flight_requests(mut self, max_in_flight: usize) -> Self {
+        self.in_flight = Arc::new(Semaphore::new(max_in_flight.max(1)));
+        self
+    }
+
+    /// Fail any single request that takes longer than `timeout`
+    ///
+    /// Each retry attempt gets the full timeout; a timed-out attempt is
+    /// retried like any other transport error.
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.client = http_client(timeout);
+        self
+    }
+
+    /// Set how long [`Self::latest_ledger_cached`] reuses a fetched sequence
+    pub fn with_latest_ledger_ttl(mut self, ttl: Duration) -> Self {
+        self.latest_ledger_ttl = ttl;
+        self
     }
 
     /// Create a new client with default OnFinality RPC and Horizon URLs
@@ -222,6 +536,27 @@ impl StellarRpcClient {
         json_response.result.context("No result in health response")
     }
 
+    /// Latest ledger sequence known to RPC, reused for the latest-ledger TTL
+    ///
+    /// Callers arriving while a fetch is in progress wait for it rather than
+    /// sending their own request. Failed fetches are not cached.
+    pub async fn latest_ledger_cached(&self) -> Result<u64> {
+        if self.mock_mode {
+            return Ok(Self::mock_health_response().latest_ledger);
+        }
+
+        let mut cached = self.latest_ledger.lock().await;
+        if let Some((fetched_at, sequence)) = *cached {
+            if fetched_at.elapsed() < self.latest_ledger_ttl {
+                return Ok(sequence);
+            }
+        }
+
+        let sequence = self.check_health().await?.latest_ledger;
+        *cached = Some((Instant::now(), sequence));
+        Ok(sequence)
+    }
+
     /// Fetch latest ledger information
     pub async fn fetch_latest_ledger(&self) -> Result<LedgerInfo> {
         if self.mock_mode {
@@ -232,22 +567,25 @@ impl StellarRpcClient {
 
         let url = format!("{}/ledgers?order=desc&limit=1", self.horizon_url);
 
-        let response = self
-            .retry_request(|| async { self.client.get(&url).send().await })
-            .await
-            .context("Failed to fetch latest ledger")?;
-
-        let horizon_response: HorizonResponse<LedgerInfo> = response
-            .json()
-            .await
-            .context("Failed to parse ledger response")?;
-
-        let ledger = horizon_response
-            .embedded
-            .and_then(|e| e.records.into_iter().next())
-            .context("No ledger data found")?;
-
-        Ok(ledger)
+        self.single_flight(url.clone(), |client| async move {
+            let response = client
+                .retry_request(|| async { client.client.get(&url).send().await })
+                .await
+                .context("Failed to fetch latest ledger")?;
+
+            let horizon_response: HorizonResponse<LedgerInfo> = response
+                .json()
+                .await
+                .context("Failed to parse ledger response")?;
+
+            let ledger = horizon_response
+                .embedded
+                .and_then(|e| e.records.into_iter().next())
+                .context("No ledger data found")?;
+
+            Ok(ledger)
+        })
+        .await
     }
 
     /// I'm fetching ledgers via RPC getLedgers for sequential ingestion (issue #2)
@@ -258,7 +596,11 @@ impl StellarRpcClient {
         cursor: Option<&str>,
     ) -> Result<GetLedgersResult> {
         if self.mock_mode {
-            return Ok(Self::mock_get_ledgers(start_ledger.unwrap_or(1000), limit));
+            return Ok(Self::mock_get_ledgers(
+                start_ledger.unwrap_or(MOCK_OLDEST_LEDGER),
+                limit,
+                cursor,
+            ));
         }
 
         info!("Fetching ledgers via RPC getLedgers");
@@ -304,10 +646,77 @@ impl StellarRpcClient {
             .context("No result in getLedgers response")
     }
 
+    /// Fetch events emitted by a Soroban contract via RPC `getEvents`
+    ///
+    /// Returns at most `limit` events from ledgers `start_ledger` through
+    /// `end_ledger` (or the latest ledger when `None`).
+    pub async fn get_events(
+        &self,
+        contract_id: &str,
+        start_ledger: u64,
+        end_ledger: Option<u64>,
+        limit: u32,
+    ) -> Result<GetEventsResult> {
+        if self.mock_mode {
+            return Ok(Self::mock_events(
+                contract_id,
+                start_ledger,
+                end_ledger,
+                limit,
+            ));
+        }
+
+        info!(
+            "Fetching events for contract {} from ledger {} via RPC getEvents",
+            contract_id, start_ledger
+        );
+
+        let mut params = json!({
+            "startLedger": start_ledger,
+            "filters": [{ "type": "contract", "contractIds": [contract_id] }],
+            "pagination": { "limit": limit }
+        });
+        if let Some(end) = end_ledger {
+            // getEvents treats endLedger as exclusive
+            params["endLedger"] = json!(end + 1);
+        }
+
+        let payload = json!({
+            "jsonrpc": "2.0",
+            "method": "getEvents",
+            "id": 1,
+            "params": params
+        });
+
+        let response = self
+            .retry_request(|| async { self.client.post(&self.rpc_url).json(&payload).send().await })
+            .await
+            .context("Failed to fetch events")?;
+
+        let json_response: JsonRpcResponse<GetEventsResult> = response
+            .json()
+            .await
+            .context("Failed to parse getEvents response")?;
+
+        if let Some(error) = json_response.error {
+            anyhow::bail!("RPC error: {} (code: {})", error.message, error.code);
+        }
+
+        let mut result = json_response
+            .result
+            .context("No result in getEvents response")?;
+        // Older RPC versions ignore endLedger
+        if let Some(end) = end_ledger {
+            result.events.retain(|event| event.ledger <= end);
+        }
+
+        Ok(result)
+    }
+
     /// Fetch recent payments
     pub async fn fetch_payments(&self, limit: u32, cursor: Option<&str>) -> Result<Vec<Payment>> {
         if self.mock_mode {
-            return Ok(Self::mock_payments(limit));
+            return Self::mock_payments(limit, cursor);
         }
 
         info!("Fetching {} payments from Horizon API", limit);
@@ -318,28 +727,63 @@ impl StellarRpcClient {
             url.push_str(&format!("&cursor={}", cursor));
         }
 
-        let response = self
-            .retry_request(|| async { self.client.get(&url).send().await })
-            .await
-            .context("Failed to fetch payments")?;
+        self.single_flight(url.clone(), |client| async move {
+            let response = client
+                .retry_request(|| async { client.client.get(&url).send().await })
+                .await
+                .context("Failed to fetch payments")?;
+
+            let horizon_response: HorizonResponse<Payment> = response
+                .json()
+                .await
+                .context("Failed to parse payments response")?;
+
+            let payments = horizon_response
+                .embedded
+                .map(|e| e.records)
+                .unwrap_or_default();
+
+            Ok(payments)
+        })
+        .await
+    }
 
-        let horizon_response: HorizonResponse<Payment> = response
-            .json()
-            .await
-            .context("Failed to parse payments response")?;
+    /// Fetch payments newer than `cursor`, oldest first
+    pub async fn fetch_payments_after(&self, limit: u32, cursor: &str) -> Result<Vec<Payment>> {
+        if self.mock_mode {
+            return Self::mock_payments_after(limit, cursor);
+        }
 
-        let payments = horizon_response
-            .embedded
-            .map(|e| e.records)
-            .unwrap_or_default();
+        info!("Fetching {} payments from Horizon API", limit);
 
-        Ok(payments)
+        let url = format!(
+            "{}/payments?order=asc&limit={}&cursor={}",
+            self.horizon_url, limit, cursor
+        );
+
+        self.single_flight(url.clone(), |client| async move {
+            let response = client
+                .retry_request(|| async { client.client.get(&url).send().await })
+                .await
+                .context("Failed to fetch payments")?;
+
+            let horizon_response: HorizonResponse<Payment> = response
+                .json()
+                .await
+                .context("Failed to parse payments response")?;
+
+            Ok(horizon_response
+                .embedded
+                .map(|e| e.records)
+                .unwrap_or_default())
+        })
+        .await
     }
 
     /// Fetch recent trades
     pub async fn fetch_trades(&self, limit: u32, cursor: Option<&str>) -> Result<Vec<Trade>> {
         if self.mock_mode {
-            return Ok(Self::mock_trades(limit));
+            return Self::mock_trades(limit, cursor);
         }
 
         info!("Fetching {} trades from Horizon API", limit);
@@ -350,22 +794,140 @@ impl StellarRpcClient {
             url.push_str(&format!("&cursor={}", cursor));
         }
 
-        let response = self
-            .retry_request(|| async { self.client.get(&url).send().await })
-            .await
-            .context("Failed to fetch trades")?;
+        self.single_flight(url.clone(), |client| async move {
+            let response = client
+                .retry_request(|| async { client.client.get(&url).send().await })
+                .await
+                .context("Failed to fetch trades")?;
+
+            let horizon_response: HorizonResponse<Trade> = response
+                .json()
+                .await
+                .context("Failed to parse trades response")?;
+
+            let trades = horizon_response
+                .embedded
+                .map(|e| e.records)
+                .unwrap_or_default();
+
+            Ok(trades)
+        })
+        .await
+    }
 
-        let horizon_response: HorizonResponse<Trade> = response
-            .json()
-            .await
-            .context("Failed to parse trades response")?;
+    /// Fetch recent trades between `base` and `counter`, newest first
+    ///
+    /// Mock mode ignores the pair and serves the XLM/USDC trade fixtures.
+    pub async fn fetch_pair_trades(
+        &self,
+        base: &Asset,
+        counter: &Asset,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<Vec<Trade>> {
+        if self.mock_mode {
+            return Self::mock_trades(limit, cursor);
+        }
 
-        let trades = horizon_response
-            .embedded
-            .map(|e| e.records)
-            .unwrap_or_default();
+        let mut url = format!(
+            "{}/trades?{}&{}&order=desc&limit={}",
+            self.horizon_url,
+            Self::asset_to_query_params("base", base),
+            Self::asset_to_query_params("counter", counter),
+            limit
+        );
+
+        if let Some(cursor) = cursor {
+            url.push_str(&format!("&cursor={}", cursor));
+        }
+
+        self.single_flight(url.clone(), |client| async move {
+            let response = client
+                .retry_request(|| async { client.client.get(&url).send().await })
+                .await
+                .context("Failed to fetch pair trades")?;
+
+            let horizon_response: HorizonResponse<Trade> = response
+                .json()
+                .await
+                .context("Failed to parse trades response")?;
+
+            Ok(horizon_response
+                .embedded
+                .map(|e| e.records)
+                .unwrap_or_default())
+        })
+        .await
+    }
+
+    /// Sum the trades between `base` and `counter` over the last `window`
+    ///
+    /// Pages through Horizon newest first until a trade older than the window
+    /// turns up, reading at most `max_records` trades. In mock mode the window
+    /// ends at the mock network tip rather than now.
+    pub async fn fetch_trade_volume(
+        &self,
+        base: &Asset,
+        counter: &Asset,
+        window: chrono::Duration,
+        max_records: usize,
+    ) -> Result<TradeVolume> {
+        let now = if self.mock_mode {
+            chrono::DateTime::from_timestamp(MOCK_LATEST_CLOSE_TIME, 0).unwrap_or_default()
+        } else {
+            chrono::Utc::now()
+        };
+        let since = now - window;
+
+        info!("Summing trade volume since {}", since);
+
+        let mut volume = TradeVolume {
+            base: base.clone(),
+            counter: counter.clone(),
+            since: since.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            trade_count: 0,
+            base_volume: 0.0,
+            counter_volume: 0.0,
+            truncated: false,
+        };
+        let mut cursor: Option<String> = None;
+
+        'pages: loop {
+            let remaining = max_records - volume.trade_count as usize;
+            if remaining == 0 {
+                volume.truncated = true;
+                break;
+            }
+            let page_size = HORIZON_MAX_PAGE_SIZE.min(remaining as u32);
+            let trades = self
+                .fetch_pair_trades(base, counter, page_size, cursor.as_deref())
+                .await?;
+
+            for trade in &trades {
+                let closed_at = chrono::DateTime::parse_from_rfc3339(&trade.ledger_close_time)
+                    .with_context(|| format!("Invalid close time on trade {}", trade.id))?;
+                if closed_at < since {
+                    break 'pages;
+                }
+                volume.trade_count += 1;
+                volume.base_volume += trade
+                    .base_amount
+                    .parse::<f64>()
+                    .with_context(|| format!("Invalid base amount on trade {}", trade.id))?;
+                volume.counter_volume += trade
+                    .counter_amount
+                    .parse::<f64>()
+                    .with_context(|| format!("Invalid counter amount on trade {}", trade.id))?;
+            }
+
+            // A trade's id is also its paging token
+            match trades.last() {
+                Some(last) if trades.len() == page_size as usize => cursor = Some(last.id.clone()),
+                _ => break,
+            }
+        }
 
-        Ok(trades)
+        Ok(volume)
     }
 
     /// Fetch order book for a trading pair
@@ -376,7 +938,7 @@ impl StellarRpcClient {
         limit: u32,
     ) -> Result<OrderBook> {
         if self.mock_mode {
-            return Ok(Self::mock_order_book(selling_asset, buying_asset));
+            return Ok(Self::mock_order_book(selling_asset, buying_asset, limit));
         }
 
         info!("Fetching order book from Horizon API");
@@ -389,22 +951,25 @@ impl StellarRpcClient {
             self.horizon_url, selling_params, buying_params, limit
         );
 
-        let response = self
-            .retry_request(|| async { self.client.get(&url).send().await })
-            .await
-            .context("Failed to fetch order book")?;
+        self.single_flight(url.clone(), |client| async move {
+            let response = client
+                .retry_request(|| async { client.client.get(&url).send().await })
+                .await
+                .context("Failed to fetch order book")?;
 
-        let order_book: OrderBook = response
-            .json()
-            .await
-            .context("Failed to parse order book response")?;
+            let order_book: OrderBook = response
+                .json()
+                .await
+                .context("Failed to parse order book response")?;
 
-        Ok(order_book)
+            Ok(order_book)
+        })
+        .await
     }
 
     pub async fn fetch_payments_for_ledger(&self, sequence: u64) -> Result<Vec<Payment>> {
         if self.mock_mode {
-            return Ok(Self::mock_payments(5));
+            return Ok(Self::mock_ledger_payments(sequence));
         }
 
         let url = format!("{}/ledgers/{}/payments?limit=200", self.horizon_url, sequence);
@@ -432,7 +997,7 @@ impl StellarRpcClient {
         limit: u32,
     ) -> Result<Vec<Payment>> {
         if self.mock_mode {
-            return Ok(Self::mock_payments(limit));
+            return Ok(Self::mock_account_payments(account_id, limit));
         }
 
         info!(
@@ -463,6 +1028,102 @@ impl StellarRpcClient {
         Ok(payments)
     }
 
+    /// Fetch current network fee statistics
+    pub async fn fetch_fee_stats(&self) -> Result<FeeStats> {
+        if self.mock_mode {
+            return Ok(Self::mock_fee_stats());
+        }
+
+        info!("Fetching fee stats from Horizon API");
+
+        let url = format!("{}/fee_stats", self.horizon_url);
+
+        self.single_flight(url.clone(), |client| async move {
+            let response = client
+                .retry_request(|| async { client.client.get(&url).send().await })
+                .await
+                .context("Failed to fetch fee stats")?;
+
+            let fee_stats: FeeStats = response
+                .json()
+                .await
+                .context("Failed to parse fee stats response")?;
+
+            Ok(fee_stats)
+        })
+        .await
+    }
+
+    /// Fetch the balances and trustlines of an account.
+    ///
+    /// Fails with [`HorizonNotFound`] when the account does not exist.
+    pub async fn fetch_account_balances(&self, account_id: &str) -> Result<AccountBalances> {
+        if self.mock_mode {
+            return Ok(Self::mock_account_balances(account_id));
+        }
+
+        info!(
+            "Fetching balances for account {} from Horizon API",
+            account_id
+        );
+
+        let url = format!("{}/accounts/{}", self.horizon_url, account_id);
+
+        let response = self
+            .retry_request(|| async { self.client.get(&url).send().await })
+            .await
+            .context("Failed to fetch account")?;
+
+        let account: AccountBalances = response
+            .json()
+            .await
+            .context("Failed to parse account response")?;
+
+        Ok(account)
+    }
+
+    /// Fetch the operation effects of a transaction.
+    ///
+    /// Fails with [`HorizonNotFound`] when the transaction does not exist.
+    pub async fn fetch_transaction_effects(&self, tx_hash: &str) -> Result<Vec<TransactionEffect>> {
+        if self.mock_mode {
+            return Ok(Self::mock_transaction_effects());
+        }
+
+        info!(
+            "Fetching effects for transaction {} from Horizon API",
+            tx_hash
+        );
+
+        let url = format!(
+            "{}/transactions/{}/effects?limit=200",
+            self.horizon_url, tx_hash
+        );
+
+        let response = self
+            .retry_request(|| async { self.client.get(&url).send().await })
+            .await
+            .context("Failed to fetch transaction effects")?;
+
+        let horizon_response: HorizonResponse<TransactionEffect> = response
+            .json()
+            .await
+            .context("Failed to parse transaction effects response")?;
+
+        let mut effects = horizon_response
+            .embedded
+            .map(|e| e.records)
+            .unwrap_or_default();
+
+        // Links are navigation noise for API consumers
+        for effect in &mut effects {
+            effect.details.remove("_links");
+            effect.details.remove("paging_token");
+        }
+
+        Ok(effects)
+    }
+
     // ============================================================================
     // Helper Methods
     // ============================================================================
@@ -484,76 +1145,117 @@ impl StellarRpcClient {
         }
     }
 
-    /// Retry a request with exponential backoff
+    /// Run `fetch` once for all concurrent callers asking for `key`
+    ///
+    /// The first caller starts the upstream call and later callers with the
+    /// same key wait for its result instead of sending their own. Nothing is
+    /// kept once the call finishes, so this only coalesces overlapping
+    /// requests; caching stays with the callers. Errors reach every waiter as
+    /// messages, except [`HorizonNotFound`] which keeps its type.
+    async fn single_flight<T, F, Fut>(&self, key: String, fetch: F) -> Result<T>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce(Self) -> Fut,
+        Fut: std::future::Future<Output = Result<T>> + Send + 'static,
+    {
+        let call = {
+            let mut calls = self.calls.lock().unwrap();
+            match calls.get(&key) {
+                Some(call) => {
+                    debug!("Joining in-flight request for {}", key);
+                    call.clone()
+                }
+                None => {
+                    let calls_in_flight = Arc::clone(&self.calls);
+                    let fetch = fetch(self.clone());
+                    let call_key = key.clone();
+                    let call = async move {
+                        let result = fetch
+                            .await
+                            .map(|value| Arc::new(value) as Arc<dyn Any + Send + Sync>)
+                            .map_err(Arc::new);
+                        calls_in_flight.lock().unwrap().remove(&call_key);
+                        result
+                    }
+                    .boxed()
+                    .shared();
+                    calls.insert(key, call.clone());
+                    call
+                }
+            }
+        };
+
+        match call.await {
+            Ok(value) => Ok(value
+                .downcast_ref::<T>()
+                .expect("single-flight key shared by different response types")
+                .clone()),
+            Err(err) => match err.downcast_ref::<HorizonNotFound>() {
+                Some(not_found) => Err(not_found.clone().into()),
+                None => Err(anyhow::anyhow!("{:#}", err)),
+            },
+        }
+    }
+
+    /// Send a request, retrying transient failures with [`RPC_RETRY_POLICY`]
     async fn retry_request<F, Fut>(&self, request_fn: F) -> Result<reqwest::Response>
     where
         F: Fn() -> Fut,
         Fut: std::future::Future<Output = Result<reqwest::Response, reqwest::Error>>,
     {
-        let mut attempt = 0;
-        let mut backoff_ms = INITIAL_BACKOFF_MS;
-
-        loop {
+        retry_with_backoff(&RPC_RETRY_POLICY, is_retryable_error, || async {
+            // Held for this attempt only, so backoff sleeps don't block other callers
+            let _permit = self
+                .in_flight
+                .acquire()
+                .await
+                .context("RPC request limiter closed")?;
             let start_time = Instant::now();
 
-            match request_fn().await {
-                Ok(response) => {
-                    let elapsed = start_time.elapsed().as_millis();
-
-                    if response.status().is_success() {
-                        debug!("Request succeeded in {} ms", elapsed);
-                        return Ok(response);
-                    } else {
-                        let status = response.status();
-                        let error_text = response
-                            .text()
-                            .await
-                            .unwrap_or_else(|_| "Unknown error".to_string());
-
-                        warn!(
-                            "Request failed with status {} in {} ms: {}",
-                            status, elapsed, error_text
-                        );
-
-                        if attempt >= MAX_RETRIES {
-                            anyhow::bail!(
-                                "Request failed after {} retries. Status: {}, Error: {}",
-                                MAX_RETRIES,
-                                status,
-                                error_text
-                            );
-                        }
-                    }
-                }
-                Err(err) => {
-                    let elapsed = start_time.elapsed().as_millis();
-                    warn!(
-                        "Request error after {} ms (attempt {}/{}): {}",
-                        elapsed,
-                        attempt + 1,
-                        MAX_RETRIES + 1,
-                        err
-                    );
-
-                    if attempt >= MAX_RETRIES {
-                        return Err(err)
-                            .context(format!("Request failed after {} retries", MAX_RETRIES));
-                    }
+            // Timeouts land here too and are retried
+            let result = request_fn().await;
+            let elapsed = start_time.elapsed().as_millis();
+            let response = result.map_err(|err| {
+                let outcome = if err.is_timeout() {
+                    "timed out"
+                } else {
+                    "failed"
+                };
+                anyhow::Error::new(err).context(format!("Request {} after {} ms", outcome, elapsed))
+            })?;
+
+            if response.status().is_success() {
+                debug!("Request succeeded in {} ms", elapsed);
+                return Ok(response);
+            }
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                // Missing resources will not appear on retry
+                return Err(HorizonNotFound {
+                    url: response.url().to_string(),
                 }
+                .into());
             }
 
-            attempt += 1;
-
-            info!(
-                "Retrying request in {} ms (attempt {}/{})",
-                backoff_ms,
-                attempt + 1,
-                MAX_RETRIES + 1
-            );
-
-            tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
-            backoff_ms *= BACKOFF_MULTIPLIER;
-        }
+            let status = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            anyhow::bail!(
+                "Request failed with status {} in {} ms: {}",
+                status,
+                elapsed,
+                error_text
+            )
+        })
+        .await
+        .map_err(|err| {
+            if is_retryable_error(&err) {
+                err.context(format!("Request failed after {} retries", MAX_RETRIES))
+            } else {
+                err
+            }
+        })
     }
 
     // ============================================================================
@@ -563,20 +1265,20 @@ impl StellarRpcClient {
     fn mock_health_response() -> HealthResponse {
         HealthResponse {
             status: "healthy".to_string(),
-            latest_ledger: 51583040,
-            oldest_ledger: 51565760,
-            ledger_retention_window: 17280,
+            latest_ledger: MOCK_LATEST_LEDGER,
+            oldest_ledger: MOCK_OLDEST_LEDGER,
+            ledger_retention_window: MOCK_LATEST_LEDGER - MOCK_OLDEST_LEDGER,
         }
     }
 
     fn mock_ledger_info() -> LedgerInfo {
         LedgerInfo {
-            sequence: 51583040,
-            hash: "abc123def456".to_string(),
-            previous_hash: "xyz789uvw012".to_string(),
-            transaction_count: 245,
-            operation_count: 1203,
-            closed_at: "2026-01-22T10:30:00Z".to_string(),
+            sequence: MOCK_LATEST_LEDGER,
+            hash: Self::mock_ledger_hash(MOCK_LATEST_LEDGER),
+            previous_hash: Self::mock_ledger_hash(MOCK_LATEST_LEDGER - 1),
+            transaction_count: MOCK_OPS_PER_LEDGER as u32,
+            operation_count: MOCK_OPS_PER_LEDGER as u32,
+            closed_at: Self::mock_close_time(MOCK_LATEST_LEDGER),
             total_coins: "105443902087.3472865".to_string(),
             fee_pool: "3145678.9012345".to_string(),
             base_fee: 100,
@@ -584,126 +1286,306 @@ impl StellarRpcClient {
         }
     }
 
-    // I'm mocking getLedgers response for testing
-    fn mock_get_ledgers(start: u64, limit: u32) -> GetLedgersResult {
-        let ledgers = (0..limit)
-            .map(|i| RpcLedger {
-                hash: format!("hash_{}", start + i as u64),
-                sequence: start + i as u64,
-                ledger_close_time: format!("{}", 1734032457 + i as u64 * 5),
+    /// Hex hash of a mock ledger, stable across runs
+    fn mock_ledger_hash(sequence: u64) -> String {
+        use sha2::{Digest, Sha256};
+        hex::encode(Sha256::digest(format!("mock-ledger-{}", sequence)))
+    }
+
+    fn mock_close_timestamp(sequence: u64) -> i64 {
+        MOCK_LATEST_CLOSE_TIME
+            - (MOCK_LATEST_LEDGER as i64 - sequence as i64) * MOCK_LEDGER_CLOSE_SECS
+    }
+
+    fn mock_close_time(sequence: u64) -> String {
+        chrono::DateTime::from_timestamp(Self::mock_close_timestamp(sequence), 0)
+            .unwrap_or_default()
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    }
+
+    /// Sequential ledgers from `start`, or just past `cursor`, up to the mock tip
+    fn mock_get_ledgers(start: u64, limit: u32, cursor: Option<&str>) -> GetLedgersResult {
+        let start = cursor
+            .and_then(|c| c.parse::<u64>().ok())
+            .map_or(start, |last| last + 1);
+        let end = start
+            .saturating_add(limit as u64)
+            .min(MOCK_LATEST_LEDGER + 1);
+
+        let ledgers: Vec<RpcLedger> = (start..end)
+            .map(|sequence| RpcLedger {
+                hash: Self::mock_ledger_hash(sequence),
+                sequence,
+                ledger_close_time: Self::mock_close_timestamp(sequence).to_string(),
                 header_xdr: Some("mock_header".to_string()),
                 metadata_xdr: Some("mock_metadata".to_string()),
             })
             .collect();
+
         GetLedgersResult {
+            cursor: ledgers.last().map(|l| l.sequence.to_string()),
             ledgers,
-            latest_ledger: start + limit as u64 + 100,
-            oldest_ledger: start.saturating_sub(1000),
-            cursor: Some(format!("{}", start + limit as u64 - 1)),
-        }
-    }
-
-    fn mock_payments(limit: u32) -> Vec<Payment> {
-        (0..limit)
-            .map(|i| Payment {
-                id: format!("payment_{}", i),
-                paging_token: format!("paging_{}", i),
-                transaction_hash: format!("txhash_{}", i),
-                source_account: format!(
-                    "GXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX{:03}",
-                    i
-                ),
-                destination: format!("GDYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYY{:03}", i),
-                asset_type: if i % 3 == 0 {
-                    "native".to_string()
-                } else {
-                    "credit_alphanum4".to_string()
-                },
-                asset_code: if i % 3 == 0 {
-                    None
-                } else {
-                    Some("USDC".to_string())
-                },
-                asset_issuer: if i % 3 == 0 {
-                    None
-                } else {
-                    Some("GBXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX".to_string())
-                },
-                amount: format!("{}.0000000", 100 + i * 10),
-                created_at: format!("2026-01-22T10:{:02}:00Z", i % 60),
+            latest_ledger: MOCK_LATEST_LEDGER,
+            oldest_ledger: MOCK_OLDEST_LEDGER.min(start),
+        }
+    }
+
+    fn mock_events(
+        contract_id: &str,
+        start_ledger: u64,
+        end_ledger: Option<u64>,
+        limit: u32,
+    ) -> GetEventsResult {
+        let latest_ledger = Self::mock_health_response().latest_ledger;
+        let end_ledger = end_ledger.unwrap_or(latest_ledger).min(latest_ledger);
+        let events = (start_ledger..=end_ledger)
+            .step_by(10)
+            .take(limit as usize)
+            .map(|ledger| ContractEvent {
+                id: format!("{:019}-0000000001", ledger << 32),
+                event_type: "contract".to_string(),
+                ledger,
+                ledger_closed_at: "2026-01-22T10:30:00Z".to_string(),
+                contract_id: contract_id.to_string(),
+                // Symbol "transfer"
+                topic: vec!["AAAADwAAAAh0cmFuc2Zlcg==".to_string()],
+                // u32 1000
+                value: "AAAAAwAAA+g=".to_string(),
+                in_successful_contract_call: true,
+                tx_hash: Some(format!("txhash_{}", ledger)),
             })
-            .collect()
+            .collect();
+
+        GetEventsResult {
+            events,
+            latest_ledger,
+            cursor: None,
+        }
     }
 
-    fn mock_trades(limit: u32) -> Vec<Trade> {
-        (0..limit)
-            .map(|i| Trade {
-                id: format!("trade_{}", i),
-                ledger_close_time: format!("2026-01-22T10:{:02}:00Z", i % 60),
-                base_account: format!("GXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX{:03}", i),
-                base_amount: format!("{}.0000000", 1000 + i * 100),
-                base_asset_type: "native".to_string(),
-                base_asset_code: None,
-                base_asset_issuer: None,
-                counter_account: format!(
-                    "GDYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYY{:03}",
-                    i
-                ),
-                counter_amount: format!("{}.0000000", 500 + i * 50),
-                counter_asset_type: "credit_alphanum4".to_string(),
-                counter_asset_code: Some("USDC".to_string()),
-                counter_asset_issuer: Some(
-                    "GBXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX".to_string(),
-                ),
-                price: Price {
-                    n: 2 + i as i64,
-                    d: 1,
-                },
-                trade_type: "orderbook".to_string(),
+    /// Position of the operation just after `cursor` in the newest-first mock history
+    ///
+    /// Cursors are Horizon-style paging tokens, `ledger << 32 | operation`.
+    fn mock_page_start(cursor: Option<&str>) -> Result<u64> {
+        let Some(cursor) = cursor else {
+            return Ok(0);
+        };
+        let token: u64 = cursor
+            .trim_end_matches("-0")
+            .parse()
+            .with_context(|| format!("Invalid cursor: {}", cursor))?;
+        let (ledger, op) = (token >> 32, token & 0xffff_ffff);
+        if !(1..=MOCK_LATEST_LEDGER).contains(&ledger) || !(1..=MOCK_OPS_PER_LEDGER).contains(&op) {
+            anyhow::bail!("Invalid cursor: {}", cursor);
+        }
+        Ok((MOCK_LATEST_LEDGER - ledger) * MOCK_OPS_PER_LEDGER + (MOCK_OPS_PER_LEDGER - op) + 1)
+    }
+
+    /// Ledger and operation number of the `index`-th newest mock operation
+    fn mock_operation(index: u64) -> Option<(u64, u64)> {
+        let ledger = MOCK_LATEST_LEDGER.checked_sub(index / MOCK_OPS_PER_LEDGER)?;
+        (ledger > 0).then_some((ledger, MOCK_OPS_PER_LEDGER - index % MOCK_OPS_PER_LEDGER))
+    }
+
+    /// A page of the mock history, newest first, starting just past `cursor`
+    fn mock_page<T>(limit: u32, cursor: Option<&str>, item: fn(u64, u64) -> T) -> Result<Vec<T>> {
+        let start = Self::mock_page_start(cursor)?;
+        Ok((start..start + limit as u64)
+            .map_while(Self::mock_operation)
+            .map(|(ledger, op)| item(ledger, op))
+            .collect())
+    }
+
+    /// The `op`-th payment of mock ledger `ledger`; the first one in each ledger is XLM
+    fn mock_payment(ledger: u64, op: u64) -> Payment {
+        let token = (ledger << 32 | op).to_string();
+        let account = (ledger * MOCK_OPS_PER_LEDGER + op) % 1000;
+        let native = op == 1;
+
+        Payment {
+            id: token.clone(),
+            paging_token: token,
+            transaction_hash: {
+                use sha2::{Digest, Sha256};
+                hex::encode(Sha256::digest(format!("mock-tx-{}-{}", ledger, op)))
+            },
+            source_account: format!(
+                "GXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX{:03}",
+                account
+            ),
+            destination: format!(
+                "GDYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYY{:03}",
+                account
+            ),
+            asset_type: if native { "native" } else { "credit_alphanum4" }.to_string(),
+            asset_code: (!native).then(|| "USDC".to_string()),
+            asset_issuer: (!native).then(|| MOCK_ISSUER.to_string()),
+            amount: format!("{}.0000000", 100 + (ledger % 50) * 10 + op),
+            created_at: Self::mock_close_time(ledger),
+            source_asset_type: None,
+            source_asset_code: None,
+            source_asset_issuer: None,
+        }
+    }
+
+    fn mock_payments(limit: u32, cursor: Option<&str>) -> Result<Vec<Payment>> {
+        Self::mock_page(limit, cursor, Self::mock_payment)
+    }
+
+    /// Mock payments newer than `cursor`, oldest first
+    fn mock_payments_after(limit: u32, cursor: &str) -> Result<Vec<Payment>> {
+        let newer = Self::mock_page_start(Some(cursor))?.saturating_sub(1);
+        Ok((0..newer)
+            .rev()
+            .take(limit as usize)
+            .filter_map(Self::mock_operation)
+            .map(|(ledger, op)| Self::mock_payment(ledger, op))
+            .collect())
+    }
+
+    /// The newest `limit` mock payments, all sent by `account_id`
+    fn mock_account_payments(account_id: &str, limit: u32) -> Vec<Payment> {
+        (0..limit as u64)
+            .map_while(Self::mock_operation)
+            .map(|(ledger, op)| Payment {
+                source_account: account_id.to_string(),
+                ..Self::mock_payment(ledger, op)
             })
             .collect()
     }
 
-    fn mock_order_book(selling_asset: &Asset, buying_asset: &Asset) -> OrderBook {
-        let bids = vec![
-            OrderBookEntry {
-                price: "0.9950".to_string(),
-                amount: "1000.0000000".to_string(),
-                price_r: Price { n: 199, d: 200 },
-            },
-            OrderBookEntry {
-                price: "0.9900".to_string(),
-                amount: "2500.0000000".to_string(),
-                price_r: Price { n: 99, d: 100 },
-            },
-            OrderBookEntry {
-                price: "0.9850".to_string(),
-                amount: "5000.0000000".to_string(),
-                price_r: Price { n: 197, d: 200 },
-            },
-        ];
+    /// Payments of a single ledger, matching those in the paginated history
+    fn mock_ledger_payments(sequence: u64) -> Vec<Payment> {
+        if sequence == 0 || sequence > MOCK_LATEST_LEDGER {
+            return Vec::new();
+        }
+        (1..=MOCK_OPS_PER_LEDGER)
+            .map(|op| Self::mock_payment(sequence, op))
+            .collect()
+    }
 
-        let asks = vec![
-            OrderBookEntry {
-                price: "1.0050".to_string(),
-                amount: "1200.0000000".to_string(),
-                price_r: Price { n: 201, d: 200 },
-            },
-            OrderBookEntry {
-                price: "1.0100".to_string(),
-                amount: "3000.0000000".to_string(),
-                price_r: Price { n: 101, d: 100 },
-            },
-            OrderBookEntry {
-                price: "1.0150".to_string(),
-                amount: "4500.0000000".to_string(),
-                price_r: Price { n: 203, d: 200 },
-            },
-        ];
+    fn mock_fee_stats() -> FeeStats {
+        let distribution = |min: u32, mode: u32, max: u32| FeeDistribution {
+            max: max.to_string(),
+            min: min.to_string(),
+            mode: mode.to_string(),
+            p10: min.to_string(),
+            p20: min.to_string(),
+            p30: min.to_string(),
+            p40: min.to_string(),
+            p50: mode.to_string(),
+            p60: mode.to_string(),
+            p70: mode.to_string(),
+            p80: (mode * 2).to_string(),
+            p90: (mode * 5).to_string(),
+            p95: (mode * 10).to_string(),
+            p99: (mode * 50).to_string(),
+        };
+
+        FeeStats {
+            last_ledger: MOCK_LATEST_LEDGER.to_string(),
+            last_ledger_base_fee: "100".to_string(),
+            ledger_capacity_usage: "0.42".to_string(),
+            fee_charged: distribution(100, 100, 10000),
+            max_fee: distribution(100, 1000, 2000000),
+        }
+    }
+
+    fn mock_account_balances(account_id: &str) -> AccountBalances {
+        AccountBalances {
+            account_id: account_id.to_string(),
+            balances: vec![
+                AccountBalance {
+                    asset_type: "credit_alphanum4".to_string(),
+                    asset_code: Some("USDC".to_string()),
+                    asset_issuer: Some(MOCK_ISSUER.to_string()),
+                    balance: "2500.0000000".to_string(),
+                    limit: Some("922337203685.4775807".to_string()),
+                },
+                AccountBalance {
+                    asset_type: "native".to_string(),
+                    asset_code: None,
+                    asset_issuer: None,
+                    balance: "150.5000000".to_string(),
+                    limit: None,
+                },
+            ],
+        }
+    }
+
+    fn mock_transaction_effects() -> Vec<TransactionEffect> {
+        let effect = |index: u32, account: &str, effect_type: &str| TransactionEffect {
+            id: format!("0221598467096576-{:010}", index),
+            account: account.to_string(),
+            effect_type: effect_type.to_string(),
+            created_at: "2026-01-22T10:30:00Z".to_string(),
+            amount: Some("250.0000000".to_string()),
+            asset_type: Some("credit_alphanum4".to_string()),
+            asset_code: Some("USDC".to_string()),
+            asset_issuer: Some(MOCK_ISSUER.to_string()),
+            details: serde_json::Map::new(),
+        };
+
+        vec![
+            effect(
+                1,
+                "GXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX000",
+                "account_debited",
+            ),
+            effect(
+                2,
+                "GDYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYY000",
+                "account_credited",
+            ),
+        ]
+    }
+
+    /// The `op`-th XLM/USDC trade of mock ledger `ledger`, priced at 1/8 USDC per XLM
+    fn mock_trade(ledger: u64, op: u64) -> Trade {
+        let account = (ledger * MOCK_OPS_PER_LEDGER + op) % 1000;
+        let base_amount = 800 + (ledger % 10) * 80 + op * 8;
+
+        Trade {
+            id: format!("{}-0", ledger << 32 | op),
+            ledger_close_time: Self::mock_close_time(ledger),
+            base_account: format!(
+                "GXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX{:03}",
+                account
+            ),
+            base_amount: format!("{}.0000000", base_amount),
+            base_asset_type: "native".to_string(),
+            base_asset_code: None,
+            base_asset_issuer: None,
+            counter_account: format!(
+                "GDYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYY{:03}",
+                account
+            ),
+            counter_amount: format!("{}.0000000", base_amount / 8),
+            counter_asset_type: "credit_alphanum4".to_string(),
+            counter_asset_code: Some("USDC".to_string()),
+            counter_asset_issuer: Some(MOCK_ISSUER.to_string()),
+            price: Price { n: 1, d: 8 },
+            trade_type: "orderbook".to_string(),
+        }
+    }
+
+    /// Trades follow the same history as payments; a trade's `id` is its cursor
+    fn mock_trades(limit: u32, cursor: Option<&str>) -> Result<Vec<Trade>> {
+        Self::mock_page(limit, cursor, Self::mock_trade)
+    }
+
+    /// `limit` levels each side of a 1.0 mid price, 0.5% apart
+    fn mock_order_book(selling_asset: &Asset, buying_asset: &Asset, limit: u32) -> OrderBook {
+        let level = |n: i64, i: i64| OrderBookEntry {
+            price: format!("{:.7}", n as f64 / 1000.0),
+            amount: format!("{}.0000000", 1000 + i * 500),
+            price_r: Price { n, d: 1000 },
+        };
+        let depth = limit.clamp(1, 100) as i64;
 
         OrderBook {
-            bids,
-            asks,
+            bids: (0..depth).map(|i| level(995 - i * 5, i)).collect(),
+            asks: (0..depth).map(|i| level(1005 + i * 5, i)).collect(),
             base: selling_asset.clone(),
             counter: buying_asset.clone(),
         }
@@ -718,6 +1600,24 @@ impl StellarRpcClient {
 mod tests {
     use super::*;
 
+    /// Serve `router` on a random local port and return its base URL
+    async fn mock_horizon(router: axum::Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+        format!("http://{}", addr)
+    }
+
+    fn horizon_client(horizon_url: String) -> StellarRpcClient {
+        StellarRpcClient::new("http://127.0.0.1:1".to_string(), horizon_url, false)
+    }
+
+    fn soroban_client(rpc_url: String) -> StellarRpcClient {
+        StellarRpcClient::new(rpc_url, "http://127.0.0.1:1".to_string(), false)
+    }
+
     #[tokio::test]
     async fn test_mock_health_check() {
         let client = StellarRpcClient::new_with_defaults(true);
@@ -778,4 +1678,736 @@ mod tests {
         assert!(!order_book.bids.is_empty());
         assert!(!order_book.asks.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_mock_get_events_stays_in_range() {
+        let client = StellarRpcClient::new_with_defaults(true);
+        let result = client
+            .get_events("CCONTRACT", 1000, Some(1025), 10)
+            .await
+            .unwrap();
+
+        let ledgers: Vec<u64> = result.events.iter().map(|e| e.ledger).collect();
+        assert_eq!(ledgers, vec![1000, 1010, 1020]);
+        assert!(result.events.iter().all(|e| e.contract_id == "CCONTRACT"));
+    }
+
+    #[tokio::test]
+    async fn test_get_events_from_rpc() {
+        let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let router = axum::Router::new().route(
+            "/",
+            axum::routing::post({
+                let requests = Arc::clone(&requests);
+                move |axum::Json(body): axum::Json<serde_json::Value>| async move {
+                    requests.lock().unwrap().push(body);
+                    axum::Json(json!({
+                        "jsonrpc": "2.0",
+                        "id": 1,
+                        "result": {
+                            "latestLedger": 2000,
+                            "cursor": "0000004294967296-0000000001",
+                            "events": [
+                                {
+                                    "type": "contract",
+                                    "ledger": 1005,
+                                    "ledgerClosedAt": "2026-01-22T10:30:00Z",
+                                    "contractId": "CANCHOR",
+                                    "id": "0004316285927424-0000000001",
+                                    "pagingToken": "0004316285927424-0000000001",
+                                    "topic": ["AAAADwAAAAh0cmFuc2Zlcg=="],
+                                    "value": "AAAAAwAAA+g=",
+                                    "inSuccessfulContractCall": true,
+                                    "txHash": "abc123"
+                                },
+                                {
+                                    "type": "contract",
+                                    "ledger": 1050,
+                                    "ledgerClosedAt": "2026-01-22T10:35:00Z",
+                                    "contractId": "CANCHOR",
+                                    "id": "0004509558300672-0000000001",
+                                    "topic": [],
+                                    "value": "AAAAAQ=="
+                                }
+                            ]
+                        }
+                    }))
+                }
+            }),
+        );
+        let client = soroban_client(mock_horizon(router).await);
+
+        let result = client
+            .get_events("CANCHOR", 1000, Some(1010), 50)
+            .await
+            .unwrap();
+
+        let request = requests.lock().unwrap()[0].clone();
+        assert_eq!(request["method"], "getEvents");
+        assert_eq!(request["params"]["startLedger"], 1000);
+        assert_eq!(request["params"]["endLedger"], 1011);
+        assert_eq!(
+            request["params"]["filters"][0]["contractIds"],
+            json!(["CANCHOR"])
+        );
+        assert_eq!(request["params"]["pagination"]["limit"], 50);
+
+        // The event past to_ledger is dropped even if RPC returns it
+        assert_eq!(result.latest_ledger, 2000);
+        assert_eq!(result.events.len(), 1);
+        assert_eq!(result.events[0].event_type, "contract");
+        assert_eq!(result.events[0].contract_id, "CANCHOR");
+        assert_eq!(result.events[0].topic, vec!["AAAADwAAAAh0cmFuc2Zlcg=="]);
+        assert!(result.events[0].in_successful_contract_call);
+        assert_eq!(result.events[0].tx_hash.as_deref(), Some("abc123"));
+    }
+
+    #[tokio::test]
+    async fn test_get_events_surfaces_rpc_errors() {
+        let router = axum::Router::new().route(
+            "/",
+            axum::routing::post(|| async {
+                axum::Json(json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "error": { "code": -32600, "message": "startLedger must be positive" }
+                }))
+            }),
+        );
+        let client = soroban_client(mock_horizon(router).await);
+
+        let err = client.get_events("CANCHOR", 0, None, 10).await.unwrap_err();
+
+        assert!(err.to_string().contains("startLedger must be positive"));
+    }
+
+    #[tokio::test]
+    async fn test_latest_ledger_cached_shares_one_fetch() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let requests = Arc::new(AtomicUsize::new(0));
+        let router = axum::Router::new().route(
+            "/",
+            axum::routing::post({
+                let requests = Arc::clone(&requests);
+                move || async move {
+                    let latest = 5000 + requests.fetch_add(1, Ordering::SeqCst) as u64;
+                    axum::Json(json!({
+                        "jsonrpc": "2.0",
+                        "id": 1,
+                        "result": {
+                            "status": "healthy",
+                            "latestLedger": latest,
+                            "oldestLedger": 1000,
+                            "ledgerRetentionWindow": 17280
+                        }
+                    }))
+                }
+            }),
+        );
+        let client = soroban_client(mock_horizon(router).await)
+            .with_latest_ledger_ttl(Duration::from_millis(200));
+
+        let clone = client.clone();
+        let (first, second) =
+            tokio::join!(client.latest_ledger_cached(), clone.latest_ledger_cached());
+        assert_eq!(first.unwrap(), 5000);
+        assert_eq!(second.unwrap(), 5000);
+        assert_eq!(client.latest_ledger_cached().await.unwrap(), 5000);
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert_eq!(client.latest_ledger_cached().await.unwrap(), 5001);
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_mock_latest_ledger_cached() {
+        let client = StellarRpcClient::new_with_defaults(true);
+        let health = client.check_health().await.unwrap();
+
+        assert_eq!(
+            client.latest_ledger_cached().await.unwrap(),
+            health.latest_ledger
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mock_fetch_fee_stats() {
+        let client = StellarRpcClient::new_with_defaults(true);
+        let fee_stats = client.fetch_fee_stats().await.unwrap();
+
+        assert_eq!(fee_stats.last_ledger_base_fee, "100");
+        assert_eq!(fee_stats.fee_charged.mode, "100");
+    }
+
+    #[tokio::test]
+    async fn test_mock_fixtures_are_deterministic() {
+        let first = StellarRpcClient::new_with_defaults(true);
+        let second = StellarRpcClient::new_with_defaults(true);
+
+        let snapshot = |client: StellarRpcClient| async move {
+            json!({
+                "ledger": client.fetch_latest_ledger().await.unwrap(),
+                "ledgers": client.fetch_ledgers(Some(1000), 3, None).await.unwrap(),
+                "payments": client.fetch_payments(10, None).await.unwrap(),
+                "trades": client.fetch_trades(10, None).await.unwrap(),
+            })
+        };
+
+        assert_eq!(snapshot(first).await, snapshot(second).await);
+    }
+
+    #[tokio::test]
+    async fn test_mock_ledgers_are_consistent() {
+        let client = StellarRpcClient::new_with_defaults(true);
+        let latest = client.fetch_latest_ledger().await.unwrap();
+        assert_eq!(latest.sequence, MOCK_LATEST_LEDGER);
+        assert_eq!(
+            client.latest_ledger_cached().await.unwrap(),
+            latest.sequence
+        );
+
+        let page = client
+            .fetch_ledgers(Some(MOCK_LATEST_LEDGER - 3), 2, None)
+            .await
+            .unwrap();
+        let next = client
+            .fetch_ledgers(Some(1), 10, page.cursor.as_deref())
+            .await
+            .unwrap();
+
+        let sequences: Vec<u64> = page
+            .ledgers
+            .iter()
+            .chain(&next.ledgers)
+            .map(|l| l.sequence)
+            .collect();
+        // The cursor wins over start_ledger, and nothing is served past the tip
+        assert_eq!(
+            sequences,
+            (MOCK_LATEST_LEDGER - 3..=MOCK_LATEST_LEDGER).collect::<Vec<_>>()
+        );
+        assert_eq!(next.latest_ledger, MOCK_LATEST_LEDGER);
+
+        let tip = next.ledgers.last().unwrap();
+        assert_eq!(tip.hash, latest.hash);
+        assert_eq!(
+            next.ledgers[next.ledgers.len() - 2].hash,
+            latest.previous_hash
+        );
+        assert_eq!(
+            tip.ledger_close_time,
+            chrono::DateTime::parse_from_rfc3339(&latest.closed_at)
+                .unwrap()
+                .timestamp()
+                .to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_payments_after_cursor_page_forward() {
+        let client = StellarRpcClient::new_with_defaults(true);
+        let newest: Vec<Payment> = client.fetch_payments(6, None).await.unwrap();
+
+        let after = client
+            .fetch_payments_after(3, &newest[5].paging_token)
+            .await
+            .unwrap();
+        let ids: Vec<&str> = after.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, [&newest[4].id, &newest[3].id, &newest[2].id]);
+
+        let caught_up = client
+            .fetch_payments_after(3, &newest[0].paging_token)
+            .await
+            .unwrap();
+        assert!(caught_up.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_mock_payments_paginate_with_cursors() {
+        let client = StellarRpcClient::new_with_defaults(true);
+        let all = client.fetch_payments(10, None).await.unwrap();
+        let first = client.fetch_payments(4, None).await.unwrap();
+        let second = client
+            .fetch_payments(6, Some(&first.last().unwrap().paging_token))
+            .await
+            .unwrap();
+
+        let paged: Vec<&str> = first.iter().chain(&second).map(|p| p.id.as_str()).collect();
+        let expected: Vec<&str> = all.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(paged, expected);
+
+        // Newest first, with Horizon-shaped tokens and amounts
+        let tokens: Vec<u64> = all
+            .iter()
+            .map(|p| p.paging_token.parse().unwrap())
+            .collect();
+        assert!(tokens.windows(2).all(|w| w[0] > w[1]));
+        assert_eq!(tokens[0] >> 32, MOCK_LATEST_LEDGER);
+        for payment in &all {
+            assert_eq!(payment.id, payment.paging_token);
+            assert_eq!(payment.transaction_hash.len(), 64);
+            assert!(payment.amount.parse::<f64>().unwrap() > 0.0);
+            assert_eq!(payment.asset_code.is_none(), payment.asset_type == "native");
+            assert!(chrono::DateTime::parse_from_rfc3339(&payment.created_at).is_ok());
+        }
+
+        // Per-ledger payments are the same records as in the paginated history
+        let ledger_payments = client
+            .fetch_payments_for_ledger(MOCK_LATEST_LEDGER)
+            .await
+            .unwrap();
+        let ledger_ids: Vec<&str> = ledger_payments.iter().map(|p| p.id.as_str()).collect();
+        let mut newest = expected[..ledger_ids.len()].to_vec();
+        newest.reverse();
+        assert_eq!(ledger_ids, newest);
+    }
+
+    #[tokio::test]
+    async fn test_mock_trades_paginate_with_cursors() {
+        let client = StellarRpcClient::new_with_defaults(true);
+        let all = client.fetch_trades(6, None).await.unwrap();
+        let first = client.fetch_trades(2, None).await.unwrap();
+        let second = client
+            .fetch_trades(4, Some(&first.last().unwrap().id))
+            .await
+            .unwrap();
+
+        let paged: Vec<&str> = first.iter().chain(&second).map(|t| t.id.as_str()).collect();
+        let expected: Vec<&str> = all.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(paged, expected);
+
+        for trade in &all {
+            let base: f64 = trade.base_amount.parse().unwrap();
+            let counter: f64 = trade.counter_amount.parse().unwrap();
+            assert_eq!(counter / base, trade.price.n as f64 / trade.price.d as f64);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mock_rejects_unknown_cursor() {
+        let client = StellarRpcClient::new_with_defaults(true);
+
+        assert!(client.fetch_payments(5, Some("paging_3")).await.is_err());
+        assert!(client.fetch_trades(5, Some("12345")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mock_order_book_is_well_formed() {
+        let client = StellarRpcClient::new_with_defaults(true);
+        let xlm = Asset {
+            asset_type: "native".to_string(),
+            asset_code: None,
+            asset_issuer: None,
+        };
+        let usdc = Asset {
+            asset_type: "credit_alphanum4".to_string(),
+            asset_code: Some("USDC".to_string()),
+            asset_issuer: Some(MOCK_ISSUER.to_string()),
+        };
+
+        let book = client.fetch_order_book(&xlm, &usdc, 5).await.unwrap();
+
+        assert_eq!(book.bids.len(), 5);
+        assert_eq!(book.asks.len(), 5);
+        let price = |e: &OrderBookEntry| e.price.parse::<f64>().unwrap();
+        assert!(book.bids.windows(2).all(|w| price(&w[0]) > price(&w[1])));
+        assert!(book.asks.windows(2).all(|w| price(&w[0]) < price(&w[1])));
+        assert!(price(&book.bids[0]) < price(&book.asks[0]));
+        for entry in book.bids.iter().chain(&book.asks) {
+            assert_eq!(
+                price(entry),
+                entry.price_r.n as f64 / entry.price_r.d as f64
+            );
+        }
+        assert_eq!(book.counter.asset_code.as_deref(), Some("USDC"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_fee_stats_from_horizon() {
+        let body = json!({
+            "last_ledger": "48512345",
+            "last_ledger_base_fee": "100",
+            "ledger_capacity_usage": "0.97",
+            "fee_charged": {
+                "max": "50000", "min": "100", "mode": "100",
+                "p10": "100", "p20": "100", "p30": "100", "p40": "100", "p50": "100",
+                "p60": "120", "p70": "150", "p80": "200", "p90": "500", "p95": "1000", "p99": "20000"
+            },
+            "max_fee": {
+                "max": "1000000", "min": "100", "mode": "2000",
+                "p10": "150", "p20": "200", "p30": "300", "p40": "500", "p50": "1000",
+                "p60": "2000", "p70": "2000", "p80": "5000", "p90": "10000", "p95": "50000", "p99": "500000"
+            }
+        });
+        let router = axum::Router::new().route(
+            "/fee_stats",
+            axum::routing::get(move || async move { axum::Json(body) }),
+        );
+        let client = horizon_client(mock_horizon(router).await);
+
+        let fee_stats = client.fetch_fee_stats().await.unwrap();
+
+        assert_eq!(fee_stats.last_ledger, "48512345");
+        assert_eq!(fee_stats.ledger_capacity_usage, "0.97");
+        assert_eq!(fee_stats.fee_charged.min, "100");
+        assert_eq!(fee_stats.fee_charged.p99, "20000");
+        assert_eq!(fee_stats.max_fee.mode, "2000");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_account_balances_from_horizon() {
+        let body = json!({
+            "id": "GANCHOR",
+            "account_id": "GANCHOR",
+            "sequence": "123456789",
+            "balances": [
+                {
+                    "balance": "1000.5000000",
+                    "limit": "5000.0000000",
+                    "buying_liabilities": "0.0000000",
+                    "selling_liabilities": "0.0000000",
+                    "is_authorized": true,
+                    "asset_type": "credit_alphanum4",
+                    "asset_code": "USDC",
+                    "asset_issuer": "GISSUER"
+                },
+                {
+                    "balance": "42.0000000",
+                    "buying_liabilities": "0.0000000",
+                    "selling_liabilities": "0.0000000",
+                    "asset_type": "native"
+                }
+            ]
+        });
+        let router = axum::Router::new().route(
+            "/accounts/GANCHOR",
+            axum::routing::get(move || async move { axum::Json(body) }),
+        );
+        let client = horizon_client(mock_horizon(router).await);
+
+        let account = client.fetch_account_balances("GANCHOR").await.unwrap();
+
+        assert_eq!(account.account_id, "GANCHOR");
+        assert_eq!(account.balances.len(), 2);
+        assert_eq!(account.balances[0].asset_code.as_deref(), Some("USDC"));
+        assert_eq!(account.balances[0].asset_issuer.as_deref(), Some("GISSUER"));
+        assert_eq!(account.balances[0].balance, "1000.5000000");
+        assert_eq!(account.balances[0].limit.as_deref(), Some("5000.0000000"));
+        assert_eq!(account.balances[1].asset_type, "native");
+        assert_eq!(account.balances[1].limit, None);
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_requests_are_capped() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let current = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let router = axum::Router::new().route(
+            "/accounts/GSLOW",
+            axum::routing::get({
+                let current = Arc::clone(&current);
+                let peak = Arc::clone(&peak);
+                move || async move {
+                    let now = current.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    current.fetch_sub(1, Ordering::SeqCst);
+                    axum::Json(json!({ "account_id": "GSLOW", "balances": [] }))
+                }
+            }),
+        );
+        let client = horizon_client(mock_horizon(router).await).with_max_in_flight_requests(2);
+
+        let requests = (0..8).map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.fetch_account_balances("GSLOW").await })
+        });
+        for result in futures::future::join_all(requests).await {
+            assert!(result.unwrap().is_ok());
+        }
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_for_network_routes_to_that_networks_horizon() {
+        let ledger_horizon = |sequence: u64| {
+            mock_horizon(axum::Router::new().route(
+                "/ledgers",
+                axum::routing::get(move || async move {
+                    axum::Json(json!({ "_embedded": { "records": [{
+                        "sequence": sequence,
+                        "hash": "hash",
+                        "previous_hash": "previous",
+                        "transaction_count": 1,
+                        "operation_count": 1,
+                        "closed_at": "2026-01-22T10:30:00Z",
+                        "total_coins": "105443902087.3472865",
+                        "fee_pool": "1807038.9372218",
+                        "base_fee": 100,
+                        "base_reserve": "0.5000000"
+                    }] } }))
+                }),
+            ))
+        };
+        let client = horizon_client(ledger_horizon(100).await).with_network_urls(
+            Network::Testnet,
+            "http://127.0.0.1:1".to_string(),
+            ledger_horizon(200).await,
+        );
+
+        assert_eq!(client.network(), Network::Mainnet);
+        assert_eq!(client.fetch_latest_ledger().await.unwrap().sequence, 100);
+
+        let testnet = client.for_network(Network::Testnet);
+        assert_eq!(testnet.network(), Network::Testnet);
+        assert_eq!(testnet.fetch_latest_ledger().await.unwrap().sequence, 200);
+        let mainnet = testnet.for_network(Network::Mainnet);
+        assert_eq!(mainnet.fetch_latest_ledger().await.unwrap().sequence, 100);
+    }
+
+    #[tokio::test]
+    async fn test_trade_volume_sums_pages_within_window() {
+        use std::collections::HashMap as Params;
+
+        let trade = |id: String, closed_at: chrono::DateTime<chrono::Utc>| {
+            json!({
+                "id": id,
+                "ledger_close_time": closed_at.to_rfc3339(),
+                "base_account": "GBASE",
+                "base_amount": "10.5000000",
+                "base_asset_type": "native",
+                "counter_account": "GCOUNTER",
+                "counter_amount": "2.0000000",
+                "counter_asset_type": "credit_alphanum4",
+                "counter_asset_code": "USDC",
+                "counter_asset_issuer": "GISSUER",
+                "price": { "n": 4, "d": 21 },
+                "trade_type": "orderbook"
+            })
+        };
+        let router = axum::Router::new().route(
+            "/trades",
+            axum::routing::get(
+                move |axum::extract::Query(params): axum::extract::Query<
+                    Params<String, String>,
+                >| async move {
+                    assert_eq!(params["base_asset_type"], "native");
+                    assert_eq!(params["counter_asset_code"], "USDC");
+                    let recent = chrono::Utc::now() - chrono::Duration::minutes(5);
+                    let limit: usize = params["limit"].parse().unwrap();
+                    let records: Vec<_> = match params.get("cursor").map(String::as_str) {
+                        // A full first page, all inside the window
+                        None => (0..limit)
+                            .map(|i| trade(format!("1-{}", i), recent))
+                            .collect(),
+                        // Two more recent trades, then one from before the window
+                        Some("1-199") => vec![
+                            trade("2-0".to_string(), recent),
+                            trade("2-1".to_string(), recent),
+                            trade("2-2".to_string(), recent - chrono::Duration::days(2)),
+                        ],
+                        Some(other) => panic!("unexpected cursor {}", other),
+                    };
+                    axum::Json(json!({ "_embedded": { "records": records } }))
+                },
+            ),
+        );
+        let client = horizon_client(mock_horizon(router).await);
+        let base = Asset {
+            asset_type: "native".to_string(),
+            asset_code: None,
+            asset_issuer: None,
+        };
+        let counter = Asset {
+            asset_type: "credit_alphanum4".to_string(),
+            asset_code: Some("USDC".to_string()),
+            asset_issuer: Some("GISSUER".to_string()),
+        };
+
+        let volume = client
+            .fetch_trade_volume(&base, &counter, chrono::Duration::hours(24), 10_000)
+            .await
+            .unwrap();
+        assert_eq!(volume.trade_count, 202);
+        assert!((volume.base_volume - 202.0 * 10.5).abs() < 1e-6);
+        assert!((volume.counter_volume - 404.0).abs() < 1e-6);
+        assert!(!volume.truncated);
+
+        // The record cap stops paging early
+        let capped = client
+            .fetch_trade_volume(&base, &counter, chrono::Duration::hours(24), 150)
+            .await
+            .unwrap();
+        assert_eq!(capped.trade_count, 150);
+        assert!(capped.truncated);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_identical_order_book_requests_share_one_call() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let requests = Arc::new(AtomicUsize::new(0));
+        let router = axum::Router::new().route(
+            "/order_book",
+            axum::routing::get({
+                let requests = Arc::clone(&requests);
+                move || async move {
+                    requests.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    axum::Json(json!({
+                        "bids": [{ "price": "0.9950000", "amount": "10.0000000", "price_r": { "n": 199, "d": 200 } }],
+                        "asks": [],
+                        "base": { "asset_type": "native" },
+                        "counter": {
+                            "asset_type": "credit_alphanum4",
+                            "asset_code": "USDC",
+                            "asset_issuer": "GISSUER"
+                        }
+                    }))
+                }
+            }),
+        );
+        let client = horizon_client(mock_horizon(router).await);
+        let selling = Asset {
+            asset_type: "native".to_string(),
+            asset_code: None,
+            asset_issuer: None,
+        };
+        let buying = Asset {
+            asset_type: "credit_alphanum4".to_string(),
+            asset_code: Some("USDC".to_string()),
+            asset_issuer: Some("GISSUER".to_string()),
+        };
+
+        let calls = (0..10).map(|_| {
+            let (client, selling, buying) = (client.clone(), selling.clone(), buying.clone());
+            tokio::spawn(async move { client.fetch_order_book(&selling, &buying, 20).await })
+        });
+        for result in futures::future::join_all(calls).await {
+            assert_eq!(result.unwrap().unwrap().bids[0].price, "0.9950000");
+        }
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        // Finished calls are not reused
+        client
+            .fetch_order_book(&selling, &buying, 20)
+            .await
+            .unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        // A different pair or limit is a different call
+        client.fetch_order_book(&selling, &buying, 5).await.unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_slow_horizon_times_out_and_retries() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let router = axum::Router::new().route(
+            "/accounts/GSTUCK",
+            axum::routing::get({
+                let attempts = Arc::clone(&attempts);
+                move || async move {
+                    attempts.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    axum::Json(json!({ "account_id": "GSTUCK", "balances": [] }))
+                }
+            }),
+        );
+        let client = horizon_client(mock_horizon(router).await)
+            .with_request_timeout(Duration::from_millis(100));
+
+        let started = Instant::now();
+        let err = client.fetch_account_balances("GSTUCK").await.unwrap_err();
+
+        // Four attempts at 100ms plus 700ms of backoff, well short of the handler's 5s
+        assert!(started.elapsed() < Duration::from_secs(3));
+        assert!(err
+            .chain()
+            .any(|e| e.downcast_ref::<reqwest::Error>().is_some_and(|e| e.is_timeout())));
+        assert_eq!(attempts.load(Ordering::SeqCst), MAX_RETRIES as usize + 1);
+    }
+
+    #[test]
+    fn test_not_found_is_not_retryable() {
+        let not_found = anyhow::Error::new(HorizonNotFound {
+            url: "https://horizon.example/accounts/GMISSING".to_string(),
+        });
+        assert!(!is_retryable_error(&not_found));
+        assert!(!is_retryable_error(&not_found.context("fetching balances")));
+        assert!(is_retryable_error(&anyhow::anyhow!(
+            "Request failed with status 503 Service Unavailable in 12 ms: busy"
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_account_balances_not_found() {
+        let client = horizon_client(mock_horizon(axum::Router::new()).await);
+
+        let err = client.fetch_account_balances("GMISSING").await.unwrap_err();
+
+        assert!(err.downcast_ref::<HorizonNotFound>().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_transaction_effects_from_horizon() {
+        let hash = "3389e9f0f1a65f19736cacf544c2e825313e8447f569233bb8db39aa607c8889";
+        let body = json!({
+            "_links": {},
+            "_embedded": {
+                "records": [
+                    {
+                        "_links": {},
+                        "id": "0000000012884905985-0000000001",
+                        "paging_token": "12884905985-1",
+                        "account": "GSENDER",
+                        "type": "account_debited",
+                        "type_i": 3,
+                        "created_at": "2024-01-01T00:00:00Z",
+                        "asset_type": "credit_alphanum4",
+                        "asset_code": "USDC",
+                        "asset_issuer": "GISSUER",
+                        "amount": "10.0000000"
+                    },
+                    {
+                        "_links": {},
+                        "id": "0000000012884905985-0000000002",
+                        "paging_token": "12884905985-2",
+                        "account": "GRECEIVER",
+                        "type": "trustline_created",
+                        "type_i": 20,
+                        "created_at": "2024-01-01T00:00:00Z",
+                        "asset_type": "credit_alphanum4",
+                        "asset_code": "USDC",
+                        "asset_issuer": "GISSUER",
+                        "limit": "1000.0000000"
+                    }
+                ]
+            }
+        });
+        let router = axum::Router::new().route(
+            &format!("/transactions/{}/effects", hash),
+            axum::routing::get(move || async move { axum::Json(body) }),
+        );
+        let client = horizon_client(mock_horizon(router).await);
+
+        let effects = client.fetch_transaction_effects(hash).await.unwrap();
+
+        assert_eq!(effects.len(), 2);
+        assert_eq!(effects[0].effect_type, "account_debited");
+        assert_eq!(effects[0].account, "GSENDER");
+        assert_eq!(effects[0].amount.as_deref(), Some("10.0000000"));
+        assert_eq!(effects[0].details.get("type_i"), Some(&json!(3)));
+        assert!(!effects[0].details.contains_key("_links"));
+        assert_eq!(effects[1].amount, None);
+        assert_eq!(
+            effects[1].details.get("limit"),
+            Some(&json!("1000.0000000"))
+        );
+    }
 }
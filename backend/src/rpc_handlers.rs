@@ -1,13 +1,101 @@
 use axum::{
-    extract::{Path, Query, State},
+    extract::{MatchedPath, Path, Query, Request, State},
     http::StatusCode,
-    response::IntoResponse,
+    middleware::Next,
+    response::{IntoResponse, Response},
     Json,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::sync::Arc;
 
-use crate::rpc::{Asset, StellarRpcClient};
+use crate::cache::{keys, CacheManager};
+use crate::cache_middleware::CacheAware;
+use crate::ingestion::config::parse_list;
+use crate::rpc::stellar::DEFAULT_TRADE_VOLUME_MAX_RECORDS;
+use crate::rpc::{Asset, HorizonNotFound, Network, StellarRpcClient};
+
+/// RPC proxy methods by route; `/api/rpc/health` is always served
+const RPC_METHODS: &[(&str, &str)] = &[
+    ("/api/rpc/ledger/latest", "latest_ledger"),
+    ("/api/rpc/payments", "payments"),
+    ("/api/rpc/payments/account/:account_id", "account_payments"),
+    ("/api/rpc/trades", "trades"),
+    ("/api/rpc/trades/volume", "trade_volume"),
+    ("/api/rpc/orderbook", "orderbook"),
+    ("/api/rpc/events", "events"),
+    ("/api/rpc/transaction/:hash/effects", "transaction_effects"),
+    ("/api/rpc/fee-stats", "fee_stats"),
+    ("/api/rpc/account/:account_id/balances", "account_balances"),
+];
+
+/// RPC proxy methods this deployment serves
+///
+/// Read from `RPC_ENABLED_METHODS`, a comma-separated list of the names in
+/// [`RPC_METHODS`]; unset or empty enables every method.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RpcMethodFilter {
+    enabled: Option<HashSet<String>>,
+}
+
+impl RpcMethodFilter {
+    pub fn from_env() -> anyhow::Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> anyhow::Result<Self> {
+        let names = parse_list(&lookup, "RPC_ENABLED_METHODS");
+        if names.is_empty() {
+            return Ok(Self::default());
+        }
+        for name in &names {
+            if !RPC_METHODS.iter().any(|(_, method)| method == name) {
+                let known: Vec<&str> = RPC_METHODS.iter().map(|(_, method)| *method).collect();
+                anyhow::bail!(
+                    "Unknown RPC method '{}' in RPC_ENABLED_METHODS; expected any of: {}",
+                    name,
+                    known.join(", ")
+                );
+            }
+        }
+        Ok(Self {
+            enabled: Some(names.into_iter().collect()),
+        })
+    }
+
+    /// Whether the route `route` may be served; routes without a method name
+    /// are never filtered
+    pub fn allows(&self, route: &str) -> bool {
+        let Some(enabled) = &self.enabled else {
+            return true;
+        };
+        RPC_METHODS
+            .iter()
+            .find(|(path, _)| *path == route)
+            .is_none_or(|(_, method)| enabled.contains(*method))
+    }
+}
+
+/// Middleware answering 404 for RPC methods disabled by [`RpcMethodFilter`]
+pub async fn rpc_method_filter_middleware(
+    State(filter): State<Arc<RpcMethodFilter>>,
+    matched_path: Option<MatchedPath>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if let Some(route) = &matched_path {
+        if !filter.allows(route.as_str()) {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "RPC method is disabled".to_string(),
+                }),
+            )
+                .into_response();
+        }
+    }
+    next.run(req).await
+}
 
 #[derive(Debug, Deserialize)]
 pub struct PaginationQuery {
@@ -32,15 +120,125 @@ pub struct OrderBookQuery {
     pub limit: u32,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct TradeVolumeQuery {
+    /// `native` or `CODE:ISSUER`
+    pub base: String,
+    /// `native` or `CODE:ISSUER`
+    pub counter: String,
+    /// Trailing window such as `1h`, `24h` or `7d`
+    #[serde(default = "default_volume_window")]
+    pub window: String,
+}
+
+fn default_volume_window() -> String {
+    "24h".to_string()
+}
+
+/// Longest window the trades-volume endpoint will page through
+const MAX_VOLUME_WINDOW_HOURS: i64 = 7 * 24;
+
+#[derive(Debug, Deserialize)]
+pub struct EventsQuery {
+    /// Soroban contract id (`C...` strkey)
+    pub contract: String,
+    pub from_ledger: u64,
+    /// Last ledger to include; defaults to the latest ledger
+    pub to_ledger: Option<u64>,
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+}
+
+/// Whether `id` looks like a Soroban contract strkey
+fn is_valid_contract_id(id: &str) -> bool {
+    id.len() == 56 && id.starts_with('C') && id.chars().all(|c| matches!(c, 'A'..='Z' | '2'..='7'))
+}
+
+/// Parse `native` or `CODE:ISSUER` into a Horizon asset
+fn parse_asset_param(asset: &str) -> Option<Asset> {
+    if asset == "native" || asset == "XLM:native" {
+        return Some(Asset {
+            asset_type: "native".to_string(),
+            asset_code: None,
+            asset_issuer: None,
+        });
+    }
+    let (code, issuer) = asset.split_once(':')?;
+    let asset_type = match code.len() {
+        1..=4 => "credit_alphanum4",
+        5..=12 => "credit_alphanum12",
+        _ => return None,
+    };
+    if !code.chars().all(|c| c.is_ascii_alphanumeric()) || issuer.is_empty() {
+        return None;
+    }
+    Some(Asset {
+        asset_type: asset_type.to_string(),
+        asset_code: Some(code.to_string()),
+        asset_issuer: Some(issuer.to_string()),
+    })
+}
+
+/// Parse a window like `30m`, `24h` or `7d`, up to [`MAX_VOLUME_WINDOW_HOURS`]
+fn parse_volume_window(window: &str) -> Option<chrono::Duration> {
+    let unit = window.chars().last()?;
+    let amount: i64 = window[..window.len() - unit.len_utf8()].parse().ok()?;
+    let duration = match unit {
+        'm' => chrono::Duration::try_minutes(amount)?,
+        'h' => chrono::Duration::try_hours(amount)?,
+        'd' => chrono::Duration::try_days(amount)?,
+        _ => return None,
+    };
+    (amount > 0 && duration <= chrono::Duration::hours(MAX_VOLUME_WINDOW_HOURS)).then_some(duration)
+}
+
+/// Whether `id` is a well-formed account strkey (`G...`), checksum included
+fn is_valid_account_id(id: &str) -> bool {
+    id.chars().all(|c| matches!(c, 'A'..='Z' | '2'..='7'))
+        && id.parse::<stellar_xdr::curr::AccountId>().is_ok()
+}
+
+/// Whether `hash` is a 64-character hex transaction hash
+fn is_valid_tx_hash(hash: &str) -> bool {
+    hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())
+}
+
 #[derive(Debug, Serialize)]
 pub struct ErrorResponse {
     pub error: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct NetworkQuery {
+    /// `mainnet` (the default) or `testnet`
+    pub network: Option<String>,
+}
+
+/// Client for the network named by `?network=`, defaulting to mainnet
+fn network_client(
+    client: &StellarRpcClient,
+    query: &NetworkQuery,
+) -> Result<StellarRpcClient, (StatusCode, Json<ErrorResponse>)> {
+    let network = match query.network.as_deref() {
+        Some(value) => value.parse::<Network>().map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: e.to_string(),
+                }),
+            )
+        })?,
+        None => Network::default(),
+    };
+    Ok(client.for_network(network))
+}
+
 /// Health check for Stellar RPC
 pub async fn rpc_health_check(
     State(client): State<Arc<StellarRpcClient>>,
+    Query(network): Query<NetworkQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let client = network_client(&client, &network)?;
     match client.check_health().await {
         Ok(health) => Ok(Json(health)),
         Err(e) => Err((
@@ -55,7 +253,9 @@ pub async fn rpc_health_check(
 /// Get latest ledger information
 pub async fn get_latest_ledger(
     State(client): State<Arc<StellarRpcClient>>,
+    Query(network): Query<NetworkQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let client = network_client(&client, &network)?;
     match client.fetch_latest_ledger().await {
         Ok(ledger) => Ok(Json(ledger)),
         Err(e) => Err((
@@ -70,8 +270,10 @@ pub async fn get_latest_ledger(
 /// Get recent payments
 pub async fn get_payments(
     State(client): State<Arc<StellarRpcClient>>,
+    Query(network): Query<NetworkQuery>,
     Query(params): Query<PaginationQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let client = network_client(&client, &network)?;
     let cursor = params.cursor.as_deref();
     match client.fetch_payments(params.limit, cursor).await {
         Ok(payments) => Ok(Json(payments)),
@@ -87,9 +289,11 @@ pub async fn get_payments(
 /// Get payments for a specific account
 pub async fn get_account_payments(
     State(client): State<Arc<StellarRpcClient>>,
+    Query(network): Query<NetworkQuery>,
     Path(account_id): Path<String>,
     Query(params): Query<PaginationQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let client = network_client(&client, &network)?;
     match client
         .fetch_account_payments(&account_id, params.limit)
         .await
@@ -107,8 +311,10 @@ pub async fn get_account_payments(
 /// Get recent trades
 pub async fn get_trades(
     State(client): State<Arc<StellarRpcClient>>,
+    Query(network): Query<NetworkQuery>,
     Query(params): Query<PaginationQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let client = network_client(&client, &network)?;
     let cursor = params.cursor.as_deref();
     match client.fetch_trades(params.limit, cursor).await {
         Ok(trades) => Ok(Json(trades)),
@@ -121,11 +327,55 @@ pub async fn get_trades(
     }
 }
 
+/// Get total DEX volume for an asset pair over a trailing window (cached briefly)
+pub async fn get_trade_volume(
+    State((client, cache)): State<(Arc<StellarRpcClient>, Arc<CacheManager>)>,
+    Query(network): Query<NetworkQuery>,
+    Query(params): Query<TradeVolumeQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let client = network_client(&client, &network)?;
+    let bad_request = |error: String| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error }));
+    let base = parse_asset_param(&params.base)
+        .ok_or_else(|| bad_request("base must be native or CODE:ISSUER".to_string()))?;
+    let counter = parse_asset_param(&params.counter)
+        .ok_or_else(|| bad_request("counter must be native or CODE:ISSUER".to_string()))?;
+    let window = parse_volume_window(&params.window).ok_or_else(|| {
+        bad_request(format!(
+            "window must be like 30m, 24h or 7d, at most {}h",
+            MAX_VOLUME_WINDOW_HOURS
+        ))
+    })?;
+
+    match <()>::get_or_fetch(
+        &cache,
+        &keys::rpc_trade_volume(
+            client.network(),
+            &params.base,
+            &params.counter,
+            window.num_seconds(),
+        ),
+        cache.config.get_ttl("trade_volume"),
+        client.fetch_trade_volume(&base, &counter, window, DEFAULT_TRADE_VOLUME_MAX_RECORDS),
+    )
+    .await
+    {
+        Ok(volume) => Ok(Json(volume)),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Failed to fetch trade volume: {}", e),
+            }),
+        )),
+    }
+}
+
 /// Get order book for a trading pair
 pub async fn get_order_book(
     State(client): State<Arc<StellarRpcClient>>,
+    Query(network): Query<NetworkQuery>,
     Query(params): Query<OrderBookQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let client = network_client(&client, &network)?;
     let selling_asset = Asset {
         asset_type: params.selling_asset_type,
         asset_code: params.selling_asset_code,
@@ -151,3 +401,343 @@ pub async fn get_order_book(
         )),
     }
 }
+
+/// Get current network fee statistics (cached for about one ledger)
+pub async fn get_fee_stats(
+    State((client, cache)): State<(Arc<StellarRpcClient>, Arc<CacheManager>)>,
+    Query(network): Query<NetworkQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let client = network_client(&client, &network)?;
+    match <()>::get_or_fetch(
+        &cache,
+        &keys::rpc_fee_stats(client.network()),
+        cache.config.get_ttl("fee_stats"),
+        client.fetch_fee_stats(),
+    )
+    .await
+    {
+        Ok(fee_stats) => Ok(Json(fee_stats)),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Failed to fetch fee stats: {}", e),
+            }),
+        )),
+    }
+}
+
+/// Get balances and trustlines for an account (cached briefly)
+pub async fn get_account_balances(
+    State((client, cache)): State<(Arc<StellarRpcClient>, Arc<CacheManager>)>,
+    Query(network): Query<NetworkQuery>,
+    Path(account_id): Path<String>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let client = network_client(&client, &network)?;
+    if !is_valid_account_id(&account_id) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: format!("Invalid account id {}", account_id),
+            }),
+        ));
+    }
+    match <()>::get_or_fetch(
+        &cache,
+        &keys::rpc_account_balances(client.network(), &account_id),
+        cache.config.get_ttl("account_balances"),
+        client.fetch_account_balances(&account_id),
+    )
+    .await
+    {
+        Ok(balances) => Ok(Json(balances)),
+        Err(e) if e.downcast_ref::<HorizonNotFound>().is_some() => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("Account {} not found", account_id),
+            }),
+        )),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Failed to fetch account balances: {}", e),
+            }),
+        )),
+    }
+}
+
+/// Get the operation effects of a transaction
+pub async fn get_transaction_effects(
+    State(client): State<Arc<StellarRpcClient>>,
+    Query(network): Query<NetworkQuery>,
+    Path(hash): Path<String>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let client = network_client(&client, &network)?;
+    if !is_valid_tx_hash(&hash) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Transaction hash must be 64 hex characters".to_string(),
+            }),
+        ));
+    }
+
+    match client.fetch_transaction_effects(&hash).await {
+        Ok(effects) => Ok(Json(effects)),
+        Err(e) if e.downcast_ref::<HorizonNotFound>().is_some() => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("Transaction {} not found", hash),
+            }),
+        )),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Failed to fetch transaction effects: {}", e),
+            }),
+        )),
+    }
+}
+
+/// Get Soroban events emitted by a contract over a ledger range
+pub async fn get_events(
+    State(client): State<Arc<StellarRpcClient>>,
+    Query(network): Query<NetworkQuery>,
+    Query(params): Query<EventsQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let client = network_client(&client, &network)?;
+    let bad_request = |error: &str| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: error.to_string(),
+            }),
+        )
+    };
+    if !is_valid_contract_id(&params.contract) {
+        return Err(bad_request(
+            "contract must be a 56-character C... contract id",
+        ));
+    }
+    if params.from_ledger == 0 {
+        return Err(bad_request("from_ledger must be at least 1"));
+    }
+    if params.to_ledger.is_some_and(|to| to < params.from_ledger) {
+        return Err(bad_request("to_ledger must not be before from_ledger"));
+    }
+
+    match client
+        .get_events(
+            &params.contract,
+            params.from_ledger,
+            params.to_ledger,
+            params.limit,
+        )
+        .await
+    {
+        Ok(events) => Ok(Json(events)),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Failed to fetch events: {}", e),
+            }),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn filter_from(vars: &[(&str, &str)]) -> anyhow::Result<RpcMethodFilter> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        RpcMethodFilter::from_lookup(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_rpc_method_filter_from_lookup() {
+        assert_eq!(filter_from(&[]).unwrap(), RpcMethodFilter::default());
+        assert_eq!(
+            filter_from(&[("RPC_ENABLED_METHODS", " ")]).unwrap(),
+            RpcMethodFilter::default()
+        );
+        assert!(filter_from(&[("RPC_ENABLED_METHODS", "payments,order_book")]).is_err());
+
+        let filter = filter_from(&[("RPC_ENABLED_METHODS", "payments, fee_stats")]).unwrap();
+        assert!(filter.allows("/api/rpc/payments"));
+        assert!(filter.allows("/api/rpc/fee-stats"));
+        assert!(filter.allows("/api/rpc/health"));
+        assert!(!filter.allows("/api/rpc/orderbook"));
+        assert!(!filter.allows("/api/rpc/account/:account_id/balances"));
+    }
+
+    #[tokio::test]
+    async fn test_disabled_rpc_method_returns_not_found() {
+        use axum::{body::Body, http::Request, middleware, routing::get, Router};
+        use tower::ServiceExt;
+
+        let filter = Arc::new(filter_from(&[("RPC_ENABLED_METHODS", "payments")]).unwrap());
+        let app = Router::new()
+            .route("/api/rpc/health", get(rpc_health_check))
+            .route("/api/rpc/payments", get(get_payments))
+            .route("/api/rpc/orderbook", get(get_order_book))
+            .with_state(Arc::new(StellarRpcClient::new_with_defaults(true)))
+            .layer(middleware::from_fn_with_state(
+                filter,
+                rpc_method_filter_middleware,
+            ));
+        let status = |uri: &'static str| {
+            let app = app.clone();
+            async move {
+                app.oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap()
+                    .status()
+            }
+        };
+
+        assert_eq!(status("/api/rpc/payments").await, StatusCode::OK);
+        assert_eq!(status("/api/rpc/health").await, StatusCode::OK);
+        assert_eq!(
+            status("/api/rpc/orderbook?selling_asset_type=native&buying_asset_type=native").await,
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[test]
+    fn test_is_valid_tx_hash() {
+        assert!(is_valid_tx_hash(
+            "3389e9f0f1a65f19736cacf544c2e825313e8447f569233bb8db39aa607c8889"
+        ));
+        assert!(is_valid_tx_hash(
+            "3389E9F0F1A65F19736CACF544C2E825313E8447F569233BB8DB39AA607C8889"
+        ));
+        assert!(!is_valid_tx_hash("3389e9f0"));
+        assert!(!is_valid_tx_hash(
+            "zz89e9f0f1a65f19736cacf544c2e825313e8447f569233bb8db39aa607c8889"
+        ));
+        assert!(!is_valid_tx_hash(""));
+    }
+
+    #[test]
+    fn test_is_valid_account_id() {
+        assert!(is_valid_account_id(
+            "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN"
+        ));
+        // Checksum mismatch
+        assert!(!is_valid_account_id(
+            "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVM"
+        ));
+        // Contracts are not accounts
+        assert!(!is_valid_account_id(
+            "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC"
+        ));
+        assert!(!is_valid_account_id(
+            "ga5zsejyb37jrc5avcia5mop4rhtm335x2kgx3ihojapp5re34k4kzvn"
+        ));
+        assert!(!is_valid_account_id("GABC"));
+        assert!(!is_valid_account_id(""));
+    }
+
+    #[test]
+    fn test_parse_asset_param() {
+        assert_eq!(parse_asset_param("native").unwrap().asset_type, "native");
+        let usdc = parse_asset_param("USDC:GISSUER").unwrap();
+        assert_eq!(usdc.asset_type, "credit_alphanum4");
+        assert_eq!(usdc.asset_code.as_deref(), Some("USDC"));
+        assert_eq!(
+            parse_asset_param("LONGASSET:GISSUER").unwrap().asset_type,
+            "credit_alphanum12"
+        );
+        assert!(parse_asset_param("USDC").is_none());
+        assert!(parse_asset_param("USDC:").is_none());
+        assert!(parse_asset_param("WAYTOOLONGASSET:GISSUER").is_none());
+    }
+
+    #[test]
+    fn test_parse_volume_window() {
+        assert_eq!(
+            parse_volume_window("24h"),
+            Some(chrono::Duration::hours(24))
+        );
+        assert_eq!(
+            parse_volume_window("30m"),
+            Some(chrono::Duration::minutes(30))
+        );
+        assert_eq!(parse_volume_window("7d"), Some(chrono::Duration::days(7)));
+        assert!(parse_volume_window("8d").is_none());
+        assert!(parse_volume_window("0h").is_none());
+        assert!(parse_volume_window("24").is_none());
+        assert!(parse_volume_window("").is_none());
+        assert!(parse_volume_window("24é").is_none());
+        assert!(parse_volume_window("99999999999999999d").is_none());
+    }
+
+    #[test]
+    fn test_is_valid_contract_id() {
+        assert!(is_valid_contract_id(
+            "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC"
+        ));
+        // Account ids are not contracts
+        assert!(!is_valid_contract_id(
+            "GDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC"
+        ));
+        assert!(!is_valid_contract_id(
+            "cdlzfc3syjydzt7k67vz75hpjvieuvnixf47zg2fb2rmqqvu2hhgcysc"
+        ));
+        assert!(!is_valid_contract_id("CDLZFC3SYJYDZT7K"));
+    }
+
+    #[tokio::test]
+    async fn test_get_events_validates_query() {
+        use axum::{body::Body, http::Request, routing::get, Router};
+        use tower::ServiceExt;
+
+        let app = Router::new()
+            .route("/api/rpc/events", get(get_events))
+            .with_state(Arc::new(StellarRpcClient::new_with_defaults(true)));
+        let contract = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC";
+        let status = |uri: String| {
+            let app = app.clone();
+            async move {
+                app.oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap()
+                    .status()
+            }
+        };
+
+        assert_eq!(
+            status(format!(
+                "/api/rpc/events?contract={}&from_ledger=1000&to_ledger=1100",
+                contract
+            ))
+            .await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(format!(
+                "/api/rpc/events?contract={}&from_ledger=1000&network=testnet",
+                contract
+            ))
+            .await,
+            StatusCode::OK
+        );
+        for query in [
+            format!("contract={}&from_ledger=1000&to_ledger=999", contract),
+            format!("contract={}&from_ledger=0", contract),
+            "contract=GABC&from_ledger=1000".to_string(),
+            format!("contract={}&from_ledger=1000&network=futurenet", contract),
+        ] {
+            assert_eq!(
+                status(format!("/api/rpc/events?{}", query)).await,
+                StatusCode::BAD_REQUEST,
+                "{}",
+                query
+            );
+        }
+    }
+}
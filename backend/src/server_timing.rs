@@ -0,0 +1,98 @@
+//! `Server-Timing` response headers built from per-phase request durations
+//!
+//! [`server_timing_middleware`] opens a timing scope for each request.
+//! Handlers wrap their cache, database and RPC calls in [`timed`], and the
+//! middleware reports the summed phases plus the total, e.g.
+//! `cache;dur=2.1, db;dur=18.4, total;dur=21.0`.
+
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub const SERVER_TIMING: HeaderName = HeaderName::from_static("server-timing");
+
+tokio::task_local! {
+    static TIMINGS: ServerTimings;
+}
+
+/// Durations recorded for one request, summed per phase in first-seen order
+#[derive(Debug, Clone, Default)]
+pub struct ServerTimings {
+    phases: Arc<Mutex<Vec<(&'static str, Duration)>>>,
+}
+
+impl ServerTimings {
+    pub fn record(&self, phase: &'static str, elapsed: Duration) {
+        let mut phases = self.phases.lock().unwrap_or_else(|e| e.into_inner());
+        match phases.iter_mut().find(|(name, _)| *name == phase) {
+            Some((_, total)) => *total += elapsed,
+            None => phases.push((phase, elapsed)),
+        }
+    }
+
+    /// Header value listing every recorded phase, in milliseconds
+    pub fn header_value(&self) -> String {
+        let phases = self.phases.lock().unwrap_or_else(|e| e.into_inner());
+        phases
+            .iter()
+            .map(|(name, elapsed)| format!("{};dur={:.1}", name, elapsed.as_secs_f64() * 1000.0))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Add `elapsed` to `phase` for the current request
+///
+/// Does nothing outside [`server_timing_middleware`], so instrumented code
+/// also runs unchanged in background tasks and tests.
+pub fn record(phase: &'static str, elapsed: Duration) {
+    let _ = TIMINGS.try_with(|timings| timings.record(phase, elapsed));
+}
+
+/// Await `fut`, recording how long it took under `phase`
+pub async fn timed<F: Future>(phase: &'static str, fut: F) -> F::Output {
+    let started = Instant::now();
+    let output = fut.await;
+    record(phase, started.elapsed());
+    output
+}
+
+/// Middleware emitting a `Server-Timing` header for the phases a handler recorded
+pub async fn server_timing_middleware(req: Request, next: Next) -> Response {
+    let timings = ServerTimings::default();
+    let started = Instant::now();
+    let mut response = TIMINGS.scope(timings.clone(), next.run(req)).await;
+    timings.record("total", started.elapsed());
+
+    if let Ok(value) = HeaderValue::from_str(&timings.header_value()) {
+        response.headers_mut().insert(SERVER_TIMING, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phases_are_summed_in_order() {
+        let timings = ServerTimings::default();
+        timings.record("cache", Duration::from_micros(1500));
+        timings.record("db", Duration::from_millis(18));
+        timings.record("cache", Duration::from_micros(500));
+
+        assert_eq!(timings.header_value(), "cache;dur=2.0, db;dur=18.0");
+    }
+
+    #[tokio::test]
+    async fn test_record_outside_request_is_ignored() {
+        record("db", Duration::from_millis(5));
+        assert_eq!(timed("db", async { 7 }).await, 7);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::ingestion::config::DustThreshold;
 use crate::models::corridor::{compute_median, CorridorMetrics, PaymentRecord};
 use std::collections::HashMap;
 
@@ -142,6 +143,18 @@ pub fn compute_corridor_metrics(
 
 /// Computes corridor metrics from payment records, aggregating settlement latency (both average and median) per corridor.
 pub fn compute_metrics_from_payments(payments: &[PaymentRecord]) -> Vec<CorridorMetrics> {
+    compute_metrics_excluding_dust(payments, &DustThreshold::default())
+}
+
+/// Like [`compute_metrics_from_payments`], leaving payments below `dust` out
+/// of the transaction counts and success rate
+///
+/// Dust still adds to `volume_usd`. A corridor with nothing but dust in
+/// `payments` gets no metrics, since it has no success rate.
+pub fn compute_metrics_excluding_dust(
+    payments: &[PaymentRecord],
+    dust: &DustThreshold,
+) -> Vec<CorridorMetrics> {
     let mut corridor_map: HashMap<String, Vec<&PaymentRecord>> = HashMap::new();
 
     // Group payments by corridor
@@ -159,7 +172,7 @@ pub fn compute_metrics_from_payments(payments: &[PaymentRecord]) -> Vec<Corridor
         let first = corridor_payments[0];
         let corridor = first.get_corridor();
 
-        let total_transactions = corridor_payments.len() as i64;
+        let mut total_transactions = 0i64;
         let mut successful_transactions = 0;
         let mut failed_transactions = 0;
         let mut volume_usd = 0.0;
@@ -167,6 +180,13 @@ pub fn compute_metrics_from_payments(payments: &[PaymentRecord]) -> Vec<Corridor
         let mut latency_values: Vec<i64> = Vec::new();
 
         for p in &corridor_payments {
+            if dust.is_dust(&p.source_asset_code, p.amount) {
+                if p.successful {
+                    volume_usd += p.amount;
+                }
+                continue;
+            }
+            total_transactions += 1;
             if p.successful {
                 successful_transactions += 1;
                 volume_usd += p.amount; // Assuming amount is already USD or normalized.
@@ -182,6 +202,9 @@ pub fn compute_metrics_from_payments(payments: &[PaymentRecord]) -> Vec<Corridor
                 failed_transactions += 1;
             }
         }
+        if total_transactions == 0 {
+            continue;
+        }
 
         let success_rate = if total_transactions > 0 {
             (successful_transactions as f64 / total_transactions as f64) * 100.0
@@ -309,6 +332,37 @@ mod tests {
         assert_eq!(usdc_metrics.volume_usd, 150.0);
     }
 
+    #[test]
+    fn test_dust_payment_does_not_affect_success_rate() {
+        let payments = vec![
+            create_test_payment_record("USDC", "EURC", 100.0, true, Utc::now()),
+            create_test_payment_record("USDC", "EURC", 50.0, true, Utc::now()),
+            create_test_payment_record("USDC", "EURC", 0.0001, false, Utc::now()),
+            create_test_payment_record("USDC", "EURC", 0.005, true, Utc::now()),
+            create_test_payment_record("XLM", "EURC", 0.5, false, Utc::now()),
+        ];
+        let dust = DustThreshold {
+            min_amount_usd: 0.01,
+            min_amounts: [("XLM".to_string(), 1.0)].into_iter().collect(),
+        };
+
+        let metrics = compute_metrics_excluding_dust(&payments, &dust);
+        // The XLM corridor only had dust
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(metrics[0].total_transactions, 2);
+        assert_eq!(metrics[0].failed_transactions, 0);
+        assert_eq!(metrics[0].success_rate, 100.0);
+        // Successful dust still moved value
+        assert!((metrics[0].volume_usd - 150.005).abs() < 1e-9);
+
+        let unfiltered = compute_metrics_from_payments(&payments);
+        let usdc = unfiltered
+            .iter()
+            .find(|m| m.corridor_key == metrics[0].corridor_key)
+            .unwrap();
+        assert_eq!(usdc.total_transactions, 4);
+    }
+
     #[test]
     fn test_compute_metrics_by_window() {
         let now = Utc::now();
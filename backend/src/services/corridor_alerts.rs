@@ -0,0 +1,205 @@
+//! Corridor alert evaluation
+//!
+//! After corridor metrics are updated, active alerts for that corridor are
+//! checked against the new success rate. A breached alert fires once and is
+//! then suppressed for the debounce window; recovering above the floor clears
+//! the alert so the next breach notifies immediately.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use reqwest::Client;
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::database::Database;
+use crate::models::CorridorAlertRecord;
+use crate::services::webhook::{deliver, notification_client};
+
+/// Event type sent when a corridor drops below its alert floor
+pub const CORRIDOR_ALERT_EVENT: &str = "corridor.success_rate_below_threshold";
+
+const DEFAULT_DEBOUNCE_MINUTES: i64 = 60;
+
+/// Outcome of evaluating an alert against a fresh success rate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertDecision {
+    /// Threshold breached and not recently notified
+    Fire,
+    /// Threshold breached but a notification was sent within the debounce window
+    Suppress,
+    /// Success rate recovered; reset the alert so the next breach fires
+    Clear,
+    /// Nothing to do
+    Idle,
+}
+
+/// Payload posted to an alert's notify URL
+#[derive(Debug, Clone, Serialize)]
+pub struct CorridorAlertNotification {
+    pub alert_id: String,
+    pub corridor_key: String,
+    pub success_rate: f64,
+    pub min_success_rate: f64,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Decide what to do with an alert given the corridor's latest success rate
+pub fn evaluate_alert(
+    alert: &CorridorAlertRecord,
+    success_rate: f64,
+    now: DateTime<Utc>,
+    debounce: Duration,
+) -> AlertDecision {
+    if success_rate < alert.min_success_rate {
+        match alert.last_triggered_at {
+            Some(last) if now - last < debounce => AlertDecision::Suppress,
+            _ => AlertDecision::Fire,
+        }
+    } else if alert.last_triggered_at.is_some() {
+        AlertDecision::Clear
+    } else {
+        AlertDecision::Idle
+    }
+}
+
+/// Service evaluating corridor alerts and notifying receivers
+pub struct CorridorAlertService {
+    client: Client,
+    db: Arc<Database>,
+    debounce: Duration,
+}
+
+impl CorridorAlertService {
+    pub fn new(db: Arc<Database>) -> Self {
+        let debounce_minutes = std::env::var("CORRIDOR_ALERT_DEBOUNCE_MINUTES")
+            .ok()
+            .and_then(|v| v.parse::<i64>().ok())
+            .unwrap_or(DEFAULT_DEBOUNCE_MINUTES);
+
+        Self {
+            client: notification_client(),
+            db,
+            debounce: Duration::minutes(debounce_minutes),
+        }
+    }
+
+    /// Evaluate all active alerts for a corridor after a metric update
+    pub async fn evaluate(&self, corridor_key: &str, success_rate: f64) -> Result<()> {
+        let alerts = self.db.list_active_corridor_alerts(corridor_key).await?;
+        let now = Utc::now();
+
+        for alert in alerts {
+            match evaluate_alert(&alert, success_rate, now, self.debounce) {
+                AlertDecision::Fire => {
+                    info!(
+                        "Corridor {} success rate {:.2}% below alert floor {:.2}%",
+                        corridor_key, success_rate, alert.min_success_rate
+                    );
+                    self.db
+                        .set_corridor_alert_triggered_at(&alert.id, Some(now))
+                        .await?;
+                    self.notify(&alert, success_rate, now)?;
+                }
+                AlertDecision::Clear => {
+                    self.db
+                        .set_corridor_alert_triggered_at(&alert.id, None)
+                        .await?;
+                }
+                AlertDecision::Suppress | AlertDecision::Idle => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn notify(
+        &self,
+        alert: &CorridorAlertRecord,
+        success_rate: f64,
+        now: DateTime<Utc>,
+    ) -> Result<()> {
+        let notification = CorridorAlertNotification {
+            alert_id: alert.id.clone(),
+            corridor_key: alert.corridor_key.clone(),
+            success_rate,
+            min_success_rate: alert.min_success_rate,
+            timestamp: now,
+        };
+        let body =
+            serde_json::to_vec(&notification).context("Failed to serialize alert payload")?;
+
+        let client = self.client.clone();
+        let url = alert.notify_url.clone();
+        tokio::spawn(async move {
+            if let Err(e) = deliver(&client, &url, None, CORRIDOR_ALERT_EVENT, body).await {
+                warn!("Corridor alert delivery to {} failed: {}", url, e);
+            }
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(
+        min_success_rate: f64,
+        last_triggered_at: Option<DateTime<Utc>>,
+    ) -> CorridorAlertRecord {
+        CorridorAlertRecord {
+            id: "alert-1".to_string(),
+            corridor_key: "USDC:issuer1->EURC:issuer2".to_string(),
+            min_success_rate,
+            notify_url: "https://ops.example.com/alerts".to_string(),
+            is_active: true,
+            last_triggered_at,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_breach_fires_when_never_triggered() {
+        let decision = evaluate_alert(&alert(95.0, None), 90.0, Utc::now(), Duration::minutes(60));
+        assert_eq!(decision, AlertDecision::Fire);
+    }
+
+    #[test]
+    fn test_no_breach_is_idle() {
+        let decision = evaluate_alert(&alert(95.0, None), 97.5, Utc::now(), Duration::minutes(60));
+        assert_eq!(decision, AlertDecision::Idle);
+    }
+
+    #[test]
+    fn test_breach_within_debounce_window_is_suppressed() {
+        let now = Utc::now();
+        let recent = alert(95.0, Some(now - Duration::minutes(10)));
+        assert_eq!(
+            evaluate_alert(&recent, 90.0, now, Duration::minutes(60)),
+            AlertDecision::Suppress
+        );
+    }
+
+    #[test]
+    fn test_breach_after_debounce_window_fires_again() {
+        let now = Utc::now();
+        let stale = alert(95.0, Some(now - Duration::minutes(90)));
+        assert_eq!(
+            evaluate_alert(&stale, 90.0, now, Duration::minutes(60)),
+            AlertDecision::Fire
+        );
+    }
+
+    #[test]
+    fn test_recovery_clears_triggered_alert() {
+        let now = Utc::now();
+        let triggered = alert(95.0, Some(now - Duration::minutes(5)));
+        assert_eq!(
+            evaluate_alert(&triggered, 99.0, now, Duration::minutes(60)),
+            AlertDecision::Clear
+        );
+    }
+}
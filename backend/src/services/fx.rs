@@ -0,0 +1,264 @@
+//! Foreign exchange rates for quoting USD volumes in other currencies
+//!
+//! Rates come from a pluggable [`PriceSource`] and are cached per currency.
+//! When a rate cannot be fetched, callers get a USD quote flagged as a
+//! fallback instead of an error.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::cache::{keys, CacheManager};
+use crate::cache_middleware::CacheAware;
+
+/// Currency all stored volumes are denominated in
+pub const BASE_CURRENCY: &str = "USD";
+
+const DEFAULT_FX_RATES_URL: &str = "https://api.frankfurter.app/latest";
+const FX_RATE_TTL_SECS: usize = 900;
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+
+/// Source of USD exchange rates
+#[async_trait]
+pub trait PriceSource: Send + Sync {
+    /// Units of `currency` worth one US dollar
+    async fn usd_rate(&self, currency: &str) -> Result<f64>;
+}
+
+/// Rates fetched from a Frankfurter-compatible `/latest?from=USD&to=EUR` API
+pub struct HttpPriceSource {
+    client: Client,
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct LatestRatesResponse {
+    rates: HashMap<String, f64>,
+}
+
+impl HttpPriceSource {
+    pub fn new(url: String) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self { client, url }
+    }
+}
+
+#[async_trait]
+impl PriceSource for HttpPriceSource {
+    async fn usd_rate(&self, currency: &str) -> Result<f64> {
+        let response: LatestRatesResponse = self
+            .client
+            .get(&self.url)
+            .query(&[("from", BASE_CURRENCY), ("to", currency)])
+            .send()
+            .await
+            .context("Failed to fetch FX rates")?
+            .error_for_status()
+            .context("FX rate request failed")?
+            .json()
+            .await
+            .context("Failed to parse FX rates")?;
+
+        response
+            .rates
+            .get(currency)
+            .copied()
+            .with_context(|| format!("No FX rate for {}", currency))
+    }
+}
+
+/// Fixed rates, for tests and offline deployments
+#[derive(Default)]
+pub struct StaticPriceSource {
+    rates: HashMap<String, f64>,
+}
+
+impl StaticPriceSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_rate(mut self, currency: &str, rate: f64) -> Self {
+        self.rates.insert(currency.to_string(), rate);
+        self
+    }
+}
+
+#[async_trait]
+impl PriceSource for StaticPriceSource {
+    async fn usd_rate(&self, currency: &str) -> Result<f64> {
+        self.rates
+            .get(currency)
+            .copied()
+            .with_context(|| format!("No FX rate for {}", currency))
+    }
+}
+
+/// Currency and rate used to quote USD volumes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quote {
+    pub currency: String,
+    pub rate: f64,
+    /// The requested currency had no rate, so volumes stayed in USD
+    pub fallback: bool,
+}
+
+impl Quote {
+    pub fn usd() -> Self {
+        Self {
+            currency: BASE_CURRENCY.to_string(),
+            rate: 1.0,
+            fallback: false,
+        }
+    }
+
+    fn usd_fallback() -> Self {
+        Self {
+            fallback: true,
+            ..Self::usd()
+        }
+    }
+
+    /// Convert a USD amount into the quote currency
+    pub fn convert(&self, amount_usd: f64) -> f64 {
+        amount_usd * self.rate
+    }
+}
+
+/// Normalize a `?quote=` value to an upper-case ISO 4217 style code
+pub fn parse_currency(code: &str) -> Result<String, String> {
+    let code = code.trim().to_ascii_uppercase();
+    if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(format!("Invalid quote currency '{}'", code));
+    }
+    Ok(code)
+}
+
+/// Resolves quotes through a [`PriceSource`], caching rates per currency
+pub struct FxService {
+    source: Arc<dyn PriceSource>,
+    cache: Option<Arc<CacheManager>>,
+}
+
+impl FxService {
+    pub fn new(source: Arc<dyn PriceSource>, cache: Option<Arc<CacheManager>>) -> Self {
+        Self { source, cache }
+    }
+
+    /// HTTP price source at `FX_RATES_URL` (Frankfurter by default)
+    pub fn from_env(cache: Arc<CacheManager>) -> Self {
+        let url =
+            std::env::var("FX_RATES_URL").unwrap_or_else(|_| DEFAULT_FX_RATES_URL.to_string());
+        Self::new(Arc::new(HttpPriceSource::new(url)), Some(cache))
+    }
+
+    /// Quote for an optional `?quote=` value; `None` means USD
+    ///
+    /// Only a malformed currency code is an error. Missing or invalid rates
+    /// fall back to USD with [`Quote::fallback`] set.
+    pub async fn quote(&self, currency: Option<&str>) -> Result<Quote, String> {
+        let currency = match currency {
+            Some(code) => parse_currency(code)?,
+            None => return Ok(Quote::usd()),
+        };
+        if currency == BASE_CURRENCY {
+            return Ok(Quote::usd());
+        }
+
+        match self.rate(&currency).await {
+            Ok(rate) if rate.is_finite() && rate > 0.0 => Ok(Quote {
+                currency,
+                rate,
+                fallback: false,
+            }),
+            Ok(rate) => {
+                tracing::warn!("Ignoring FX rate {} for {}, quoting USD", rate, currency);
+                Ok(Quote::usd_fallback())
+            }
+            Err(e) => {
+                tracing::warn!("FX rate for {} unavailable, quoting USD: {}", currency, e);
+                Ok(Quote::usd_fallback())
+            }
+        }
+    }
+
+    async fn rate(&self, currency: &str) -> Result<f64> {
+        match &self.cache {
+            Some(cache) => {
+                <()>::get_or_fetch(
+                    cache,
+                    &keys::fx_rate(currency),
+                    FX_RATE_TTL_SECS,
+                    self.source.usd_rate(currency),
+                )
+                .await
+            }
+            None => self.source.usd_rate(currency).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service() -> FxService {
+        FxService::new(
+            Arc::new(
+                StaticPriceSource::new()
+                    .with_rate("EUR", 0.92)
+                    .with_rate("XXX", 0.0),
+            ),
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_quote_converts_with_source_rate() {
+        let quote = service().quote(Some("eur")).await.unwrap();
+
+        assert_eq!(quote.currency, "EUR");
+        assert!(!quote.fallback);
+        assert!((quote.convert(1_000.0) - 920.0).abs() < 1e-9);
+        assert_eq!(quote.convert(0.0), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_quote_defaults_to_usd() {
+        let fx = service();
+
+        assert_eq!(fx.quote(None).await.unwrap(), Quote::usd());
+        assert_eq!(fx.quote(Some("USD")).await.unwrap(), Quote::usd());
+        assert_eq!(Quote::usd().convert(123.45), 123.45);
+    }
+
+    #[tokio::test]
+    async fn test_missing_rate_falls_back_to_usd() {
+        let fx = service();
+
+        let unknown = fx.quote(Some("GBP")).await.unwrap();
+        assert_eq!(unknown.currency, "USD");
+        assert!(unknown.fallback);
+        assert_eq!(unknown.convert(50.0), 50.0);
+
+        // A zero rate would wipe out every volume
+        assert!(fx.quote(Some("XXX")).await.unwrap().fallback);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_malformed_currency() {
+        let fx = service();
+
+        assert!(fx.quote(Some("EURO")).await.is_err());
+        assert!(fx.quote(Some("E1R")).await.is_err());
+        assert!(fx.quote(Some("")).await.is_err());
+    }
+}
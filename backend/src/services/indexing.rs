@@ -27,12 +27,16 @@ impl IndexingService {
             last_cursor.as_deref().unwrap_or("none")
         );
 
-        // Fetch payments from Horizon
-        let payments = self
-            .rpc_client
-            .fetch_payments(100, last_cursor.as_deref())
-            .await
-            .context("Failed to fetch payments from RPC")?;
+        // Page forward from the cursor; the first run starts from the newest page
+        let payments = match last_cursor.as_deref() {
+            Some(cursor) => self.rpc_client.fetch_payments_after(100, cursor).await,
+            None => self
+                .rpc_client
+                .fetch_payments(100, None)
+                .await
+                .map(newest_last),
+        }
+        .context("Failed to fetch payments from RPC")?;
 
         if payments.is_empty() {
             info!("No new payments to ingest");
@@ -50,6 +54,7 @@ impl IndexingService {
                     .ok()?
                     .with_timezone(&chrono::Utc);
 
+                let (source_asset_code, source_asset_issuer) = p.source_asset().unzip();
                 Some(PaymentRecord {
                     id: p.id,
                     transaction_hash: p.transaction_hash,
@@ -58,6 +63,8 @@ impl IndexingService {
                     asset_type: p.asset_type,
                     asset_code: p.asset_code,
                     asset_issuer: p.asset_issuer,
+                    source_asset_code,
+                    source_asset_issuer,
                     amount,
                     created_at,
                 })
@@ -84,3 +91,8 @@ impl IndexingService {
         Ok(())
     }
 }
+
+fn newest_last<T>(mut page: Vec<T>) -> Vec<T> {
+    page.reverse();
+    page
+}
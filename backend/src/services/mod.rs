@@ -1,8 +1,12 @@
 pub mod aggregation;
 pub mod analytics;
 pub mod contract;
+pub mod corridor_alerts;
+pub mod fx;
 pub mod indexing;
+pub mod retention;
 pub mod snapshot;
+pub mod webhook;
 
 #[cfg(test)]
 mod snapshot_test;
@@ -0,0 +1,170 @@
+//! Periodic pruning of metrics history past the retention window
+
+use anyhow::{bail, Result};
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::database::{Database, MetricsHistoryTable};
+use crate::ingestion::config::parse_var;
+
+const DEFAULT_RETENTION_DAYS: i64 = 90;
+const DEFAULT_PRUNE_INTERVAL_SECS: u64 = 3600;
+const DEFAULT_PRUNE_BATCH_SIZE: i64 = 1000;
+
+/// Retention settings, read once at startup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetricsRetentionConfig {
+    /// History rows older than this many days are deleted
+    pub retention_days: i64,
+    /// Interval between prune passes
+    pub prune_interval: Duration,
+    /// Rows deleted per statement
+    pub batch_size: i64,
+}
+
+impl Default for MetricsRetentionConfig {
+    fn default() -> Self {
+        Self {
+            retention_days: DEFAULT_RETENTION_DAYS,
+            prune_interval: Duration::from_secs(DEFAULT_PRUNE_INTERVAL_SECS),
+            batch_size: DEFAULT_PRUNE_BATCH_SIZE,
+        }
+    }
+}
+
+impl MetricsRetentionConfig {
+    /// Create from environment variables
+    pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let retention_days = parse_var(&lookup, "METRICS_RETENTION_DAYS", DEFAULT_RETENTION_DAYS)?;
+        if retention_days < 1 {
+            bail!("METRICS_RETENTION_DAYS must be at least 1");
+        }
+
+        let prune_interval_secs = parse_var(
+            &lookup,
+            "METRICS_PRUNE_INTERVAL_SECS",
+            DEFAULT_PRUNE_INTERVAL_SECS,
+        )?;
+        if prune_interval_secs == 0 {
+            bail!("METRICS_PRUNE_INTERVAL_SECS must be at least 1");
+        }
+
+        let batch_size = parse_var(
+            &lookup,
+            "METRICS_PRUNE_BATCH_SIZE",
+            DEFAULT_PRUNE_BATCH_SIZE,
+        )?;
+        if batch_size < 1 {
+            bail!("METRICS_PRUNE_BATCH_SIZE must be at least 1");
+        }
+
+        Ok(Self {
+            retention_days,
+            prune_interval: Duration::from_secs(prune_interval_secs),
+            batch_size,
+        })
+    }
+}
+
+/// Deletes anchor and corridor metrics history older than the retention window
+pub struct MetricsRetentionService {
+    db: Arc<Database>,
+    config: MetricsRetentionConfig,
+}
+
+impl MetricsRetentionService {
+    pub fn new(db: Arc<Database>, config: MetricsRetentionConfig) -> Self {
+        Self { db, config }
+    }
+
+    pub fn config(&self) -> &MetricsRetentionConfig {
+        &self.config
+    }
+
+    /// Prune every history table in batches, returning the total rows deleted
+    pub async fn prune(&self) -> Result<u64> {
+        let cutoff = Utc::now() - chrono::Duration::days(self.config.retention_days);
+        let mut total = 0;
+
+        for table in MetricsHistoryTable::ALL {
+            let mut pruned = 0;
+            loop {
+                let deleted = self
+                    .db
+                    .prune_metrics_history_batch(table, cutoff, self.config.batch_size)
+                    .await?;
+                pruned += deleted;
+                if deleted < self.config.batch_size as u64 {
+                    break;
+                }
+                // Let other writers take the lock between batches
+                tokio::task::yield_now().await;
+            }
+
+            if pruned > 0 {
+                tracing::info!(
+                    "Pruned {} rows from {} older than {}",
+                    pruned,
+                    table.table_name(),
+                    cutoff
+                );
+            }
+            total += pruned;
+        }
+
+        tracing::info!(
+            "Metrics retention pass removed {} rows (retention {} days)",
+            total,
+            self.config.retention_days
+        );
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config_from(vars: &[(&str, &str)]) -> Result<MetricsRetentionConfig> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        MetricsRetentionConfig::from_lookup(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_defaults_when_unset() {
+        let config = config_from(&[]).unwrap();
+        assert_eq!(config, MetricsRetentionConfig::default());
+        assert_eq!(config.retention_days, 90);
+    }
+
+    #[test]
+    fn test_parses_overrides() {
+        let config = config_from(&[
+            ("METRICS_RETENTION_DAYS", "30"),
+            ("METRICS_PRUNE_INTERVAL_SECS", "600"),
+            ("METRICS_PRUNE_BATCH_SIZE", "250"),
+        ])
+        .unwrap();
+
+        assert_eq!(config.retention_days, 30);
+        assert_eq!(config.prune_interval, Duration::from_secs(600));
+        assert_eq!(config.batch_size, 250);
+    }
+
+    #[test]
+    fn test_rejects_invalid_values() {
+        assert!(config_from(&[("METRICS_RETENTION_DAYS", "0")]).is_err());
+        assert!(config_from(&[("METRICS_RETENTION_DAYS", "forever")]).is_err());
+        assert!(config_from(&[("METRICS_PRUNE_INTERVAL_SECS", "0")]).is_err());
+        assert!(config_from(&[("METRICS_PRUNE_BATCH_SIZE", "-5")]).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+//! Webhook Service for notifying external receivers about anchor events
+//!
+//! This service handles:
+//! - Detecting anchor status transitions (e.g. green -> red)
+//! - Signing JSON payloads with HMAC-SHA256 so receivers can verify them
+//! - Delivering payloads to registered URLs with exponential backoff
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::database::Database;
+
+const MAX_RETRIES: u32 = 3;
+const INITIAL_BACKOFF_MS: u64 = 500;
+const BACKOFF_MULTIPLIER: u64 = 2;
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+
+/// Event type sent when an anchor's status changes
+pub const ANCHOR_STATUS_CHANGED_EVENT: &str = "anchor.status_changed";
+
+/// Header carrying the hex-encoded HMAC-SHA256 signature of the request body
+pub const SIGNATURE_HEADER: &str = "X-Stellar-Insights-Signature";
+
+/// Header carrying the event type of the payload
+pub const EVENT_HEADER: &str = "X-Stellar-Insights-Event";
+
+/// Payload posted to receivers when an anchor's status changes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnchorStatusChange {
+    pub anchor_id: String,
+    pub old_status: String,
+    pub new_status: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Detect an anchor status transition, returning the change if the status differs
+pub fn detect_status_transition(
+    anchor_id: &str,
+    old_status: &str,
+    new_status: &str,
+) -> Option<AnchorStatusChange> {
+    if old_status.eq_ignore_ascii_case(new_status) {
+        return None;
+    }
+
+    Some(AnchorStatusChange {
+        anchor_id: anchor_id.to_string(),
+        old_status: old_status.to_string(),
+        new_status: new_status.to_string(),
+        timestamp: Utc::now(),
+    })
+}
+
+/// Compute the hex-encoded HMAC-SHA256 signature of a payload
+pub fn sign_payload(secret: &str, payload: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(payload);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Service for delivering webhook notifications
+pub struct WebhookService {
+    client: Client,
+    db: Arc<Database>,
+}
+
+/// Build the HTTP client used for outbound notifications
+pub(crate) fn notification_client() -> Client {
+    Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .build()
+        .expect("Failed to build HTTP client")
+}
+
+impl WebhookService {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self {
+            client: notification_client(),
+            db,
+        }
+    }
+
+    /// Notify all registered receivers about an anchor status change.
+    ///
+    /// Deliveries run in background tasks so callers are never blocked on
+    /// slow or failing receivers.
+    pub async fn notify_anchor_status_change(&self, change: &AnchorStatusChange) -> Result<()> {
+        let webhooks = self
+            .db
+            .list_active_webhooks(ANCHOR_STATUS_CHANGED_EVENT)
+            .await?;
+
+        if webhooks.is_empty() {
+            return Ok(());
+        }
+
+        let body = serde_json::to_vec(change).context("Failed to serialize webhook payload")?;
+
+        info!(
+            "Anchor {} changed status {} -> {}, notifying {} webhook(s)",
+            change.anchor_id,
+            change.old_status,
+            change.new_status,
+            webhooks.len()
+        );
+
+        for webhook in webhooks {
+            let client = self.client.clone();
+            let body = body.clone();
+            tokio::spawn(async move {
+                if let Err(e) = deliver(
+                    &client,
+                    &webhook.url,
+                    Some(&webhook.secret),
+                    ANCHOR_STATUS_CHANGED_EVENT,
+                    body,
+                )
+                .await
+                {
+                    warn!("Webhook delivery to {} failed: {}", webhook.url, e);
+                }
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Deliver a JSON payload to a receiver, retrying with exponential backoff.
+///
+/// When a secret is given the body is signed and the signature sent in
+/// [`SIGNATURE_HEADER`].
+pub(crate) async fn deliver(
+    client: &Client,
+    url: &str,
+    secret: Option<&str>,
+    event_type: &str,
+    body: Vec<u8>,
+) -> Result<()> {
+    let signature = secret.map(|secret| sign_payload(secret, &body));
+    let mut attempt = 0;
+    let mut backoff_ms = INITIAL_BACKOFF_MS;
+
+    loop {
+        let mut request = client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, event_type);
+        if let Some(signature) = &signature {
+            request = request.header(SIGNATURE_HEADER, signature);
+        }
+
+        match request.body(body.clone()).send().await {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) => warn!(
+                "Webhook {} responded with {} (attempt {}/{})",
+                url,
+                response.status(),
+                attempt + 1,
+                MAX_RETRIES + 1
+            ),
+            Err(e) => warn!(
+                "Webhook {} request error (attempt {}/{}): {}",
+                url,
+                attempt + 1,
+                MAX_RETRIES + 1,
+                e
+            ),
+        }
+
+        if attempt >= MAX_RETRIES {
+            anyhow::bail!("Webhook delivery failed after {} retries", MAX_RETRIES);
+        }
+
+        attempt += 1;
+        tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+        backoff_ms *= BACKOFF_MULTIPLIER;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_status_transition() {
+        let change = detect_status_transition("anchor-1", "green", "red").unwrap();
+        assert_eq!(change.anchor_id, "anchor-1");
+        assert_eq!(change.old_status, "green");
+        assert_eq!(change.new_status, "red");
+    }
+
+    #[test]
+    fn test_no_transition_when_status_unchanged() {
+        assert!(detect_status_transition("anchor-1", "green", "green").is_none());
+        assert!(detect_status_transition("anchor-1", "Yellow", "yellow").is_none());
+    }
+
+    #[test]
+    fn test_sign_payload_known_vector() {
+        // RFC 4231 test case 2
+        let signature = sign_payload("Jefe", b"what do ya want for nothing?");
+        assert_eq!(
+            signature,
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_sign_payload_depends_on_secret() {
+        let payload = br#"{"anchor_id":"a"}"#;
+        assert_ne!(sign_payload("secret-a", payload), sign_payload("secret-b", payload));
+    }
+}
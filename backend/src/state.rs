@@ -1,7 +1,16 @@
 use std::sync::Arc;
+use crate::cache::CacheManager;
+use crate::cache_invalidation::CacheInvalidationService;
 use crate::database::Database;
+use crate::db::backend::DatabaseBackend;
+use crate::rpc::StellarRpcClient;
 use crate::websocket::WsState;
 use crate::ingestion::DataIngestionService;
+use crate::services::corridor_alerts::CorridorAlertService;
+use crate::services::webhook::WebhookService;
+
+/// State for the cached list handlers
+pub type CachedState = (Arc<dyn DatabaseBackend>, Arc<CacheManager>, Arc<StellarRpcClient>);
 
 /// Shared application state for handlers
 #[derive(Clone)]
@@ -9,6 +18,9 @@ pub struct AppState {
     pub db: Arc<Database>,
     pub ws_state: Arc<WsState>,
     pub ingestion: Arc<DataIngestionService>,
+    pub webhooks: Arc<WebhookService>,
+    pub corridor_alerts: Arc<CorridorAlertService>,
+    pub cache_invalidation: Arc<CacheInvalidationService>,
 }
 
 impl AppState {
@@ -16,11 +28,17 @@ impl AppState {
         db: Arc<Database>,
         ws_state: Arc<WsState>,
         ingestion: Arc<DataIngestionService>,
+        webhooks: Arc<WebhookService>,
+        corridor_alerts: Arc<CorridorAlertService>,
+        cache_invalidation: Arc<CacheInvalidationService>,
     ) -> Self {
         Self {
             db,
             ws_state,
             ingestion,
+            webhooks,
+            corridor_alerts,
+            cache_invalidation,
         }
     }
 }
@@ -0,0 +1,195 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use rand::Rng;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::Instrument;
+
+/// Route prefixes whose request spans are sampled
+const HIGH_VOLUME_PATH_PREFIXES: &[&str] = &["/api/anchors", "/api/rpc/"];
+
+/// Log a summary after this many untraced requests
+const UNSAMPLED_SUMMARY_EVERY: u64 = 1000;
+
+/// Request span sampling for high-volume routes
+///
+/// A `sample_rate` fraction of requests to high-volume routes get a full
+/// request span; the rest are only counted. Other routes are always traced,
+/// and failed responses are logged in full whether sampled or not.
+pub struct TraceSampler {
+    sample_rate: f64,
+    sampled: AtomicU64,
+    unsampled: AtomicU64,
+}
+
+impl TraceSampler {
+    pub fn new(sample_rate: f64) -> Self {
+        Self {
+            sample_rate: if sample_rate.is_nan() {
+                1.0
+            } else {
+                sample_rate.clamp(0.0, 1.0)
+            },
+            sampled: AtomicU64::new(0),
+            unsampled: AtomicU64::new(0),
+        }
+    }
+
+    /// Load the sample rate from `TRACE_SAMPLE_RATE` (0.0-1.0, default 1.0)
+    pub fn from_env() -> Self {
+        let sample_rate = match std::env::var("TRACE_SAMPLE_RATE") {
+            Ok(value) => value.trim().parse::<f64>().unwrap_or_else(|_| {
+                tracing::warn!(
+                    "Invalid TRACE_SAMPLE_RATE '{}', tracing all requests",
+                    value
+                );
+                1.0
+            }),
+            Err(_) => 1.0,
+        };
+
+        Self::new(sample_rate)
+    }
+
+    pub fn sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+
+    /// Requests on high-volume routes that were traced and skipped
+    pub fn counts(&self) -> (u64, u64) {
+        (
+            self.sampled.load(Ordering::Relaxed),
+            self.unsampled.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Decide whether a request to `path` gets a full span
+    pub fn should_sample(&self, path: &str, rng: &mut impl Rng) -> bool {
+        if !is_high_volume(path) {
+            return true;
+        }
+
+        let sampled = sample_decision(self.sample_rate, rng);
+        if sampled {
+            self.sampled.fetch_add(1, Ordering::Relaxed);
+        } else {
+            let unsampled = self.unsampled.fetch_add(1, Ordering::Relaxed) + 1;
+            if unsampled.is_multiple_of(UNSAMPLED_SUMMARY_EVERY) {
+                tracing::info!(
+                    "{} high-volume requests not traced (sample rate {})",
+                    unsampled,
+                    self.sample_rate
+                );
+            }
+        }
+        sampled
+    }
+}
+
+fn is_high_volume(path: &str) -> bool {
+    HIGH_VOLUME_PATH_PREFIXES
+        .iter()
+        .any(|prefix| path.starts_with(prefix))
+}
+
+/// Sampling decision for a single request at `sample_rate`
+pub fn sample_decision(sample_rate: f64, rng: &mut impl Rng) -> bool {
+    if sample_rate >= 1.0 {
+        true
+    } else if sample_rate <= 0.0 {
+        false
+    } else {
+        rng.gen::<f64>() < sample_rate
+    }
+}
+
+/// Middleware wrapping sampled requests in a request span
+pub async fn trace_sampling_middleware(
+    State(sampler): State<Arc<TraceSampler>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+    let sampled = sampler.should_sample(&path, &mut rand::thread_rng());
+    let started = Instant::now();
+
+    if !sampled {
+        let response = next.run(req).await;
+        let status = response.status();
+        // Failures are always traced, even when the request was not sampled
+        if status.is_server_error() {
+            tracing::error!(
+                %method,
+                %path,
+                status = status.as_u16(),
+                latency_ms = started.elapsed().as_millis() as u64,
+                "request failed"
+            );
+        }
+        return response;
+    }
+
+    let span = tracing::info_span!("request", %method, %path);
+    async move {
+        let response = next.run(req).await;
+        let status = response.status();
+        let latency_ms = started.elapsed().as_millis() as u64;
+        if status.is_server_error() {
+            tracing::error!(status = status.as_u16(), latency_ms, "request failed");
+        } else {
+            tracing::info!(status = status.as_u16(), latency_ms, "request completed");
+        }
+        response
+    }
+    .instrument(span)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_sample_decision_with_fixed_seed() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let sampled = (0..10_000)
+            .filter(|_| sample_decision(0.1, &mut rng))
+            .count();
+        assert!((900..1100).contains(&sampled), "sampled {}", sampled);
+
+        // Same seed, same decisions
+        let mut a = StdRng::seed_from_u64(7);
+        let mut b = StdRng::seed_from_u64(7);
+        let first: Vec<bool> = (0..100).map(|_| sample_decision(0.5, &mut a)).collect();
+        let second: Vec<bool> = (0..100).map(|_| sample_decision(0.5, &mut b)).collect();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_sample_decision_bounds() {
+        let mut rng = StdRng::seed_from_u64(1);
+        assert!((0..100).all(|_| sample_decision(1.0, &mut rng)));
+        assert!((0..100).all(|_| !sample_decision(0.0, &mut rng)));
+    }
+
+    #[test]
+    fn test_only_high_volume_routes_are_sampled() {
+        let sampler = TraceSampler::new(0.0);
+        let mut rng = StdRng::seed_from_u64(3);
+
+        assert!(!sampler.should_sample("/api/anchors", &mut rng));
+        assert!(!sampler.should_sample("/api/rpc/payments", &mut rng));
+        assert!(sampler.should_sample("/api/corridors", &mut rng));
+        assert_eq!(sampler.counts(), (0, 2));
+
+        assert_eq!(TraceSampler::new(2.5).sample_rate(), 1.0);
+        assert_eq!(TraceSampler::new(f64::NAN).sample_rate(), 1.0);
+    }
+}
@@ -1,33 +1,205 @@
 use axum::{
     extract::{
-        ws::{Message, WebSocket, WebSocketUpgrade},
+        ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade},
         Query, State,
     },
+    http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
 use dashmap::DashMap;
 use futures::{sink::SinkExt, stream::StreamExt};
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
-use tokio::sync::broadcast;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
 use tracing::{error, info, warn};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::api::cache_stats::CacheStatsResponse;
+use crate::corridor_feed::{CorridorFeed, CorridorSnapshot, SequencedCorridor};
+use crate::ingestion::config::parse_var;
+
+/// Channel name for ops clients that want live cache statistics
+pub const CACHE_STATS_CHANNEL: &str = "cache_stats";
+
+/// Channel name for clients that want live corridor metrics as deltas
+pub const CORRIDORS_CHANNEL: &str = "corridors";
+
+/// Connections allowed at once when `WS_MAX_CONNECTIONS` is unset
+pub const DEFAULT_MAX_WS_CONNECTIONS: usize = 10_000;
+
+/// Messages queued per connection before a client counts as too slow
+pub const DEFAULT_SEND_QUEUE_CAPACITY: usize = 32;
+
+/// Most corridors a client may ask for in one resync request
+pub const MAX_RESYNC_CORRIDORS: usize = 100;
+
+/// Reason in the close frame sent to clients dropped for falling behind
+pub const TOO_SLOW_CLOSE_REASON: &str = "too slow";
+
+/// How long to try sending the close frame to a client that is too slow
+const CLOSE_FRAME_TIMEOUT: Duration = Duration::from_secs(1);
+
 /// WebSocket connection state
 pub struct WsState {
     /// Map of connection ID to broadcast sender
     pub connections: DashMap<Uuid, tokio::sync::mpsc::Sender<WsMessage>>,///Broadcast channel for sending messages to all connections
     pub tx: broadcast::Sender<WsMessage>,
+    /// Broadcast channel for clients subscribed to cache statistics
+    pub cache_stats_tx: broadcast::Sender<WsMessage>,
+    /// Broadcast channel for corridor metric deltas
+    pub corridors_tx: broadcast::Sender<WsMessage>,
+    /// Last published metrics per corridor, the base for deltas
+    corridor_feed: CorridorFeed,
+    /// Upgrades beyond this many open connections are refused
+    max_connections: usize,
+    /// Open connections, counted from upgrade until the socket closes
+    open_connections: AtomicUsize,
+    /// Messages each connection may have queued for its socket
+    send_queue_capacity: usize,
+    /// Connections closed because their send queue overflowed
+    slow_clients_dropped: AtomicU64,
+}
+
+/// Open connection count and limit reported by `GET /api/metrics/websocket`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+pub struct WsConnectionStats {
+    pub open_connections: usize,
+    pub max_connections: usize,
+    pub slow_clients_dropped: u64,
+}
+
+/// A place under the connection limit, released when dropped
+pub struct ConnectionSlot {
+    state: Arc<WsState>,
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.state.open_connections.fetch_sub(1, Ordering::AcqRel);
+    }
 }
 
 impl WsState {
     pub fn new() -> Self {
         let (tx, _rx) = broadcast::channel(100);
+        let (cache_stats_tx, _rx) = broadcast::channel(16);
+        let (corridors_tx, _rx) = broadcast::channel(256);
         Self {
             connections: DashMap::new(),
             tx,
+            cache_stats_tx,
+            corridors_tx,
+            corridor_feed: CorridorFeed::new(),
+            max_connections: DEFAULT_MAX_WS_CONNECTIONS,
+            open_connections: AtomicUsize::new(0),
+            send_queue_capacity: DEFAULT_SEND_QUEUE_CAPACITY,
+            slow_clients_dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Create with the limit from `WS_MAX_CONNECTIONS`
+    pub fn from_env() -> anyhow::Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> anyhow::Result<Self> {
+        let max_connections = parse_var(&lookup, "WS_MAX_CONNECTIONS", DEFAULT_MAX_WS_CONNECTIONS)?;
+        if max_connections == 0 {
+            anyhow::bail!("WS_MAX_CONNECTIONS must be at least 1");
+        }
+        Ok(Self::new().with_max_connections(max_connections))
+    }
+
+    /// Refuse upgrades once `max_connections` sockets are open
+    pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = max_connections;
+        self
+    }
+
+    /// Drop clients with more than `capacity` messages waiting to be written
+    pub fn with_send_queue_capacity(mut self, capacity: usize) -> Self {
+        self.send_queue_capacity = capacity.max(1);
+        self
+    }
+
+    /// Reserve a connection, or `None` if the limit is reached
+    pub fn try_acquire_slot(self: &Arc<Self>) -> Option<ConnectionSlot> {
+        self.open_connections
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |open| {
+                (open < self.max_connections).then_some(open + 1)
+            })
+            .ok()
+            .map(|_| ConnectionSlot {
+                state: Arc::clone(self),
+            })
+    }
+
+    pub fn connection_stats(&self) -> WsConnectionStats {
+        WsConnectionStats {
+            open_connections: self.open_connections.load(Ordering::Acquire),
+            max_connections: self.max_connections,
+            slow_clients_dropped: self.slow_clients_dropped.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Receive cache statistics snapshots
+    pub fn subscribe_cache_stats(&self) -> broadcast::Receiver<WsMessage> {
+        self.cache_stats_tx.subscribe()
+    }
+
+    /// Whether any client is subscribed to cache statistics
+    pub fn has_cache_stats_subscribers(&self) -> bool {
+        self.cache_stats_tx.receiver_count() > 0
+    }
+
+    /// Push a cache statistics snapshot to subscribed clients
+    pub fn publish_cache_stats(&self, stats: CacheStatsResponse) {
+        // Sending only fails when nobody is subscribed
+        let _ = self.cache_stats_tx.send(WsMessage::CacheStats(stats));
+    }
+
+    /// Receive corridor deltas, and the snapshot they apply on top of
+    ///
+    /// The receiver is created first so no change falls between the two.
+    pub fn subscribe_corridors(&self) -> (broadcast::Receiver<WsMessage>, WsMessage) {
+        let rx = self.corridors_tx.subscribe();
+        let snapshot = WsMessage::CorridorSnapshot {
+            corridors: self.corridor_feed.snapshot(),
+        };
+        (rx, snapshot)
+    }
+
+    /// Current metrics of the requested corridors, for a client that missed a delta
+    ///
+    /// Requests for more than [`MAX_RESYNC_CORRIDORS`] get an error instead.
+    pub fn resync_corridors(&self, keys: &[String]) -> WsMessage {
+        if keys.len() > MAX_RESYNC_CORRIDORS {
+            return WsMessage::Error {
+                message: format!(
+                    "resync accepts at most {} corridors, got {}",
+                    MAX_RESYNC_CORRIDORS,
+                    keys.len()
+                ),
+            };
         }
+        let (corridors, unknown) = self.corridor_feed.resync(keys);
+        WsMessage::CorridorResync { corridors, unknown }
+    }
+
+    /// Record a corridor's current metrics and send subscribers what changed
+    pub fn publish_corridor(&self, snapshot: CorridorSnapshot) {
+        self.corridor_feed.update(snapshot, |change| {
+            // Sending only fails when nobody is subscribed
+            let _ = self.corridors_tx.send(WsMessage::CorridorDelta {
+                corridor_key: change.corridor_key.clone(),
+                seq: change.seq,
+                changes: change.changes.clone(),
+            });
+        });
     }
 
     /// Broadcast a message to all connected clients
@@ -68,6 +240,22 @@ pub enum WsMessage {
         reliability_score: f64,
         status: String,
     },
+    /// Cache statistics snapshot, sent to `cache_stats` subscribers only
+    CacheStats(CacheStatsResponse),
+    /// Every corridor's metrics, sent once to a `corridors` subscriber on connect
+    CorridorSnapshot { corridors: Vec<SequencedCorridor> },
+    /// Fields of one corridor that changed, to merge into the client's copy
+    CorridorDelta {
+        corridor_key: String,
+        /// One past the sequence of the copy this applies to
+        seq: u64,
+        changes: serde_json::Map<String, serde_json::Value>,
+    },
+    /// Reply to a resync request; `unknown` lists keys with no metrics yet
+    CorridorResync {
+        corridors: Vec<SequencedCorridor>,
+        unknown: Vec<String>,
+    },
     /// Heartbeat/Ping message
     Ping { timestamp: i64 },
     /// Pong response
@@ -78,10 +266,28 @@ pub enum WsMessage {
     Error { message: String },
 }
 
+/// Requests a client can send besides pings
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ClientAction {
+    /// Send the current metrics of these corridors again
+    Resync { corridors: Vec<String> },
+}
+
 #[derive(Debug, Deserialize)]
 pub struct WsQueryParams {
     /// Optional authentication token
     pub token: Option<String>,
+    /// Comma-separated extra channels: `cache_stats`, `corridors`
+    pub channels: Option<String>,
+}
+
+impl WsQueryParams {
+    fn wants_channel(&self, channel: &str) -> bool {
+        self.channels
+            .as_deref()
+            .is_some_and(|channels| channels.split(',').any(|c| c.trim() == channel))
+    }
 }
 
 /// WebSocket handler endpoint
@@ -91,8 +297,8 @@ pub async fn ws_handler(
     State(state): State<Arc<WsState>>,
 ) -> Response {
     // Validate authentication token if provided
-    if let Some(token) = params.token {
-        if !validate_token(&token) {
+    if let Some(token) = &params.token {
+        if !validate_token(token) {
             return (
                 axum::http::StatusCode::UNAUTHORIZED,
                 Json(serde_json::json!({"error": "Unauthorized"}))
@@ -101,7 +307,29 @@ pub async fn ws_handler(
         }
     }
 
-    ws.on_upgrade(move |socket| handle_socket(socket, state))
+    let Some(slot) = state.try_acquire_slot() else {
+        warn!(
+            "Refusing WebSocket upgrade: {} connections open",
+            state.max_connections
+        );
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({
+                "error": format!(
+                    "WebSocket connection limit of {} reached, try again later",
+                    state.max_connections
+                )
+            })),
+        )
+            .into_response();
+    };
+
+    let cache_stats = params.wants_channel(CACHE_STATS_CHANNEL);
+    let corridors = params.wants_channel(CORRIDORS_CHANNEL);
+    ws.on_upgrade(move |socket| async move {
+        handle_socket(socket, state, cache_stats, corridors).await;
+        drop(slot);
+    })
 }
 
 /// Validate authentication token
@@ -121,22 +349,40 @@ fn validate_token(token: &str) -> bool {
     }
 }
 
+/// Next message from an optional subscription; pends forever without one
+async fn recv_subscription(
+    rx: &mut Option<broadcast::Receiver<WsMessage>>,
+) -> Result<WsMessage, broadcast::error::RecvError> {
+    match rx {
+        Some(rx) => rx.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
 /// Handle individual WebSocket connection
-async fn handle_socket(socket: WebSocket, state: Arc<WsState>) {
+async fn handle_socket(socket: WebSocket, state: Arc<WsState>, cache_stats: bool, corridors: bool) {
     let connection_id = Uuid::new_v4();
     info!("New WebSocket connection: {}", connection_id);
 
     let (sender, receiver) = socket.split();
     let sender = Arc::new(tokio::sync::Mutex::new(sender));
 
-    // Create a channel for this specific connection
-    let (tx, mut rx) = tokio::sync::mpsc::channel::<WsMessage>(32);
+    // Bounded queue feeding this connection's socket writer
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<WsMessage>(state.send_queue_capacity);
 
     // Register the connection
-    state.connections.insert(connection_id, tx);
+    state.connections.insert(connection_id, tx.clone());
 
     // Subscribe to broadcast messages
     let mut broadcast_rx = state.tx.subscribe();
+    let mut cache_stats_rx = cache_stats.then(|| state.subscribe_cache_stats());
+    let mut corridors_rx = None;
+    if corridors {
+        let (rx, snapshot) = state.subscribe_corridors();
+        // The queue is empty, so the snapshot always fits
+        let _ = tx.try_send(snapshot);
+        corridors_rx = Some(rx);
+    }
 
     // Send connection confirmation
     let connected_msg = WsMessage::Connected {
@@ -152,14 +398,40 @@ async fn handle_socket(socket: WebSocket, state: Arc<WsState>) {
     let recv_sender = Arc::clone(&sender);
 
     // Task for receiving messages from client
-    let recv_task = {
+    let mut recv_task = {
         let connection_id = connection_id;
+        let state = Arc::clone(&state);
+        let tx = tx.clone();
         tokio::spawn(async move {
             let mut receiver = receiver;
             while let Some(Ok(msg)) = receiver.next().await {
                 match msg {
                     Message::Text(text) => {
-                        if let Ok(ws_msg) = serde_json::from_str::<WsMessage>(&text) {
+                        if let Ok(action) = serde_json::from_str::<ClientAction>(&text) {
+                            let reply = match action {
+                                ClientAction::Resync { .. } if !corridors => WsMessage::Error {
+                                    message: format!(
+                                        "resync needs the `{}` channel",
+                                        CORRIDORS_CHANNEL
+                                    ),
+                                },
+                                ClientAction::Resync { corridors: keys } => {
+                                    state.resync_corridors(&keys)
+                                }
+                            };
+                            // Queued behind deltas already forwarded, which the
+                            // reply's `seq` supersedes. A full queue means the
+                            // client is not reading, same as for deltas.
+                            match tx.try_send(reply) {
+                                Ok(()) => {}
+                                Err(mpsc::error::TrySendError::Full(_)) => {
+                                    return ForwardEnd::TooSlow
+                                }
+                                Err(mpsc::error::TrySendError::Closed(_)) => {
+                                    return ForwardEnd::Closed
+                                }
+                            }
+                        } else if let Ok(ws_msg) = serde_json::from_str::<WsMessage>(&text) {
                             match ws_msg {
                                 WsMessage::Ping { timestamp } => {
                                     info!("Received ping from {}", connection_id);
@@ -187,63 +459,92 @@ async fn handle_socket(socket: WebSocket, state: Arc<WsState>) {
                     _ => {}
                 }
             }
+            ForwardEnd::Closed
         })
     };
 
-    // Task for sending messages to client
-    let send_task = {
-        let connection_id = connection_id;
-        tokio::spawn(async move {
-            let mut ping_interval = tokio::time::interval(tokio::time::Duration::from_secs(30));
-            
-            loop {
-                tokio::select! {
-                    // Send ping every 30 seconds
-                    _ = ping_interval.tick() => {
-                        let ping = WsMessage::Ping {
-                            timestamp: chrono::Utc::now().timestamp(),
-                        };
-                        if let Ok(json) = serde_json::to_string(&ping) {
-                            let mut sender_guard = send_sender.lock().await;
-                            if sender_guard.send(Message::Text(json)).await.is_err() {
-                                error!("Failed to send ping to {}", connection_id);
-                                break;
-                            }
-                        }
-                    }
-                    // Receive from broadcast channel
-                    Ok(msg) = broadcast_rx.recv() => {
-                        if let Ok(json) = serde_json::to_string(&msg) {
-                            let mut sender_guard = send_sender.lock().await;
-                            if sender_guard.send(Message::Text(json)).await.is_err() {
-                                error!("Failed to send broadcast message to {}", connection_id);
-                                break;
-                            }
-                        }
-                    }
-                    // Receive from connection-specific channel
-                    Some(msg) = rx.recv() => {
-                        if let Ok(json) = serde_json::to_string(&msg) {
-                            let mut sender_guard = send_sender.lock().await;
-                            if sender_guard.send(Message::Text(json)).await.is_err() {
-                                error!("Failed to send message to {}", connection_id);
-                                break;
-                            }
-                        }
-                    }
+    // Task writing queued messages to the socket
+    let mut write_task = tokio::spawn(async move {
+        while let Some(msg) = rx.recv().await {
+            if let Ok(json) = serde_json::to_string(&msg) {
+                let mut sender_guard = send_sender.lock().await;
+                if sender_guard.send(Message::Text(json)).await.is_err() {
+                    error!("Failed to send message to {}", connection_id);
+                    break;
                 }
             }
-        })
-    };
+        }
+    });
 
-    // Wait for either task to finish
-    tokio::select! {
-        _ = recv_task => {
+    // Task queueing pings and subscribed messages; never waits on the socket,
+    // so a client that stops reading fills its queue and is dropped
+    let mut forward_task = tokio::spawn(async move {
+        let mut ping_interval = tokio::time::interval(tokio::time::Duration::from_secs(30));
+
+        loop {
+            let queued = tokio::select! {
+                // Send ping every 30 seconds
+                _ = ping_interval.tick() => tx.try_send(WsMessage::Ping {
+                    timestamp: chrono::Utc::now().timestamp(),
+                }),
+                // Receive from broadcast channel
+                msg = broadcast_rx.recv() => match msg {
+                    Ok(msg) => tx.try_send(msg),
+                    Err(broadcast::error::RecvError::Lagged(_)) => return ForwardEnd::TooSlow,
+                    Err(broadcast::error::RecvError::Closed) => return ForwardEnd::Closed,
+                },
+                // Receive cache statistics if subscribed
+                msg = recv_subscription(&mut cache_stats_rx) => match msg {
+                    Ok(msg) => tx.try_send(msg),
+                    Err(broadcast::error::RecvError::Lagged(_)) => return ForwardEnd::TooSlow,
+                    Err(broadcast::error::RecvError::Closed) => return ForwardEnd::Closed,
+                },
+                // Receive corridor deltas if subscribed
+                msg = recv_subscription(&mut corridors_rx) => match msg {
+                    Ok(msg) => tx.try_send(msg),
+                    Err(broadcast::error::RecvError::Lagged(_)) => return ForwardEnd::TooSlow,
+                    Err(broadcast::error::RecvError::Closed) => return ForwardEnd::Closed,
+                },
+            };
+            match queued {
+                Ok(()) => {}
+                Err(mpsc::error::TrySendError::Full(_)) => return ForwardEnd::TooSlow,
+                Err(mpsc::error::TrySendError::Closed(_)) => return ForwardEnd::Closed,
+            }
+        }
+    });
+
+    // Wait for any task to finish
+    let too_slow = tokio::select! {
+        end = &mut recv_task => {
             info!("Receive task finished for {}", connection_id);
+            matches!(end, Ok(ForwardEnd::TooSlow))
         }
-        _ = send_task => {
+        _ = &mut write_task => {
             info!("Send task finished for {}", connection_id);
+            false
         }
+        end = &mut forward_task => matches!(end, Ok(ForwardEnd::TooSlow)),
+    };
+    recv_task.abort();
+    write_task.abort();
+    forward_task.abort();
+
+    if too_slow {
+        state.slow_clients_dropped.fetch_add(1, Ordering::Relaxed);
+        warn!(
+            "Dropping WebSocket connection {}: send queue of {} overflowed",
+            connection_id, state.send_queue_capacity
+        );
+        // The socket may be the thing that is stuck, so don't wait long to say why
+        let close = Message::Close(Some(CloseFrame {
+            code: close_code::POLICY,
+            reason: TOO_SLOW_CLOSE_REASON.into(),
+        }));
+        let _ = tokio::time::timeout(CLOSE_FRAME_TIMEOUT, async {
+            sender.lock().await.send(close).await
+        })
+        .await;
     }
 
     // Clean up connection
@@ -255,6 +556,14 @@ async fn handle_socket(socket: WebSocket, state: Arc<WsState>) {
     );
 }
 
+/// Why a connection's forwarding or receive task stopped
+enum ForwardEnd {
+    /// The socket writer or broadcast channel went away, or the client left
+    Closed,
+    /// The send queue overflowed or the broadcast receiver lagged
+    TooSlow,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -265,12 +574,256 @@ mod tests {
         assert_eq!(state.connection_count(), 0);
     }
 
+    #[tokio::test]
+    async fn test_connection_over_limit_is_refused() {
+        use axum::{routing::get, Router};
+
+        let state = Arc::new(WsState::new().with_max_connections(2));
+        let app = Router::new()
+            .route("/ws", get(ws_handler))
+            .with_state(Arc::clone(&state));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/ws", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let mut open = Vec::new();
+        for _ in 0..2 {
+            let (socket, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+            open.push(socket);
+        }
+        assert_eq!(
+            state.connection_stats(),
+            WsConnectionStats {
+                open_connections: 2,
+                max_connections: 2,
+                slow_clients_dropped: 0,
+            }
+        );
+
+        match tokio_tungstenite::connect_async(&url).await {
+            Err(tokio_tungstenite::tungstenite::Error::Http(response)) => {
+                assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+            }
+            other => panic!("expected 503, got {:?}", other.map(|(_, r)| r.status())),
+        }
+
+        // Closing a socket frees its slot
+        open.pop().unwrap().close(None).await.unwrap();
+        for _ in 0..50 {
+            if state.connection_stats().open_connections < 2 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert!(tokio_tungstenite::connect_async(&url).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_client_that_never_reads_is_dropped() {
+        use axum::{routing::get, Router};
+
+        let state = Arc::new(WsState::new().with_send_queue_capacity(4));
+        let app = Router::new()
+            .route("/ws", get(ws_handler))
+            .with_state(Arc::clone(&state));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/ws", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        // Never polled, so the socket buffers and then the queue fill up
+        let (_idle_client, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+        while state.connection_count() == 0 {
+            tokio::task::yield_now().await;
+        }
+
+        let payload = "x".repeat(64 * 1024);
+        for _ in 0..2_000 {
+            if state.connection_count() == 0 {
+                break;
+            }
+            state.broadcast(WsMessage::Error {
+                message: payload.clone(),
+            });
+            tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+        }
+
+        assert_eq!(state.connection_count(), 0);
+        assert_eq!(state.connection_stats().slow_clients_dropped, 1);
+    }
+
+    #[tokio::test]
+    async fn test_client_that_resyncs_without_reading_is_dropped() {
+        use crate::corridor_feed::CorridorSnapshot;
+        use axum::{routing::get, Router};
+        use tokio_tungstenite::tungstenite::Message as ClientMessage;
+
+        let state = Arc::new(WsState::new().with_send_queue_capacity(4));
+        let keys: Vec<String> = (0..MAX_RESYNC_CORRIDORS)
+            .map(|i| format!("C{}:GA->XLM:native", i))
+            .collect();
+        for key in &keys {
+            state.publish_corridor(CorridorSnapshot {
+                corridor_key: key.clone(),
+                success_rate: 90.0,
+                total_transactions: 10,
+                successful_transactions: 9,
+                failed_transactions: 1,
+                volume_usd: 100.0,
+                avg_settlement_latency_ms: None,
+                liquidity_depth_usd: 0.0,
+            });
+        }
+        let app = Router::new()
+            .route("/ws", get(ws_handler))
+            .with_state(Arc::clone(&state));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!(
+            "ws://{}/ws?channels=corridors",
+            listener.local_addr().unwrap()
+        );
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        // Sends resync requests but never reads the replies
+        let (mut client, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+        while state.connection_count() == 0 {
+            tokio::task::yield_now().await;
+        }
+        let request = serde_json::json!({ "action": "resync", "corridors": keys }).to_string();
+        for _ in 0..2_000 {
+            let request = ClientMessage::Text(request.clone());
+            if state.connection_count() == 0 || client.send(request).await.is_err() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+        }
+
+        assert_eq!(state.connection_count(), 0);
+        assert_eq!(state.connection_stats().slow_clients_dropped, 1);
+    }
+
+    #[test]
+    fn test_max_connections_from_env() {
+        let state =
+            WsState::from_lookup(|name| (name == "WS_MAX_CONNECTIONS").then(|| "5".to_string()))
+                .unwrap();
+        assert_eq!(state.connection_stats().max_connections, 5);
+        assert!(WsState::from_lookup(|_| Some("0".to_string())).is_err());
+    }
+
     #[test]
     fn test_validate_token_no_env() {
         // Without WS_AUTH_TOKEN env var, should accept any token
         assert!(validate_token("any_token"));
     }
 
+    #[test]
+    fn test_channels_query_selects_cache_stats() {
+        let params = |channels: Option<&str>| WsQueryParams {
+            token: None,
+            channels: channels.map(str::to_string),
+        };
+
+        assert!(params(Some("cache_stats")).wants_channel(CACHE_STATS_CHANNEL));
+        assert!(params(Some("alerts, cache_stats")).wants_channel(CACHE_STATS_CHANNEL));
+        assert!(!params(Some("alerts")).wants_channel(CACHE_STATS_CHANNEL));
+        assert!(!params(None).wants_channel(CACHE_STATS_CHANNEL));
+    }
+
+    #[test]
+    fn test_corridor_subscriber_gets_snapshot_then_deltas() {
+        use crate::corridor_feed::CorridorSnapshot;
+
+        let state = WsState::new();
+        let corridor = |success_rate| CorridorSnapshot {
+            corridor_key: "USDC:GA->XLM:native".to_string(),
+            success_rate,
+            total_transactions: 10,
+            successful_transactions: 9,
+            failed_transactions: 1,
+            volume_usd: 100.0,
+            avg_settlement_latency_ms: None,
+            liquidity_depth_usd: 0.0,
+        };
+        state.publish_corridor(corridor(90.0));
+
+        let (mut rx, snapshot) = state.subscribe_corridors();
+        match snapshot {
+            WsMessage::CorridorSnapshot { corridors } => {
+                assert_eq!(corridors.len(), 1);
+                assert_eq!(corridors[0].seq, 1);
+                assert_eq!(corridors[0].snapshot, corridor(90.0));
+            }
+            other => panic!("unexpected message {:?}", other),
+        }
+
+        state.publish_corridor(corridor(90.0));
+        state.publish_corridor(corridor(80.0));
+        let json = serde_json::to_value(rx.try_recv().unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "corridor_delta",
+                "corridor_key": "USDC:GA->XLM:native",
+                "seq": 2,
+                "changes": { "success_rate": 80.0 },
+            })
+        );
+        // Republishing unchanged metrics sent nothing
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_resync_sends_current_metrics_and_seq() {
+        use crate::corridor_feed::CorridorSnapshot;
+
+        let action: ClientAction = serde_json::from_str(
+            r#"{"action": "resync", "corridors": ["USDC:GA->XLM:native", "EURC:GB->XLM:native"]}"#,
+        )
+        .unwrap();
+        let ClientAction::Resync { corridors: keys } = action;
+
+        let state = WsState::new();
+        let corridor = |success_rate| CorridorSnapshot {
+            corridor_key: "USDC:GA->XLM:native".to_string(),
+            success_rate,
+            total_transactions: 10,
+            successful_transactions: 9,
+            failed_transactions: 1,
+            volume_usd: 100.0,
+            avg_settlement_latency_ms: None,
+            liquidity_depth_usd: 0.0,
+        };
+        state.publish_corridor(corridor(90.0));
+        state.publish_corridor(corridor(85.0));
+        state.publish_corridor(corridor(70.0));
+
+        let json = serde_json::to_value(state.resync_corridors(&keys)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "corridor_resync",
+                "corridors": [{
+                    "seq": 3,
+                    "corridor_key": "USDC:GA->XLM:native",
+                    "success_rate": 70.0,
+                    "total_transactions": 10,
+                    "successful_transactions": 9,
+                    "failed_transactions": 1,
+                    "volume_usd": 100.0,
+                    "avg_settlement_latency_ms": null,
+                    "liquidity_depth_usd": 0.0,
+                }],
+                "unknown": ["EURC:GB->XLM:native"],
+            })
+        );
+
+        let too_many = vec!["USDC:GA->XLM:native".to_string(); MAX_RESYNC_CORRIDORS + 1];
+        assert!(matches!(
+            state.resync_corridors(&too_many),
+            WsMessage::Error { .. }
+        ));
+    }
+
     #[test]
     fn test_ws_message_serialization() {
         let msg = WsMessage::SnapshotUpdate {
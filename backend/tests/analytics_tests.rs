@@ -2,7 +2,7 @@ use backend::{analytics::*, models::AnchorStatus};
 
 #[test]
 fn test_compute_metrics_green_status() {
-    let metrics = compute_anchor_metrics(10000, 9900, 100, Some(2000));
+    let metrics = compute_anchor_metrics(10000, 9900, 100, Some(2000), &health::StatusThresholds::default());
 
     assert_eq!(metrics.total_transactions, 10000);
     assert_eq!(metrics.successful_transactions, 9900);
@@ -16,7 +16,7 @@ fn test_compute_metrics_green_status() {
 
 #[test]
 fn test_compute_metrics_yellow_status() {
-    let metrics = compute_anchor_metrics(10000, 9600, 400, Some(5000));
+    let metrics = compute_anchor_metrics(10000, 9600, 400, Some(5000), &health::StatusThresholds::default());
 
     assert_eq!(metrics.success_rate, 96.0);
     assert_eq!(metrics.failure_rate, 4.0);
@@ -26,7 +26,7 @@ fn test_compute_metrics_yellow_status() {
 
 #[test]
 fn test_compute_metrics_red_status() {
-    let metrics = compute_anchor_metrics(10000, 9300, 700, Some(8000));
+    let metrics = compute_anchor_metrics(10000, 9300, 700, Some(8000), &health::StatusThresholds::default());
 
     assert_eq!(metrics.success_rate, 93.0);
     assert!((metrics.failure_rate - 7.0).abs() < 1e-9);
@@ -35,7 +35,7 @@ fn test_compute_metrics_red_status() {
 
 #[test]
 fn test_compute_metrics_zero_transactions() {
-    let metrics = compute_anchor_metrics(0, 0, 0, None);
+    let metrics = compute_anchor_metrics(0, 0, 0, None, &health::StatusThresholds::default());
 
     assert_eq!(metrics.success_rate, 0.0);
     assert_eq!(metrics.failure_rate, 0.0);
@@ -45,7 +45,7 @@ fn test_compute_metrics_zero_transactions() {
 
 #[test]
 fn test_compute_metrics_fast_settlement() {
-    let metrics = compute_anchor_metrics(1000, 990, 10, Some(500));
+    let metrics = compute_anchor_metrics(1000, 990, 10, Some(500), &health::StatusThresholds::default());
 
     // Fast settlement should contribute to high reliability score
     assert!(metrics.reliability_score > 95.0);
@@ -53,7 +53,7 @@ fn test_compute_metrics_fast_settlement() {
 
 #[test]
 fn test_compute_metrics_slow_settlement() {
-    let metrics = compute_anchor_metrics(1000, 990, 10, Some(12000));
+    let metrics = compute_anchor_metrics(1000, 990, 10, Some(12000), &health::StatusThresholds::default());
 
     // Slow settlement should lower the reliability score despite high success rate
     assert!(metrics.reliability_score < 95.0);
@@ -98,8 +98,8 @@ fn test_count_assets_empty() {
 #[test]
 fn test_reliability_score_calculation() {
     // Test that reliability score is properly weighted
-    let high_success = compute_anchor_metrics(1000, 990, 10, Some(1000));
-    let low_success = compute_anchor_metrics(1000, 900, 100, Some(1000));
+    let high_success = compute_anchor_metrics(1000, 990, 10, Some(1000), &health::StatusThresholds::default());
+    let low_success = compute_anchor_metrics(1000, 900, 100, Some(1000), &health::StatusThresholds::default());
 
     // Higher success rate should yield higher reliability score
     assert!(high_success.reliability_score > low_success.reliability_score);
@@ -108,8 +108,8 @@ fn test_reliability_score_calculation() {
 #[test]
 fn test_settlement_time_impact() {
     // Same success rate, different settlement times
-    let fast = compute_anchor_metrics(1000, 950, 50, Some(1000));
-    let slow = compute_anchor_metrics(1000, 950, 50, Some(9000));
+    let fast = compute_anchor_metrics(1000, 950, 50, Some(1000), &health::StatusThresholds::default());
+    let slow = compute_anchor_metrics(1000, 950, 50, Some(9000), &health::StatusThresholds::default());
 
     // Faster settlement should yield higher reliability score
     assert!(fast.reliability_score > slow.reliability_score);
@@ -117,7 +117,7 @@ fn test_settlement_time_impact() {
 
 #[test]
 fn test_perfect_anchor() {
-    let metrics = compute_anchor_metrics(10000, 10000, 0, Some(500));
+    let metrics = compute_anchor_metrics(10000, 10000, 0, Some(500), &health::StatusThresholds::default());
 
     assert_eq!(metrics.success_rate, 100.0);
     assert_eq!(metrics.failure_rate, 0.0);
@@ -127,7 +127,7 @@ fn test_perfect_anchor() {
 
 #[test]
 fn test_completely_failed_anchor() {
-    let metrics = compute_anchor_metrics(1000, 0, 1000, Some(20000));
+    let metrics = compute_anchor_metrics(1000, 0, 1000, Some(20000), &health::StatusThresholds::default());
 
     assert_eq!(metrics.success_rate, 0.0);
     assert_eq!(metrics.failure_rate, 100.0);